@@ -176,6 +176,7 @@ fn prepare_setup() -> anyhow::Result<GenerationInputs<F>> {
         signed_txns: vec![txn.to_vec()],
         burn_addr: None,
         withdrawals: vec![],
+        process_requests: false,
         tries: tries_before,
         trie_roots_after,
         contract_code,
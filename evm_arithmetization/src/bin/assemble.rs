@@ -1,12 +1,32 @@
-use std::{env, fs};
+use std::{env, fs, process};
 
-use evm_arithmetization::cpu::kernel::assemble_to_bytes;
+use evm_arithmetization::cpu::kernel::{assemble_to_bytes, check_stack_effects};
 use hex::encode;
 
+/// Usage: `assemble [--check-stack] <files>...`
+///
+/// With `--check-stack`, checks the stack effects of the files instead of
+/// assembling them, exiting with an error if any mismatch is found.
 fn main() {
-    let mut args = env::args();
-    args.next();
-    let file_contents: Vec<_> = args.map(|path| fs::read_to_string(path).unwrap()).collect();
+    let mut args = env::args().skip(1).peekable();
+    let check_stack = args.next_if(|arg| arg == "--check-stack").is_some();
+    let paths: Vec<_> = args.collect();
+    let file_contents: Vec<_> = paths
+        .iter()
+        .map(|path| fs::read_to_string(path).unwrap())
+        .collect();
+
+    if check_stack {
+        let errors = check_stack_effects(&file_contents[..]);
+        for error in &errors {
+            println!("{}: {error}", paths[error.file]);
+        }
+        if !errors.is_empty() {
+            process::exit(1);
+        }
+        return;
+    }
+
     let assembled = assemble_to_bytes(&file_contents[..]);
     println!("{}", encode(assembled));
 }
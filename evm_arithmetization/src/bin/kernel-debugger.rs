@@ -0,0 +1,179 @@
+//! An interactive debugger for the kernel, running it on `GenerationInputs`
+//! loaded from a JSON file.
+//!
+//! Usage: `cargo run --features debugger --bin kernel-debugger --
+//! <inputs.json>`, then type `help` for the list of commands.
+
+use std::io::{self, BufRead as _, Write as _};
+use std::{env, fs};
+
+use anyhow::{anyhow, bail, Context as _, Result};
+use evm_arithmetization::cpu::kernel::debugger::{
+    segment_names, Breakpoint, KernelDebugger, StopReason,
+};
+use evm_arithmetization::{Field as F, GenerationInputs};
+
+const HELP: &str = "\
+Commands:
+  break <label> | break user <pc>    add a breakpoint on a kernel label or a user code PC
+  delete <label> | delete user <pc>  remove a breakpoint
+  breakpoints                        list the breakpoints
+  step [n]                           execute n instructions, 1 by default
+  continue                           execute until a breakpoint or the end of the kernel
+  where                              show the current location and instruction
+  registers                          show the registers
+  stack                              show the stack, from its top
+  memory <segment> [start] [len] [context]
+                                     show a memory segment, of the current context by default
+  segments                           list the memory segments
+  metadata                           show the global metadata
+  help                               show this message
+  quit                               exit the debugger";
+
+fn main() -> Result<()> {
+    let _ = env_logger::try_init();
+
+    let path = env::args()
+        .nth(1)
+        .ok_or_else(|| anyhow!("usage: kernel-debugger <inputs.json>"))?;
+    let inputs: GenerationInputs = serde_json::from_slice(&fs::read(&path)?)
+        .with_context(|| format!("invalid generation inputs in {path}"))?;
+
+    let mut debugger = KernelDebugger::new(&inputs);
+    println!("{HELP}");
+    print_location(&debugger);
+
+    let mut lines = io::stdin().lock().lines();
+    loop {
+        print!("(kdb) ");
+        io::stdout().flush()?;
+        let Some(line) = lines.next().transpose()? else {
+            return Ok(());
+        };
+
+        let words: Vec<_> = line.split_whitespace().collect();
+        match words.as_slice() {
+            [] => {}
+            ["quit" | "q"] => return Ok(()),
+            command => {
+                if let Err(e) = run_command(&mut debugger, command) {
+                    println!("error: {e:#}");
+                }
+            }
+        }
+    }
+}
+
+fn run_command(debugger: &mut KernelDebugger<F>, command: &[&str]) -> Result<()> {
+    match command {
+        ["break" | "b", breakpoint @ ..] => {
+            let breakpoint = parse_breakpoint(breakpoint)?;
+            if !debugger.add_breakpoint(breakpoint) {
+                println!("breakpoint already set at {breakpoint}");
+            }
+        }
+        ["delete" | "d", breakpoint @ ..] => {
+            let breakpoint = parse_breakpoint(breakpoint)?;
+            if !debugger.remove_breakpoint(breakpoint) {
+                println!("no breakpoint at {breakpoint}");
+            }
+        }
+        ["breakpoints"] => {
+            for breakpoint in debugger.breakpoints() {
+                println!("{breakpoint}");
+            }
+        }
+        ["step" | "s", n @ ..] => {
+            let n = match n {
+                [] => 1,
+                [n] => n.parse()?,
+                _ => bail!("usage: step [n]"),
+            };
+            let reason = debugger.step(n);
+            print_stop(debugger, reason);
+        }
+        ["continue" | "c"] => {
+            let reason = debugger.resume();
+            print_stop(debugger, reason);
+        }
+        ["where" | "w"] => print_location(debugger),
+        ["registers" | "r"] => {
+            println!("clock: {}", debugger.clock());
+            println!("program counter: {}", debugger.program_counter());
+            println!("kernel mode: {}", debugger.is_kernel());
+            println!("context: {}", debugger.context());
+            println!("gas used: {}", debugger.gas_used());
+        }
+        ["stack"] => {
+            for (i, value) in debugger.stack().iter().enumerate() {
+                println!("{i:>4}: {value:#x}");
+            }
+        }
+        ["memory" | "m", segment, args @ ..] => {
+            let args = args
+                .iter()
+                .map(|arg| arg.parse::<usize>())
+                .collect::<Result<Vec<_>, _>>()?;
+            let (start, len, context) = match args.as_slice() {
+                [] => (0, 32, debugger.context()),
+                [start] => (*start, 32, debugger.context()),
+                [start, len] => (*start, *len, debugger.context()),
+                [start, len, context] => (*start, *len, *context),
+                _ => bail!("usage: memory <segment> [start] [len] [context]"),
+            };
+            let values = debugger.memory(context, segment, start..start + len)?;
+            for (offset, value) in (start..).zip(values) {
+                println!("{offset:>6}: {value:#x}");
+            }
+        }
+        ["segments"] => {
+            for name in segment_names() {
+                println!("{name}");
+            }
+        }
+        ["metadata"] => {
+            for (name, value) in debugger.global_metadata() {
+                println!("{name}: {value:#x}");
+            }
+        }
+        ["help" | "h"] => println!("{HELP}"),
+        _ => bail!("unknown command, type `help` for the list of commands"),
+    }
+
+    Ok(())
+}
+
+fn parse_breakpoint(args: &[&str]) -> Result<Breakpoint> {
+    match args {
+        ["user", pc] => Ok(Breakpoint::User(parse_offset(pc)?)),
+        [label] => Breakpoint::kernel_label(label),
+        _ => bail!("usage: break <label> | break user <pc>"),
+    }
+}
+
+/// Parses a decimal or `0x`-prefixed hexadecimal offset.
+fn parse_offset(offset: &str) -> Result<usize> {
+    Ok(match offset.strip_prefix("0x") {
+        Some(hex) => usize::from_str_radix(hex, 16)?,
+        None => offset.parse()?,
+    })
+}
+
+fn print_stop(debugger: &KernelDebugger<F>, reason: Result<StopReason>) {
+    match reason {
+        Ok(StopReason::Stepped) => {}
+        Ok(StopReason::Breakpoint(breakpoint)) => println!("stopped at breakpoint {breakpoint}"),
+        Ok(StopReason::Halted) => println!("the kernel halted"),
+        Err(e) => println!("execution failed: {e:#}"),
+    }
+    print_location(debugger);
+}
+
+fn print_location(debugger: &KernelDebugger<F>) {
+    println!(
+        "cycle {}, {}: {}",
+        debugger.clock(),
+        debugger.location(),
+        debugger.next_instruction()
+    );
+}
@@ -1,40 +1,75 @@
 //! Design goals
 //! - Only one parser implementation
 //! - Simplicity - no delta processing!
+//!
+//! Cross-file features (definitions, references, hover and completion) are
+//! backed by an index of every file under the `cpu/kernel/asm` directory
+//! containing an opened document. Constants are defined in Rust, so their
+//! definitions are found by looking for their names as string literals in
+//! [`CONSTANT_SOURCES`].
+//!
+//! Stack effects are checked across the indexed files when a document is opened
+//! or saved.
 
 use std::{
     borrow::Cow,
     cell::OnceCell,
-    collections::{hash_map::Entry, HashMap},
-    iter,
+    collections::{hash_map::Entry, BTreeMap, HashMap, HashSet},
+    fs, iter, panic,
+    path::{Path, PathBuf},
     sync::{Mutex, MutexGuard},
 };
 
-use evm_arithmetization::cpu::kernel::parser::{AsmParser, Rule};
+use ethereum_types::U256;
+use evm_arithmetization::cpu::kernel::{
+    check_stack_effects, evm_constants,
+    parser::{AsmParser, Rule},
+    stack::stack_effects::StackScope,
+};
 use line_index::{LineIndex, TextRange, TextSize, WideEncoding, WideLineCol};
-use pest::{iterators::Pairs, Parser as _, RuleType};
+use pest::{
+    error::InputLocation,
+    iterators::{Pair, Pairs},
+    Parser as _, RuleType, Span,
+};
 use serde_json::{json, Value};
 use tower_lsp::{
     jsonrpc,
     lsp_types::{
-        DidChangeTextDocumentParams, DidCloseTextDocumentParams, DidOpenTextDocumentParams,
-        InitializeParams, InitializeResult, PartialResultParams, Position, Range, SemanticToken,
-        SemanticTokenType, SemanticTokens, SemanticTokensFullOptions, SemanticTokensLegend,
-        SemanticTokensOptions, SemanticTokensParams, SemanticTokensResult,
-        SemanticTokensServerCapabilities, ServerCapabilities, TextDocumentContentChangeEvent,
-        TextDocumentIdentifier, TextDocumentItem, TextDocumentSyncCapability, TextDocumentSyncKind,
+        CompletionItem, CompletionItemKind, CompletionOptions, CompletionParams,
+        CompletionResponse, Diagnostic, DiagnosticSeverity, DidChangeTextDocumentParams,
+        DidCloseTextDocumentParams, DidOpenTextDocumentParams, DidSaveTextDocumentParams,
+        Documentation, GotoDefinitionParams, GotoDefinitionResponse, Hover, HoverContents,
+        HoverParams, HoverProviderCapability, InitializeParams, InitializeResult, Location,
+        MarkupContent, MarkupKind, OneOf, PartialResultParams, Position, Range, ReferenceContext,
+        ReferenceParams, SemanticToken, SemanticTokenType, SemanticTokens,
+        SemanticTokensFullOptions, SemanticTokensLegend, SemanticTokensOptions,
+        SemanticTokensParams, SemanticTokensResult, SemanticTokensServerCapabilities,
+        ServerCapabilities, TextDocumentContentChangeEvent, TextDocumentIdentifier,
+        TextDocumentItem, TextDocumentPositionParams, TextDocumentSyncCapability,
+        TextDocumentSyncKind, TextDocumentSyncOptions, TextDocumentSyncSaveOptions,
         VersionedTextDocumentIdentifier, WorkDoneProgressOptions, WorkDoneProgressParams,
     },
-    LspService, Server,
+    Client, LspService, Server,
 };
 use tracing::{debug, warn};
 use url::Url;
 
+/// Paths, relative to the `src` directory of the kernel, of the Rust sources
+/// defining the constants available to kernel assembly.
+const CONSTANT_SOURCES: &[&str] = &["cpu/kernel/constants", "memory/segments.rs"];
+
 fn main() -> anyhow::Result<()> {
-    let (svc, socket) = LspService::new(|_client| {
-        LanguageServer(Mutex::new(State {
+    let (svc, socket) = LspService::new(|client| LanguageServer {
+        client,
+        state: Mutex::new(State {
             documents: HashMap::new(),
-        }))
+            index: HashMap::new(),
+            roots: HashSet::new(),
+            constants: evm_constants(),
+            parse_errors: HashMap::new(),
+            stack_errors: HashMap::new(),
+        }),
     });
     tokio::runtime::Builder::new_current_thread()
         .enable_all()
@@ -46,13 +81,201 @@ fn main() -> anyhow::Result<()> {
 struct State {
     /// Stores the latest version of a document.
     documents: HashMap<Url, (i32, SourceFile)>,
+    /// The symbols of every indexed file.
+    ///
+    /// Open documents replace their on-disk contents whenever they parse.
+    index: HashMap<Url, FileIndex>,
+    /// The `cpu/kernel/asm` directories which have been indexed.
+    roots: HashSet<PathBuf>,
+    /// The values of the constants accessible to kernel assembly, by name.
+    constants: HashMap<String, U256>,
+    parse_errors: HashMap<Url, Diagnostic>,
+    /// Diagnostics from the last stack effect check.
+    stack_errors: HashMap<Url, Vec<Diagnostic>>,
+}
+
+impl State {
+    /// Returns the occurrence of a symbol under `position`.
+    fn occurrence_at(&self, uri: &Url, position: Position) -> Option<&Occurrence> {
+        let file = self.index.get(uri)?;
+        let offset = file.source.offset(position)?;
+        file.occurrences
+            .iter()
+            .find(|it| it.range.contains_inclusive(offset))
+    }
+
+    /// Returns every occurrence of `symbol` in the index.
+    fn occurrences<'a>(
+        &'a self,
+        symbol: &'a Symbol,
+    ) -> impl Iterator<Item = (&'a Url, &'a FileIndex, &'a Occurrence)> {
+        self.index.iter().flat_map(move |(uri, file)| {
+            file.occurrences
+                .iter()
+                .filter(move |it| it.symbol == *symbol)
+                .map(move |it| (uri, file, it))
+        })
+    }
+
+    fn locations(
+        &self,
+        symbol: &Symbol,
+        mut filter: impl FnMut(&Occurrence) -> bool,
+    ) -> Vec<Location> {
+        self.occurrences(symbol)
+            .filter(|(_, _, it)| filter(it))
+            .filter_map(|(uri, file, it)| {
+                Some(Location {
+                    uri: uri.clone(),
+                    range: file.source.range(it.range)?,
+                })
+            })
+            .collect()
+    }
+
+    /// Indexes the kernel sources around `uri`, the first time a document in
+    /// their `cpu/kernel/asm` directory is opened.
+    fn index_kernel_of(&mut self, uri: &Url) {
+        let Ok(path) = uri.to_file_path() else {
+            return;
+        };
+        let Some(asm) = path.ancestors().find(|it| it.ends_with("cpu/kernel/asm")) else {
+            return;
+        };
+        if !self.roots.insert(asm.to_owned()) {
+            return;
+        }
+
+        let mut paths = vec![];
+        find_files(asm, "asm", &mut paths);
+        if let Some(src) = asm.ancestors().nth(3) {
+            for constants in CONSTANT_SOURCES {
+                find_files(&src.join(constants), "rs", &mut paths);
+            }
+        }
+        for path in paths {
+            let Ok(uri) = Url::from_file_path(&path) else {
+                continue;
+            };
+            // Open documents are indexed as they change.
+            if !self.documents.contains_key(&uri) {
+                self.index_from_disk(uri, &path);
+            }
+        }
+        debug!(root = %asm.display(), files = self.index.len(), "indexed the kernel");
+    }
+
+    /// Checks the stack effects of the indexed assembly files.
+    fn check_stack_effects(&mut self) {
+        let (uris, files): (Vec<_>, Vec<_>) = self
+            .index
+            .iter()
+            .filter(|(uri, _)| uri.path().ends_with(".asm"))
+            .map(|(uri, file)| (uri, file.source.text().to_owned()))
+            .unzip();
+        // The assembler panics on invalid code, such as calls to unknown macros.
+        let Ok(errors) = panic::catch_unwind(|| check_stack_effects(&files)) else {
+            warn!("couldn't check stack effects");
+            return;
+        };
+
+        let mut stack_errors = HashMap::<_, Vec<_>>::new();
+        for error in errors {
+            let uri = uris[error.file];
+            let source = &self.index[uri].source;
+            let range = declaration_range(source.text(), &error.scope)
+                .and_then(|range| source.range(range))
+                .unwrap_or_default();
+            stack_errors
+                .entry(uri.clone())
+                .or_default()
+                .push(Diagnostic {
+                    range,
+                    severity: Some(DiagnosticSeverity::WARNING),
+                    source: Some(String::from("evm_asm")),
+                    message: error.message,
+                    ..Default::default()
+                });
+        }
+        self.stack_errors = stack_errors;
+    }
+
+    fn diagnostics(&self, uri: &Url) -> Vec<Diagnostic> {
+        self.parse_errors
+            .get(uri)
+            .into_iter()
+            .chain(self.stack_errors.get(uri).into_iter().flatten())
+            .cloned()
+            .collect()
+    }
+
+    fn index_from_disk(&mut self, uri: Url, path: &Path) {
+        let source = match fs::read_to_string(path) {
+            Ok(text) => SourceFile::new(text),
+            Err(e) => {
+                warn!(path = %path.display(), %e, "couldn't read file to index");
+                return;
+            }
+        };
+        let file = match path.extension().and_then(|it| it.to_str()) {
+            Some("rs") => FileIndex::constant_definitions(source, &self.constants),
+            _ => match FileIndex::parse(source) {
+                Ok(file) => file,
+                Err(_) => return,
+            },
+        };
+        self.index.insert(uri, file);
+    }
 }
 
-struct LanguageServer(Mutex<State>);
+struct LanguageServer {
+    client: Client,
+    state: Mutex<State>,
+}
 
 impl LanguageServer {
     fn state(&self) -> MutexGuard<State> {
-        self.0.lock().unwrap()
+        self.state.lock().unwrap()
+    }
+
+    /// Re-indexes an open document if it parses, and publishes its
+    /// diagnostics, or those of all open documents if `check_stack`.
+    async fn analyze(&self, uri: Url, check_stack: bool) {
+        let published = {
+            let mut state = self.state();
+            let Some((_, source)) = state.documents.get(&uri).cloned() else {
+                return;
+            };
+            match FileIndex::parse(source.clone()) {
+                Ok(file) => {
+                    state.index.insert(uri.clone(), file);
+                    state.parse_errors.remove(&uri);
+                }
+                Err(e) => {
+                    let diagnostic = parse_error_diagnostic(&source, &e);
+                    state.parse_errors.insert(uri.clone(), diagnostic);
+                }
+            }
+            let uris = match check_stack {
+                true => {
+                    state.check_stack_effects();
+                    state.documents.keys().cloned().collect()
+                }
+                false => vec![uri],
+            };
+            uris.into_iter()
+                .map(|uri| {
+                    let (version, _) = state.documents[&uri];
+                    let diagnostics = state.diagnostics(&uri);
+                    (uri, diagnostics, version)
+                })
+                .collect::<Vec<_>>()
+        };
+        for (uri, diagnostics, version) in published {
+            self.client
+                .publish_diagnostics(uri, diagnostics, Some(version))
+                .await;
+        }
     }
 }
 
@@ -67,8 +290,13 @@ impl tower_lsp::LanguageServer for LanguageServer {
     ) -> Result<InitializeResult, jsonrpc::Error> {
         Ok(InitializeResult {
             capabilities: ServerCapabilities {
-                text_document_sync: Some(TextDocumentSyncCapability::Kind(
-                    TextDocumentSyncKind::FULL,
+                text_document_sync: Some(TextDocumentSyncCapability::Options(
+                    TextDocumentSyncOptions {
+                        open_close: Some(true),
+                        change: Some(TextDocumentSyncKind::FULL),
+                        save: Some(TextDocumentSyncSaveOptions::Supported(true)),
+                        ..Default::default()
+                    },
                 )),
                 semantic_tokens_provider: Some(
                     SemanticTokensServerCapabilities::SemanticTokensOptions(
@@ -85,6 +313,13 @@ impl tower_lsp::LanguageServer for LanguageServer {
                         },
                     ),
                 ),
+                definition_provider: Some(OneOf::Left(true)),
+                references_provider: Some(OneOf::Left(true)),
+                hover_provider: Some(HoverProviderCapability::Simple(true)),
+                completion_provider: Some(CompletionOptions {
+                    trigger_characters: Some(vec![String::from("%"), String::from("@")]),
+                    ..Default::default()
+                }),
                 ..Default::default()
             },
             server_info: None,
@@ -107,9 +342,14 @@ impl tower_lsp::LanguageServer for LanguageServer {
                     text,
                 },
         } = params;
-        self.state()
-            .documents
-            .insert(uri, (version, SourceFile::new(text)));
+        {
+            let mut state = self.state();
+            state
+                .documents
+                .insert(uri.clone(), (version, SourceFile::new(text)));
+            state.index_kernel_of(&uri);
+        }
+        self.analyze(uri, true).await;
     }
     async fn did_change(&self, params: DidChangeTextDocumentParams) {
         let DidChangeTextDocumentParams {
@@ -145,14 +385,34 @@ impl tower_lsp::LanguageServer for LanguageServer {
                 _ => warn!(%uri, %version, "ignoring relative didChange"),
             }
         }
+        self.analyze(uri, false).await;
+    }
+    async fn did_save(&self, params: DidSaveTextDocumentParams) {
+        let DidSaveTextDocumentParams {
+            text_document: TextDocumentIdentifier { uri },
+            text: _,
+        } = params;
+        self.analyze(uri, true).await;
     }
     async fn did_close(&self, params: DidCloseTextDocumentParams) {
         let DidCloseTextDocumentParams {
             text_document: TextDocumentIdentifier { uri },
         } = params;
-        if self.state().documents.remove(&uri).is_none() {
-            warn!(%uri, "no such document on didClose")
+        {
+            let mut state = self.state();
+            if state.documents.remove(&uri).is_none() {
+                warn!(%uri, "no such document on didClose")
+            }
+            // Unsaved changes no longer apply.
+            state.index.remove(&uri);
+            state.parse_errors.remove(&uri);
+            if let Ok(path) = uri.to_file_path() {
+                if state.roots.iter().any(|root| path.starts_with(root)) {
+                    state.index_from_disk(uri.clone(), &path);
+                }
+            }
         }
+        self.client.publish_diagnostics(uri, vec![], None).await;
     }
 
     // features
@@ -197,6 +457,372 @@ impl tower_lsp::LanguageServer for LanguageServer {
             data: builder.data,
         })))
     }
+
+    async fn goto_definition(
+        &self,
+        params: GotoDefinitionParams,
+    ) -> Result<Option<GotoDefinitionResponse>, jsonrpc::Error> {
+        let GotoDefinitionParams {
+            text_document_position_params:
+                TextDocumentPositionParams {
+                    text_document: TextDocumentIdentifier { uri },
+                    position,
+                },
+            work_done_progress_params: _,
+            partial_result_params: _,
+        } = params;
+        let state = self.state();
+        let Some(occurrence) = state.occurrence_at(&uri, position) else {
+            return Ok(None);
+        };
+        Ok(Some(GotoDefinitionResponse::Array(
+            state.locations(&occurrence.symbol, |it| it.definition.is_some()),
+        )))
+    }
+
+    async fn references(
+        &self,
+        params: ReferenceParams,
+    ) -> Result<Option<Vec<Location>>, jsonrpc::Error> {
+        let ReferenceParams {
+            text_document_position:
+                TextDocumentPositionParams {
+                    text_document: TextDocumentIdentifier { uri },
+                    position,
+                },
+            work_done_progress_params: _,
+            partial_result_params: _,
+            context: ReferenceContext {
+                include_declaration,
+            },
+        } = params;
+        let state = self.state();
+        let Some(occurrence) = state.occurrence_at(&uri, position) else {
+            return Ok(None);
+        };
+        Ok(Some(state.locations(&occurrence.symbol, |it| {
+            include_declaration || it.definition.is_none()
+        })))
+    }
+
+    async fn hover(&self, params: HoverParams) -> Result<Option<Hover>, jsonrpc::Error> {
+        let HoverParams {
+            text_document_position_params:
+                TextDocumentPositionParams {
+                    text_document: TextDocumentIdentifier { uri },
+                    position,
+                },
+            work_done_progress_params: _,
+        } = params;
+        let state = self.state();
+        let Some(occurrence) = state.occurrence_at(&uri, position) else {
+            return Ok(None);
+        };
+        let value = match &occurrence.symbol {
+            Symbol::Constant(name) => match state.constants.get(name) {
+                Some(value) => constant_hover(name, *value),
+                None => return Ok(None),
+            },
+            symbol => {
+                let definitions = state
+                    .occurrences(symbol)
+                    .filter_map(|(_, _, it)| it.definition.as_deref())
+                    .collect::<Vec<_>>();
+                if definitions.is_empty() {
+                    return Ok(None);
+                }
+                // Macros may be overloaded on their number of arguments.
+                definitions.join("\n\n---\n\n")
+            }
+        };
+        Ok(Some(Hover {
+            contents: HoverContents::Markup(MarkupContent {
+                kind: MarkupKind::Markdown,
+                value,
+            }),
+            range: state.index[&uri].source.range(occurrence.range),
+        }))
+    }
+
+    async fn completion(
+        &self,
+        params: CompletionParams,
+    ) -> Result<Option<CompletionResponse>, jsonrpc::Error> {
+        let CompletionParams {
+            text_document_position:
+                TextDocumentPositionParams {
+                    text_document: TextDocumentIdentifier { uri },
+                    position,
+                },
+            work_done_progress_params: _,
+            partial_result_params: _,
+            context: _,
+        } = params;
+        let state = self.state();
+        let Some((_, source)) = state.documents.get(&uri) else {
+            bail!("no such document with uri {}", uri)
+        };
+        let Some(offset) = source.offset(position) else {
+            return Ok(None);
+        };
+        let before_word = source.text()[..usize::from(offset)]
+            .trim_end_matches(|c: char| c == '_' || c.is_ascii_alphanumeric());
+        let items = match before_word.chars().last() {
+            Some('%') => {
+                let mut macros = BTreeMap::<_, Vec<_>>::new();
+                for file in state.index.values() {
+                    for occurrence in &file.occurrences {
+                        if let (Symbol::Macro(name), Some(definition)) =
+                            (&occurrence.symbol, &occurrence.definition)
+                        {
+                            macros.entry(name).or_default().push(definition.as_str());
+                        }
+                    }
+                }
+                macros
+                    .into_iter()
+                    .map(|(name, definitions)| CompletionItem {
+                        label: name.clone(),
+                        kind: Some(CompletionItemKind::FUNCTION),
+                        documentation: Some(Documentation::MarkupContent(MarkupContent {
+                            kind: MarkupKind::Markdown,
+                            value: definitions.join("\n\n---\n\n"),
+                        })),
+                        ..Default::default()
+                    })
+                    .collect()
+            }
+            Some('@') => state
+                .constants
+                .iter()
+                .map(|(name, value)| CompletionItem {
+                    label: name.clone(),
+                    kind: Some(CompletionItemKind::CONSTANT),
+                    detail: Some(format_constant(*value)),
+                    ..Default::default()
+                })
+                .collect(),
+            _ => return Ok(None),
+        };
+        Ok(Some(CompletionResponse::Array(items)))
+    }
+}
+
+/// A name which can be defined and referred to across files.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+enum Symbol {
+    /// A `GLOBAL` label.
+    Label(String),
+    /// A `%macro`, of any arity.
+    Macro(String),
+    /// An `@` constant.
+    Constant(String),
+}
+
+#[derive(Debug)]
+struct Occurrence {
+    symbol: Symbol,
+    /// The range of the name of the symbol.
+    range: TextRange,
+    /// The markdown shown on hover, if this occurrence is a definition.
+    definition: Option<String>,
+}
+
+struct FileIndex {
+    source: SourceFile,
+    occurrences: Vec<Occurrence>,
+}
+
+impl FileIndex {
+    /// Indexes an assembly file.
+    fn parse(source: SourceFile) -> Result<Self, pest::error::Error<Rule>> {
+        let mut occurrences = vec![];
+        collect_occurrences(
+            &mut occurrences,
+            source.text(),
+            AsmParser::parse(Rule::file, source.text())?,
+        );
+        Ok(Self {
+            source,
+            occurrences,
+        })
+    }
+
+    /// Indexes the first string literal naming each of `constants` in a Rust
+    /// file as its definition.
+    fn constant_definitions(source: SourceFile, constants: &HashMap<String, U256>) -> Self {
+        let mut occurrences = vec![];
+        let mut seen = HashSet::new();
+        let mut quotes = source.text().match_indices('"').map(|(i, _)| i);
+        while let (Some(open), Some(close)) = (quotes.next(), quotes.next()) {
+            let name = &source.text()[open + 1..close];
+            if let Some(value) = constants.get(name) {
+                if seen.insert(name) {
+                    occurrences.push(Occurrence {
+                        symbol: Symbol::Constant(name.into()),
+                        range: text_range(open + 1, close),
+                        definition: Some(constant_hover(name, *value)),
+                    });
+                }
+            }
+        }
+        Self {
+            source,
+            occurrences,
+        }
+    }
+}
+
+fn collect_occurrences(occurrences: &mut Vec<Occurrence>, text: &str, ast: Pairs<'_, Rule>) {
+    let identifiers = |pair: &Pair<'_, Rule>| {
+        pair.clone()
+            .into_inner()
+            .filter(|it| it.as_rule() == Rule::identifier)
+    };
+    for pair in ast {
+        match pair.as_rule() {
+            Rule::macro_def => {
+                if let Some(name) = identifiers(&pair).next() {
+                    occurrences.push(Occurrence {
+                        symbol: Symbol::Macro(name.as_str().into()),
+                        range: span_range(name.as_span()),
+                        definition: Some(definition_hover(text, pair.as_span(), pair.as_str())),
+                    });
+                }
+            }
+            Rule::global_label_decl => {
+                if let Some(name) = identifiers(&pair).next() {
+                    occurrences.push(Occurrence {
+                        symbol: Symbol::Label(name.as_str().into()),
+                        range: span_range(name.as_span()),
+                        definition: Some(definition_hover(
+                            text,
+                            pair.as_span(),
+                            &format!("GLOBAL {}", name.as_str()),
+                        )),
+                    });
+                }
+            }
+            Rule::macro_call => {
+                if let Some(name) = identifiers(&pair).next() {
+                    occurrences.push(Occurrence {
+                        symbol: Symbol::Macro(name.as_str().into()),
+                        range: span_range(name.as_span()),
+                        definition: None,
+                    });
+                }
+            }
+            // Local labels share the syntax, but aren't indexed.
+            Rule::push_target | Rule::jumptable_item => {
+                occurrences.extend(identifiers(&pair).map(|name| Occurrence {
+                    symbol: Symbol::Label(name.as_str().into()),
+                    range: span_range(name.as_span()),
+                    definition: None,
+                }))
+            }
+            Rule::constant => {
+                if let Some(name) = identifiers(&pair).next() {
+                    occurrences.push(Occurrence {
+                        symbol: Symbol::Constant(name.as_str().into()),
+                        range: span_range(name.as_span()),
+                        definition: None,
+                    });
+                }
+            }
+            _ => {}
+        }
+        collect_occurrences(occurrences, text, pair.into_inner());
+    }
+}
+
+/// Shows the code of a definition, followed by the `//` comment directly above
+/// it, which documents its stack effects by convention.
+fn definition_hover(text: &str, span: Span<'_>, code: &str) -> String {
+    let mut comment = text[..span.start()]
+        .trim_end_matches([' ', '\t'])
+        .lines()
+        .rev()
+        .map_while(|line| line.trim().strip_prefix("//"))
+        .map(|line| line.strip_prefix(' ').unwrap_or(line))
+        .collect::<Vec<_>>();
+    comment.reverse();
+    match comment.is_empty() {
+        true => format!("```\n{code}\n```"),
+        false => format!("```\n{code}\n```\n\n{}", comment.join("  \n")),
+    }
+}
+
+fn constant_hover(name: &str, value: U256) -> String {
+    format!("```\n@{name} = {}\n```", format_constant(value))
+}
+
+fn format_constant(value: U256) -> String {
+    match value > U256::from(u32::MAX) {
+        true => format!("{value:#x}"),
+        false => value.to_string(),
+    }
+}
+
+/// Finds the declaration of the label or macro with a stack error.
+fn declaration_range(text: &str, scope: &StackScope) -> Option<TextRange> {
+    let (name, is_macro) = match scope {
+        StackScope::Label(label) => (label, false),
+        StackScope::Macro(name) => (name, true),
+    };
+    let is_identifier_char = |c: char| c == '_' || c.is_ascii_alphanumeric();
+    text.match_indices(name.as_str()).find_map(|(start, _)| {
+        let end = start + name.len();
+        let (before, after) = (&text[..start], &text[end..]);
+        let is_declaration = match is_macro {
+            true => before.trim_end().to_lowercase().ends_with("%macro"),
+            false => after.starts_with(':'),
+        };
+        let is_whole_word =
+            !before.ends_with(is_identifier_char) && !after.starts_with(is_identifier_char);
+        (is_declaration && is_whole_word).then(|| text_range(start, end))
+    })
+}
+
+fn parse_error_diagnostic(source: &SourceFile, e: &pest::error::Error<Rule>) -> Diagnostic {
+    let (start, end) = match e.location {
+        InputLocation::Pos(pos) => (pos, pos),
+        InputLocation::Span(span) => span,
+    };
+    Diagnostic {
+        range: source.range(text_range(start, end)).unwrap_or_default(),
+        severity: Some(DiagnosticSeverity::ERROR),
+        source: Some(String::from("evm_asm")),
+        message: e.variant.message().into_owned(),
+        ..Default::default()
+    }
+}
+
+/// Collects the files with the given extension under `path`, which may itself
+/// be such a file.
+fn find_files(path: &Path, extension: &str, files: &mut Vec<PathBuf>) {
+    if path.is_file() {
+        if path.extension().is_some_and(|it| it == extension) {
+            files.push(path.to_owned());
+        }
+        return;
+    }
+    let Ok(entries) = fs::read_dir(path) else {
+        return;
+    };
+    for entry in entries.flatten() {
+        find_files(&entry.path(), extension, files);
+    }
+}
+
+fn span_range(span: Span<'_>) -> TextRange {
+    text_range(span.start(), span.end())
+}
+
+fn text_range(start: usize, end: usize) -> TextRange {
+    TextRange::new(
+        TextSize::new(start.try_into().unwrap()),
+        TextSize::new(end.try_into().unwrap()),
+    )
 }
 
 /// If `select` returns [`Some`],
@@ -209,14 +835,6 @@ fn colour_ast<R: RuleType>(
     ast: Pairs<'_, R>,
     mut select: impl FnMut(R) -> Option<TokenKind>,
 ) {
-    let offset2position = |offset: TextSize| {
-        let lc = source.offset_lookup().try_line_col(offset)?;
-        let WideLineCol { line, col } = source.offset_lookup().to_wide(WideEncoding::Utf16, lc)?;
-        Some(Position {
-            line,
-            character: col,
-        })
-    };
     for pair in ast {
         match select(pair.as_rule()) {
             Some(token_kind) => {
@@ -228,13 +846,10 @@ fn colour_ast<R: RuleType>(
                 let len = TextSize::of(pair.as_str());
                 for text_range in source.offset_lookup().lines(TextRange::at(offset, len)) {
                     let text_range = trim_end(text_range, source.text(), "\n");
-                    let (Some(start), Some(end)) = (
-                        offset2position(text_range.start()),
-                        offset2position(text_range.end()),
-                    ) else {
+                    let Some(range) = source.range(text_range) else {
                         continue;
                     };
-                    builder.push(Range { start, end }, token_kind);
+                    builder.push(range, token_kind);
                 }
             }
             None => colour_ast(
@@ -319,6 +934,30 @@ impl SourceFile {
         self.offset_lookup
             .get_or_init(|| LineIndex::new(&self.text))
     }
+    fn position(&self, offset: TextSize) -> Option<Position> {
+        let lc = self.offset_lookup().try_line_col(offset)?;
+        let WideLineCol { line, col } = self.offset_lookup().to_wide(WideEncoding::Utf16, lc)?;
+        Some(Position {
+            line,
+            character: col,
+        })
+    }
+    fn range(&self, range: TextRange) -> Option<Range> {
+        Some(Range {
+            start: self.position(range.start())?,
+            end: self.position(range.end())?,
+        })
+    }
+    fn offset(&self, position: Position) -> Option<TextSize> {
+        let lc = self.offset_lookup().to_utf8(
+            WideEncoding::Utf16,
+            WideLineCol {
+                line: position.line,
+                col: position.character,
+            },
+        )?;
+        self.offset_lookup().offset(lc)
+    }
 }
 
 macro_rules! legend {
@@ -394,3 +1033,57 @@ macro_rules! bail {
 	};
 }
 pub(crate) use bail;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn indexes_definitions_and_references() {
+        let file = FileIndex::parse(SourceFile::new(String::from(
+            "\
+// Pre stack: x, retdest
+// Post stack: (empty)
+%macro consume
+    POP
+%endmacro
+
+GLOBAL consume_and_return:
+    %consume
+    PUSH @SEGMENT_CODE
+    %jump(consume_and_return)
+",
+        )))
+        .unwrap();
+        let symbols = file
+            .occurrences
+            .iter()
+            .map(|it| (&it.symbol, &file.source.text()[it.range]))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            symbols,
+            [
+                (&Symbol::Macro("consume".into()), "consume"),
+                (
+                    &Symbol::Label("consume_and_return".into()),
+                    "consume_and_return"
+                ),
+                (&Symbol::Macro("consume".into()), "consume"),
+                (&Symbol::Constant("SEGMENT_CODE".into()), "SEGMENT_CODE"),
+                (&Symbol::Macro("jump".into()), "jump"),
+                (
+                    &Symbol::Label("consume_and_return".into()),
+                    "consume_and_return"
+                ),
+            ]
+        );
+        assert_eq!(
+            file.occurrences[0].definition.as_deref(),
+            Some("```\n%macro consume\n    POP\n%endmacro\n```\n\nPre stack: x, retdest  \nPost stack: (empty)")
+        );
+        assert_eq!(
+            file.occurrences[1].definition.as_deref(),
+            Some("```\nGLOBAL consume_and_return\n```")
+        );
+    }
+}
@@ -1,6 +1,9 @@
 //! Loads each kernel assembly file and concatenates them.
 
+#[cfg(test)]
+use std::cell::Cell;
 use std::collections::HashSet;
+use std::ops::Deref;
 
 use itertools::Itertools;
 use once_cell::sync::Lazy;
@@ -10,9 +13,9 @@ use crate::cpu::kernel::constants::evm_constants;
 use crate::cpu::kernel::parser::parse;
 
 pub const NUMBER_KERNEL_FILES: usize = if cfg!(feature = "eth_mainnet") {
-    157
+    173
 } else if cfg!(feature = "cdk_erigon") || cfg!(feature = "polygon_pos") {
-    154
+    155
 } else {
     // unreachable
     0
@@ -55,6 +58,8 @@ pub static KERNEL_FILES: [&str; NUMBER_KERNEL_FILES] = [
     include_str!("asm/core/touched_addresses.asm"),
     #[cfg(feature = "eth_mainnet")]
     include_str!("asm/core/withdrawals.asm"),
+    #[cfg(feature = "eth_mainnet")]
+    include_str!("asm/core/requests.asm"),
     include_str!("asm/core/precompiles/main.asm"),
     include_str!("asm/core/precompiles/ecrec.asm"),
     include_str!("asm/core/precompiles/sha256.asm"),
@@ -67,7 +72,32 @@ pub static KERNEL_FILES: [&str; NUMBER_KERNEL_FILES] = [
     include_str!("asm/core/precompiles/blake2_f.asm"),
     #[cfg(feature = "eth_mainnet")]
     include_str!("asm/core/precompiles/kzg_peval.asm"),
-    // include_str!("asm/curve/bls381/util.asm"),
+    #[cfg(feature = "eth_mainnet")]
+    include_str!("asm/core/precompiles/bls12_381.asm"),
+    #[cfg(feature = "eth_mainnet")]
+    include_str!("asm/curve/bls381/field_arithmetic/util.asm"),
+    #[cfg(feature = "eth_mainnet")]
+    include_str!("asm/curve/bls381/curve_arithmetic/curve_add.asm"),
+    #[cfg(feature = "eth_mainnet")]
+    include_str!("asm/curve/bls381/curve_arithmetic/curve_checks.asm"),
+    #[cfg(feature = "eth_mainnet")]
+    include_str!("asm/curve/bls381/curve_arithmetic/curve_mul.asm"),
+    #[cfg(feature = "eth_mainnet")]
+    include_str!("asm/curve/bls381/curve_arithmetic/final_exponent.asm"),
+    #[cfg(feature = "eth_mainnet")]
+    include_str!("asm/curve/bls381/curve_arithmetic/map_constants.asm"),
+    #[cfg(feature = "eth_mainnet")]
+    include_str!("asm/curve/bls381/curve_arithmetic/map_to_curve.asm"),
+    #[cfg(feature = "eth_mainnet")]
+    include_str!("asm/curve/bls381/curve_arithmetic/miller_loop.asm"),
+    #[cfg(feature = "eth_mainnet")]
+    include_str!("asm/curve/bls381/field_arithmetic/degree_6_mul.asm"),
+    #[cfg(feature = "eth_mainnet")]
+    include_str!("asm/curve/bls381/field_arithmetic/degree_12_mul.asm"),
+    #[cfg(feature = "eth_mainnet")]
+    include_str!("asm/curve/bls381/field_arithmetic/frobenius.asm"),
+    #[cfg(feature = "eth_mainnet")]
+    include_str!("asm/curve/bls381/field_arithmetic/inverse.asm"),
     include_str!("asm/curve/bn254/curve_arithmetic/constants.asm"),
     include_str!("asm/curve/bn254/curve_arithmetic/curve_add.asm"),
     include_str!("asm/curve/bn254/curve_arithmetic/curve_mul.asm"),
@@ -119,6 +149,7 @@ pub static KERNEL_FILES: [&str; NUMBER_KERNEL_FILES] = [
     include_str!("asm/hash/sha2/ops.asm"),
     include_str!("asm/hash/sha2/temp_words.asm"),
     include_str!("asm/hash/sha2/write_length.asm"),
+    include_str!("asm/history_storage.asm"),
     include_str!("asm/main.asm"),
     include_str!("asm/memory/core.asm"),
     include_str!("asm/memory/memcpy.asm"),
@@ -176,6 +207,8 @@ pub static KERNEL_FILES: [&str; NUMBER_KERNEL_FILES] = [
     include_str!("asm/transactions/type_2.asm"),
     #[cfg(feature = "eth_mainnet")]
     include_str!("asm/transactions/type_3.asm"),
+    #[cfg(feature = "eth_mainnet")]
+    include_str!("asm/transactions/type_4.asm"),
     include_str!("asm/util/assertions.asm"),
     include_str!("asm/util/basic_macros.asm"),
     include_str!("asm/util/keccak.asm"),
@@ -187,9 +220,40 @@ pub static KERNEL_FILES: [&str; NUMBER_KERNEL_FILES] = [
     include_str!("asm/cdk_pre_execution.asm"),
 ];
 
-pub static KERNEL: Lazy<Kernel> = Lazy::new(combined_kernel);
+pub static KERNEL: KernelRef = KernelRef(Lazy::new(combined_kernel));
 
-pub(crate) fn combined_kernel_from_files<const N: usize>(files: [&str; N]) -> Kernel {
+/// The [`KERNEL`], assembled on first use. Tests may run another assembly of
+/// the kernel in its place with `with_kernel`.
+pub struct KernelRef(Lazy<Kernel>);
+
+impl Deref for KernelRef {
+    type Target = Kernel;
+
+    fn deref(&self) -> &Kernel {
+        #[cfg(test)]
+        if let Some(kernel) = KERNEL_OVERRIDE.get() {
+            return kernel;
+        }
+        &self.0
+    }
+}
+
+#[cfg(test)]
+thread_local! {
+    static KERNEL_OVERRIDE: Cell<Option<&'static Kernel>> = const { Cell::new(None) };
+}
+
+/// Runs `f` with `kernel` in place of the [`KERNEL`] on the current thread.
+#[cfg(test)]
+pub(crate) fn with_kernel<R>(kernel: &'static Kernel, f: impl FnOnce() -> R) -> R {
+    let previous = KERNEL_OVERRIDE.replace(Some(kernel));
+    let result = f();
+    KERNEL_OVERRIDE.set(previous);
+    result
+}
+
+/// The features enabling conditional blocks of kernel assembly.
+pub(crate) fn active_features() -> HashSet<&'static str> {
     let mut active_features = HashSet::new();
     if cfg!(feature = "cdk_erigon") {
         active_features.insert("cdk_erigon");
@@ -198,7 +262,11 @@ pub(crate) fn combined_kernel_from_files<const N: usize>(files: [&str; N]) -> Ke
     } else {
         active_features.insert("eth_mainnet");
     }
+    active_features
+}
 
+pub(crate) fn combined_kernel_from_files<const N: usize>(files: [&str; N]) -> Kernel {
+    let active_features = active_features();
     let parsed_files = files
         .iter()
         .map(|f| parse(f, &active_features))
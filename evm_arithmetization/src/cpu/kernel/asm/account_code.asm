@@ -138,3 +138,173 @@ load_code_padded_ctd:
     MSTORE_GENERAL
     // stack: retdest, code_size
     JUMP
+
+/// EIP-7702 specific helpers.
+#[cfg(feature = eth_mainnet)]
+{
+    // Returns the address the given account delegates its code execution to, or 0 if the
+    // account's code is not a delegation designator `0xef0100 || delegate`.
+    // The delegate is provided non-deterministically, and checked against the account's code.
+    // Pre stack: address, retdest
+    // Post stack: delegate
+    global get_delegate:
+        // stack: address, retdest
+        %stack (address, retdest) -> (address, get_delegate_ctd, address, retdest)
+        %jump(get_delegate_unchecked)
+    get_delegate_ctd:
+        // stack: delegate, address, retdest
+        DUP1 %jumpi(get_delegate_return)
+        // stack: 0, address, retdest
+        DUP2 %extcodehash
+        DUP1 ISZERO
+        SWAP1 %eq_const(@EMPTY_STRING_HASH)
+        OR
+        %jumpi(get_delegate_return)
+        // stack: 0, address, retdest
+        // The prover claimed that the code isn't a delegation designator, which we check
+        // by loading it in a fresh context.
+        POP
+        %next_context_id
+        %stack (ctx, address, retdest) -> (address, ctx, get_delegate_none_loaded, ctx, retdest)
+        %jump(load_code)
+    get_delegate_none_loaded:
+        // stack: code_size, ctx, retdest
+        DUP2 SWAP1
+        %assert_not_designator
+        // stack: ctx, retdest
+        %prune_context
+        %stack (retdest) -> (retdest, 0)
+        JUMP
+    get_delegate_return:
+        // stack: delegate, address, retdest
+        %stack (delegate, address, retdest) -> (retdest, delegate)
+        JUMP
+
+    // Identical to get_delegate, but a 0 delegate is not checked: callers relying on it
+    // must check that the account's code is indeed not a delegation designator.
+    // Pre stack: address, retdest
+    // Post stack: delegate
+    global get_delegate_unchecked:
+        // stack: address, retdest
+        %extcodehash
+        // stack: codehash, retdest
+        DUP1 ISZERO %jumpi(get_delegate_none)
+        DUP1 %eq_const(@EMPTY_STRING_HASH) %jumpi(get_delegate_none)
+        PROVER_INPUT(delegation)
+        // stack: delegate, codehash, retdest
+        DUP1 ISZERO %jumpi(get_delegate_none_unchecked)
+        DUP1 %delegation_designator
+        %keccak256_word(23) // DELEGATION_DESIGNATOR_LEN
+        // stack: designator_hash, delegate, codehash, retdest
+        DUP3 %assert_eq
+        %stack (delegate, codehash, retdest) -> (retdest, delegate)
+        JUMP
+    get_delegate_none_unchecked:
+        // stack: 0, codehash, retdest
+        POP
+    get_delegate_none:
+        // stack: codehash, retdest
+        %stack (codehash, retdest) -> (retdest, 0)
+        JUMP
+
+    %macro get_delegate
+        %stack (address) -> (address, %%after)
+        %jump(get_delegate)
+    %%after:
+    %endmacro
+
+    %macro get_delegate_unchecked
+        %stack (address) -> (address, %%after)
+        %jump(get_delegate_unchecked)
+    %%after:
+    %endmacro
+
+    // Returns the delegation designator `0xef0100 || delegate`, as a 23-byte word.
+    %macro delegation_designator
+        // stack: delegate
+        PUSH @DELEGATION_DESIGNATOR_PREFIX
+        %shl_const(160)
+        ADD
+    %endmacro
+
+    // Panics if the code of size `code_size` stored in the given context is a delegation designator.
+    // Pre stack: code_size, ctx
+    // Post stack: (empty)
+    %macro assert_not_designator
+        // stack: code_size, ctx
+        %eq_const(@DELEGATION_DESIGNATOR_LEN)
+        SWAP1
+        // stack: ctx, has_designator_len
+        // ctx == addr, as SEGMENT_CODE == offset == 0.
+        PUSH 3 SWAP1
+        MLOAD_32BYTES
+        // stack: code_prefix, has_designator_len
+        %eq_const(@DELEGATION_DESIGNATOR_PREFIX)
+        AND
+        %jumpi(panic)
+    %endmacro
+
+    // Identical to load_code_padded, but follows delegation designators: if the account at
+    // `address` delegates to another account, the code of the latter is loaded instead.
+    // Pre stack: address, ctx, retdest
+    // Post stack: code_size
+    global load_delegated_code_padded:
+        // stack: address, ctx, retdest
+        // A 0 delegate is checked below, once the code is loaded.
+        DUP1 %get_delegate_unchecked
+        // stack: delegate, address, ctx, retdest
+        DUP1 ISZERO %jumpi(load_non_delegated_code_padded)
+        // stack: delegate, address, ctx, retdest
+        SWAP1 POP
+        %jump(load_code_padded)
+    load_non_delegated_code_padded:
+        // stack: 0, address, ctx, retdest
+        %stack (zero, address, ctx, retdest) -> (address, ctx, load_non_delegated_code_padded_ctd, ctx, retdest)
+        %jump(load_code_padded)
+    load_non_delegated_code_padded_ctd:
+        // stack: code_size, ctx, retdest
+        // The prover claimed that the code isn't a delegation designator, which we check here.
+        DUP2 DUP2
+        %assert_not_designator
+        %stack (code_size, ctx, retdest) -> (retdest, code_size)
+        JUMP
+
+    // Returns 1 if the code of the given account is empty or is a delegation designator, 0 otherwise.
+    // Pre stack: address, retdest
+    // Post stack: is_code_empty_or_delegated
+    global is_code_empty_or_delegated:
+        // stack: address, retdest
+        DUP1 %extcodehash
+        // stack: codehash, address, retdest
+        DUP1 ISZERO
+        SWAP1 %eq_const(@EMPTY_STRING_HASH)
+        OR
+        %jumpi(code_empty_or_delegated)
+        // stack: address, retdest
+        %get_delegate
+        // stack: delegate, retdest
+        ISZERO ISZERO
+        SWAP1 JUMP
+    code_empty_or_delegated:
+        %stack (address, retdest) -> (retdest, 1)
+        JUMP
+
+    // Adds the delegate of the given account, if any, to the accessed addresses.
+    %macro warm_delegate
+        // stack: address
+        %get_delegate
+        DUP1 ISZERO %jumpi(%%no_delegate)
+        %insert_accessed_addresses_no_return
+        %jump(%%after)
+    %%no_delegate:
+        // stack: 0
+        POP
+    %%after:
+    %endmacro
+
+    %macro is_code_empty_or_delegated
+        %stack (address) -> (address, %%after)
+        %jump(is_code_empty_or_delegated)
+    %%after:
+    %endmacro
+}
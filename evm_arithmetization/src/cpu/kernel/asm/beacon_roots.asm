@@ -7,7 +7,8 @@
 /// Post-stack: (empty)
 global set_beacon_root:
     // stack: (empty)
-    PUSH txn_loop
+    // Once done, update the block hash history (EIP-2935).
+    PUSH set_parent_block_hash
     %timestamp
     // stack: timestamp, retdest
     PUSH @HISTORY_BUFFER_LENGTH
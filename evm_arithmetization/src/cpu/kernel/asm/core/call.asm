@@ -23,6 +23,10 @@ global sys_call:
     // stack: address, gas, kexit_info, value, args_offset, args_size, ret_offset, ret_size
     %u256_to_addr // Truncate to 160 bits
     DUP1 %insert_accessed_addresses
+    #[cfg(feature = eth_mainnet)]
+    {
+        %charge_delegate_access
+    }
 
     %call_charge_gas(1, 1)
     %check_depth
@@ -76,6 +80,10 @@ global sys_callcode:
     // stack: address, gas, kexit_info, value, args_offset, args_size, ret_offset, ret_size
     %u256_to_addr // Truncate to 160 bits
     DUP1 %insert_accessed_addresses
+    #[cfg(feature = eth_mainnet)]
+    {
+        %charge_delegate_access
+    }
 
     %call_charge_gas(1, 0)
     %check_depth
@@ -132,6 +140,10 @@ global sys_staticcall:
     // stack: address, gas, kexit_info, args_offset, args_size, ret_offset, ret_size
     %u256_to_addr // Truncate to 160 bits
     DUP1 %insert_accessed_addresses
+    #[cfg(feature = eth_mainnet)]
+    {
+        %charge_delegate_access
+    }
 
     // Add a value of 0 to the stack. Slightly inefficient but that way we can reuse %call_charge_gas.
     %stack (cold_access, address, gas, kexit_info) -> (cold_access, address, gas, kexit_info, 0)
@@ -187,6 +199,10 @@ global sys_delegatecall:
     // stack: address, gas, kexit_info, args_offset, args_size, ret_offset, ret_size
     %u256_to_addr // Truncate to 160 bits
     DUP1 %insert_accessed_addresses
+    #[cfg(feature = eth_mainnet)]
+    {
+        %charge_delegate_access
+    }
 
     // Add a value of 0 to the stack. Slightly inefficient but that way we can reuse %call_charge_gas.
     %stack (cold_access, address, gas, kexit_info) -> (cold_access, address, gas, kexit_info, 0)
@@ -396,7 +412,15 @@ call_too_deep:
 
 %macro set_new_ctx_code
     %stack (address, new_ctx) -> (address, new_ctx, %%after, new_ctx)
-    %jump(load_code_padded)
+    // EIP-7702: Accounts with a delegation designator execute the code of their delegate.
+    #[cfg(feature = eth_mainnet)]
+    {
+        %jump(load_delegated_code_padded)
+    }
+    #[cfg(not(feature = eth_mainnet))]
+    {
+        %jump(load_code_padded)
+    }
 %%after:
     %set_new_ctx_code_size
     // stack: new_ctx
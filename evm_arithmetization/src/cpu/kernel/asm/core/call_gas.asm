@@ -110,3 +110,31 @@ charge_cold_access_gas:
     SWAP4
     // stack: is_call_or_staticcall, is_call_or_callcode, address, gas, kexit_info, value, retdest
     %jump(call_charge_gas_contd)
+
+#[cfg(feature = eth_mainnet)]
+{
+    // EIP-7702: If `address` has a delegation designator, charges the warm or cold access
+    // cost of its delegate, and adds the latter to the accessed addresses.
+    // Pre stack: cold_access, address, gas, kexit_info
+    // Post stack: cold_access, address, gas, kexit_info
+    %macro charge_delegate_access
+        DUP2 %get_delegate
+        // stack: delegate, cold_access, address, gas, kexit_info
+        DUP1 ISZERO %jumpi(%%no_delegate)
+        %insert_accessed_addresses
+        // stack: delegate_cold_access, cold_access, address, gas, kexit_info
+        PUSH @GAS_COLDACCOUNTACCESS_MINUS_WARMACCESS
+        MUL
+        PUSH @GAS_WARMACCESS
+        ADD
+        // stack: cost, cold_access, address, gas, kexit_info
+        %stack (cost, cold_access, address, gas, kexit_info) -> (cost, kexit_info, cold_access, address, gas)
+        %charge_gas
+        %stack (kexit_info, cold_access, address, gas) -> (cold_access, address, gas, kexit_info)
+        %jump(%%after)
+    %%no_delegate:
+        // stack: 0, cold_access, address, gas, kexit_info
+        POP
+    %%after:
+    %endmacro
+}
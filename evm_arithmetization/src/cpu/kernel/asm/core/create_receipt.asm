@@ -60,6 +60,7 @@ process_receipt_after_bloom:
     DUP1 %eq_const(1) %jumpi(receipt_nonzero_type)
     DUP1 %eq_const(2) %jumpi(receipt_nonzero_type)
     DUP1 %eq_const(3) %jumpi(receipt_nonzero_type)
+    DUP1 %eq_const(4) %jumpi(receipt_nonzero_type)
     // If we are here, we are dealing with a legacy transaction, and we do not need to write the type.
     POP
 
@@ -223,17 +224,17 @@ process_receipt_after_write:
     JUMP
     
 receipt_nonzero_type:
-    // stack: txn_type, receipt_ptr, payload_len, status, new_cum_gas, txn_nb, new_cum_gas, txn_nb, retdest
+    // stack: txn_type, receipt_ptr, payload_len, status, new_cum_gas, txn_nb, new_cum_gas, txn_nb, num_nibbles, retdest
     %append_to_trie_data
     %jump(process_receipt_after_type)
 
 failed_receipt:
-    // stack: status, new_cum_gas, num_nibbles, txn_nb
+    // stack: status, new_cum_gas, txn_nb, num_nibbles
     // It is the receipt of a failed transaction, so set num_logs to 0. This will also lead to Bloom filter = 0.
     PUSH 0
     %mstore_global_metadata(@GLOBAL_METADATA_LOGS_LEN)
     PUSH 0 %mstore_global_metadata(@GLOBAL_METADATA_LOGS_PAYLOAD_LEN)
-    // stack: status, new_cum_gas, num_nibbles, txn_nb
+    // stack: status, new_cum_gas, txn_nb, num_nibbles
     %jump(process_receipt_after_status)
 
 %macro process_receipt
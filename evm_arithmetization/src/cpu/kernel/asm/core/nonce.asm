@@ -1,6 +1,6 @@
 // Get the nonce of the given account.
 // Pre stack: address, retdest
-// Post stack: (empty)
+// Post stack: nonce
 global nonce:
     // stack: address, retdest
     %mpt_read_state_trie
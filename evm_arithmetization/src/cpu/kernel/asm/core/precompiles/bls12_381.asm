@@ -0,0 +1,496 @@
+// BLS12-381 precompiles, see EIP-2537.
+//
+// The decoding and validation of the inputs, the subgroup checks and the operations themselves are
+// carried out here, on top of the curve arithmetic of curve/bls381, which only takes from the prover
+// hints that it checks. The points are decoded to the offsets 1000 and 1008 of the current kernel
+// general memory, and the multi-scalar multiplications accumulate at offset 1016.
+
+%macro bls12_381_prelude
+    // stack: address, retdest, new_ctx, (old stack)
+    %pop2
+    // stack: new_ctx, (old stack)
+    %set_new_ctx_parent_pc(after_precompile)
+    // stack: new_ctx, (old stack)
+    DUP1
+    SET_CONTEXT
+    %checkpoint // Checkpoint
+    %increment_call_depth
+    // stack: (empty)
+    PUSH @IS_KERNEL // true
+    // stack: kexit_info
+%endmacro
+
+// Faults unless the calldata size is a nonzero multiple of `pair_len`, and
+// returns the number of pairs `k`.
+// Pre stack: kexit_info
+// Post stack: k, kexit_info
+%macro bls12_381_num_pairs(pair_len)
+    // stack: kexit_info
+    %calldatasize
+    // stack: calldata_size, kexit_info
+    DUP1 ISZERO %jumpi(fault_exception)
+    DUP1 %mod_const($pair_len) %jumpi(fault_exception)
+    %div_const($pair_len)
+    // stack: k, kexit_info
+%endmacro
+
+// Computes the gas cost of a multi-scalar multiplication of `k` pairs, i.e.
+//     k * mul_gas * discount(k) / 1000
+// where `discount(k)` is given by the provided table, whose last entry applies to all k >= 128.
+%macro bls12_381_msm_gas(discounts, mul_gas)
+    // stack: k
+    DUP1 %min_const(128)
+    %decrement
+    // stack: index, k
+    %mload_kernel_code_u32($discounts)
+    // stack: discount, k
+    MUL
+    PUSH $mul_gas
+    MUL
+    %div_const(1000)
+    // stack: gas
+%endmacro
+
+global precompile_bls12_g1add:
+    %bls12_381_prelude
+global bls12_g1add:
+    // stack: kexit_info
+    %charge_gas_const(@BLS12_G1ADD_GAS)
+    %calldatasize %eq_const(256) ISZERO %jumpi(fault_exception)
+    // stack: kexit_info
+    PUSH 1000 %build_current_general_address
+    PUSH 0
+    %bls12_381_load_g1
+    PUSH 1008 %build_current_general_address
+    PUSH 128
+    %bls12_381_load_g1
+    %bls381_curve_add_at(1000, 1008, 1000)
+    // stack: kexit_info
+    %stack () -> (1000, 4, 2)
+    %jump(bls12_381_return_point)
+
+global precompile_bls12_g1msm:
+    %bls12_381_prelude
+global bls12_g1msm:
+    // stack: kexit_info
+    %bls12_381_num_pairs(160)
+    // stack: k, kexit_info
+    DUP1
+    %bls12_381_msm_gas(bls12_g1msm_discounts, @BLS12_G1MSM_MUL_GAS)
+    %stack (gas, k, kexit_info) -> (gas, kexit_info, k)
+    %charge_gas
+    SWAP1
+    // stack: k, kexit_info
+    PUSH 1016 %build_current_general_address
+    %set_infinity_bls381
+    PUSH 0
+bls12_g1msm_loop:
+    // stack: i, k, kexit_info
+    DUP2 DUP2 EQ %jumpi(bls12_g1msm_end)
+    DUP1 %mul_const(160)
+    PUSH 1000 %build_current_general_address
+    DUP2
+    %bls12_381_load_g1
+    PUSH 1000 %build_current_general_address
+    %bls12_381_assert_in_subgroup(bls381_is_in_g1)
+    // stack: offset, i, k, kexit_info
+    %add_const(128)
+    %bls12_381_scalar_mul_acc
+    // stack: i, k, kexit_info
+    %increment
+    %jump(bls12_g1msm_loop)
+bls12_g1msm_end:
+    // stack: i, k, kexit_info
+    %stack (i, k) -> (1016, 4, 2)
+    %jump(bls12_381_return_point)
+
+global precompile_bls12_g2add:
+    %bls12_381_prelude
+global bls12_g2add:
+    // stack: kexit_info
+    %charge_gas_const(@BLS12_G2ADD_GAS)
+    %calldatasize %eq_const(512) ISZERO %jumpi(fault_exception)
+    // stack: kexit_info
+    PUSH 1000 %build_current_general_address
+    PUSH 0
+    %bls12_381_load_g2
+    PUSH 1008 %build_current_general_address
+    PUSH 256
+    %bls12_381_load_g2
+    %bls381_curve_add_at(1000, 1008, 1000)
+    // stack: kexit_info
+    %stack () -> (1000, 2, 4)
+    %jump(bls12_381_return_point)
+
+global precompile_bls12_g2msm:
+    %bls12_381_prelude
+global bls12_g2msm:
+    // stack: kexit_info
+    %bls12_381_num_pairs(288)
+    // stack: k, kexit_info
+    DUP1
+    %bls12_381_msm_gas(bls12_g2msm_discounts, @BLS12_G2MSM_MUL_GAS)
+    %stack (gas, k, kexit_info) -> (gas, kexit_info, k)
+    %charge_gas
+    SWAP1
+    // stack: k, kexit_info
+    PUSH 1016 %build_current_general_address
+    %set_infinity_bls381
+    PUSH 0
+bls12_g2msm_loop:
+    // stack: i, k, kexit_info
+    DUP2 DUP2 EQ %jumpi(bls12_g2msm_end)
+    DUP1 %mul_const(288)
+    PUSH 1000 %build_current_general_address
+    DUP2
+    %bls12_381_load_g2
+    PUSH 1000 %build_current_general_address
+    %bls12_381_assert_in_subgroup(bls381_is_in_g2)
+    // stack: offset, i, k, kexit_info
+    %add_const(256)
+    %bls12_381_scalar_mul_acc
+    // stack: i, k, kexit_info
+    %increment
+    %jump(bls12_g2msm_loop)
+bls12_g2msm_end:
+    // stack: i, k, kexit_info
+    %stack (i, k) -> (1016, 2, 4)
+    %jump(bls12_381_return_point)
+
+global precompile_bls12_pairing:
+    %bls12_381_prelude
+global bls12_pairing:
+    // stack: kexit_info
+    %bls12_381_num_pairs(384)
+    // stack: k, kexit_info
+    DUP1
+    %mul_const(@BLS12_PAIRING_DYNAMIC_GAS)
+    %add_const(@BLS12_PAIRING_STATIC_GAS)
+    %stack (gas, k, kexit_info) -> (gas, kexit_info, k)
+    %charge_gas
+    SWAP1
+    // stack: k, kexit_info
+    // The product of the Miller loops is accumulated at offset 130, where the final exponentiation
+    // takes its input.
+    PUSH 130 %build_current_general_address
+    %set_unit_fp381_12
+    PUSH 0
+bls12_pairing_loop:
+    // stack: i, k, kexit_info
+    DUP2 DUP2 EQ %jumpi(bls12_pairing_end)
+    DUP1 %mul_const(384)
+    PUSH 1000 %build_current_general_address
+    DUP2
+    %bls12_381_load_g1
+    // stack: offset, i, k, kexit_info
+    PUSH 1008 %build_current_general_address
+    SWAP1
+    %add_const(128)
+    %bls12_381_load_g2
+    PUSH 1000 %build_current_general_address
+    %bls12_381_assert_in_subgroup(bls381_is_in_g1)
+    PUSH 1008 %build_current_general_address
+    %bls12_381_assert_in_subgroup(bls381_is_in_g2)
+    // stack: i, k, kexit_info
+    // Pairs with a point at infinity don't contribute to the product.
+    PUSH 1000 %build_current_general_address
+    %is_infinity_bls381
+    PUSH 1008 %build_current_general_address
+    %is_infinity_bls381
+    OR %jumpi(bls12_pairing_next)
+    // The Miller loop takes P at offset 190 and Q at offset 180.
+    %load_fp381(1000)
+    PUSH 190 %build_current_general_address
+    %store_fp381
+    %load_fp381(1004)
+    PUSH 192 %build_current_general_address
+    %store_fp381
+    %load_fp381_2(1008)
+    %store_fp381_2(180)
+    %load_fp381_2(1012)
+    %store_fp381_2(184)
+    PUSH bls12_pairing_miller_loop_end
+    %jump(bls381_miller_loop)
+bls12_pairing_miller_loop_end:
+    // stack: i, k, kexit_info
+    %mul_fp381_12_at(130, 100, 130)
+bls12_pairing_next:
+    // stack: i, k, kexit_info
+    %increment
+    %jump(bls12_pairing_loop)
+bls12_pairing_end:
+    // stack: i, k, kexit_info
+    %pop2
+    // As the BLS parameter is negative, the Miller loops are conjugated.
+    PUSH 130 %build_current_general_address
+    %conj_fp381_12
+    PUSH bls12_pairing_final_exponent_end
+    %jump(bls381_final_exponent)
+bls12_pairing_final_exponent_end:
+    // stack: kexit_info
+    PUSH 130 %build_current_general_address
+    %is_unit_fp381_12
+    // stack: is_unit, kexit_info
+    %mstore_parent_context_metadata(@CTX_METADATA_RETURNDATA_SIZE, 32)
+    %mload_context_metadata(@CTX_METADATA_PARENT_CONTEXT)
+    PUSH @SEGMENT_RETURNDATA
+    %build_address_no_offset
+    // stack: addr, is_unit, kexit_info
+    MSTORE_32BYTES_32
+    POP
+    %jump(pop_and_return_success)
+
+global precompile_bls12_map_fp_to_g1:
+    %bls12_381_prelude
+global bls12_map_fp_to_g1:
+    // stack: kexit_info
+    %charge_gas_const(@BLS12_MAP_FP_TO_G1_GAS)
+    %calldatasize %eq_const(64) ISZERO %jumpi(fault_exception)
+    // stack: kexit_info
+    PUSH 700 %build_current_general_address
+    PUSH 0
+    %bls12_381_load_fp_as_fp2
+    PUSH bls12_map_fp_to_g1_end
+    %jump(bls381_map_to_g1)
+bls12_map_fp_to_g1_end:
+    // stack: kexit_info
+    %stack () -> (708, 4, 2)
+    %jump(bls12_381_return_point)
+
+global precompile_bls12_map_fp2_to_g2:
+    %bls12_381_prelude
+global bls12_map_fp2_to_g2:
+    // stack: kexit_info
+    %charge_gas_const(@BLS12_MAP_FP2_TO_G2_GAS)
+    %calldatasize %eq_const(128) ISZERO %jumpi(fault_exception)
+    // stack: kexit_info
+    PUSH 700 %build_current_general_address
+    PUSH 0
+    %bls12_381_load_fp2
+    PUSH bls12_map_fp2_to_g2_end
+    %jump(bls381_map_to_g2)
+bls12_map_fp2_to_g2_end:
+    // stack: kexit_info
+    %stack () -> (708, 2, 4)
+    %jump(bls12_381_return_point)
+
+// Loads the point of E encoded in the 128 bytes of calldata at offset to addr, faulting unless its
+// coordinates are canonical and it lies on E.
+global bls12_381_load_g1:
+    // stack: offset, addr, retdest
+    DUP2 %add_const(4)
+    DUP2 %add_const(64)
+    %bls12_381_load_fp_as_fp2
+    DUP2 DUP2
+    %bls12_381_load_fp_as_fp2
+    // stack: offset, addr, retdest
+    POP
+    PUSH bls12_381_assert_on_curve
+    SWAP1
+    %jump(bls381_is_on_curve_g1)
+
+// Loads the point of E' encoded in the 256 bytes of calldata at offset to addr, faulting unless its
+// coordinates are canonical and it lies on E'.
+global bls12_381_load_g2:
+    // stack: offset, addr, retdest
+    DUP2 %add_const(4)
+    DUP2 %add_const(128)
+    %bls12_381_load_fp2
+    DUP2 DUP2
+    %bls12_381_load_fp2
+    // stack: offset, addr, retdest
+    POP
+    PUSH bls12_381_assert_on_curve
+    SWAP1
+    %jump(bls381_is_on_curve_g2)
+
+bls12_381_assert_on_curve:
+    // stack: is_on_curve, retdest
+    ISZERO %jumpi(fault_exception)
+    JUMP
+
+%macro bls12_381_load_g1
+    // stack: offset, addr
+    %stack (offset, addr) -> (offset, addr, %%after)
+    %jump(bls12_381_load_g1)
+%%after:
+%endmacro
+
+%macro bls12_381_load_g2
+    // stack: offset, addr
+    %stack (offset, addr) -> (offset, addr, %%after)
+    %jump(bls12_381_load_g2)
+%%after:
+%endmacro
+
+// Faults unless the point at addr lies in the subgroup, as given by the function is_in_subgroup.
+%macro bls12_381_assert_in_subgroup(is_in_subgroup)
+    // stack: addr
+    %stack (addr) -> (addr, %%after)
+    %jump($is_in_subgroup)
+%%after:
+    // stack: is_in_subgroup
+    ISZERO %jumpi(fault_exception)
+%endmacro
+
+// Adds [s] P to the point at offset 1016, where P is the point at offset 1000 and s is the scalar
+// encoded in the 32 bytes of calldata at offset.
+%macro bls12_381_scalar_mul_acc
+    // stack: offset
+    PUSH 1000 %build_current_general_address
+    SWAP1
+    %bls12_381_calldata_word
+    PUSH 1000 %build_current_general_address
+    // stack: P, s, P
+    %bls381_curve_mul
+    %bls381_curve_add_at(1016, 1000, 1016)
+%endmacro
+
+// Loads the element of Fp2 encoded in the 128 bytes of calldata at offset to addr.
+%macro bls12_381_load_fp2
+    // stack: offset, addr
+    DUP1 %add_const(64)
+    %bls12_381_load_fp
+    DUP3
+    %bls12_381_load_fp
+    // stack: x_re: 2, x_im: 2, offset, addr
+    DUP6
+    %store_fp381_2
+    %pop2
+%endmacro
+
+// Loads the element of Fp encoded in the 64 bytes of calldata at offset to addr, as an element of Fp2
+// with zero imaginary part.
+%macro bls12_381_load_fp_as_fp2
+    // stack: offset, addr
+    PUSH 0
+    PUSH 0
+    DUP3
+    %bls12_381_load_fp
+    // stack: x: 2, 0, 0, offset, addr
+    DUP6
+    %store_fp381_2
+    %pop2
+%endmacro
+
+// Returns the element of Fp encoded in the 64 bytes of calldata at offset, as its two 32-byte
+// big-endian halves, faulting unless it is canonical. In particular, the top 16 bytes must be zero.
+%macro bls12_381_load_fp
+    // stack: offset
+    DUP1 %add_const(32)
+    %bls12_381_calldata_word
+    SWAP1
+    %bls12_381_calldata_word
+    SWAP1
+    // stack: x0, x1
+    DUP2 DUP2
+    %is_canonical_fp381
+    ISZERO %jumpi(fault_exception)
+%endmacro
+
+%macro bls12_381_calldata_word
+    // stack: offset
+    GET_CONTEXT
+    %stack (ctx, offset) -> (ctx, @SEGMENT_CALLDATA, offset, 32)
+    %build_address
+    MLOAD_32BYTES
+%endmacro
+
+// Writes to the parent's return data the count elements of Fp at addr, addr + step, ..., each as 64
+// bytes, and returns.
+global bls12_381_return_point:
+    // stack: offset, step, count, kexit_info
+    %build_current_general_address
+    DUP3 %mul_const(64)
+    %mstore_parent_context_metadata(@CTX_METADATA_RETURNDATA_SIZE)
+    %mload_context_metadata(@CTX_METADATA_PARENT_CONTEXT)
+    PUSH @SEGMENT_RETURNDATA
+    %build_address_no_offset
+bls12_381_return_point_loop:
+    // stack: out, addr, step, count, kexit_info
+    DUP4 ISZERO %jumpi(bls12_381_return_point_end)
+    DUP2
+    %load_fp381
+    // stack: x0, x1, out, addr, step, count, kexit_info
+    SWAP1
+    DUP3
+    MSTORE_32BYTES_32
+    MSTORE_32BYTES_32
+    // stack: out + 64, out, addr, step, count, kexit_info
+    SWAP1 POP
+    SWAP1 DUP3 ADD SWAP1
+    SWAP3 %decrement SWAP3
+    %jump(bls12_381_return_point_loop)
+bls12_381_return_point_end:
+    // stack: out, addr, step, count, kexit_info
+    %pop4
+    %jump(pop_and_return_success)
+
+// Discounts for multi-scalar multiplications, as big-endian u32s, see EIP-2537.
+global bls12_g1msm_discounts:
+    BYTES 0, 0, 3, 232, 0, 0, 3, 181, 0, 0, 3, 80, 0, 0, 3, 29 // k = 1..4
+    BYTES 0, 0, 2, 252, 0, 0, 2, 238, 0, 0, 2, 226, 0, 0, 2, 216 // k = 5..8
+    BYTES 0, 0, 2, 207, 0, 0, 2, 200, 0, 0, 2, 193, 0, 0, 2, 186 // k = 9..12
+    BYTES 0, 0, 2, 180, 0, 0, 2, 175, 0, 0, 2, 170, 0, 0, 2, 165 // k = 13..16
+    BYTES 0, 0, 2, 161, 0, 0, 2, 157, 0, 0, 2, 153, 0, 0, 2, 149 // k = 17..20
+    BYTES 0, 0, 2, 146, 0, 0, 2, 142, 0, 0, 2, 139, 0, 0, 2, 136 // k = 21..24
+    BYTES 0, 0, 2, 133, 0, 0, 2, 130, 0, 0, 2, 128, 0, 0, 2, 125 // k = 25..28
+    BYTES 0, 0, 2, 123, 0, 0, 2, 120, 0, 0, 2, 118, 0, 0, 2, 115 // k = 29..32
+    BYTES 0, 0, 2, 113, 0, 0, 2, 111, 0, 0, 2, 109, 0, 0, 2, 107 // k = 33..36
+    BYTES 0, 0, 2, 105, 0, 0, 2, 103, 0, 0, 2, 101, 0, 0, 2, 99 // k = 37..40
+    BYTES 0, 0, 2, 97, 0, 0, 2, 96, 0, 0, 2, 94, 0, 0, 2, 92 // k = 41..44
+    BYTES 0, 0, 2, 91, 0, 0, 2, 89, 0, 0, 2, 87, 0, 0, 2, 86 // k = 45..48
+    BYTES 0, 0, 2, 84, 0, 0, 2, 83, 0, 0, 2, 81, 0, 0, 2, 80 // k = 49..52
+    BYTES 0, 0, 2, 79, 0, 0, 2, 77, 0, 0, 2, 76, 0, 0, 2, 74 // k = 53..56
+    BYTES 0, 0, 2, 73, 0, 0, 2, 72, 0, 0, 2, 70, 0, 0, 2, 69 // k = 57..60
+    BYTES 0, 0, 2, 68, 0, 0, 2, 67, 0, 0, 2, 65, 0, 0, 2, 64 // k = 61..64
+    BYTES 0, 0, 2, 63, 0, 0, 2, 62, 0, 0, 2, 61, 0, 0, 2, 60 // k = 65..68
+    BYTES 0, 0, 2, 58, 0, 0, 2, 57, 0, 0, 2, 56, 0, 0, 2, 55 // k = 69..72
+    BYTES 0, 0, 2, 54, 0, 0, 2, 53, 0, 0, 2, 52, 0, 0, 2, 51 // k = 73..76
+    BYTES 0, 0, 2, 50, 0, 0, 2, 49, 0, 0, 2, 48, 0, 0, 2, 47 // k = 77..80
+    BYTES 0, 0, 2, 46, 0, 0, 2, 45, 0, 0, 2, 44, 0, 0, 2, 43 // k = 81..84
+    BYTES 0, 0, 2, 42, 0, 0, 2, 41, 0, 0, 2, 40, 0, 0, 2, 39 // k = 85..88
+    BYTES 0, 0, 2, 38, 0, 0, 2, 37, 0, 0, 2, 36, 0, 0, 2, 35 // k = 89..92
+    BYTES 0, 0, 2, 35, 0, 0, 2, 34, 0, 0, 2, 33, 0, 0, 2, 32 // k = 93..96
+    BYTES 0, 0, 2, 31, 0, 0, 2, 30, 0, 0, 2, 29, 0, 0, 2, 28 // k = 97..100
+    BYTES 0, 0, 2, 28, 0, 0, 2, 27, 0, 0, 2, 26, 0, 0, 2, 25 // k = 101..104
+    BYTES 0, 0, 2, 24, 0, 0, 2, 24, 0, 0, 2, 23, 0, 0, 2, 22 // k = 105..108
+    BYTES 0, 0, 2, 21, 0, 0, 2, 20, 0, 0, 2, 20, 0, 0, 2, 19 // k = 109..112
+    BYTES 0, 0, 2, 18, 0, 0, 2, 17, 0, 0, 2, 16, 0, 0, 2, 16 // k = 113..116
+    BYTES 0, 0, 2, 15, 0, 0, 2, 14, 0, 0, 2, 13, 0, 0, 2, 13 // k = 117..120
+    BYTES 0, 0, 2, 12, 0, 0, 2, 11, 0, 0, 2, 10, 0, 0, 2, 10 // k = 121..124
+    BYTES 0, 0, 2, 9, 0, 0, 2, 8, 0, 0, 2, 8, 0, 0, 2, 7 // k = 125..128
+
+global bls12_g2msm_discounts:
+    BYTES 0, 0, 3, 232, 0, 0, 3, 232, 0, 0, 3, 155, 0, 0, 3, 116 // k = 1..4
+    BYTES 0, 0, 3, 87, 0, 0, 3, 64, 0, 0, 3, 44, 0, 0, 3, 28 // k = 5..8
+    BYTES 0, 0, 3, 14, 0, 0, 3, 2, 0, 0, 2, 247, 0, 0, 2, 237 // k = 9..12
+    BYTES 0, 0, 2, 228, 0, 0, 2, 220, 0, 0, 2, 212, 0, 0, 2, 205 // k = 13..16
+    BYTES 0, 0, 2, 199, 0, 0, 2, 192, 0, 0, 2, 187, 0, 0, 2, 181 // k = 17..20
+    BYTES 0, 0, 2, 176, 0, 0, 2, 171, 0, 0, 2, 167, 0, 0, 2, 162 // k = 21..24
+    BYTES 0, 0, 2, 158, 0, 0, 2, 154, 0, 0, 2, 151, 0, 0, 2, 147 // k = 25..28
+    BYTES 0, 0, 2, 143, 0, 0, 2, 140, 0, 0, 2, 137, 0, 0, 2, 134 // k = 29..32
+    BYTES 0, 0, 2, 131, 0, 0, 2, 128, 0, 0, 2, 125, 0, 0, 2, 122 // k = 33..36
+    BYTES 0, 0, 2, 120, 0, 0, 2, 117, 0, 0, 2, 115, 0, 0, 2, 112 // k = 37..40
+    BYTES 0, 0, 2, 110, 0, 0, 2, 108, 0, 0, 2, 106, 0, 0, 2, 103 // k = 41..44
+    BYTES 0, 0, 2, 101, 0, 0, 2, 99, 0, 0, 2, 97, 0, 0, 2, 95 // k = 45..48
+    BYTES 0, 0, 2, 94, 0, 0, 2, 92, 0, 0, 2, 90, 0, 0, 2, 88 // k = 49..52
+    BYTES 0, 0, 2, 86, 0, 0, 2, 85, 0, 0, 2, 83, 0, 0, 2, 81 // k = 53..56
+    BYTES 0, 0, 2, 80, 0, 0, 2, 78, 0, 0, 2, 77, 0, 0, 2, 75 // k = 57..60
+    BYTES 0, 0, 2, 74, 0, 0, 2, 72, 0, 0, 2, 71, 0, 0, 2, 70 // k = 61..64
+    BYTES 0, 0, 2, 68, 0, 0, 2, 67, 0, 0, 2, 66, 0, 0, 2, 64 // k = 65..68
+    BYTES 0, 0, 2, 63, 0, 0, 2, 62, 0, 0, 2, 61, 0, 0, 2, 59 // k = 69..72
+    BYTES 0, 0, 2, 58, 0, 0, 2, 57, 0, 0, 2, 56, 0, 0, 2, 55 // k = 73..76
+    BYTES 0, 0, 2, 54, 0, 0, 2, 53, 0, 0, 2, 51, 0, 0, 2, 50 // k = 77..80
+    BYTES 0, 0, 2, 49, 0, 0, 2, 48, 0, 0, 2, 47, 0, 0, 2, 46 // k = 81..84
+    BYTES 0, 0, 2, 45, 0, 0, 2, 44, 0, 0, 2, 43, 0, 0, 2, 42 // k = 85..88
+    BYTES 0, 0, 2, 41, 0, 0, 2, 40, 0, 0, 2, 40, 0, 0, 2, 39 // k = 89..92
+    BYTES 0, 0, 2, 38, 0, 0, 2, 37, 0, 0, 2, 36, 0, 0, 2, 35 // k = 93..96
+    BYTES 0, 0, 2, 34, 0, 0, 2, 33, 0, 0, 2, 33, 0, 0, 2, 32 // k = 97..100
+    BYTES 0, 0, 2, 31, 0, 0, 2, 30, 0, 0, 2, 29, 0, 0, 2, 29 // k = 101..104
+    BYTES 0, 0, 2, 28, 0, 0, 2, 27, 0, 0, 2, 26, 0, 0, 2, 25 // k = 105..108
+    BYTES 0, 0, 2, 25, 0, 0, 2, 24, 0, 0, 2, 23, 0, 0, 2, 23 // k = 109..112
+    BYTES 0, 0, 2, 22, 0, 0, 2, 21, 0, 0, 2, 20, 0, 0, 2, 20 // k = 113..116
+    BYTES 0, 0, 2, 19, 0, 0, 2, 18, 0, 0, 2, 18, 0, 0, 2, 17 // k = 117..120
+    BYTES 0, 0, 2, 16, 0, 0, 2, 16, 0, 0, 2, 15, 0, 0, 2, 14 // k = 121..124
+    BYTES 0, 0, 2, 14, 0, 0, 2, 13, 0, 0, 2, 12, 0, 0, 2, 12 // k = 125..128
@@ -20,8 +20,15 @@ global handle_precompiles:
     DUP1 %eq_const(@SNARKV)   %jumpi(precompile_snarkv)
     #[cfg(feature = eth_mainnet)]
     {
-        DUP1 %eq_const(@BLAKE2_F) %jumpi(precompile_blake2_f)
-        %eq_const(@KZG_PEVAL)     %jumpi(precompile_kzg_peval)
+        DUP1 %eq_const(@BLAKE2_F)            %jumpi(precompile_blake2_f)
+        DUP1 %eq_const(@BLS12_G1ADD)         %jumpi(precompile_bls12_g1add)
+        DUP1 %eq_const(@BLS12_G1MSM)         %jumpi(precompile_bls12_g1msm)
+        DUP1 %eq_const(@BLS12_G2ADD)         %jumpi(precompile_bls12_g2add)
+        DUP1 %eq_const(@BLS12_G2MSM)         %jumpi(precompile_bls12_g2msm)
+        DUP1 %eq_const(@BLS12_PAIRING)       %jumpi(precompile_bls12_pairing)
+        DUP1 %eq_const(@BLS12_MAP_FP_TO_G1)  %jumpi(precompile_bls12_map_fp_to_g1)
+        DUP1 %eq_const(@BLS12_MAP_FP2_TO_G2) %jumpi(precompile_bls12_map_fp2_to_g2)
+        %eq_const(@KZG_PEVAL)                %jumpi(precompile_kzg_peval)
     }
     #[cfg(not(feature = eth_mainnet))]
     {
@@ -36,7 +36,17 @@ global process_normalized_txn:
     // stack: sender, retdest
 
     // Assert sender has no code.
-    DUP1 %ext_code_empty %assert_nonzero(invalid_txn_1)
+    #[cfg(not(feature = eth_mainnet))]
+    {
+        DUP1 %ext_code_empty %assert_nonzero(invalid_txn_1)
+    }
+    // EIP-7702: The sender may however have a delegation designator.
+    #[cfg(feature = eth_mainnet)]
+    {
+        DUP1 %ext_code_empty
+        DUP2 %get_delegate ISZERO ISZERO
+        OR %assert_nonzero(invalid_txn_1)
+    }
     // stack: sender, retdest
 
     // Assert sender balance >= gas_limit * gas_price + value.
@@ -101,12 +111,31 @@ global warm_precompiles:
     PUSH @SNARKV %insert_accessed_addresses_no_return
     PUSH @BLAKE2_F %insert_accessed_addresses_no_return
     PUSH @KZG_PEVAL %insert_accessed_addresses_no_return
+    #[cfg(feature = eth_mainnet)]
+    {
+        PUSH @BLS12_G1ADD %insert_accessed_addresses_no_return
+        PUSH @BLS12_G1MSM %insert_accessed_addresses_no_return
+        PUSH @BLS12_G2ADD %insert_accessed_addresses_no_return
+        PUSH @BLS12_G2MSM %insert_accessed_addresses_no_return
+        PUSH @BLS12_PAIRING %insert_accessed_addresses_no_return
+        PUSH @BLS12_MAP_FP_TO_G1 %insert_accessed_addresses_no_return
+        PUSH @BLS12_MAP_FP2_TO_G2 %insert_accessed_addresses_no_return
+    }
 
 // EIP-3651
 global warm_coinbase:
     %mload_global_metadata(@GLOBAL_METADATA_BLOCK_BENEFICIARY)
     %insert_accessed_addresses_no_return
 
+// EIP-7702: Process the authorization list of set-code transactions.
+#[cfg(feature = eth_mainnet)]
+{
+    global process_authorizations:
+        %mload_global_metadata(@GLOBAL_METADATA_AUTH_LIST_LEN)
+        ISZERO %jumpi(process_based_on_type)
+        %process_authorization_list
+}
+
 global process_based_on_type:
     %is_contract_creation
     %jumpi(process_contract_creation_txn)
@@ -236,6 +265,11 @@ global process_message_txn:
     %mload_txn_field(@TXN_FIELD_VALUE)
     %mload_txn_field(@TXN_FIELD_TO)
     DUP1 %insert_accessed_addresses_no_return
+    #[cfg(feature = eth_mainnet)]
+    {
+        // EIP-7702: The delegate of `to`, if any, is warmed as well.
+        DUP1 %warm_delegate
+    }
     %mload_txn_field(@TXN_FIELD_ORIGIN)
     // stack: from, to, amount, retdest
     %transfer_eth
@@ -260,7 +294,14 @@ global process_message_txn:
     DUP2 // new_ctx
     %mload_txn_field(@TXN_FIELD_TO)
     // stack: address, new_ctx, process_message_txn_code_loaded, new_ctx, retdest
-    %jump(load_code_padded)
+    #[cfg(feature = eth_mainnet)]
+    {
+        %jump(load_delegated_code_padded)
+    }
+    #[cfg(not(feature = eth_mainnet))]
+    {
+        %jump(load_code_padded)
+    }
 
 global process_message_txn_insufficient_balance:
     // stack: retdest
@@ -0,0 +1,287 @@
+/// EIP-7685: General purpose execution layer requests
+/// <https://eips.ethereum.org/EIPS/eip-7685>
+///
+/// Collects the execution layer requests of the block once all transactions
+/// and withdrawals have been processed, and checks their commitment against
+/// `GLOBAL_METADATA_REQUESTS_HASH`:
+///  - deposit requests (EIP-6110) are parsed from the `DepositEvent` logs
+///    emitted by the deposit contract at
+///    `GLOBAL_METADATA_DEPOSIT_CONTRACT_ADDRESS`, in the receipts of all the
+///    transactions of the block;
+///  - withdrawal requests (EIP-7002) and consolidation requests (EIP-7251) are
+///    dequeued through system calls to their respective predeploy contracts.
+///
+/// The commitment is `sha256(sha256(type || data) || ...)`, taken over
+/// non-empty requests only.
+///
+/// *NOTE*: This is a no-op unless the prover signals the last batch of a
+/// Prague block, which thus needs the receipts of all the previous batches of
+/// the block in its receipts trie.
+
+%macro requests
+    // stack: (empty)
+    PUSH %%after
+    %jump(requests)
+%%after:
+    // stack: (empty)
+%endmacro
+
+global requests:
+    // stack: retdest
+    PROVER_INPUT(requests)
+    // stack: is_last_batch, retdest
+    ISZERO %jumpi(requests_end)
+    // stack: retdest
+    PUSH after_deposit_requests
+    %jump(deposit_requests)
+after_deposit_requests:
+    // stack: deposits_hash, retdest
+    %stack () -> (@WITHDRAWAL_REQUEST_PREDEPLOY_ADDRESS, @WITHDRAWAL_REQUEST_TYPE, after_withdrawal_requests)
+    %jump(dequeue_requests)
+after_withdrawal_requests:
+    // stack: withdrawals_hash, deposits_hash, retdest
+    %stack () -> (@CONSOLIDATION_REQUEST_PREDEPLOY_ADDRESS, @CONSOLIDATION_REQUEST_TYPE, after_consolidation_requests)
+    %jump(dequeue_requests)
+after_consolidation_requests:
+    // stack: consolidations_hash, withdrawals_hash, deposits_hash, retdest
+    // Append the hashes of non-empty requests at @SEGMENT_KERNEL_GENERAL[1..].
+    PUSH 1 PUSH @SEGMENT_KERNEL_GENERAL %build_kernel_address
+    %stack (addr, consolidations_hash, withdrawals_hash, deposits_hash) -> (addr, deposits_hash, withdrawals_hash, consolidations_hash)
+    %append_request_hash
+    %append_request_hash
+    %append_request_hash
+    // stack: addr, retdest
+    %sub_const(@SEGMENT_KERNEL_GENERAL)
+    %decrement
+    // stack: num_bytes, retdest
+    %stack (num_bytes) -> (0, num_bytes, check_requests_hash)
+    %jump(sha2)
+check_requests_hash:
+    // stack: requests_hash, retdest
+    %mload_global_metadata(@GLOBAL_METADATA_REQUESTS_HASH)
+    %assert_eq
+    // stack: retdest
+    JUMP
+
+requests_end:
+    // stack: retdest
+    JUMP
+
+// Collects the deposit requests of the block at @SEGMENT_KERNEL_GENERAL[2..],
+// by going through the logs of the receipt of each of its transactions, then
+// hashes them.
+// Pre stack: retdest
+// Post stack: hash
+global deposit_requests:
+    // stack: retdest
+    PUSH 2 PUSH @SEGMENT_KERNEL_GENERAL %build_kernel_address
+    PUSH 0
+deposit_requests_loop:
+    // stack: i, addr, retdest
+    DUP1 %mload_global_metadata(@GLOBAL_METADATA_TXN_NUMBER_AFTER) EQ %jumpi(deposit_requests_end)
+    // stack: i, addr, retdest
+    PUSH deposit_requests_receipt_found
+    DUP2 %scalar_to_rlp
+    // stack: key, deposit_requests_receipt_found, i, addr, retdest
+    DUP1 %num_bytes %mul_const(2)
+    // stack: num_nibbles, key, deposit_requests_receipt_found, i, addr, retdest
+    %mload_global_metadata(@GLOBAL_METADATA_RECEIPT_TRIE_ROOT)
+    // stack: root_ptr, num_nibbles, key, deposit_requests_receipt_found, i, addr, retdest
+    %jump(mpt_read)
+deposit_requests_receipt_found:
+    // stack: receipt_ptr_ptr, i, addr, retdest
+    // Every transaction of the block has a receipt.
+    DUP1 ISZERO %jumpi(panic)
+    %mload_trie_data
+    // stack: receipt_ptr, i, addr, retdest
+    // Skip the type of non-legacy receipts: the payload length is always
+    // greater than 256, as the payload contains the bloom filter.
+    DUP1 %mload_trie_data %lt_const(256) ADD
+    // stack: payload_len_ptr, i, addr, retdest
+    DUP1 %add_const(260) %mload_trie_data
+    // stack: num_logs, payload_len_ptr, i, addr, retdest
+    SWAP1 %add_const(261)
+    // stack: log_ptr, num_logs, i, addr, retdest
+deposit_requests_logs_loop:
+    // stack: log_ptr, num_logs, i, addr, retdest
+    DUP2 ISZERO %jumpi(deposit_requests_logs_end)
+    // A log is laid out as `payload_len, address, num_topics, topics..., data_len, data...`.
+    DUP1 %add_const(2) %mload_trie_data
+    DUP2 %add_const(3) ADD
+    // stack: data_len_ptr, log_ptr, num_logs, i, addr, retdest
+    SWAP1
+    // stack: log_ptr, data_len_ptr, num_logs, i, addr, retdest
+    DUP1 %increment %mload_trie_data
+    %mload_global_metadata(@GLOBAL_METADATA_DEPOSIT_CONTRACT_ADDRESS)
+    EQ
+    // stack: from_deposit_contract, log_ptr, data_len_ptr, num_logs, i, addr, retdest
+    DUP2 %add_const(2) %mload_trie_data
+    ISZERO ISZERO MUL
+    // stack: from_deposit_contract && has_topics, log_ptr, data_len_ptr, num_logs, i, addr, retdest
+    SWAP1 %add_const(3) %mload_trie_data
+    %eq_const(@DEPOSIT_EVENT_SIGNATURE_HASH)
+    MUL
+    // stack: is_deposit, data_len_ptr, num_logs, i, addr, retdest
+    %jumpi(deposit_requests_append)
+deposit_requests_next_log:
+    // stack: data_len_ptr, num_logs, i, addr, retdest
+    DUP1 %mload_trie_data ADD %increment
+    // stack: next_log_ptr, num_logs, i, addr, retdest
+    SWAP1 %decrement SWAP1
+    %jump(deposit_requests_logs_loop)
+
+deposit_requests_append:
+    // stack: data_len_ptr, num_logs, i, addr, retdest
+    // The data of a `DepositEvent` is the ABI encoding of the `bytes` fields of
+    // the request, which must be laid out as expected by EIP-6110.
+    DUP1 %mload_trie_data %assert_eq_const(@DEPOSIT_EVENT_DATA_LEN)
+    DUP1 %increment PUSH @SEGMENT_TRIE_DATA %build_kernel_address
+    // stack: data_addr, data_len_ptr, num_logs, i, addr, retdest
+    %stack (data_addr, data_len_ptr, num_logs, i, addr) -> (data_addr, addr, data_len_ptr, num_logs, i)
+    // stack: data_addr, addr, data_len_ptr, num_logs, i, retdest
+    %append_deposit_field(0, 160, 48)  // pubkey
+    %append_deposit_field(32, 256, 32) // withdrawal_credentials
+    %append_deposit_field(64, 320, 8)  // amount
+    %append_deposit_field(96, 384, 96) // signature
+    %append_deposit_field(128, 512, 8) // index
+    // stack: data_addr, addr', data_len_ptr, num_logs, i, retdest
+    %stack (data_addr, addr, data_len_ptr, num_logs, i) -> (data_len_ptr, num_logs, i, addr)
+    // stack: data_len_ptr, num_logs, i, addr', retdest
+    %jump(deposit_requests_next_log)
+
+deposit_requests_logs_end:
+    // stack: log_ptr, 0, i, addr, retdest
+    %pop2
+    %increment
+    // stack: i', addr, retdest
+    %jump(deposit_requests_loop)
+
+deposit_requests_end:
+    // stack: i, addr, retdest
+    POP
+    %sub_const(@SEGMENT_KERNEL_GENERAL)
+    %sub_const(2)
+    // stack: size, retdest
+    PUSH @DEPOSIT_REQUEST_TYPE
+    // stack: request_type, size, retdest
+    %jump(hash_requests)
+
+// Checks that the head word at `head` of the `DepositEvent` data at
+// `data_addr` is the offset `offset` of a field of `len` bytes, then appends
+// this field at `addr`.
+// Pre stack: data_addr, addr
+// Post stack: data_addr, addr'
+%macro append_deposit_field(head, offset, len)
+    // stack: data_addr, addr
+    PUSH 32 DUP2 %add_const($head) MLOAD_32BYTES
+    %assert_eq_const($offset)
+    PUSH 32 DUP2 %add_const($offset) MLOAD_32BYTES
+    %assert_eq_const($len)
+    // stack: data_addr, addr
+    PUSH $len
+    DUP2 %add_const($offset) %add_const(32)
+    DUP4
+    // stack: addr, SRC, len, data_addr, addr
+    %memcpy_bytes
+    // stack: data_addr, addr
+    SWAP1 %add_const($len) SWAP1
+    // stack: data_addr, addr'
+%endmacro
+
+// Writes `hash` at `addr` unless it is zero, i.e. unless the corresponding
+// requests are empty.
+// Pre stack: addr, hash
+// Post stack: addr'
+%macro append_request_hash
+    // stack: addr, hash
+    DUP2 ISZERO %jumpi(%%skip)
+    MSTORE_32BYTES_32
+    // stack: addr'
+    %jump(%%after)
+%%skip:
+    // stack: addr, 0
+    SWAP1 POP
+%%after:
+    // stack: addr'
+%endmacro
+
+// Hashes `request_type || data`, where `data` lies at @SEGMENT_KERNEL_GENERAL[2..2+size].
+// Returns 0 if there are no requests.
+// Pre stack: request_type, size, retdest
+// Post stack: hash
+global hash_requests:
+    // stack: request_type, size, retdest
+    DUP2 ISZERO %jumpi(hash_requests_empty)
+    PUSH 1
+    %mstore_kernel_general
+    // stack: size, retdest
+    %increment
+    PUSH 0
+    // stack: virt=0, size+1, retdest
+    %jump(sha2)
+hash_requests_empty:
+    %stack (request_type, size, retdest) -> (retdest, 0)
+    JUMP
+
+// Calls the predeploy contract at `address` to dequeue its pending requests,
+// then hashes them.
+// Pre stack: address, request_type, retdest
+// Post stack: hash
+dequeue_requests:
+    // stack: address, request_type, retdest
+    %stack (address) -> (address, dequeue_requests_after_call)
+    %jump(system_call)
+dequeue_requests_after_call:
+    // stack: request_type, retdest
+    // Copy the returned requests to @SEGMENT_KERNEL_GENERAL[2..].
+    %mload_context_metadata(@CTX_METADATA_RETURNDATA_SIZE)
+    // stack: size, request_type, retdest
+    PUSH @SEGMENT_RETURNDATA GET_CONTEXT %build_address_no_offset
+    PUSH 2 PUSH @SEGMENT_KERNEL_GENERAL %build_kernel_address
+    %stack (DST, SRC, size) -> (DST, SRC, size, dequeue_requests_copied, size)
+    %jump(memcpy_bytes)
+dequeue_requests_copied:
+    // stack: size, request_type, retdest
+    SWAP1
+    %jump(hash_requests)
+
+// Performs a system call, i.e. a call from @SYSTEM_ADDRESS with no value nor
+// calldata and a gas limit of @SYSTEM_CALL_GAS_LIMIT, which doesn't count
+// against the block gas limit.
+// Missing code or a failing call invalidates the block.
+// Pre stack: address, retdest
+// Post stack: (empty)
+global system_call:
+    // stack: address, retdest
+    DUP1 %extcodehash
+    // stack: codehash, address, retdest
+    DUP1 ISZERO %jumpi(panic)
+    %eq_const(@EMPTY_STRING_HASH) %jumpi(panic)
+    // stack: address, retdest
+    %create_context
+    // stack: new_ctx, address, retdest
+    %stack (new_ctx, address) -> (address, new_ctx, system_call_code_loaded, new_ctx, address)
+    %jump(load_code_padded)
+
+system_call_code_loaded:
+    // stack: code_size, new_ctx, address, retdest
+    %set_new_ctx_code_size
+    // stack: new_ctx, address, retdest
+
+    // Each line in the block below does not change the stack.
+    DUP2 %set_new_ctx_addr
+    PUSH @SYSTEM_ADDRESS %set_new_ctx_caller
+    PUSH 0 %set_new_ctx_value
+    %set_new_ctx_parent_pc(system_call_after_call)
+    PUSH @SYSTEM_CALL_GAS_LIMIT %set_new_ctx_gas_limit_no_check
+    // stack: new_ctx, address, retdest
+
+    %enter_new_ctx
+    // (Old context) stack: new_ctx, address, retdest
+
+system_call_after_call:
+    // stack: success, leftover_gas, new_ctx, address, retdest
+    ISZERO %jumpi(panic)
+    %pop3
+    // stack: retdest
+    JUMP
@@ -42,8 +42,8 @@
     // stack: addr, addr>=1
     #[cfg(feature = eth_mainnet)]
     {
-        %le_const(@KZG_PEVAL)
-        // stack: addr>=1, addr<=10
+        %le_const(@BLS12_MAP_FP2_TO_G2)
+        // stack: addr>=1, addr<=17
     }
     // TODO: Update after support of EIP-7712 for Polygon Pos, https://github.com/0xPolygonZero/zk_evm/issues/265
     #[cfg(not(feature = eth_mainnet))]
@@ -0,0 +1,162 @@
+// Affine arithmetic over the BLS12-381 curves E: y^2 = x^3 + 4 over Fp and E': y^2 = x^3 + 4 (1 + i)
+// over Fp2, and over the isogenous curves used by the hash-to-curve maps.
+//
+// A point is stored in memory as its coordinates (x, y) in Fp2, i.e. eight words, with the points of
+// E having zero imaginary parts, and the point at infinity is stored as (0, 0), which lies on none of
+// these curves. The functions below take the full addresses of the points.
+
+// Sets out = p + q. The output may overlap the inputs.
+global bls381_curve_add:
+    // stack: p, q, out, retdest
+    DUP1
+    %is_infinity_bls381
+    %jumpi(bls381_curve_add_return_q)
+    DUP2
+    %is_infinity_bls381
+    %jumpi(bls381_curve_add_return_p)
+    DUP2
+    %load_fp381_2
+    DUP5
+    %load_fp381_2
+    %eq_fp381_2
+    // stack: x_p == x_q, p, q, out, retdest
+    %jumpi(bls381_curve_add_equal_x)
+    // lambda = (y_q - y_p) / (x_q - x_p)
+    DUP1
+    %load_fp381_2
+    DUP6
+    %load_fp381_2
+    %sub_fp381_2
+    // stack: x_q - x_p: 4, p, q, out, retdest
+    DUP5
+    %add_const(4)
+    %load_fp381_2
+    DUP10
+    %add_const(4)
+    %load_fp381_2
+    %sub_fp381_2
+    // stack: y_q - y_p: 4, x_q - x_p: 4, p, q, out, retdest
+    %div_fp381_2
+    %jump(bls381_curve_add_with_slope)
+bls381_curve_add_equal_x:
+    // stack: p, q, out, retdest
+    DUP2
+    %add_const(4)
+    %load_fp381_2
+    DUP5
+    %add_const(4)
+    %load_fp381_2
+    %add_fp381_2
+    %is_zero_fp381_2
+    // stack: y_p + y_q == 0, p, q, out, retdest
+    %jumpi(bls381_curve_add_return_infinity)
+    // lambda = 3 x_p^2 / (2 y_p)
+    DUP1
+    %add_const(4)
+    %load_fp381_2
+    %double_fp381_2
+    DUP5
+    %load_fp381_2
+    %square_fp381_2
+    %dup_fp381_2_0
+    %double_fp381_2
+    %add_fp381_2
+    // stack: 3 x_p^2: 4, 2 y_p: 4, p, q, out, retdest
+    %div_fp381_2
+bls381_curve_add_with_slope:
+    // stack: lambda: 4, p, q, out, retdest
+    // x = lambda^2 - x_p - x_q
+    DUP6
+    %load_fp381_2
+    DUP9
+    %load_fp381_2
+    %add_fp381_2
+    %dup_fp381_2_4
+    %square_fp381_2
+    %sub_fp381_2
+    // stack: x: 4, lambda: 4, p, q, out, retdest
+    // y = lambda (x_p - x) - y_p
+    %dup_fp381_2_0
+    DUP13
+    %load_fp381_2
+    %sub_fp381_2
+    %dup_fp381_2_8
+    %mul_fp381_2
+    DUP13
+    %add_const(4)
+    %load_fp381_2
+    %swap_fp381_2
+    %sub_fp381_2
+    // stack: y: 4, x: 4, lambda: 4, p, q, out, retdest
+    DUP15
+    %add_const(4)
+    %store_fp381_2
+    DUP11
+    %store_fp381_2
+    // stack: lambda: 4, p, q, out, retdest
+    %pop7
+    JUMP
+bls381_curve_add_return_p:
+    // stack: p, q, out, retdest
+    %stack (p, q, out) -> (out, p, 8)
+    %memcpy
+    JUMP
+bls381_curve_add_return_q:
+    // stack: p, q, out, retdest
+    %stack (p, q, out) -> (out, q, 8)
+    %memcpy
+    JUMP
+bls381_curve_add_return_infinity:
+    // stack: p, q, out, retdest
+    %pop2
+    %set_infinity_bls381
+    JUMP
+
+%macro bls381_curve_add
+    // stack: p, q, out
+    %stack (p, q, out) -> (p, q, out, %%after)
+    %jump(bls381_curve_add)
+%%after:
+%endmacro
+
+// Returns whether the point at addr is the point at infinity.
+%macro is_infinity_bls381
+    // stack: addr
+    DUP1
+    %add_const(4)
+    %load_fp381_2
+    OR
+    OR
+    OR
+    SWAP1
+    %load_fp381_2
+    OR
+    OR
+    OR
+    OR
+    ISZERO
+%endmacro
+
+// Sets the point at addr to the point at infinity.
+%macro set_infinity_bls381
+    // stack: addr
+    %rep 8
+        DUP1
+        PUSH 0
+        MSTORE_GENERAL
+        %increment
+    %endrep
+    POP
+%endmacro
+
+// Negates the point at p in place.
+%macro neg_bls381
+    // stack: p
+    %add_const(4)
+    DUP1
+    %load_fp381_2
+    %neg_fp381_2
+    DUP5
+    %store_fp381_2
+    POP
+%endmacro
@@ -0,0 +1,142 @@
+// Membership checks for the points of the BLS12-381 curves E: y^2 = x^3 + 4 and E': y^2 = x^3 + 4 (1 + i),
+// stored as in curve_add.asm. The subgroup checks use the endomorphisms of E and E' as in section 4 of
+// https://eprint.iacr.org/2021/1130.pdf, rather than a multiplication by the order r, and the points
+// at offsets 616 and 624 of the current kernel general memory, besides the scratch space of
+// bls381_curve_mul.
+
+// Returns whether the point at p lies on E, the point at infinity included.
+global bls381_is_on_curve_g1:
+    // stack: p, retdest
+    PUSH 0
+    PUSH 0
+    PUSH 0
+    PUSH 4
+    %jump(bls381_is_on_curve)
+
+// Returns whether the point at p lies on E', the point at infinity included.
+global bls381_is_on_curve_g2:
+    // stack: p, retdest
+    PUSH 0
+    PUSH 4
+    PUSH 0
+    PUSH 4
+bls381_is_on_curve:
+    // stack: b: 4, p, retdest
+    DUP5
+    %is_infinity_bls381
+    %jumpi(bls381_is_on_curve_infinity)
+    DUP5
+    %load_fp381_2
+    %dup_fp381_2_0
+    %square_fp381_2
+    %mul_fp381_2
+    %add_fp381_2
+    // stack: x^3 + b: 4, p, retdest
+    DUP5
+    %add_const(4)
+    %load_fp381_2
+    %square_fp381_2
+    %eq_fp381_2
+    // stack: y^2 == x^3 + b, p, retdest
+    %stack (is_on_curve, p, retdest) -> (retdest, is_on_curve)
+    JUMP
+bls381_is_on_curve_infinity:
+    // stack: b: 4, p, retdest
+    %stack (b: 4, p, retdest) -> (retdest, 1)
+    JUMP
+
+// Returns whether the point at p, which must lie on E, lies in G1, i.e. whether
+//     sigma(p) + [x^2] p = O,
+// where sigma(x, y) = (beta x, y) for the cube root of unity beta of Fp such that sigma acts on G1 as
+// the multiplication by -x^2.
+global bls381_is_in_g1:
+    // stack: p, retdest
+    PUSH 0x5f19672fdf76ce51
+    PUSH 0xba69c6076a0f77eaddb3a93be6f89688de17d813620a00022e01fffffffefffe
+    DUP3
+    %load_fp381_2
+    %mul_fp381_2_by_fp381
+    %store_fp381_2(616)
+    DUP1
+    %add_const(4)
+    %load_fp381_2
+    %store_fp381_2(620)
+    // stack: p, retdest
+    PUSH 624
+    %build_current_general_address
+    PUSH 0xac45a4010001a4020000000100000000
+    DUP3
+    // stack: p, x^2, [x^2] p, p, retdest
+    %bls381_curve_mul
+    %jump(bls381_is_in_subgroup_end)
+
+// Returns whether the point at p, which must lie on E', lies in G2, i.e. whether
+//     psi(p) + [|x|] p = O,
+// as psi acts on G2 as the multiplication by x = -0xd201000000010000.
+global bls381_is_in_g2:
+    // stack: p, retdest
+    PUSH 616
+    %build_current_general_address
+    DUP2
+    %bls381_psi
+    PUSH 624
+    %build_current_general_address
+    PUSH 0xd201000000010000
+    DUP3
+    // stack: p, |x|, [|x|] p, p, retdest
+    %bls381_curve_mul
+bls381_is_in_subgroup_end:
+    // stack: p, retdest
+    PUSH 616
+    %build_current_general_address
+    PUSH 624
+    %build_current_general_address
+    DUP2
+    %bls381_curve_add
+    PUSH 616
+    %build_current_general_address
+    %is_infinity_bls381
+    // stack: is_in_subgroup, p, retdest
+    %stack (is_in_subgroup, p, retdest) -> (retdest, is_in_subgroup)
+    JUMP
+
+// Sets out = psi(p) for the untwist-Frobenius-twist endomorphism
+//     psi(x, y) = (conj(x) / xi^((p - 1) / 3), conj(y) / xi^((p - 1) / 2))
+// of E'. The output may overlap the input.
+global bls381_psi:
+    // stack: p, out, retdest
+    PUSH 0x6af0e0437ff400b6831e36d6bd17ffe
+    PUSH 0x48395dabc2d3435e77f76e17009241c5ee67992f72ec05f4c81084fbede3cc09
+    PUSH 0x135203e60180a68ee2e9c448d77a2cd9
+    PUSH 0x1c3dedd930b1cf60ef396489f61eb45e304466cf3e67fa0af1ee7b04121bdea2
+    DUP5
+    %add_const(4)
+    %load_fp381_2
+    %conj_fp381_2
+    %mul_fp381_2
+    // stack: psi(p).y: 4, p, out, retdest
+    PUSH 0x1a0111ea397fe699ec02408663d4de85
+    PUSH 0xaa0d857d89759ad4897d29650fb85f9b409427eb4f49fffd8bfd00000000aaad
+    PUSH 0
+    PUSH 0
+    DUP9
+    %load_fp381_2
+    %conj_fp381_2
+    %mul_fp381_2
+    // stack: psi(p).x: 4, psi(p).y: 4, p, out, retdest
+    DUP10
+    %store_fp381_2
+    DUP6
+    %add_const(4)
+    %store_fp381_2
+    // stack: p, out, retdest
+    %pop2
+    JUMP
+
+%macro bls381_psi
+    // stack: p, out
+    %stack (p, out) -> (p, out, %%after)
+    %jump(bls381_psi)
+%%after:
+%endmacro
+
@@ -0,0 +1,67 @@
+// Scalar multiplication over the BLS12-381 curves, with the double-and-add method from the least
+// significant bit. Uses the points R at offset 600 and Q at offset 608 of the current kernel general
+// memory.
+
+// Sets out = [k] p, for any scalar k < 2^256. The output may overlap the input.
+global bls381_curve_mul:
+    // stack: p, k, out, retdest
+    PUSH 8
+    DUP2
+    PUSH 608
+    %build_current_general_address
+    // stack: Q, p, 8, p, k, out, retdest
+    %memcpy
+    POP
+    PUSH 600
+    %build_current_general_address
+    %set_infinity_bls381
+bls381_curve_mul_loop:
+    // stack: k, out, retdest
+    DUP1
+    ISZERO
+    %jumpi(bls381_curve_mul_end)
+    DUP1
+    %and_const(1)
+    ISZERO
+    %jumpi(bls381_curve_mul_double)
+    // R = R + Q
+    PUSH 600
+    %build_current_general_address
+    PUSH 608
+    %build_current_general_address
+    DUP2
+    // stack: R, Q, R, k, out, retdest
+    %bls381_curve_add
+bls381_curve_mul_double:
+    // stack: k, out, retdest
+    %shr_const(1)
+    DUP1
+    ISZERO
+    %jumpi(bls381_curve_mul_end)
+    // Q = Q + Q
+    PUSH 608
+    %build_current_general_address
+    DUP1
+    DUP1
+    // stack: Q, Q, Q, k, out, retdest
+    %bls381_curve_add
+    %jump(bls381_curve_mul_loop)
+bls381_curve_mul_end:
+    // stack: k, out, retdest
+    POP
+    PUSH 8
+    PUSH 600
+    %build_current_general_address
+    // stack: R, 8, out, retdest
+    DUP3
+    %memcpy
+    // stack: out, retdest
+    POP
+    JUMP
+
+%macro bls381_curve_mul
+    // stack: p, k, out
+    %stack (p, k, out) -> (p, k, out, %%after)
+    %jump(bls381_curve_mul)
+%%after:
+%endmacro
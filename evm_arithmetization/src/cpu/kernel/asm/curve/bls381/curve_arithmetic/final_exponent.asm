@@ -0,0 +1,255 @@
+// The final exponentiation of the optimal ate pairing over BLS12-381, raising f to the power
+//     (p^12 - 1) / r = (p^6 - 1) (p^2 + 1) (p^4 - p^2 + 1) / r
+// as in section 5 of https://eprint.iacr.org/2020/875.pdf.
+//
+// The exponentiation works on the elements t0, ..., t6 of Fp12 at offsets 300, 324, ..., 444 of the
+// current kernel general memory, and the cyclotomic squaring on the elements of Fp2 at offsets 250,
+// 254, 258 and 262, besides the scratch space of the field operations.
+
+// Raises the element of Fp12 at offset 130 to the power (p^12 - 1) / r, in place. The element must
+// not be zero.
+global bls381_final_exponent:
+    // stack: retdest
+    // t0 = f^(p^6) = conj(f)
+    PUSH 300
+    %build_current_general_address
+    PUSH 130
+    %build_current_general_address
+    %copy_fp381_12
+    PUSH 300
+    %build_current_general_address
+    %conj_fp381_12
+    // t1 = f^-1
+    PUSH 324
+    %build_current_general_address
+    PUSH 130
+    %build_current_general_address
+    %inv_fp381_12
+    // t2 = t0 t1, t1 = t2
+    %mul_fp381_12_at(300, 324, 348)
+    PUSH 324
+    %build_current_general_address
+    PUSH 348
+    %build_current_general_address
+    %copy_fp381_12
+    // t2 = t2^(p^2) t1
+    PUSH 348
+    %build_current_general_address
+    %frob_fp381_12_2
+    %mul_fp381_12_at(348, 324, 348)
+    // t1 = conj(t2^2)
+    %mul_fp381_12_at(348, 348, 324)
+    PUSH 324
+    %build_current_general_address
+    %conj_fp381_12
+    // t3 = t2^x, t4 = t3^2, t5 = t1 t3
+    %cyclotomic_exp_at(348, 372)
+    %mul_fp381_12_at(372, 372, 396)
+    %mul_fp381_12_at(324, 372, 420)
+    // t1 = t5^x, t0 = t1^x, t6 = t0^x t4
+    %cyclotomic_exp_at(420, 324)
+    %cyclotomic_exp_at(324, 300)
+    %cyclotomic_exp_at(300, 444)
+    %mul_fp381_12_at(444, 396, 444)
+    // t4 = t6^x conj(t5) t2
+    %cyclotomic_exp_at(444, 396)
+    PUSH 420
+    %build_current_general_address
+    %conj_fp381_12
+    %mul_fp381_12_at(396, 420, 396)
+    %mul_fp381_12_at(396, 348, 396)
+    // t5 = conj(t2)
+    PUSH 420
+    %build_current_general_address
+    PUSH 348
+    %build_current_general_address
+    %copy_fp381_12
+    PUSH 420
+    %build_current_general_address
+    %conj_fp381_12
+    // t1 = (t1 t2)^(p^3)
+    %mul_fp381_12_at(324, 348, 324)
+    PUSH 324
+    %build_current_general_address
+    %frob_fp381_12_3
+    // t6 = (t6 t5)^p
+    %mul_fp381_12_at(444, 420, 444)
+    PUSH 444
+    %build_current_general_address
+    %frob_fp381_12_1
+    // t3 = (t3 t0)^(p^2) t1 t6
+    %mul_fp381_12_at(372, 300, 372)
+    PUSH 372
+    %build_current_general_address
+    %frob_fp381_12_2
+    %mul_fp381_12_at(372, 324, 372)
+    %mul_fp381_12_at(372, 444, 372)
+    // f = t3 t4
+    %mul_fp381_12_at(372, 396, 130)
+    // stack: retdest
+    JUMP
+
+// Sets out = f^x, where x = -0xd201000000010000 is the BLS parameter, for f in the cyclotomic
+// subgroup, where the conjugation is the inverse. f and out must not overlap.
+global bls381_cyclotomic_exp:
+    // stack: f, out, retdest
+    DUP2
+    DUP2
+    %copy_fp381_12
+    // Skip the leading bit of |x|.
+    PUSH 62
+bls381_cyclotomic_exp_loop:
+    // stack: i, f, out, retdest
+    PUSH bls381_cyclotomic_exp_mul
+    DUP4
+    %jump(bls381_cyclotomic_square)
+bls381_cyclotomic_exp_mul:
+    // stack: i, f, out, retdest
+    PUSH 0xd201000000010000
+    DUP2
+    SHR
+    PUSH 1
+    AND
+    ISZERO
+    %jumpi(bls381_cyclotomic_exp_next)
+    DUP3
+    DUP3
+    DUP2
+    // stack: out, f, out, i, f, out, retdest
+    %mul_fp381_12
+bls381_cyclotomic_exp_next:
+    // stack: i, f, out, retdest
+    DUP1
+    ISZERO
+    %jumpi(bls381_cyclotomic_exp_end)
+    %decrement
+    %jump(bls381_cyclotomic_exp_loop)
+bls381_cyclotomic_exp_end:
+    // stack: i, f, out, retdest
+    %pop2
+    %jump(conj_fp381_12)
+
+// Squares f in place, for f in the cyclotomic subgroup, following Algorithm 5.5.4 of Guide to
+// Pairing-Based Cryptography, from https://eprint.iacr.org/2009/565.pdf. In terms of the Fp2
+// coefficients z0, z4, z3, z2, z1, z5 of f in memory order, and of the squares
+//     (t0, t1) = (a + b sqrt(xi))^2 = (a^2 + xi b^2, 2 a b)
+// in Fp4 = Fp2[sqrt(xi)], the result is
+//     z0 = 3 t0 - 2 z0, z1 = 3 t1 + 2 z1 with (t0, t1) the square of (z0, z1),
+//     z4 = 3 t0 - 2 z4, z5 = 3 t1 + 2 z5 with (t0, t1) the square of (z2, z3),
+//     z3 = 3 t0 - 2 z3, z2 = 3 xi t1 + 2 z2 with (t0, t1) the square of (z4, z5).
+global bls381_cyclotomic_square:
+    // stack: f, retdest
+    %fp4_square_fp381(0, 16, 250, 254)
+    %cyclotomic_square_sub(0, 250)
+    %cyclotomic_square_add(16, 254)
+    %fp4_square_fp381(12, 8, 250, 254)
+    %fp4_square_fp381(4, 20, 258, 262)
+    %cyclotomic_square_sub(4, 250)
+    %cyclotomic_square_add(20, 254)
+    %load_fp381_2(262)
+    %mul_fp381_2_by_xi
+    %store_fp381_2(262)
+    %cyclotomic_square_sub(8, 258)
+    %cyclotomic_square_add(12, 262)
+    // stack: f, retdest
+    POP
+    JUMP
+
+// Stores the square (t0, t1) of the element (a, b) of Fp4 given by the coefficients at word offsets a
+// and b of f at the offsets t0 and t1 of the current kernel general memory, where
+//     t0 = a^2 + xi b^2 and t1 = (a + b)^2 - a^2 - b^2.
+%macro fp4_square_fp381(a, b, t0, t1)
+    // stack: f
+    DUP1
+    %add_const($b)
+    %load_fp381_2
+    DUP5
+    %add_const($a)
+    %load_fp381_2
+    %add_fp381_2
+    %square_fp381_2
+    %store_fp381_2($t1)
+    // stack: f
+    DUP1
+    %add_const($b)
+    %load_fp381_2
+    %square_fp381_2
+    DUP5
+    %add_const($a)
+    %load_fp381_2
+    %square_fp381_2
+    // stack: a^2: 4, b^2: 4, f
+    %dup_fp381_2_4
+    %dup_fp381_2_4
+    %add_fp381_2
+    %load_fp381_2($t1)
+    %sub_fp381_2
+    %store_fp381_2($t1)
+    // stack: a^2: 4, b^2: 4, f
+    %swap_fp381_2
+    %mul_fp381_2_by_xi
+    %add_fp381_2
+    %store_fp381_2($t0)
+    // stack: f
+%endmacro
+
+// Sets the coefficient at word offset k of f to 3 t - 2 z, where z is its value and t is the element
+// of Fp2 at the offset t of the current kernel general memory.
+%macro cyclotomic_square_sub(k, t)
+    // stack: f
+    DUP1
+    %add_const($k)
+    %load_fp381_2
+    %double_fp381_2
+    %load_fp381_2($t)
+    %dup_fp381_2_0
+    %double_fp381_2
+    %add_fp381_2
+    %sub_fp381_2
+    // stack: 3 t - 2 z: 4, f
+    DUP5
+    %add_const($k)
+    %store_fp381_2
+%endmacro
+
+// Sets the coefficient at word offset k of f to 3 t + 2 z, where z is its value and t is the element
+// of Fp2 at the offset t of the current kernel general memory.
+%macro cyclotomic_square_add(k, t)
+    // stack: f
+    DUP1
+    %add_const($k)
+    %load_fp381_2
+    %double_fp381_2
+    %load_fp381_2($t)
+    %dup_fp381_2_0
+    %double_fp381_2
+    %add_fp381_2
+    %add_fp381_2
+    // stack: 3 t + 2 z: 4, f
+    DUP5
+    %add_const($k)
+    %store_fp381_2
+%endmacro
+
+%macro cyclotomic_exp_at(f, out)
+    PUSH %%after
+    PUSH $out
+    %build_current_general_address
+    PUSH $f
+    %build_current_general_address
+    // stack: f, out, %%after
+    %jump(bls381_cyclotomic_exp)
+%%after:
+%endmacro
+
+// Multiplies the elements of Fp12 at the given offsets of the current kernel general memory.
+%macro mul_fp381_12_at(a, b, out)
+    PUSH $out
+    %build_current_general_address
+    PUSH $b
+    %build_current_general_address
+    PUSH $a
+    %build_current_general_address
+    // stack: a, b, out
+    %mul_fp381_12
+%endmacro
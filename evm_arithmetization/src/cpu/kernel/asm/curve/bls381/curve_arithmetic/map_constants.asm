@@ -0,0 +1,497 @@
+// Constants of the maps to the BLS12-381 curves of EIP-2537, from section 8.8 and appendix E of RFC 9380,
+// https://www.rfc-editor.org/rfc/rfc9380. Each element (re, im) of Fp2 is stored as the 48-byte big-endian
+// encodings of re and im, to be read with load_fp381_2_code.
+
+// The constants A', B', Z, -B' / A' and B' / (Z A') of the simplified SWU map onto the 11-isogenous curve of E.
+global bls381_sswu_g1_params:
+    BYTES 0x00, 0x14, 0x46, 0x98, 0xa3, 0xb8, 0xe9, 0x43, 0x3d, 0x69, 0x3a, 0x02, 0xc9, 0x6d, 0x49, 0x82
+    BYTES 0xb0, 0xea, 0x98, 0x53, 0x83, 0xee, 0x66, 0xa8, 0xd8, 0xe8, 0x98, 0x1a, 0xef, 0xd8, 0x81, 0xac
+    BYTES 0x98, 0x93, 0x6f, 0x8d, 0xa0, 0xe0, 0xf9, 0x7f, 0x5c, 0xf4, 0x28, 0x08, 0x2d, 0x58, 0x4c, 0x1d
+    BYTES 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00
+    BYTES 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00
+    BYTES 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00
+    BYTES 0x12, 0xe2, 0x90, 0x8d, 0x11, 0x68, 0x80, 0x30, 0x01, 0x8b, 0x12, 0xe8, 0x75, 0x3e, 0xee, 0x3b
+    BYTES 0x20, 0x16, 0xc1, 0xf0, 0xf2, 0x4f, 0x40, 0x70, 0xa0, 0xb9, 0xc1, 0x4f, 0xce, 0xf3, 0x5e, 0xf5
+    BYTES 0x5a, 0x23, 0x21, 0x5a, 0x31, 0x6c, 0xea, 0xa5, 0xd1, 0xcc, 0x48, 0xe9, 0x8e, 0x17, 0x2b, 0xe0
+    BYTES 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00
+    BYTES 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00
+    BYTES 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00
+    BYTES 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00
+    BYTES 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00
+    BYTES 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x0b
+    BYTES 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00
+    BYTES 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00
+    BYTES 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00
+    BYTES 0x07, 0x93, 0x15, 0x4f, 0xd8, 0x56, 0x31, 0xd9, 0x66, 0xef, 0x24, 0x70, 0x46, 0x0c, 0x78, 0xf6
+    BYTES 0xa9, 0x28, 0xad, 0x9f, 0x5b, 0xdb, 0xfa, 0xc2, 0x1d, 0xf3, 0x97, 0x53, 0xaa, 0x27, 0x8b, 0xa7
+    BYTES 0x51, 0xbd, 0xfc, 0xf9, 0x5a, 0x84, 0x18, 0x8e, 0x29, 0xd6, 0x70, 0x67, 0x5e, 0x4c, 0x9c, 0x7c
+    BYTES 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00
+    BYTES 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00
+    BYTES 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00
+    BYTES 0x12, 0x39, 0x39, 0xa3, 0x16, 0x26, 0xa3, 0x2d, 0xe7, 0x72, 0xbc, 0x7a, 0x59, 0x1e, 0xa1, 0x40
+    BYTES 0x68, 0x3b, 0xca, 0x0c, 0x62, 0xef, 0xb1, 0x05, 0x31, 0x0d, 0x5c, 0xe1, 0xd2, 0x7d, 0x1a, 0xad
+    BYTES 0xf7, 0x9a, 0x5d, 0x5c, 0xbe, 0x8e, 0x2c, 0x4f, 0xf7, 0xd4, 0x81, 0x6a, 0xf7, 0x6d, 0x28, 0x14
+    BYTES 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00
+    BYTES 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00
+    BYTES 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00
+
+// The constants A', B', Z, -B' / A' and B' / (Z A') of the simplified SWU map onto the 3-isogenous curve of E'.
+global bls381_sswu_g2_params:
+    BYTES 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00
+    BYTES 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00
+    BYTES 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00
+    BYTES 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00
+    BYTES 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00
+    BYTES 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0xf0
+    BYTES 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00
+    BYTES 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00
+    BYTES 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x03, 0xf4
+    BYTES 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00
+    BYTES 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00
+    BYTES 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x03, 0xf4
+    BYTES 0x1a, 0x01, 0x11, 0xea, 0x39, 0x7f, 0xe6, 0x9a, 0x4b, 0x1b, 0xa7, 0xb6, 0x43, 0x4b, 0xac, 0xd7
+    BYTES 0x64, 0x77, 0x4b, 0x84, 0xf3, 0x85, 0x12, 0xbf, 0x67, 0x30, 0xd2, 0xa0, 0xf6, 0xb0, 0xf6, 0x24
+    BYTES 0x1e, 0xab, 0xff, 0xfe, 0xb1, 0x53, 0xff, 0xff, 0xb9, 0xfe, 0xff, 0xff, 0xff, 0xff, 0xaa, 0xa9
+    BYTES 0x1a, 0x01, 0x11, 0xea, 0x39, 0x7f, 0xe6, 0x9a, 0x4b, 0x1b, 0xa7, 0xb6, 0x43, 0x4b, 0xac, 0xd7
+    BYTES 0x64, 0x77, 0x4b, 0x84, 0xf3, 0x85, 0x12, 0xbf, 0x67, 0x30, 0xd2, 0xa0, 0xf6, 0xb0, 0xf6, 0x24
+    BYTES 0x1e, 0xab, 0xff, 0xfe, 0xb1, 0x53, 0xff, 0xff, 0xb9, 0xfe, 0xff, 0xff, 0xff, 0xff, 0xaa, 0xaa
+    BYTES 0x08, 0x3c, 0x12, 0x79, 0x1a, 0xbd, 0xd5, 0xd2, 0xfe, 0x2f, 0x28, 0x4f, 0x0c, 0xc6, 0xe5, 0xaa
+    BYTES 0x9b, 0x8c, 0x2d, 0x3f, 0x6f, 0x3f, 0x79, 0x23, 0x02, 0xcf, 0x75, 0xe6, 0x2b, 0xfc, 0x4d, 0xf1
+    BYTES 0xd6, 0x83, 0x44, 0x43, 0xda, 0x49, 0x88, 0x88, 0x72, 0x5d, 0x8c, 0xcc, 0xcc, 0xcc, 0xb1, 0xc3
+    BYTES 0x11, 0xc4, 0xff, 0x71, 0x1e, 0xc2, 0x10, 0xc7, 0x4c, 0xec, 0x7f, 0x67, 0x36, 0x84, 0xc7, 0x2c
+    BYTES 0xc8, 0xeb, 0x1e, 0x45, 0x84, 0x45, 0x99, 0x9c, 0x64, 0x61, 0x5c, 0xba, 0xca, 0xb4, 0xa8, 0x32
+    BYTES 0x48, 0x28, 0xbb, 0xba, 0xd7, 0x0a, 0x77, 0x77, 0x47, 0xa1, 0x73, 0x33, 0x33, 0x32, 0xf8, 0xe8
+    BYTES 0x01, 0xa5, 0x9d, 0x4b, 0x6b, 0xbf, 0x91, 0x2a, 0x32, 0xd6, 0x3b, 0x43, 0x02, 0x8e, 0x2d, 0xee
+    BYTES 0xeb, 0xe8, 0xd5, 0xd9, 0x7c, 0xa6, 0x4b, 0x6d, 0x66, 0xf6, 0x4a, 0xc7, 0xa2, 0x65, 0xa9, 0x30
+    BYTES 0x5e, 0x1a, 0x40, 0xda, 0x5e, 0xdb, 0x81, 0xb4, 0xe3, 0xac, 0x4f, 0x5c, 0x28, 0xf5, 0xbd, 0x27
+    BYTES 0x15, 0x10, 0x3a, 0x07, 0xf6, 0x41, 0x33, 0x1b, 0xb2, 0x98, 0xf5, 0xed, 0x3b, 0xa1, 0x23, 0x0a
+    BYTES 0xa0, 0xbc, 0xc9, 0xf8, 0x7d, 0x92, 0x30, 0x77, 0x32, 0x4d, 0xf2, 0x4a, 0x0f, 0x7f, 0xfa, 0x93
+    BYTES 0x04, 0x5d, 0x3d, 0x6f, 0x94, 0xc1, 0x7a, 0xe1, 0x0e, 0xfa, 0x11, 0xeb, 0x85, 0x1e, 0x73, 0x36
+
+// The coefficients of the numerators and denominators of the x and y coordinates of the 11-isogeny
+// map onto E, in decreasing degree order.
+global bls381_iso_g1:
+    BYTES 0x06, 0xe0, 0x8c, 0x24, 0x8e, 0x26, 0x0e, 0x70, 0xbd, 0x1e, 0x96, 0x23, 0x81, 0xed, 0xee, 0x3d
+    BYTES 0x31, 0xd7, 0x9d, 0x7e, 0x22, 0xc8, 0x37, 0xbc, 0x23, 0xc0, 0xbf, 0x1b, 0xc2, 0x4c, 0x6b, 0x68
+    BYTES 0xc2, 0x4b, 0x1b, 0x80, 0xb6, 0x4d, 0x39, 0x1f, 0xa9, 0xc8, 0xba, 0x2e, 0x8b, 0xa2, 0xd2, 0x29
+    BYTES 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00
+    BYTES 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00
+    BYTES 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00
+    BYTES 0x10, 0x32, 0x1d, 0xa0, 0x79, 0xce, 0x07, 0xe2, 0x72, 0xd8, 0xec, 0x09, 0xd2, 0x56, 0x5b, 0x0d
+    BYTES 0xfa, 0x7d, 0xcc, 0xdd, 0xe6, 0x78, 0x7f, 0x96, 0xd5, 0x0a, 0xf3, 0x60, 0x03, 0xb1, 0x48, 0x66
+    BYTES 0xf6, 0x9b, 0x77, 0x1f, 0x8c, 0x28, 0x5d, 0xec, 0xca, 0x67, 0xdf, 0x3f, 0x16, 0x05, 0xfb, 0x7b
+    BYTES 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00
+    BYTES 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00
+    BYTES 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00
+    BYTES 0x16, 0x9b, 0x1f, 0x8e, 0x1b, 0xcf, 0xa7, 0xc4, 0x2e, 0x0c, 0x37, 0x51, 0x5d, 0x13, 0x8f, 0x22
+    BYTES 0xdd, 0x2e, 0xcb, 0x80, 0x3a, 0x0c, 0x5c, 0x99, 0x67, 0x63, 0x14, 0xba, 0xf4, 0xbb, 0x1b, 0x7f
+    BYTES 0xa3, 0x19, 0x0b, 0x2e, 0xdc, 0x03, 0x27, 0x79, 0x7f, 0x24, 0x10, 0x67, 0xbe, 0x39, 0x0c, 0x9e
+    BYTES 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00
+    BYTES 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00
+    BYTES 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00
+    BYTES 0x08, 0x0d, 0x3c, 0xf1, 0xf9, 0xa7, 0x8f, 0xc4, 0x7b, 0x90, 0xb3, 0x35, 0x63, 0xbe, 0x99, 0x0d
+    BYTES 0xc4, 0x3b, 0x75, 0x6c, 0xe7, 0x9f, 0x55, 0x74, 0xa2, 0xc5, 0x96, 0xc9, 0x28, 0xc5, 0xd1, 0xde
+    BYTES 0x4f, 0xa2, 0x95, 0xf2, 0x96, 0xb7, 0x4e, 0x95, 0x6d, 0x71, 0x98, 0x6a, 0x84, 0x97, 0xe3, 0x17
+    BYTES 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00
+    BYTES 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00
+    BYTES 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00
+    BYTES 0x17, 0xb8, 0x1e, 0x77, 0x01, 0xab, 0xdb, 0xe2, 0xe8, 0x74, 0x38, 0x84, 0xd1, 0x11, 0x7e, 0x53
+    BYTES 0x35, 0x6d, 0xe5, 0xab, 0x27, 0x5b, 0x4d, 0xb1, 0xa6, 0x82, 0xc6, 0x2e, 0xf0, 0xf2, 0x75, 0x33
+    BYTES 0x39, 0xb7, 0xc8, 0xf8, 0xc8, 0xf4, 0x75, 0xaf, 0x9c, 0xcb, 0x56, 0x18, 0xe3, 0xf0, 0xc8, 0x8e
+    BYTES 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00
+    BYTES 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00
+    BYTES 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00
+    BYTES 0x0d, 0x6e, 0xd6, 0x55, 0x3f, 0xe4, 0x4d, 0x29, 0x6a, 0x37, 0x26, 0xc3, 0x8a, 0xe6, 0x52, 0xbf
+    BYTES 0xb1, 0x15, 0x86, 0x26, 0x4f, 0x0f, 0x8c, 0xe1, 0x90, 0x08, 0xe2, 0x18, 0xf9, 0xc8, 0x6b, 0x2a
+    BYTES 0x8d, 0xa2, 0x51, 0x28, 0xc1, 0x05, 0x2e, 0xca, 0xdd, 0xd7, 0xf2, 0x25, 0xa1, 0x39, 0xed, 0x84
+    BYTES 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00
+    BYTES 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00
+    BYTES 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00
+    BYTES 0x16, 0x30, 0xc3, 0x25, 0x0d, 0x73, 0x13, 0xff, 0x01, 0xd1, 0x20, 0x1b, 0xf7, 0xa7, 0x4a, 0xb5
+    BYTES 0xdb, 0x3c, 0xb1, 0x7d, 0xd9, 0x52, 0x79, 0x9b, 0x9e, 0xd3, 0xab, 0x90, 0x97, 0xe6, 0x8f, 0x90
+    BYTES 0xa0, 0x87, 0x0d, 0x2d, 0xca, 0xe7, 0x3d, 0x19, 0xcd, 0x13, 0xc1, 0xc6, 0x6f, 0x65, 0x29, 0x83
+    BYTES 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00
+    BYTES 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00
+    BYTES 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00
+    BYTES 0x0e, 0x99, 0x72, 0x6a, 0x31, 0x99, 0xf4, 0x43, 0x66, 0x42, 0xb4, 0xb3, 0xe4, 0x11, 0x8e, 0x54
+    BYTES 0x99, 0xdb, 0x99, 0x5a, 0x12, 0x57, 0xfb, 0x3f, 0x08, 0x6e, 0xeb, 0x65, 0x98, 0x2f, 0xac, 0x18
+    BYTES 0x98, 0x5a, 0x28, 0x6f, 0x30, 0x1e, 0x77, 0xc4, 0x51, 0x15, 0x4c, 0xe9, 0xac, 0x88, 0x95, 0xd9
+    BYTES 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00
+    BYTES 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00
+    BYTES 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00
+    BYTES 0x17, 0x78, 0xe7, 0x16, 0x6f, 0xcc, 0x6d, 0xb7, 0x4e, 0x06, 0x09, 0xd3, 0x07, 0xe5, 0x54, 0x12
+    BYTES 0xd7, 0xf5, 0xe4, 0x65, 0x6a, 0x8d, 0xbf, 0x25, 0xf1, 0xb3, 0x32, 0x89, 0xf1, 0xb3, 0x30, 0x83
+    BYTES 0x53, 0x36, 0xe2, 0x5c, 0xe3, 0x10, 0x71, 0x93, 0xc5, 0xb3, 0x88, 0x64, 0x1d, 0x9b, 0x68, 0x61
+    BYTES 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00
+    BYTES 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00
+    BYTES 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00
+    BYTES 0x0d, 0x54, 0x00, 0x5d, 0xb9, 0x76, 0x78, 0xec, 0x1d, 0x10, 0x48, 0xc5, 0xd1, 0x0a, 0x9a, 0x1b
+    BYTES 0xce, 0x03, 0x24, 0x73, 0x29, 0x59, 0x83, 0xe5, 0x68, 0x78, 0xe5, 0x01, 0xec, 0x68, 0xe2, 0x5c
+    BYTES 0x95, 0x8c, 0x3e, 0x3d, 0x2a, 0x09, 0x72, 0x9f, 0xe0, 0x17, 0x9f, 0x9d, 0xac, 0x9e, 0xdc, 0xb0
+    BYTES 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00
+    BYTES 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00
+    BYTES 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00
+    BYTES 0x17, 0x29, 0x4e, 0xd3, 0xe9, 0x43, 0xab, 0x2f, 0x05, 0x88, 0xba, 0xb2, 0x21, 0x47, 0xa8, 0x1c
+    BYTES 0x7c, 0x17, 0xe7, 0x5b, 0x2f, 0x6a, 0x84, 0x17, 0xf5, 0x65, 0xe3, 0x3c, 0x70, 0xd1, 0xe8, 0x6b
+    BYTES 0x48, 0x38, 0xf2, 0xa6, 0xf3, 0x18, 0xc3, 0x56, 0xe8, 0x34, 0xee, 0xf1, 0xb3, 0xcb, 0x83, 0xbb
+    BYTES 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00
+    BYTES 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00
+    BYTES 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00
+    BYTES 0x11, 0xa0, 0x5f, 0x2b, 0x1e, 0x83, 0x33, 0x40, 0xb8, 0x09, 0x10, 0x1d, 0xd9, 0x98, 0x15, 0x85
+    BYTES 0x6b, 0x30, 0x3e, 0x88, 0xa2, 0xd7, 0x00, 0x5f, 0xf2, 0x62, 0x7b, 0x56, 0xcd, 0xb4, 0xe2, 0xc8
+    BYTES 0x56, 0x10, 0xc2, 0xd5, 0xf2, 0xe6, 0x2d, 0x6e, 0xae, 0xac, 0x16, 0x62, 0x73, 0x46, 0x49, 0xb7
+    BYTES 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00
+    BYTES 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00
+    BYTES 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00
+    BYTES 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00
+    BYTES 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00
+    BYTES 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x01
+    BYTES 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00
+    BYTES 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00
+    BYTES 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00
+    BYTES 0x09, 0x5f, 0xc1, 0x3a, 0xb9, 0xe9, 0x2a, 0xd4, 0x47, 0x6d, 0x6e, 0x3e, 0xb3, 0xa5, 0x66, 0x80
+    BYTES 0xf6, 0x82, 0xb4, 0xee, 0x96, 0xf7, 0xd0, 0x37, 0x76, 0xdf, 0x53, 0x39, 0x78, 0xf3, 0x1c, 0x15
+    BYTES 0x93, 0x17, 0x4e, 0x4b, 0x4b, 0x78, 0x65, 0x00, 0x2d, 0x63, 0x84, 0xd1, 0x68, 0xec, 0xdd, 0x0a
+    BYTES 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00
+    BYTES 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00
+    BYTES 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00
+    BYTES 0x0a, 0x10, 0xec, 0xf6, 0xad, 0xa5, 0x4f, 0x82, 0x5e, 0x92, 0x0b, 0x3d, 0xaf, 0xc7, 0xa3, 0xcc
+    BYTES 0xe0, 0x7f, 0x8d, 0x1d, 0x71, 0x61, 0x36, 0x6b, 0x74, 0x10, 0x0d, 0xa6, 0x7f, 0x39, 0x88, 0x35
+    BYTES 0x03, 0x82, 0x66, 0x92, 0xab, 0xba, 0x43, 0x70, 0x47, 0x76, 0xec, 0x3a, 0x79, 0xa1, 0xd6, 0x41
+    BYTES 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00
+    BYTES 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00
+    BYTES 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00
+    BYTES 0x14, 0xa7, 0xac, 0x2a, 0x9d, 0x64, 0xa8, 0xb2, 0x30, 0xb3, 0xf5, 0xb0, 0x74, 0xcf, 0x01, 0x99
+    BYTES 0x6e, 0x7f, 0x63, 0xc2, 0x1b, 0xca, 0x68, 0xa8, 0x19, 0x96, 0xe1, 0xcd, 0xf9, 0x82, 0x2c, 0x58
+    BYTES 0x0f, 0xa5, 0xb9, 0x48, 0x9d, 0x11, 0xe2, 0xd3, 0x11, 0xf7, 0xd9, 0x9b, 0xbd, 0xcc, 0x5a, 0x5e
+    BYTES 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00
+    BYTES 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00
+    BYTES 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00
+    BYTES 0x07, 0x72, 0xca, 0xac, 0xf1, 0x69, 0x36, 0x19, 0x0f, 0x3e, 0x0c, 0x63, 0xe0, 0x59, 0x67, 0x21
+    BYTES 0x57, 0x0f, 0x57, 0x99, 0xaf, 0x53, 0xa1, 0x89, 0x4e, 0x2e, 0x07, 0x30, 0x62, 0xae, 0xde, 0x9c
+    BYTES 0xea, 0x73, 0xb3, 0x53, 0x8f, 0x0d, 0xe0, 0x6c, 0xec, 0x25, 0x74, 0x49, 0x6e, 0xe8, 0x4a, 0x3a
+    BYTES 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00
+    BYTES 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00
+    BYTES 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00
+    BYTES 0x0e, 0x73, 0x55, 0xf8, 0xe4, 0xe6, 0x67, 0xb9, 0x55, 0x39, 0x0f, 0x7f, 0x05, 0x06, 0xc6, 0xe9
+    BYTES 0x39, 0x57, 0x35, 0xe9, 0xce, 0x9c, 0xad, 0x4d, 0x0a, 0x43, 0xbc, 0xef, 0x24, 0xb8, 0x98, 0x2f
+    BYTES 0x74, 0x00, 0xd2, 0x4b, 0xc4, 0x22, 0x8f, 0x11, 0xc0, 0x2d, 0xf9, 0xa2, 0x9f, 0x63, 0x04, 0xa5
+    BYTES 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00
+    BYTES 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00
+    BYTES 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00
+    BYTES 0x13, 0xa8, 0xe1, 0x62, 0x02, 0x29, 0x14, 0xa8, 0x0a, 0x6f, 0x1d, 0x5f, 0x43, 0xe7, 0xa0, 0x7d
+    BYTES 0xff, 0xdf, 0xc7, 0x59, 0xa1, 0x20, 0x62, 0xbb, 0x8d, 0x6b, 0x44, 0xe8, 0x33, 0xb3, 0x06, 0xda
+    BYTES 0x9b, 0xd2, 0x9b, 0xa8, 0x1f, 0x35, 0x78, 0x1d, 0x53, 0x9d, 0x39, 0x5b, 0x35, 0x32, 0xa2, 0x1e
+    BYTES 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00
+    BYTES 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00
+    BYTES 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00
+    BYTES 0x03, 0x42, 0x55, 0x81, 0xa5, 0x8a, 0xe2, 0xfe, 0xc8, 0x3a, 0xaf, 0xef, 0x7c, 0x40, 0xeb, 0x54
+    BYTES 0x5b, 0x08, 0x24, 0x3f, 0x16, 0xb1, 0x65, 0x51, 0x54, 0xcc, 0xa8, 0xab, 0xc2, 0x8d, 0x6f, 0xd0
+    BYTES 0x49, 0x76, 0xd5, 0x24, 0x3e, 0xec, 0xf5, 0xc4, 0x13, 0x0d, 0xe8, 0x93, 0x8d, 0xc6, 0x2c, 0xd8
+    BYTES 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00
+    BYTES 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00
+    BYTES 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00
+    BYTES 0x0b, 0x29, 0x62, 0xfe, 0x57, 0xa3, 0x22, 0x5e, 0x81, 0x37, 0xe6, 0x29, 0xbf, 0xf2, 0x99, 0x1f
+    BYTES 0x6f, 0x89, 0x41, 0x6f, 0x5a, 0x71, 0x8c, 0xd1, 0xfc, 0xa6, 0x4e, 0x00, 0xb1, 0x1a, 0xce, 0xac
+    BYTES 0xd6, 0xa3, 0xd0, 0x96, 0x7c, 0x94, 0xfe, 0xdc, 0xfc, 0xc2, 0x39, 0xba, 0x5c, 0xb8, 0x3e, 0x19
+    BYTES 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00
+    BYTES 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00
+    BYTES 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00
+    BYTES 0x12, 0x56, 0x1a, 0x5d, 0xeb, 0x55, 0x9c, 0x43, 0x48, 0xb4, 0x71, 0x12, 0x98, 0xe5, 0x36, 0x36
+    BYTES 0x70, 0x41, 0xe8, 0xca, 0x0c, 0xf0, 0x80, 0x0c, 0x01, 0x26, 0xc2, 0x58, 0x8c, 0x48, 0xbf, 0x57
+    BYTES 0x13, 0xda, 0xa8, 0x84, 0x6c, 0xb0, 0x26, 0xe9, 0xe5, 0xc8, 0x27, 0x6e, 0xc8, 0x2b, 0x3b, 0xff
+    BYTES 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00
+    BYTES 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00
+    BYTES 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00
+    BYTES 0x08, 0xca, 0x8d, 0x54, 0x8c, 0xff, 0x19, 0xae, 0x18, 0xb2, 0xe6, 0x2f, 0x4b, 0xd3, 0xfa, 0x6f
+    BYTES 0x01, 0xd5, 0xef, 0x4b, 0xa3, 0x5b, 0x48, 0xba, 0x9c, 0x95, 0x88, 0x61, 0x7f, 0xc8, 0xac, 0x62
+    BYTES 0xb5, 0x58, 0xd6, 0x81, 0xbe, 0x34, 0x3d, 0xf8, 0x99, 0x3c, 0xf9, 0xfa, 0x40, 0xd2, 0x1b, 0x1c
+    BYTES 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00
+    BYTES 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00
+    BYTES 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00
+    BYTES 0x15, 0xe6, 0xbe, 0x4e, 0x99, 0x0f, 0x03, 0xce, 0x4e, 0xa5, 0x0b, 0x3b, 0x42, 0xdf, 0x2e, 0xb5
+    BYTES 0xcb, 0x18, 0x1d, 0x8f, 0x84, 0x96, 0x5a, 0x39, 0x57, 0xad, 0xd4, 0xfa, 0x95, 0xaf, 0x01, 0xb2
+    BYTES 0xb6, 0x65, 0x02, 0x7e, 0xfe, 0xc0, 0x1c, 0x77, 0x04, 0xb4, 0x56, 0xbe, 0x69, 0xc8, 0xb6, 0x04
+    BYTES 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00
+    BYTES 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00
+    BYTES 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00
+    BYTES 0x05, 0xc1, 0x29, 0x64, 0x5e, 0x44, 0xcf, 0x11, 0x02, 0xa1, 0x59, 0xf7, 0x48, 0xc4, 0xa3, 0xfc
+    BYTES 0x5e, 0x67, 0x3d, 0x81, 0xd7, 0xe8, 0x65, 0x68, 0xd9, 0xab, 0x0f, 0x5d, 0x39, 0x6a, 0x7c, 0xe4
+    BYTES 0x6b, 0xa1, 0x04, 0x9b, 0x65, 0x79, 0xaf, 0xb7, 0x86, 0x6b, 0x1e, 0x71, 0x54, 0x75, 0x22, 0x4b
+    BYTES 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00
+    BYTES 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00
+    BYTES 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00
+    BYTES 0x02, 0x45, 0xa3, 0x94, 0xad, 0x1e, 0xca, 0x9b, 0x72, 0xfc, 0x00, 0xae, 0x7b, 0xe3, 0x15, 0xdc
+    BYTES 0x75, 0x7b, 0x3b, 0x08, 0x0d, 0x4c, 0x15, 0x80, 0x13, 0xe6, 0x63, 0x2d, 0x3c, 0x40, 0x65, 0x9c
+    BYTES 0xc6, 0xcf, 0x90, 0xad, 0x1c, 0x23, 0x2a, 0x64, 0x42, 0xd9, 0xd3, 0xf5, 0xdb, 0x98, 0x01, 0x33
+    BYTES 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00
+    BYTES 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00
+    BYTES 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00
+    BYTES 0x0b, 0x18, 0x2c, 0xac, 0x10, 0x1b, 0x93, 0x99, 0xd1, 0x55, 0x09, 0x60, 0x04, 0xf5, 0x3f, 0x44
+    BYTES 0x7a, 0xa7, 0xb1, 0x2a, 0x34, 0x26, 0xb0, 0x8e, 0xc0, 0x27, 0x10, 0xe8, 0x07, 0xb4, 0x63, 0x3f
+    BYTES 0x06, 0xc8, 0x51, 0xc1, 0x91, 0x92, 0x11, 0xf2, 0x0d, 0x4c, 0x04, 0xf0, 0x0b, 0x97, 0x1e, 0xf8
+    BYTES 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00
+    BYTES 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00
+    BYTES 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00
+    BYTES 0x18, 0xb4, 0x6a, 0x90, 0x8f, 0x36, 0xf6, 0xde, 0xb9, 0x18, 0xc1, 0x43, 0xfe, 0xd2, 0xed, 0xcc
+    BYTES 0x52, 0x35, 0x59, 0xb8, 0xaa, 0xf0, 0xc2, 0x46, 0x2e, 0x6b, 0xfe, 0x7f, 0x91, 0x1f, 0x64, 0x32
+    BYTES 0x49, 0xd9, 0xcd, 0xf4, 0x1b, 0x44, 0xd6, 0x06, 0xce, 0x07, 0xc8, 0xa4, 0xd0, 0x07, 0x4d, 0x8e
+    BYTES 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00
+    BYTES 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00
+    BYTES 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00
+    BYTES 0x19, 0x71, 0x3e, 0x47, 0x93, 0x7c, 0xd1, 0xbe, 0x0d, 0xfd, 0x0b, 0x8f, 0x1d, 0x43, 0xfb, 0x93
+    BYTES 0xcd, 0x2f, 0xcb, 0xcb, 0x6c, 0xaf, 0x49, 0x3f, 0xd1, 0x18, 0x3e, 0x41, 0x63, 0x89, 0xe6, 0x10
+    BYTES 0x31, 0xbf, 0x3a, 0x5c, 0xce, 0x3f, 0xba, 0xfc, 0xe8, 0x13, 0x71, 0x1a, 0xd0, 0x11, 0xc1, 0x32
+    BYTES 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00
+    BYTES 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00
+    BYTES 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00
+    BYTES 0x0e, 0x1b, 0xba, 0x7a, 0x11, 0x86, 0xbd, 0xb5, 0x22, 0x3a, 0xbd, 0xe7, 0xad, 0xa1, 0x4a, 0x23
+    BYTES 0xc4, 0x2a, 0x0c, 0xa7, 0x91, 0x5a, 0xf6, 0xfe, 0x06, 0x98, 0x5e, 0x7e, 0xd1, 0xe4, 0xd4, 0x3b
+    BYTES 0x9b, 0x3f, 0x70, 0x55, 0xdd, 0x4e, 0xba, 0x6f, 0x2b, 0xaf, 0xaa, 0xeb, 0xca, 0x73, 0x1c, 0x30
+    BYTES 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00
+    BYTES 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00
+    BYTES 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00
+    BYTES 0x09, 0xfc, 0x40, 0x18, 0xbd, 0x96, 0x68, 0x4b, 0xe8, 0x8c, 0x9e, 0x22, 0x1e, 0x4d, 0xa1, 0xbb
+    BYTES 0x8f, 0x3a, 0xbd, 0x16, 0x67, 0x9d, 0xc2, 0x6c, 0x1e, 0x8b, 0x6e, 0x6a, 0x1f, 0x20, 0xca, 0xbe
+    BYTES 0x69, 0xd6, 0x52, 0x01, 0xc7, 0x86, 0x07, 0xa3, 0x60, 0x37, 0x0e, 0x57, 0x7b, 0xdb, 0xa5, 0x87
+    BYTES 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00
+    BYTES 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00
+    BYTES 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00
+    BYTES 0x09, 0x87, 0xc8, 0xd5, 0x33, 0x3a, 0xb8, 0x6f, 0xde, 0x99, 0x26, 0xbd, 0x2c, 0xa6, 0xc6, 0x74
+    BYTES 0x17, 0x0a, 0x05, 0xbf, 0xe3, 0xbd, 0xd8, 0x1f, 0xfd, 0x03, 0x8d, 0xa6, 0xc2, 0x6c, 0x84, 0x26
+    BYTES 0x42, 0xf6, 0x45, 0x50, 0xfe, 0xdf, 0xe9, 0x35, 0xa1, 0x5e, 0x4c, 0xa3, 0x18, 0x70, 0xfb, 0x29
+    BYTES 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00
+    BYTES 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00
+    BYTES 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00
+    BYTES 0x04, 0xab, 0x0b, 0x9b, 0xcf, 0xac, 0x1b, 0xbc, 0xb2, 0xc9, 0x77, 0xd0, 0x27, 0x79, 0x6b, 0x3c
+    BYTES 0xe7, 0x5b, 0xb8, 0xca, 0x2b, 0xe1, 0x84, 0xcb, 0x52, 0x31, 0x41, 0x3c, 0x4d, 0x63, 0x4f, 0x37
+    BYTES 0x47, 0xa8, 0x7a, 0xc2, 0x46, 0x0f, 0x41, 0x5e, 0xc9, 0x61, 0xf8, 0x85, 0x5f, 0xe9, 0xd6, 0xf2
+    BYTES 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00
+    BYTES 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00
+    BYTES 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00
+    BYTES 0x16, 0x60, 0x3f, 0xca, 0x40, 0x63, 0x4b, 0x6a, 0x22, 0x11, 0xe1, 0x1d, 0xb8, 0xf0, 0xa6, 0xa0
+    BYTES 0x74, 0xa7, 0xd0, 0xd4, 0xaf, 0xad, 0xb7, 0xbd, 0x76, 0x50, 0x5c, 0x3d, 0x3a, 0xd5, 0x54, 0x4e
+    BYTES 0x20, 0x3f, 0x63, 0x26, 0xc9, 0x5a, 0x80, 0x72, 0x99, 0xb2, 0x3a, 0xb1, 0x36, 0x33, 0xa5, 0xf0
+    BYTES 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00
+    BYTES 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00
+    BYTES 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00
+    BYTES 0x08, 0xcc, 0x03, 0xfd, 0xef, 0xe0, 0xff, 0x13, 0x5c, 0xaf, 0x4f, 0xe2, 0xa2, 0x15, 0x29, 0xc4
+    BYTES 0x19, 0x55, 0x36, 0xfb, 0xe3, 0xce, 0x50, 0xb8, 0x79, 0x83, 0x3f, 0xd2, 0x21, 0x35, 0x1a, 0xdc
+    BYTES 0x2e, 0xe7, 0xf8, 0xdc, 0x09, 0x90, 0x40, 0xa8, 0x41, 0xb6, 0xda, 0xec, 0xf2, 0xe8, 0xfe, 0xdb
+    BYTES 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00
+    BYTES 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00
+    BYTES 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00
+    BYTES 0x01, 0xf8, 0x63, 0x76, 0xe8, 0x98, 0x1c, 0x21, 0x78, 0x98, 0x75, 0x1a, 0xd8, 0x74, 0x67, 0x57
+    BYTES 0xd4, 0x2a, 0xa7, 0xb9, 0x0e, 0xeb, 0x79, 0x1c, 0x09, 0xe4, 0xa3, 0xec, 0x03, 0x25, 0x1c, 0xf9
+    BYTES 0xde, 0x40, 0x5a, 0xba, 0x9e, 0xc6, 0x1d, 0xec, 0xa6, 0x35, 0x5c, 0x77, 0xb0, 0xe5, 0xf4, 0xcb
+    BYTES 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00
+    BYTES 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00
+    BYTES 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00
+    BYTES 0x00, 0xcc, 0x78, 0x6b, 0xaa, 0x96, 0x6e, 0x66, 0xf4, 0xa3, 0x84, 0xc8, 0x6a, 0x3b, 0x49, 0x94
+    BYTES 0x25, 0x52, 0xe2, 0xd6, 0x58, 0xa3, 0x1c, 0xe2, 0xc3, 0x44, 0xbe, 0x4b, 0x91, 0x40, 0x0d, 0xa7
+    BYTES 0xd2, 0x6d, 0x52, 0x16, 0x28, 0xb0, 0x05, 0x23, 0xb8, 0xdf, 0xe2, 0x40, 0xc7, 0x2d, 0xe1, 0xf6
+    BYTES 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00
+    BYTES 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00
+    BYTES 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00
+    BYTES 0x13, 0x49, 0x96, 0xa1, 0x04, 0xee, 0x58, 0x11, 0xd5, 0x10, 0x36, 0xd7, 0x76, 0xfb, 0x46, 0x83
+    BYTES 0x12, 0x23, 0xe9, 0x6c, 0x25, 0x4f, 0x38, 0x3d, 0x0f, 0x90, 0x63, 0x43, 0xeb, 0x67, 0xad, 0x34
+    BYTES 0xd6, 0xc5, 0x67, 0x11, 0x96, 0x2f, 0xa8, 0xbf, 0xe0, 0x97, 0xe7, 0x5a, 0x2e, 0x41, 0xc6, 0x96
+    BYTES 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00
+    BYTES 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00
+    BYTES 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00
+    BYTES 0x09, 0x0d, 0x97, 0xc8, 0x1b, 0xa2, 0x4e, 0xe0, 0x25, 0x9d, 0x1f, 0x09, 0x49, 0x80, 0xdc, 0xfa
+    BYTES 0x11, 0xad, 0x13, 0x8e, 0x48, 0xa8, 0x69, 0x52, 0x2b, 0x52, 0xaf, 0x6c, 0x95, 0x65, 0x43, 0xd3
+    BYTES 0xcd, 0x0c, 0x7a, 0xee, 0x9b, 0x3b, 0xa3, 0xc2, 0xbe, 0x98, 0x45, 0x71, 0x97, 0x07, 0xbb, 0x33
+    BYTES 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00
+    BYTES 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00
+    BYTES 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00
+    BYTES 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00
+    BYTES 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00
+    BYTES 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x01
+    BYTES 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00
+    BYTES 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00
+    BYTES 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00
+    BYTES 0x0e, 0x0f, 0xa1, 0xd8, 0x16, 0xdd, 0xc0, 0x3e, 0x6b, 0x24, 0x25, 0x5e, 0x0d, 0x78, 0x19, 0xc1
+    BYTES 0x71, 0xc4, 0x0f, 0x65, 0xe2, 0x73, 0xb8, 0x53, 0x32, 0x4e, 0xfc, 0xd6, 0x35, 0x6c, 0xaa, 0x20
+    BYTES 0x5c, 0xa2, 0xf5, 0x70, 0xf1, 0x34, 0x97, 0x80, 0x44, 0x15, 0x47, 0x3a, 0x1d, 0x63, 0x4b, 0x8f
+    BYTES 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00
+    BYTES 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00
+    BYTES 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00
+    BYTES 0x02, 0x66, 0x04, 0x00, 0xeb, 0x2e, 0x4f, 0x3b, 0x62, 0x8b, 0xdd, 0x0d, 0x53, 0xcd, 0x76, 0xf2
+    BYTES 0xbf, 0x56, 0x5b, 0x94, 0xe7, 0x29, 0x27, 0xc1, 0xcb, 0x74, 0x8d, 0xf2, 0x79, 0x42, 0x48, 0x0e
+    BYTES 0x42, 0x05, 0x17, 0xbd, 0x87, 0x14, 0xcc, 0x80, 0xd1, 0xfa, 0xdc, 0x13, 0x26, 0xed, 0x06, 0xf7
+    BYTES 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00
+    BYTES 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00
+    BYTES 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00
+    BYTES 0x0a, 0xd6, 0xb9, 0x51, 0x4c, 0x76, 0x7f, 0xe3, 0xc3, 0x61, 0x31, 0x44, 0xb4, 0x5f, 0x14, 0x96
+    BYTES 0x54, 0x33, 0x46, 0xd9, 0x8a, 0xdf, 0x02, 0x26, 0x7d, 0x5c, 0xee, 0xf9, 0xa0, 0x0d, 0x9b, 0x86
+    BYTES 0x93, 0x00, 0x07, 0x63, 0xe3, 0xb9, 0x0a, 0xc1, 0x1e, 0x99, 0xb1, 0x38, 0x57, 0x33, 0x45, 0xcc
+    BYTES 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00
+    BYTES 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00
+    BYTES 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00
+    BYTES 0x0a, 0xcc, 0xbb, 0x67, 0x48, 0x1d, 0x03, 0x3f, 0xf5, 0x85, 0x2c, 0x1e, 0x48, 0xc5, 0x0c, 0x47
+    BYTES 0x7f, 0x94, 0xff, 0x8a, 0xef, 0xce, 0x42, 0xd2, 0x8c, 0x0f, 0x9a, 0x88, 0xce, 0xa7, 0x91, 0x35
+    BYTES 0x16, 0xf9, 0x68, 0x98, 0x6f, 0x7e, 0xbb, 0xea, 0x96, 0x84, 0xb5, 0x29, 0xe2, 0x56, 0x10, 0x92
+    BYTES 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00
+    BYTES 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00
+    BYTES 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00
+    BYTES 0x04, 0xd2, 0xf2, 0x59, 0xee, 0xa4, 0x05, 0xbd, 0x48, 0xf0, 0x10, 0xa0, 0x1a, 0xd2, 0x91, 0x1d
+    BYTES 0x9c, 0x6d, 0xd0, 0x39, 0xbb, 0x61, 0xa6, 0x29, 0x0e, 0x59, 0x1b, 0x36, 0xe6, 0x36, 0xa5, 0xc8
+    BYTES 0x71, 0xa5, 0xc2, 0x9f, 0x4f, 0x83, 0x06, 0x04, 0x00, 0xf8, 0xb4, 0x9c, 0xba, 0x8f, 0x6a, 0xa8
+    BYTES 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00
+    BYTES 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00
+    BYTES 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00
+    BYTES 0x16, 0x7a, 0x55, 0xcd, 0xa7, 0x0a, 0x6e, 0x1c, 0xea, 0x82, 0x05, 0x97, 0xd9, 0x4a, 0x84, 0x90
+    BYTES 0x32, 0x16, 0xf7, 0x63, 0xe1, 0x3d, 0x87, 0xbb, 0x53, 0x08, 0x59, 0x2e, 0x7e, 0xa7, 0xd4, 0xfb
+    BYTES 0xc7, 0x38, 0x5e, 0xa3, 0xd5, 0x29, 0xb3, 0x5e, 0x34, 0x6e, 0xf4, 0x8b, 0xb8, 0x91, 0x3f, 0x55
+    BYTES 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00
+    BYTES 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00
+    BYTES 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00
+    BYTES 0x18, 0x66, 0xc8, 0xed, 0x33, 0x6c, 0x61, 0x23, 0x1a, 0x1b, 0xe5, 0x4f, 0xd1, 0xd7, 0x4c, 0xc4
+    BYTES 0xf9, 0xfb, 0x0c, 0xe4, 0xc6, 0xaf, 0x59, 0x20, 0xab, 0xc5, 0x75, 0x0c, 0x4b, 0xf3, 0x9b, 0x48
+    BYTES 0x52, 0xcf, 0xe2, 0xf7, 0xbb, 0x92, 0x48, 0x83, 0x6b, 0x23, 0x3d, 0x9d, 0x55, 0x53, 0x5d, 0x4a
+    BYTES 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00
+    BYTES 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00
+    BYTES 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00
+    BYTES 0x16, 0xa3, 0xef, 0x08, 0xbe, 0x3e, 0xa7, 0xea, 0x03, 0xbc, 0xdd, 0xfa, 0xbb, 0xa6, 0xff, 0x6e
+    BYTES 0xe5, 0xa4, 0x37, 0x5e, 0xfa, 0x1f, 0x4f, 0xd7, 0xfe, 0xb3, 0x4f, 0xd2, 0x06, 0x35, 0x71, 0x32
+    BYTES 0xb9, 0x20, 0xf5, 0xb0, 0x08, 0x01, 0xde, 0xe4, 0x60, 0xee, 0x41, 0x5a, 0x15, 0x81, 0x2e, 0xd9
+    BYTES 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00
+    BYTES 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00
+    BYTES 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00
+    BYTES 0x16, 0x60, 0x07, 0xc0, 0x8a, 0x99, 0xdb, 0x2f, 0xc3, 0xba, 0x87, 0x34, 0xac, 0xe9, 0x82, 0x4b
+    BYTES 0x5e, 0xec, 0xfd, 0xfa, 0x8d, 0x0c, 0xf8, 0xef, 0x5d, 0xd3, 0x65, 0xbc, 0x40, 0x0a, 0x00, 0x51
+    BYTES 0xd5, 0xfa, 0x9c, 0x01, 0xa5, 0x8b, 0x1f, 0xb9, 0x3d, 0x1a, 0x13, 0x99, 0x12, 0x6a, 0x77, 0x5c
+    BYTES 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00
+    BYTES 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00
+    BYTES 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00
+    BYTES 0x08, 0xd9, 0xe5, 0x29, 0x71, 0x86, 0xdb, 0x2d, 0x9f, 0xb2, 0x66, 0xea, 0xac, 0x78, 0x31, 0x82
+    BYTES 0xb7, 0x01, 0x52, 0xc6, 0x55, 0x50, 0xd8, 0x81, 0xc5, 0xec, 0xd8, 0x7b, 0x6f, 0x0f, 0x5a, 0x64
+    BYTES 0x49, 0xf3, 0x8d, 0xb9, 0xdf, 0xa9, 0xcc, 0xe2, 0x02, 0xc6, 0x47, 0x7f, 0xaa, 0xf9, 0xb7, 0xac
+    BYTES 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00
+    BYTES 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00
+    BYTES 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00
+    BYTES 0x0b, 0xe0, 0xe0, 0x79, 0x54, 0x5f, 0x43, 0xe4, 0xb0, 0x0c, 0xc9, 0x12, 0xf8, 0x22, 0x8d, 0xdc
+    BYTES 0xc6, 0xd1, 0x9c, 0x9f, 0x0f, 0x69, 0xbb, 0xb0, 0x54, 0x2e, 0xda, 0x0f, 0xc9, 0xde, 0xc9, 0x16
+    BYTES 0xa2, 0x0b, 0x15, 0xdc, 0x0f, 0xd2, 0xed, 0xed, 0xda, 0x39, 0x14, 0x23, 0x11, 0xa5, 0x00, 0x1d
+    BYTES 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00
+    BYTES 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00
+    BYTES 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00
+    BYTES 0x16, 0xb7, 0xd2, 0x88, 0x79, 0x8e, 0x53, 0x95, 0xf2, 0x0d, 0x23, 0xbf, 0x89, 0xed, 0xb4, 0xd1
+    BYTES 0xd1, 0x15, 0xc5, 0xdb, 0xdd, 0xbc, 0xd3, 0x0e, 0x12, 0x3d, 0xa4, 0x89, 0xe7, 0x26, 0xaf, 0x41
+    BYTES 0x72, 0x73, 0x64, 0xf2, 0xc2, 0x82, 0x97, 0xad, 0xa8, 0xd2, 0x6d, 0x98, 0x44, 0x5f, 0x54, 0x16
+    BYTES 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00
+    BYTES 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00
+    BYTES 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00
+    BYTES 0x05, 0x8d, 0xf3, 0x30, 0x66, 0x40, 0xda, 0x27, 0x6f, 0xaa, 0xae, 0x7d, 0x6e, 0x8e, 0xb1, 0x57
+    BYTES 0x78, 0xc4, 0x85, 0x55, 0x51, 0xae, 0x7f, 0x31, 0x0c, 0x35, 0xa5, 0xdd, 0x27, 0x9c, 0xd2, 0xec
+    BYTES 0xa6, 0x75, 0x7c, 0xd6, 0x36, 0xf9, 0x6f, 0x89, 0x1e, 0x25, 0x38, 0xb5, 0x3d, 0xbf, 0x67, 0xf2
+    BYTES 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00
+    BYTES 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00
+    BYTES 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00
+    BYTES 0x19, 0x62, 0xd7, 0x5c, 0x23, 0x81, 0x20, 0x1e, 0x1a, 0x0c, 0xbd, 0x6c, 0x43, 0xc3, 0x48, 0xb8
+    BYTES 0x85, 0xc8, 0x4f, 0xf7, 0x31, 0xc4, 0xd5, 0x9c, 0xa4, 0xa1, 0x03, 0x56, 0xf4, 0x53, 0xe0, 0x1f
+    BYTES 0x78, 0xa4, 0x26, 0x07, 0x63, 0x52, 0x9e, 0x35, 0x32, 0xf6, 0x10, 0x2c, 0x2e, 0x49, 0xa0, 0x3d
+    BYTES 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00
+    BYTES 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00
+    BYTES 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00
+    BYTES 0x16, 0x11, 0x2c, 0x4c, 0x3a, 0x9c, 0x98, 0xb2, 0x52, 0x18, 0x11, 0x40, 0xfa, 0xd0, 0xea, 0xe9
+    BYTES 0x60, 0x1a, 0x6d, 0xe5, 0x78, 0x98, 0x0b, 0xe6, 0xee, 0xc3, 0x23, 0x2b, 0x5b, 0xe7, 0x2e, 0x7a
+    BYTES 0x07, 0xf3, 0x68, 0x8e, 0xf6, 0x0c, 0x20, 0x6d, 0x01, 0x47, 0x92, 0x53, 0xb0, 0x36, 0x63, 0xc1
+    BYTES 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00
+    BYTES 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00
+    BYTES 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00
+
+// The coefficients of the numerators and denominators of the x and y coordinates of the 3-isogeny
+// map onto E', in decreasing degree order.
+global bls381_iso_g2:
+    BYTES 0x17, 0x1d, 0x65, 0x41, 0xfa, 0x38, 0xcc, 0xfa, 0xed, 0x6d, 0xea, 0x69, 0x1f, 0x5f, 0xb6, 0x14
+    BYTES 0xcb, 0x14, 0xb4, 0xe7, 0xf4, 0xe8, 0x10, 0xaa, 0x22, 0xd6, 0x10, 0x8f, 0x14, 0x2b, 0x85, 0x75
+    BYTES 0x70, 0x98, 0xe3, 0x8d, 0x0f, 0x67, 0x1c, 0x71, 0x88, 0xe2, 0xaa, 0xaa, 0xaa, 0xaa, 0x5e, 0xd1
+    BYTES 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00
+    BYTES 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00
+    BYTES 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00
+    BYTES 0x11, 0x56, 0x0b, 0xf1, 0x7b, 0xaa, 0x99, 0xbc, 0x32, 0x12, 0x6f, 0xce, 0xd7, 0x87, 0xc8, 0x8f
+    BYTES 0x98, 0x4f, 0x87, 0xad, 0xf7, 0xae, 0x0c, 0x7f, 0x9a, 0x20, 0x8c, 0x6b, 0x4f, 0x20, 0xa4, 0x18
+    BYTES 0x14, 0x72, 0xaa, 0xa9, 0xcb, 0x8d, 0x55, 0x55, 0x26, 0xa9, 0xff, 0xff, 0xff, 0xff, 0xc7, 0x1e
+    BYTES 0x08, 0xab, 0x05, 0xf8, 0xbd, 0xd5, 0x4c, 0xde, 0x19, 0x09, 0x37, 0xe7, 0x6b, 0xc3, 0xe4, 0x47
+    BYTES 0xcc, 0x27, 0xc3, 0xd6, 0xfb, 0xd7, 0x06, 0x3f, 0xcd, 0x10, 0x46, 0x35, 0xa7, 0x90, 0x52, 0x0c
+    BYTES 0x0a, 0x39, 0x55, 0x54, 0xe5, 0xc6, 0xaa, 0xaa, 0x93, 0x54, 0xff, 0xff, 0xff, 0xff, 0xe3, 0x8d
+    BYTES 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00
+    BYTES 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00
+    BYTES 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00
+    BYTES 0x11, 0x56, 0x0b, 0xf1, 0x7b, 0xaa, 0x99, 0xbc, 0x32, 0x12, 0x6f, 0xce, 0xd7, 0x87, 0xc8, 0x8f
+    BYTES 0x98, 0x4f, 0x87, 0xad, 0xf7, 0xae, 0x0c, 0x7f, 0x9a, 0x20, 0x8c, 0x6b, 0x4f, 0x20, 0xa4, 0x18
+    BYTES 0x14, 0x72, 0xaa, 0xa9, 0xcb, 0x8d, 0x55, 0x55, 0x26, 0xa9, 0xff, 0xff, 0xff, 0xff, 0xc7, 0x1a
+    BYTES 0x05, 0xc7, 0x59, 0x50, 0x7e, 0x8e, 0x33, 0x3e, 0xbb, 0x5b, 0x7a, 0x9a, 0x47, 0xd7, 0xed, 0x85
+    BYTES 0x32, 0xc5, 0x2d, 0x39, 0xfd, 0x3a, 0x04, 0x2a, 0x88, 0xb5, 0x84, 0x23, 0xc5, 0x0a, 0xe1, 0x5d
+    BYTES 0x5c, 0x26, 0x38, 0xe3, 0x43, 0xd9, 0xc7, 0x1c, 0x62, 0x38, 0xaa, 0xaa, 0xaa, 0xaa, 0x97, 0xd6
+    BYTES 0x05, 0xc7, 0x59, 0x50, 0x7e, 0x8e, 0x33, 0x3e, 0xbb, 0x5b, 0x7a, 0x9a, 0x47, 0xd7, 0xed, 0x85
+    BYTES 0x32, 0xc5, 0x2d, 0x39, 0xfd, 0x3a, 0x04, 0x2a, 0x88, 0xb5, 0x84, 0x23, 0xc5, 0x0a, 0xe1, 0x5d
+    BYTES 0x5c, 0x26, 0x38, 0xe3, 0x43, 0xd9, 0xc7, 0x1c, 0x62, 0x38, 0xaa, 0xaa, 0xaa, 0xaa, 0x97, 0xd6
+    BYTES 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00
+    BYTES 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00
+    BYTES 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x01
+    BYTES 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00
+    BYTES 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00
+    BYTES 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00
+    BYTES 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00
+    BYTES 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00
+    BYTES 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x0c
+    BYTES 0x1a, 0x01, 0x11, 0xea, 0x39, 0x7f, 0xe6, 0x9a, 0x4b, 0x1b, 0xa7, 0xb6, 0x43, 0x4b, 0xac, 0xd7
+    BYTES 0x64, 0x77, 0x4b, 0x84, 0xf3, 0x85, 0x12, 0xbf, 0x67, 0x30, 0xd2, 0xa0, 0xf6, 0xb0, 0xf6, 0x24
+    BYTES 0x1e, 0xab, 0xff, 0xfe, 0xb1, 0x53, 0xff, 0xff, 0xb9, 0xfe, 0xff, 0xff, 0xff, 0xff, 0xaa, 0x9f
+    BYTES 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00
+    BYTES 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00
+    BYTES 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00
+    BYTES 0x1a, 0x01, 0x11, 0xea, 0x39, 0x7f, 0xe6, 0x9a, 0x4b, 0x1b, 0xa7, 0xb6, 0x43, 0x4b, 0xac, 0xd7
+    BYTES 0x64, 0x77, 0x4b, 0x84, 0xf3, 0x85, 0x12, 0xbf, 0x67, 0x30, 0xd2, 0xa0, 0xf6, 0xb0, 0xf6, 0x24
+    BYTES 0x1e, 0xab, 0xff, 0xfe, 0xb1, 0x53, 0xff, 0xff, 0xb9, 0xfe, 0xff, 0xff, 0xff, 0xff, 0xaa, 0x63
+    BYTES 0x12, 0x4c, 0x9a, 0xd4, 0x3b, 0x6c, 0xf7, 0x9b, 0xfb, 0xf7, 0x04, 0x3d, 0xe3, 0x81, 0x1a, 0xd0
+    BYTES 0x76, 0x1b, 0x0f, 0x37, 0xa1, 0xe2, 0x62, 0x86, 0xb0, 0xe9, 0x77, 0xc6, 0x9a, 0xa2, 0x74, 0x52
+    BYTES 0x4e, 0x79, 0x09, 0x7a, 0x56, 0xdc, 0x4b, 0xd9, 0xe1, 0xb3, 0x71, 0xc7, 0x1c, 0x71, 0x8b, 0x10
+    BYTES 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00
+    BYTES 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00
+    BYTES 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00
+    BYTES 0x11, 0x56, 0x0b, 0xf1, 0x7b, 0xaa, 0x99, 0xbc, 0x32, 0x12, 0x6f, 0xce, 0xd7, 0x87, 0xc8, 0x8f
+    BYTES 0x98, 0x4f, 0x87, 0xad, 0xf7, 0xae, 0x0c, 0x7f, 0x9a, 0x20, 0x8c, 0x6b, 0x4f, 0x20, 0xa4, 0x18
+    BYTES 0x14, 0x72, 0xaa, 0xa9, 0xcb, 0x8d, 0x55, 0x55, 0x26, 0xa9, 0xff, 0xff, 0xff, 0xff, 0xc7, 0x1c
+    BYTES 0x08, 0xab, 0x05, 0xf8, 0xbd, 0xd5, 0x4c, 0xde, 0x19, 0x09, 0x37, 0xe7, 0x6b, 0xc3, 0xe4, 0x47
+    BYTES 0xcc, 0x27, 0xc3, 0xd6, 0xfb, 0xd7, 0x06, 0x3f, 0xcd, 0x10, 0x46, 0x35, 0xa7, 0x90, 0x52, 0x0c
+    BYTES 0x0a, 0x39, 0x55, 0x54, 0xe5, 0xc6, 0xaa, 0xaa, 0x93, 0x54, 0xff, 0xff, 0xff, 0xff, 0xe3, 0x8f
+    BYTES 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00
+    BYTES 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00
+    BYTES 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00
+    BYTES 0x05, 0xc7, 0x59, 0x50, 0x7e, 0x8e, 0x33, 0x3e, 0xbb, 0x5b, 0x7a, 0x9a, 0x47, 0xd7, 0xed, 0x85
+    BYTES 0x32, 0xc5, 0x2d, 0x39, 0xfd, 0x3a, 0x04, 0x2a, 0x88, 0xb5, 0x84, 0x23, 0xc5, 0x0a, 0xe1, 0x5d
+    BYTES 0x5c, 0x26, 0x38, 0xe3, 0x43, 0xd9, 0xc7, 0x1c, 0x62, 0x38, 0xaa, 0xaa, 0xaa, 0xaa, 0x97, 0xbe
+    BYTES 0x15, 0x30, 0x47, 0x7c, 0x7a, 0xb4, 0x11, 0x3b, 0x59, 0xa4, 0xc1, 0x8b, 0x07, 0x6d, 0x11, 0x93
+    BYTES 0x0f, 0x7d, 0xa5, 0xd4, 0xa0, 0x7f, 0x64, 0x9b, 0xf5, 0x44, 0x39, 0xd8, 0x7d, 0x27, 0xe5, 0x00
+    BYTES 0xfc, 0x8c, 0x25, 0xeb, 0xf8, 0xc9, 0x2f, 0x68, 0x12, 0xcf, 0xc7, 0x1c, 0x71, 0xc6, 0xd7, 0x06
+    BYTES 0x15, 0x30, 0x47, 0x7c, 0x7a, 0xb4, 0x11, 0x3b, 0x59, 0xa4, 0xc1, 0x8b, 0x07, 0x6d, 0x11, 0x93
+    BYTES 0x0f, 0x7d, 0xa5, 0xd4, 0xa0, 0x7f, 0x64, 0x9b, 0xf5, 0x44, 0x39, 0xd8, 0x7d, 0x27, 0xe5, 0x00
+    BYTES 0xfc, 0x8c, 0x25, 0xeb, 0xf8, 0xc9, 0x2f, 0x68, 0x12, 0xcf, 0xc7, 0x1c, 0x71, 0xc6, 0xd7, 0x06
+    BYTES 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00
+    BYTES 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00
+    BYTES 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x01
+    BYTES 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00
+    BYTES 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00
+    BYTES 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00
+    BYTES 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00
+    BYTES 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00
+    BYTES 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x12
+    BYTES 0x1a, 0x01, 0x11, 0xea, 0x39, 0x7f, 0xe6, 0x9a, 0x4b, 0x1b, 0xa7, 0xb6, 0x43, 0x4b, 0xac, 0xd7
+    BYTES 0x64, 0x77, 0x4b, 0x84, 0xf3, 0x85, 0x12, 0xbf, 0x67, 0x30, 0xd2, 0xa0, 0xf6, 0xb0, 0xf6, 0x24
+    BYTES 0x1e, 0xab, 0xff, 0xfe, 0xb1, 0x53, 0xff, 0xff, 0xb9, 0xfe, 0xff, 0xff, 0xff, 0xff, 0xaa, 0x99
+    BYTES 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00
+    BYTES 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00
+    BYTES 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00
+    BYTES 0x1a, 0x01, 0x11, 0xea, 0x39, 0x7f, 0xe6, 0x9a, 0x4b, 0x1b, 0xa7, 0xb6, 0x43, 0x4b, 0xac, 0xd7
+    BYTES 0x64, 0x77, 0x4b, 0x84, 0xf3, 0x85, 0x12, 0xbf, 0x67, 0x30, 0xd2, 0xa0, 0xf6, 0xb0, 0xf6, 0x24
+    BYTES 0x1e, 0xab, 0xff, 0xfe, 0xb1, 0x53, 0xff, 0xff, 0xb9, 0xfe, 0xff, 0xff, 0xff, 0xff, 0xa9, 0xd3
+    BYTES 0x1a, 0x01, 0x11, 0xea, 0x39, 0x7f, 0xe6, 0x9a, 0x4b, 0x1b, 0xa7, 0xb6, 0x43, 0x4b, 0xac, 0xd7
+    BYTES 0x64, 0x77, 0x4b, 0x84, 0xf3, 0x85, 0x12, 0xbf, 0x67, 0x30, 0xd2, 0xa0, 0xf6, 0xb0, 0xf6, 0x24
+    BYTES 0x1e, 0xab, 0xff, 0xfe, 0xb1, 0x53, 0xff, 0xff, 0xb9, 0xfe, 0xff, 0xff, 0xff, 0xff, 0xa8, 0xfb
+    BYTES 0x1a, 0x01, 0x11, 0xea, 0x39, 0x7f, 0xe6, 0x9a, 0x4b, 0x1b, 0xa7, 0xb6, 0x43, 0x4b, 0xac, 0xd7
+    BYTES 0x64, 0x77, 0x4b, 0x84, 0xf3, 0x85, 0x12, 0xbf, 0x67, 0x30, 0xd2, 0xa0, 0xf6, 0xb0, 0xf6, 0x24
+    BYTES 0x1e, 0xab, 0xff, 0xfe, 0xb1, 0x53, 0xff, 0xff, 0xb9, 0xfe, 0xff, 0xff, 0xff, 0xff, 0xa8, 0xfb
@@ -0,0 +1,349 @@
+// The maps of elements of Fp and Fp2 to the BLS12-381 curves of EIP-2537, i.e. the maps map_to_curve
+// of the suites BLS12381G1_XMD:SHA-256_SSWU_RO_ and BLS12381G2_XMD:SHA-256_SSWU_RO_ of RFC 9380,
+// followed by the clearing of the cofactor: the simplified SWU map onto a curve isogenous to E or
+// E', then the isogeny map onto E or E'.
+//
+// The maps read their input u at offset 700 of the current kernel general memory and write their
+// output at offset 708, as a point stored as in curve_add.asm, and use the offsets 716 to 739 as
+// scratch space, besides that of the curve arithmetic. The elements of Fp are the elements of Fp2
+// with zero imaginary part, for which all the operations below agree with those over Fp.
+
+// Sets the point at offset 708 to the image in G1 of the element of Fp at offset 700.
+global bls381_map_to_g1:
+    // stack: retdest
+    PUSH bls381_map_to_g1_iso
+    PUSH bls381_sqrt_g1
+    PUSH bls381_sswu_g1_params
+    %jump(bls381_sswu)
+bls381_map_to_g1_iso:
+    // stack: retdest
+    PUSH bls381_map_to_g1_clear
+    PUSH 16
+    PUSH 16
+    PUSH 11
+    PUSH 12
+    PUSH bls381_iso_g1
+    %jump(bls381_iso_map)
+bls381_map_to_g1_clear:
+    // stack: retdest
+    // The effective cofactor of G1 is 1 - x.
+    PUSH 708
+    %build_current_general_address
+    PUSH 0xd201000000010001
+    DUP2
+    %bls381_curve_mul
+    JUMP
+
+// Sets the point at offset 708 to the image in G2 of the element of Fp2 at offset 700.
+global bls381_map_to_g2:
+    // stack: retdest
+    PUSH bls381_map_to_g2_iso
+    PUSH bls381_sqrt_g2
+    PUSH bls381_sswu_g2_params
+    %jump(bls381_sswu)
+bls381_map_to_g2_iso:
+    // stack: retdest
+    PUSH bls381_clear_cofactor_g2
+    PUSH 4
+    PUSH 4
+    PUSH 3
+    PUSH 4
+    PUSH bls381_iso_g2
+    %jump(bls381_iso_map)
+
+// Multiplies the point P at offset 708 by the effective cofactor of G2, following appendix G.3 of
+// RFC 9380, with the points t1, t2 and t3 at offsets 716, 724 and 732.
+bls381_clear_cofactor_g2:
+    // stack: retdest
+    // t1 = [x] P, t2 = psi(P)
+    %bls381_curve_mul_x_at(708, 716)
+    %bls381_psi_at(708, 724)
+    // t3 = psi^2(2 P) - t2
+    %bls381_curve_add_at(708, 708, 732)
+    %bls381_psi_at(732, 732)
+    %bls381_psi_at(732, 732)
+    %neg_bls381_at(724)
+    %bls381_curve_add_at(732, 724, 732)
+    // t2 = [x] (t1 + t2)
+    %neg_bls381_at(724)
+    %bls381_curve_add_at(716, 724, 724)
+    %bls381_curve_mul_x_at(724, 724)
+    // P = t3 + t2 - t1 - P
+    %bls381_curve_add_at(732, 724, 732)
+    %neg_bls381_at(716)
+    %bls381_curve_add_at(732, 716, 732)
+    %neg_bls381_at(708)
+    %bls381_curve_add_at(732, 708, 708)
+    // stack: retdest
+    JUMP
+
+// Sets the point at offset 708 to the image of the element u of Fp2 at offset 700 under the simplified
+// SWU map of section 6.6.2 of RFC 9380, onto the curve y^2 = x^3 + A' x + B' given by the constants
+// (A', B', Z, -B' / A', B' / (Z A')) at params. The routine sqrt returns a square root of its input,
+// or of Z times its input if it isn't a square, as given by the prover, and is checked here. The
+// offsets 716, 720 and 724 hold Z u^2, x1 and g(x1).
+global bls381_sswu:
+    // stack: params, sqrt, retdest
+    %load_fp381_2(700)
+    %square_fp381_2
+    DUP5
+    %add_const(192)
+    %load_fp381_2_code
+    %mul_fp381_2
+    %dup_fp381_2_0
+    %store_fp381_2(716)
+    // stack: Z u^2: 4, params, sqrt, retdest
+    // tv1 = Z^2 u^4 + Z u^2
+    %dup_fp381_2_0
+    %one_fp381_2
+    %add_fp381_2
+    %mul_fp381_2
+    %dup_fp381_2_0
+    %is_zero_fp381_2
+    %jumpi(bls381_sswu_exceptional)
+    // x1 = (-B' / A') (1 + 1 / tv1)
+    %one_fp381_2
+    %div_fp381_2
+    %one_fp381_2
+    %add_fp381_2
+    DUP5
+    %add_const(288)
+    %load_fp381_2_code
+    %mul_fp381_2
+    %jump(bls381_sswu_x1)
+bls381_sswu_exceptional:
+    // stack: tv1: 4, params, sqrt, retdest
+    // x1 = B' / (Z A')
+    %pop4
+    DUP1
+    %add_const(384)
+    %load_fp381_2_code
+bls381_sswu_x1:
+    // stack: x1: 4, params, sqrt, retdest
+    %store_fp381_2(720)
+    // g(x1) = (x1^2 + A') x1 + B'
+    DUP1
+    %add_const(96)
+    %load_fp381_2_code
+    %load_fp381_2(720)
+    DUP9
+    %load_fp381_2_code
+    %load_fp381_2(720)
+    %square_fp381_2
+    %add_fp381_2
+    %mul_fp381_2
+    %add_fp381_2
+    %dup_fp381_2_0
+    %store_fp381_2(724)
+    // stack: g(x1): 4, params, sqrt, retdest
+    %stack (gx1: 4, params, sqrt) -> (sqrt, gx1, bls381_sswu_sqrt, params, sqrt)
+    JUMP
+bls381_sswu_sqrt:
+    // stack: s: 4, params, sqrt, retdest
+    %dup_fp381_2_0
+    %square_fp381_2
+    %load_fp381_2(724)
+    %eq_fp381_2
+    %jumpi(bls381_sswu_square)
+    // As g(x1) isn't a square, s^2 = Z g(x1), and then x2 = Z u^2 x1 and y = Z u^3 s, with
+    // y^2 = Z^3 u^6 g(x1) = g(x2).
+    %dup_fp381_2_0
+    %square_fp381_2
+    DUP9
+    %add_const(192)
+    %load_fp381_2_code
+    %load_fp381_2(724)
+    %mul_fp381_2
+    %eq_fp381_2
+    %assert_nonzero
+    // stack: s: 4, params, sqrt, retdest
+    %load_fp381_2(700)
+    %mul_fp381_2
+    %load_fp381_2(716)
+    %mul_fp381_2
+    %load_fp381_2(720)
+    %load_fp381_2(716)
+    %mul_fp381_2
+    %store_fp381_2(708)
+    %jump(bls381_sswu_sign)
+bls381_sswu_square:
+    // stack: s: 4, params, sqrt, retdest
+    %load_fp381_2(720)
+    %store_fp381_2(708)
+bls381_sswu_sign:
+    // stack: y: 4, params, sqrt, retdest
+    %dup_fp381_2_0
+    %sgn0_fp381_2
+    %load_fp381_2(700)
+    %sgn0_fp381_2
+    EQ
+    %jumpi(bls381_sswu_end)
+    %neg_fp381_2
+bls381_sswu_end:
+    // stack: y: 4, params, sqrt, retdest
+    %store_fp381_2(712)
+    %pop2
+    JUMP
+
+// Returns a square root of the element x of Fp2 with zero imaginary part, or of 11 x if x isn't a
+// square, as given by the prover. The caller checks the result.
+global bls381_sqrt_g1:
+    // stack: x: 4, retdest
+    PROVER_INPUT(sf::bls381_base::sqrt_1)
+    PROVER_INPUT(sf::bls381_base::sqrt_0)
+    %assert_canonical_fp381
+    %stack (s: 2, x: 4, retdest) -> (retdest, s, 0, 0)
+    JUMP
+
+// Returns a square root of the element x of Fp2, or of -(2 + i) x if x isn't a square, as given by the
+// prover. The caller checks the result.
+global bls381_sqrt_g2:
+    // stack: x: 4, retdest
+    PROVER_INPUT(sf::bls381_base::fp2_sqrt_3)
+    PROVER_INPUT(sf::bls381_base::fp2_sqrt_2)
+    PROVER_INPUT(sf::bls381_base::fp2_sqrt_1)
+    PROVER_INPUT(sf::bls381_base::fp2_sqrt_0)
+    %assert_canonical_fp381_2
+    %stack (s: 4, x: 4, retdest) -> (retdest, s)
+    JUMP
+
+// Sets the point (x, y) at offset 708 to its image under the isogeny map
+//     (x, y) -> (x_num(x) / x_den(x), y y_num(x) / y_den(x)),
+// where the coefficients of the polynomials are listed one after the other from table, each in
+// decreasing degree order, and the point at infinity when x_den(x) = 0. The offsets 716, 720, 724
+// and 728 hold the values of the polynomials.
+global bls381_iso_map:
+    // stack: table, n_x_num, n_x_den, n_y_num, n_y_den, retdest
+    %bls381_iso_eval(716)
+    %bls381_iso_eval(720)
+    %bls381_iso_eval(724)
+    %bls381_iso_eval(728)
+    // stack: table_end, retdest
+    POP
+    %load_fp381_2(720)
+    %is_zero_fp381_2
+    %jumpi(bls381_iso_map_infinity)
+    %load_fp381_2(728)
+    %load_fp381_2(724)
+    %load_fp381_2(712)
+    %mul_fp381_2
+    %div_fp381_2
+    %store_fp381_2(712)
+    %load_fp381_2(720)
+    %load_fp381_2(716)
+    %div_fp381_2
+    %store_fp381_2(708)
+    // stack: retdest
+    JUMP
+bls381_iso_map_infinity:
+    // stack: retdest
+    PUSH 708
+    %build_current_general_address
+    %set_infinity_bls381
+    JUMP
+
+// Returns the value at the x coordinate of the point at offset 708 of the polynomial with the n
+// coefficients listed from table, in decreasing degree order.
+global bls381_horner:
+    // stack: table, n, retdest
+    PUSH 0
+    PUSH 0
+    PUSH 0
+    PUSH 0
+bls381_horner_loop:
+    // stack: acc: 4, table, n, retdest
+    %load_fp381_2(708)
+    %mul_fp381_2
+    DUP5
+    %load_fp381_2_code
+    %add_fp381_2
+    SWAP4
+    %add_const(96)
+    SWAP4
+    SWAP5
+    %decrement
+    SWAP5
+    // stack: acc: 4, table, n, retdest
+    DUP6
+    %jumpi(bls381_horner_loop)
+    %stack (acc: 4, table, n, retdest) -> (retdest, acc)
+    JUMP
+
+// Stores the value of the polynomial with the n coefficients listed from table at the given offset,
+// and moves past its coefficients.
+%macro bls381_iso_eval(offset)
+    // stack: table, n
+    PUSH %%after
+    DUP3
+    DUP3
+    %jump(bls381_horner)
+%%after:
+    // stack: value: 4, table, n
+    %store_fp381_2($offset)
+    SWAP1
+    %mul_const(96)
+    ADD
+    // stack: table + 96 n
+%endmacro
+
+// Returns the sign of x as defined in section 4.1 of RFC 9380, i.e. the parity of its real part, or of
+// its imaginary part if the real part is zero.
+%macro sgn0_fp381_2
+    // stack: x_re: 2, x_im: 2
+    DUP2
+    DUP2
+    OR
+    ISZERO
+    DUP4
+    AND
+    SWAP1
+    OR
+    %and_const(1)
+    // stack: sgn0(x), x_re_hi, x_im: 2
+    %stack (sgn, x: 3) -> (sgn)
+%endmacro
+
+%macro one_fp381_2
+    PUSH 0
+    PUSH 0
+    PUSH 0
+    PUSH 1
+%endmacro
+
+%macro bls381_curve_add_at(p, q, out)
+    PUSH $out
+    %build_current_general_address
+    PUSH $q
+    %build_current_general_address
+    PUSH $p
+    %build_current_general_address
+    // stack: p, q, out
+    %bls381_curve_add
+%endmacro
+
+// Sets out = [x] p for x = -0xd201000000010000.
+%macro bls381_curve_mul_x_at(p, out)
+    PUSH $out
+    %build_current_general_address
+    PUSH 0xd201000000010000
+    PUSH $p
+    %build_current_general_address
+    // stack: p, |x|, out
+    %bls381_curve_mul
+    %neg_bls381_at($out)
+%endmacro
+
+%macro bls381_psi_at(p, out)
+    PUSH $out
+    %build_current_general_address
+    PUSH $p
+    %build_current_general_address
+    // stack: p, out
+    %bls381_psi
+%endmacro
+
+%macro neg_bls381_at(offset)
+    PUSH $offset
+    %build_current_general_address
+    %neg_bls381
+%endmacro
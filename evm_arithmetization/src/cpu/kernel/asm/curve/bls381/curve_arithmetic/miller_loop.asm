@@ -0,0 +1,293 @@
+// The Miller loop of the optimal ate pairing over BLS12-381, following the doubling and addition
+// steps of Algorithms 26 and 27 of https://eprint.iacr.org/2010/354.pdf.
+//
+// The loop works on the following offsets of the current kernel general memory:
+//     F at 100 (Fp12), the Miller loop accumulator,
+//     R at 160 (x, y, z in Fp2), the multiple of Q in projective coordinates,
+//     Q at 180 (x, y in Fp2), the point of G2,
+//     P at 190 (x, y in Fp), the point of G1,
+//     the temporary elements of Fp2 of the doubling and addition steps at 200 to 243,
+// and the line functions are accumulated with mul_fp381_12_by_014, which uses offsets 0 to 83.
+
+// Sets F to the Miller loop f_{x, Q}(P), where x = -0xd201000000010000 is the BLS parameter. As x is
+// negative, the result is conjugated by the caller, which is cheaper over a product of pairings.
+global bls381_miller_loop:
+    // stack: retdest
+    // F = 1
+    PUSH 100
+    %build_current_general_address
+    %set_unit_fp381_12
+    // R = (Q.x, Q.y, 1)
+    %load_fp381_2(180)
+    %store_fp381_2(160)
+    %load_fp381_2(184)
+    %store_fp381_2(164)
+    PUSH 0
+    PUSH 0
+    PUSH 0
+    PUSH 1
+    %store_fp381_2(168)
+    // Skip the leading bit of |x| >> 1 = 0x6900800000008000.
+    PUSH 61
+bls381_miller_loop_iter:
+    // stack: i, retdest
+    PUSH bls381_miller_loop_after_double
+    %jump(bls381_miller_double)
+bls381_miller_loop_after_double:
+    // stack: i, retdest
+    PUSH 0x6900800000008000
+    DUP2
+    SHR
+    PUSH 1
+    AND
+    %jumpi(bls381_miller_loop_add)
+bls381_miller_loop_square:
+    // stack: i, retdest
+    PUSH 100
+    %build_current_general_address
+    DUP1
+    %square_fp381_12
+    // stack: i, retdest
+    DUP1
+    ISZERO
+    %jumpi(bls381_miller_loop_last)
+    %decrement
+    %jump(bls381_miller_loop_iter)
+bls381_miller_loop_add:
+    // stack: i, retdest
+    PUSH bls381_miller_loop_square
+    %jump(bls381_miller_add)
+bls381_miller_loop_last:
+    // stack: i, retdest
+    POP
+    %jump(bls381_miller_double)
+
+// Doubles R and multiplies F by the line function at P of the tangent at R.
+global bls381_miller_double:
+    // stack: retdest
+    // tmp0 = R.x^2
+    %load_fp381_2(160)
+    %square_fp381_2
+    %store_fp381_2(200)
+    // tmp1 = R.y^2
+    %load_fp381_2(164)
+    %square_fp381_2
+    %store_fp381_2(204)
+    // tmp2 = tmp1^2
+    %load_fp381_2(204)
+    %square_fp381_2
+    %store_fp381_2(208)
+    // tmp3 = 2 ((tmp1 + R.x)^2 - tmp0 - tmp2)
+    %load_fp381_2(160)
+    %load_fp381_2(204)
+    %add_fp381_2
+    %square_fp381_2
+    %sub_fp381_2_at(200)
+    %sub_fp381_2_at(208)
+    %double_fp381_2
+    %store_fp381_2(212)
+    // tmp4 = 3 tmp0
+    %load_fp381_2(200)
+    %dup_fp381_2_0
+    %double_fp381_2
+    %add_fp381_2
+    %store_fp381_2(216)
+    // tmp6 = R.x + tmp4
+    %load_fp381_2(216)
+    %load_fp381_2(160)
+    %add_fp381_2
+    %store_fp381_2(224)
+    // tmp5 = tmp4^2
+    %load_fp381_2(216)
+    %square_fp381_2
+    %store_fp381_2(220)
+    // z_sq = R.z^2
+    %load_fp381_2(168)
+    %square_fp381_2
+    %store_fp381_2(228)
+    // R.x = tmp5 - 2 tmp3
+    %load_fp381_2(212)
+    %double_fp381_2
+    %load_fp381_2(220)
+    %sub_fp381_2
+    %store_fp381_2(160)
+    // R.z = (R.z + R.y)^2 - tmp1 - z_sq
+    %load_fp381_2(164)
+    %load_fp381_2(168)
+    %add_fp381_2
+    %square_fp381_2
+    %sub_fp381_2_at(204)
+    %sub_fp381_2_at(228)
+    %store_fp381_2(168)
+    // R.y = (tmp3 - R.x) tmp4 - 8 tmp2
+    %load_fp381_2(208)
+    %double_fp381_2
+    %double_fp381_2
+    %double_fp381_2
+    %load_fp381_2(216)
+    %load_fp381_2(160)
+    %load_fp381_2(212)
+    %sub_fp381_2
+    %mul_fp381_2
+    %sub_fp381_2
+    %store_fp381_2(164)
+    // The line coefficients are c4 = 2 R.z z_sq P.y, c1 = -2 tmp4 z_sq P.x and
+    // c0 = (tmp6^2 - tmp0 - tmp5) - 4 tmp1.
+    %load_fp381(192)
+    %load_fp381_2(228)
+    %load_fp381_2(168)
+    %mul_fp381_2
+    %double_fp381_2
+    %mul_fp381_2_by_fp381
+    // stack: c4: 4, retdest
+    %load_fp381(190)
+    %load_fp381_2(228)
+    %load_fp381_2(216)
+    %mul_fp381_2
+    %double_fp381_2
+    %neg_fp381_2
+    %mul_fp381_2_by_fp381
+    // stack: c1: 4, c4: 4, retdest
+    %load_fp381_2(204)
+    %double_fp381_2
+    %double_fp381_2
+    %load_fp381_2(224)
+    %square_fp381_2
+    %sub_fp381_2_at(200)
+    %sub_fp381_2_at(220)
+    %sub_fp381_2
+    // stack: c0: 4, c1: 4, c4: 4, retdest
+    PUSH 100
+    %build_current_general_address
+    %jump(mul_fp381_12_by_014)
+
+// Adds Q to R and multiplies F by the line function at P of the line through R and Q.
+global bls381_miller_add:
+    // stack: retdest
+    // z_sq = R.z^2
+    %load_fp381_2(168)
+    %square_fp381_2
+    %store_fp381_2(228)
+    // y_sq = Q.y^2
+    %load_fp381_2(184)
+    %square_fp381_2
+    %store_fp381_2(232)
+    // t0 = z_sq Q.x
+    %load_fp381_2(180)
+    %load_fp381_2(228)
+    %mul_fp381_2
+    %store_fp381_2(200)
+    // t1 = ((Q.y + R.z)^2 - y_sq - z_sq) z_sq
+    %load_fp381_2(228)
+    %load_fp381_2(168)
+    %load_fp381_2(184)
+    %add_fp381_2
+    %square_fp381_2
+    %sub_fp381_2_at(232)
+    %sub_fp381_2_at(228)
+    %mul_fp381_2
+    %store_fp381_2(204)
+    // t2 = t0 - R.x
+    %load_fp381_2(160)
+    %load_fp381_2(200)
+    %sub_fp381_2
+    %store_fp381_2(208)
+    // t3 = t2^2
+    %load_fp381_2(208)
+    %square_fp381_2
+    %store_fp381_2(212)
+    // t4 = 4 t3
+    %load_fp381_2(212)
+    %double_fp381_2
+    %double_fp381_2
+    %store_fp381_2(216)
+    // t5 = t4 t2
+    %load_fp381_2(208)
+    %load_fp381_2(216)
+    %mul_fp381_2
+    %store_fp381_2(220)
+    // t6 = t1 - 2 R.y
+    %load_fp381_2(164)
+    %double_fp381_2
+    %load_fp381_2(204)
+    %sub_fp381_2
+    %store_fp381_2(224)
+    // t9 = t6 Q.x
+    %load_fp381_2(180)
+    %load_fp381_2(224)
+    %mul_fp381_2
+    %store_fp381_2(236)
+    // t7 = t4 R.x
+    %load_fp381_2(160)
+    %load_fp381_2(216)
+    %mul_fp381_2
+    %store_fp381_2(240)
+    // R.x = t6^2 - t5 - 2 t7
+    %load_fp381_2(240)
+    %double_fp381_2
+    %load_fp381_2(220)
+    %load_fp381_2(224)
+    %square_fp381_2
+    %sub_fp381_2
+    %sub_fp381_2
+    %store_fp381_2(160)
+    // R.z = (R.z + t2)^2 - z_sq - t3
+    %load_fp381_2(212)
+    %load_fp381_2(228)
+    %load_fp381_2(208)
+    %load_fp381_2(168)
+    %add_fp381_2
+    %square_fp381_2
+    %sub_fp381_2
+    %sub_fp381_2
+    %store_fp381_2(168)
+    // R.y = (t7 - R.x) t6 - 2 R.y t5
+    %load_fp381_2(220)
+    %load_fp381_2(164)
+    %mul_fp381_2
+    %double_fp381_2
+    %load_fp381_2(224)
+    %load_fp381_2(160)
+    %load_fp381_2(240)
+    %sub_fp381_2
+    %mul_fp381_2
+    %sub_fp381_2
+    %store_fp381_2(164)
+    // The line coefficients are c4 = 2 R.z P.y, c1 = -2 t6 P.x and
+    // c0 = 2 t9 - ((Q.y + R.z)^2 - y_sq - R.z^2).
+    %load_fp381(192)
+    %load_fp381_2(168)
+    %double_fp381_2
+    %mul_fp381_2_by_fp381
+    // stack: c4: 4, retdest
+    %load_fp381(190)
+    %load_fp381_2(224)
+    %double_fp381_2
+    %neg_fp381_2
+    %mul_fp381_2_by_fp381
+    // stack: c1: 4, c4: 4, retdest
+    %load_fp381_2(168)
+    %square_fp381_2
+    %load_fp381_2(232)
+    %load_fp381_2(184)
+    %load_fp381_2(168)
+    %add_fp381_2
+    %square_fp381_2
+    %sub_fp381_2
+    %sub_fp381_2
+    %load_fp381_2(236)
+    %double_fp381_2
+    %sub_fp381_2
+    // stack: c0: 4, c1: 4, c4: 4, retdest
+    PUSH 100
+    %build_current_general_address
+    %jump(mul_fp381_12_by_014)
+
+// Subtracts the element of Fp2 at the given offset of the current kernel general memory.
+%macro sub_fp381_2_at(offset)
+    // stack: x: 4
+    %load_fp381_2($offset)
+    %swap_fp381_2
+    %sub_fp381_2
+    // stack: x - y: 4
+%endmacro
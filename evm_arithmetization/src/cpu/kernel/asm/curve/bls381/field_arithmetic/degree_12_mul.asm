@@ -0,0 +1,229 @@
+// Arithmetic over Fp12 = Fp6[w]/(w^2 - v).
+//
+// An element z0 + z1 w of Fp12 is stored in memory as its two coefficients, i.e. 24 words, and the
+// functions below take the full addresses of their operands. The first 84 words of the current
+// kernel general memory are used as scratch space:
+//     T0, ..., T4 at offsets 0, 12, 24, 36, 48 for the products and sums of Fp6 elements,
+//     L at offset 60 for the sparse elements of Fp12 given by the line functions.
+
+// Karatsuba multiplication:
+//     (a0 + a1 w)(b0 + b1 w) = (h0 + v h1) + ((a0 + a1)(b0 + b1) - h0 - h1) w
+// with h0 = a0 b0 and h1 = a1 b1. The output may overlap the inputs.
+global mul_fp381_12:
+    // stack: a, b, out, retdest
+    PUSH mul_fp381_12_h1
+    PUSH 0
+    %build_current_general_address
+    DUP4
+    DUP4
+    // stack: a, b, T0, mul_fp381_12_h1, a, b, out, retdest
+    %jump(mul_fp381_6)
+mul_fp381_12_h1:
+    // stack: a, b, out, retdest
+    PUSH mul_fp381_12_a_sum
+    PUSH 12
+    %build_current_general_address
+    DUP4
+    %add_const(12)
+    DUP4
+    %add_const(12)
+    // stack: a1, b1, T1, mul_fp381_12_a_sum, a, b, out, retdest
+    %jump(mul_fp381_6)
+mul_fp381_12_a_sum:
+    // stack: a, b, out, retdest
+    PUSH mul_fp381_12_b_sum
+    PUSH 24
+    %build_current_general_address
+    DUP3
+    %add_const(12)
+    DUP4
+    // stack: a0, a1, T2, mul_fp381_12_b_sum, a, b, out, retdest
+    %jump(add_fp381_6)
+mul_fp381_12_b_sum:
+    // stack: a, b, out, retdest
+    PUSH mul_fp381_12_z1
+    PUSH 36
+    %build_current_general_address
+    DUP4
+    %add_const(12)
+    DUP5
+    // stack: b0, b1, T3, mul_fp381_12_z1, a, b, out, retdest
+    %jump(add_fp381_6)
+mul_fp381_12_z1:
+    // stack: a, b, out, retdest
+    %pop2
+    // stack: out, retdest
+    PUSH 48
+    %build_current_general_address
+    PUSH 36
+    %build_current_general_address
+    PUSH 24
+    %build_current_general_address
+    // stack: T2, T3, T4, out, retdest
+    %mul_fp381_6
+    PUSH 48
+    %build_current_general_address
+    PUSH 0
+    %build_current_general_address
+    DUP2
+    // stack: T4, T0, T4, out, retdest
+    %sub_fp381_6
+    DUP1
+    %add_const(12)
+    PUSH 12
+    %build_current_general_address
+    PUSH 48
+    %build_current_general_address
+    // stack: T4, T1, out + 12, out, retdest
+    %sub_fp381_6
+    // z0 = h0 + v h1, where v (t0 + t1 v + t2 v^2) = xi t2 + t0 v + t1 v^2.
+    // stack: out, retdest
+    %load_fp381_2(20)
+    %mul_fp381_2_by_xi
+    %load_fp381_2(0)
+    %add_fp381_2
+    DUP5
+    %store_fp381_2
+    %load_fp381_2(12)
+    %load_fp381_2(4)
+    %add_fp381_2
+    DUP5
+    %add_const(4)
+    %store_fp381_2
+    %load_fp381_2(16)
+    %load_fp381_2(8)
+    %add_fp381_2
+    DUP5
+    %add_const(8)
+    %store_fp381_2
+    // stack: out, retdest
+    POP
+    JUMP
+
+// Multiplies f in place by the sparse element (c0 + c1 v) + c4 v w given by a line function.
+global mul_fp381_12_by_014:
+    // stack: f, c0: 4, c1: 4, c4: 4, retdest
+    %stack (f, c0: 4, c1: 4, c4: 4) -> (c0, c1, c4, f)
+    PUSH 60
+    %build_current_general_address
+    %store_fp381_2
+    PUSH 64
+    %build_current_general_address
+    %store_fp381_2
+    PUSH 76
+    %build_current_general_address
+    %store_fp381_2
+    // stack: f, retdest
+    %zero_fp381_2(68)
+    %zero_fp381_2(72)
+    %zero_fp381_2(80)
+    // stack: f, retdest
+    PUSH 60
+    %build_current_general_address
+    DUP2
+    // stack: f, L, f, retdest
+    %jump(mul_fp381_12)
+
+// Sets the element of Fp2 at the given offset of the current kernel general memory to zero.
+%macro zero_fp381_2(offset)
+    PUSH 0
+    PUSH 0
+    PUSH 0
+    PUSH 0
+    %store_fp381_2($offset)
+%endmacro
+
+%macro mul_fp381_12
+    // stack: a, b, out
+    %stack (a, b, out) -> (a, b, out, %%after)
+    %jump(mul_fp381_12)
+%%after:
+%endmacro
+
+%macro square_fp381_12
+    // stack: a, out
+    %stack (a, out) -> (a, a, out, %%after)
+    %jump(mul_fp381_12)
+%%after:
+%endmacro
+
+// Conjugation z0 + z1 w -> z0 - z1 w, in place.
+global conj_fp381_12:
+    // stack: f, retdest
+    PUSH 12
+conj_fp381_12_loop:
+    // stack: k, f, retdest
+    DUP2
+    DUP2
+    ADD
+    // stack: f + k, k, f, retdest
+    DUP1
+    %load_fp381
+    %neg_fp381
+    DUP3
+    %store_fp381
+    POP
+    // stack: k, f, retdest
+    %add_const(2)
+    DUP1
+    %lt_const(24)
+    %jumpi(conj_fp381_12_loop)
+    // stack: k, f, retdest
+    %pop2
+    JUMP
+
+%macro conj_fp381_12
+    // stack: f
+    PUSH %%after
+    SWAP1
+    %jump(conj_fp381_12)
+%%after:
+%endmacro
+
+// Returns whether f is the unit of Fp12.
+%macro is_unit_fp381_12
+    // stack: f
+    DUP1
+    MLOAD_GENERAL
+    %eq_const(1)
+    ISZERO
+    SWAP1
+    // stack: f, w0 != 1
+    %rep 23
+        %increment
+        DUP1
+        MLOAD_GENERAL
+        // stack: w, f + k, acc
+        SWAP1
+        SWAP2
+        OR
+        SWAP1
+    %endrep
+    // stack: f + 23, acc
+    POP
+    ISZERO
+%endmacro
+
+// Sets f to the unit of Fp12.
+%macro set_unit_fp381_12
+    // stack: f
+    PUSH 0
+    DUP2
+    %rep 24
+        DUP1
+        DUP3
+        MSTORE_GENERAL
+        %increment
+    %endrep
+    %pop2
+    // stack: f
+    PUSH 1
+    MSTORE_GENERAL
+%endmacro
+
+// Copies 24 words from src to dst.
+%macro copy_fp381_12
+    // stack: src, dst
+    %stack (src, dst) -> (dst, src, 24)
+    %memcpy
+%endmacro
@@ -0,0 +1,157 @@
+// Arithmetic over Fp6 = Fp2[v]/(v^3 - xi), with xi = 1 + i.
+//
+// An element t0 + t1 v + t2 v^2 of Fp6 is stored in memory as its three coefficients, i.e. twelve
+// words, and the functions below take the full addresses of their operands.
+
+// Schoolbook multiplication:
+//     c0 = a0 b0 + xi (a1 b2 + a2 b1)
+//     c1 = a0 b1 + a1 b0 + xi a2 b2
+//     c2 = a0 b2 + a1 b1 + a2 b0
+// The output may overlap the inputs, as each coefficient is stored once all of them are computed.
+global mul_fp381_6:
+    // stack: a, b, out, retdest
+    %mul_fp381_6_term(4, 8)
+    %mul_fp381_6_term_acc(8, 4)
+    %mul_fp381_2_by_xi
+    %mul_fp381_6_term_acc(0, 0)
+    // stack: c0: 4, a, b, out, retdest
+    %stack (c0: 4, a, b) -> (a, b, c0)
+    %mul_fp381_6_term(8, 8)
+    %mul_fp381_2_by_xi
+    %mul_fp381_6_term_acc(0, 4)
+    %mul_fp381_6_term_acc(4, 0)
+    // stack: c1: 4, a, b, c0: 4, out, retdest
+    %stack (c1: 4, a, b) -> (a, b, c1)
+    %mul_fp381_6_term(0, 8)
+    %mul_fp381_6_term_acc(4, 4)
+    %mul_fp381_6_term_acc(8, 0)
+    // stack: c2: 4, a, b, c1: 4, c0: 4, out, retdest
+    %stack (c2: 4, a, b) -> (c2)
+    // stack: c2: 4, c1: 4, c0: 4, out, retdest
+    DUP13
+    %add_const(8)
+    %store_fp381_2
+    DUP9
+    %add_const(4)
+    %store_fp381_2
+    DUP5
+    %store_fp381_2
+    // stack: out, retdest
+    POP
+    JUMP
+
+// Pushes a_i * b_j, where i and j are the word offsets of the coefficients.
+%macro mul_fp381_6_term(i, j)
+    // stack: a, b
+    PUSH %%after
+    DUP3
+    %add_const($j)
+    %load_fp381_2
+    // stack: b_j: 4, %%after, a, b
+    DUP6
+    %add_const($i)
+    %load_fp381_2
+    // stack: a_i: 4, b_j: 4, %%after, a, b
+    %jump(mul_fp381_2)
+%%after:
+    // stack: a_i * b_j: 4, a, b
+%endmacro
+
+// Adds a_i * b_j to the accumulator on top of the stack.
+%macro mul_fp381_6_term_acc(i, j)
+    // stack: acc: 4, a, b
+    PUSH %%after
+    DUP7
+    %add_const($j)
+    %load_fp381_2
+    // stack: b_j: 4, %%after, acc: 4, a, b
+    DUP10
+    %add_const($i)
+    %load_fp381_2
+    // stack: a_i: 4, b_j: 4, %%after, acc: 4, a, b
+    %jump(mul_fp381_2)
+%%after:
+    %add_fp381_2
+    // stack: acc + a_i * b_j: 4, a, b
+%endmacro
+
+// Coefficient-wise addition. The output may overlap the inputs.
+global add_fp381_6:
+    // stack: a, b, out, retdest
+    PUSH 0
+add_fp381_6_loop:
+    // stack: k, a, b, out, retdest
+    %load_fp381_6_operands
+    %add_fp381
+    %store_fp381_6_result
+    // stack: k, a, b, out, retdest
+    %add_const(2)
+    DUP1
+    %lt_const(12)
+    %jumpi(add_fp381_6_loop)
+    // stack: k, a, b, out, retdest
+    %pop4
+    JUMP
+
+// Coefficient-wise subtraction. The output may overlap the inputs.
+global sub_fp381_6:
+    // stack: a, b, out, retdest
+    PUSH 0
+sub_fp381_6_loop:
+    // stack: k, a, b, out, retdest
+    %load_fp381_6_operands
+    %sub_fp381
+    %store_fp381_6_result
+    // stack: k, a, b, out, retdest
+    %add_const(2)
+    DUP1
+    %lt_const(12)
+    %jumpi(sub_fp381_6_loop)
+    // stack: k, a, b, out, retdest
+    %pop4
+    JUMP
+
+// Loads the elements of Fp at word offset k of a and b.
+%macro load_fp381_6_operands
+    // stack: k, a, b
+    DUP3
+    DUP2
+    ADD
+    %load_fp381
+    DUP4
+    DUP4
+    ADD
+    %load_fp381
+    // stack: a_k: 2, b_k: 2, k, a, b
+%endmacro
+
+// Stores an element of Fp at word offset k of out.
+%macro store_fp381_6_result
+    // stack: c: 2, k, a, b, out
+    DUP6
+    DUP4
+    ADD
+    %store_fp381
+    // stack: k, a, b, out
+%endmacro
+
+%macro add_fp381_6
+    // stack: a, b, out
+    %stack (a, b, out) -> (a, b, out, %%after)
+    %jump(add_fp381_6)
+%%after:
+%endmacro
+
+%macro sub_fp381_6
+    // stack: a, b, out
+    %stack (a, b, out) -> (a, b, out, %%after)
+    %jump(sub_fp381_6)
+%%after:
+%endmacro
+
+%macro mul_fp381_6
+    // stack: a, b, out
+    %stack (a, b, out) -> (a, b, out, %%after)
+    %jump(mul_fp381_6)
+%%after:
+%endmacro
@@ -0,0 +1,99 @@
+// The Frobenius endomorphisms f -> f^(p^n) of Fp12, for n = 1, 2, 3, in place.
+//
+// Writing f = sum_k f_k w^k with f_k in Fp2, so that the coefficients z0.t0, z0.t1, z0.t2, z1.t0,
+// z1.t1, z1.t2 correspond to k = 0, 2, 4, 1, 3, 5, we have
+//     f^(p^n) = sum_k frob_n(f_k) xi^(k (p^n - 1) / 6) w^k
+// since w^6 = xi, where frob_n is the identity for even n and the conjugation for odd n.
+
+global frob_fp381_12_1:
+    // stack: f, retdest
+    %conj_fp381_12_coeffs
+    %frob_fp381_12_coeff(4, 0x0, 0x0, 0xaa0d857d89759ad4897d29650fb85f9b409427eb4f49fffd8bfd00000000aaac, 0x1a0111ea397fe699ec02408663d4de85)
+    %frob_fp381_12_coeff(8, 0xaa0d857d89759ad4897d29650fb85f9b409427eb4f49fffd8bfd00000000aaad, 0x1a0111ea397fe699ec02408663d4de85, 0x0, 0x0)
+    %frob_fp381_12_coeff(12, 0xfd603fd3cbd5f4f7b2443d784bab9c4f67ea53d63e7813d8d0775ed92235fb8, 0x1904d3bf02bb0667c231beb4202c0d1f, 0x54a14787b6c7b36fec0c8ec971f63c5f282d5ac14d6c7ec22cf78a126ddc4af3, 0xfc3e2b36c4e03288e9e902231f9fb8)
+    %frob_fp381_12_coeff(16, 0x48395dabc2d3435e77f76e17009241c5ee67992f72ec05f4c81084fbede3cc09, 0x6af0e0437ff400b6831e36d6bd17ffe, 0x48395dabc2d3435e77f76e17009241c5ee67992f72ec05f4c81084fbede3cc09, 0x6af0e0437ff400b6831e36d6bd17ffe)
+    %frob_fp381_12_coeff(20, 0xf39816240c0b8fee8beadf4d8e9c0566c63a3e6e257f87329b18fae980078116, 0x5b2cfd9013a5fd8df47fa6b48b1e045, 0x70df3560e77982d0db45f3536814f0bd5871c1908bd478cd1ee605167ff82995, 0x144e4211384586c16bd3ad4afa99cc91)
+    // stack: f, retdest
+    POP
+    JUMP
+
+global frob_fp381_12_2:
+    // stack: f, retdest
+    %frob_fp381_12_coeff(4, 0xba69c6076a0f77eaddb3a93be6f89688de17d813620a00022e01fffffffefffe, 0x5f19672fdf76ce51, 0x0, 0x0)
+    %frob_fp381_12_coeff(8, 0xaa0d857d89759ad4897d29650fb85f9b409427eb4f49fffd8bfd00000000aaac, 0x1a0111ea397fe699ec02408663d4de85, 0x0, 0x0)
+    %frob_fp381_12_coeff(12, 0xba69c6076a0f77eaddb3a93be6f89688de17d813620a00022e01fffffffeffff, 0x5f19672fdf76ce51, 0x0, 0x0)
+    %frob_fp381_12_coeff(16, 0x64774b84f38512bf6730d2a0f6b0f6241eabfffeb153ffffb9feffffffffaaaa, 0x1a0111ea397fe69a4b1ba7b6434bacd7, 0x0, 0x0)
+    %frob_fp381_12_coeff(20, 0xaa0d857d89759ad4897d29650fb85f9b409427eb4f49fffd8bfd00000000aaad, 0x1a0111ea397fe699ec02408663d4de85, 0x0, 0x0)
+    // stack: f, retdest
+    POP
+    JUMP
+
+global frob_fp381_12_3:
+    // stack: f, retdest
+    %conj_fp381_12_coeffs
+    %frob_fp381_12_coeff(4, 0x0, 0x0, 0x1, 0x0)
+    %frob_fp381_12_coeff(8, 0x64774b84f38512bf6730d2a0f6b0f6241eabfffeb153ffffb9feffffffffaaaa, 0x1a0111ea397fe69a4b1ba7b6434bacd7, 0x0, 0x0)
+    %frob_fp381_12_coeff(12, 0x1c3dedd930b1cf60ef396489f61eb45e304466cf3e67fa0af1ee7b04121bdea2, 0x135203e60180a68ee2e9c448d77a2cd9, 0x48395dabc2d3435e77f76e17009241c5ee67992f72ec05f4c81084fbede3cc09, 0x6af0e0437ff400b6831e36d6bd17ffe)
+    %frob_fp381_12_coeff(16, 0x1c3dedd930b1cf60ef396489f61eb45e304466cf3e67fa0af1ee7b04121bdea2, 0x135203e60180a68ee2e9c448d77a2cd9, 0x1c3dedd930b1cf60ef396489f61eb45e304466cf3e67fa0af1ee7b04121bdea2, 0x135203e60180a68ee2e9c448d77a2cd9)
+    %frob_fp381_12_coeff(20, 0x48395dabc2d3435e77f76e17009241c5ee67992f72ec05f4c81084fbede3cc09, 0x6af0e0437ff400b6831e36d6bd17ffe, 0x1c3dedd930b1cf60ef396489f61eb45e304466cf3e67fa0af1ee7b04121bdea2, 0x135203e60180a68ee2e9c448d77a2cd9)
+    // stack: f, retdest
+    POP
+    JUMP
+
+%macro frob_fp381_12_1
+    // stack: f
+    PUSH %%after
+    SWAP1
+    %jump(frob_fp381_12_1)
+%%after:
+%endmacro
+
+%macro frob_fp381_12_2
+    // stack: f
+    PUSH %%after
+    SWAP1
+    %jump(frob_fp381_12_2)
+%%after:
+%endmacro
+
+%macro frob_fp381_12_3
+    // stack: f
+    PUSH %%after
+    SWAP1
+    %jump(frob_fp381_12_3)
+%%after:
+%endmacro
+
+// Multiplies the coefficient at word offset k of f by the element (c0, c1, c2, c3) of Fp2.
+%macro frob_fp381_12_coeff(k, c0, c1, c2, c3)
+    // stack: f
+    PUSH $c3
+    PUSH $c2
+    PUSH $c1
+    PUSH $c0
+    DUP5
+    %add_const($k)
+    %load_fp381_2
+    %mul_fp381_2
+    // stack: c * f_k: 4, f
+    DUP5
+    %add_const($k)
+    %store_fp381_2
+    // stack: f
+%endmacro
+
+// Conjugates each coefficient of f, i.e. negates the words 2, 3, 6, 7, ..., 22, 23 as elements of Fp.
+%macro conj_fp381_12_coeffs
+    // stack: f
+    %rep 6
+        %add_const(2)
+        DUP1
+        %load_fp381
+        %neg_fp381
+        DUP3
+        %store_fp381
+        %add_const(2)
+    %endrep
+    // stack: f + 24
+    %sub_const(24)
+%endmacro
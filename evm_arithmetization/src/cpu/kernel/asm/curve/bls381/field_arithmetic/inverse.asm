@@ -0,0 +1,160 @@
+// Inversion in Fp12, with the inverse provided by the prover and checked in the kernel.
+// The input must not be the zero element, and the output must not overlap the input.
+global inv_fp381_12:
+    // stack: inp, out, retdest
+    PROVER_INPUT(ffe::bls381_base::component_0)
+    DUP3
+    %add_const(0)
+    SWAP1
+    MSTORE_GENERAL
+    PROVER_INPUT(ffe::bls381_base::component_1)
+    DUP3
+    %add_const(1)
+    SWAP1
+    MSTORE_GENERAL
+    PROVER_INPUT(ffe::bls381_base::component_2)
+    DUP3
+    %add_const(2)
+    SWAP1
+    MSTORE_GENERAL
+    PROVER_INPUT(ffe::bls381_base::component_3)
+    DUP3
+    %add_const(3)
+    SWAP1
+    MSTORE_GENERAL
+    PROVER_INPUT(ffe::bls381_base::component_4)
+    DUP3
+    %add_const(4)
+    SWAP1
+    MSTORE_GENERAL
+    PROVER_INPUT(ffe::bls381_base::component_5)
+    DUP3
+    %add_const(5)
+    SWAP1
+    MSTORE_GENERAL
+    PROVER_INPUT(ffe::bls381_base::component_6)
+    DUP3
+    %add_const(6)
+    SWAP1
+    MSTORE_GENERAL
+    PROVER_INPUT(ffe::bls381_base::component_7)
+    DUP3
+    %add_const(7)
+    SWAP1
+    MSTORE_GENERAL
+    PROVER_INPUT(ffe::bls381_base::component_8)
+    DUP3
+    %add_const(8)
+    SWAP1
+    MSTORE_GENERAL
+    PROVER_INPUT(ffe::bls381_base::component_9)
+    DUP3
+    %add_const(9)
+    SWAP1
+    MSTORE_GENERAL
+    PROVER_INPUT(ffe::bls381_base::component_10)
+    DUP3
+    %add_const(10)
+    SWAP1
+    MSTORE_GENERAL
+    PROVER_INPUT(ffe::bls381_base::component_11)
+    DUP3
+    %add_const(11)
+    SWAP1
+    MSTORE_GENERAL
+    PROVER_INPUT(ffe::bls381_base::component_12)
+    DUP3
+    %add_const(12)
+    SWAP1
+    MSTORE_GENERAL
+    PROVER_INPUT(ffe::bls381_base::component_13)
+    DUP3
+    %add_const(13)
+    SWAP1
+    MSTORE_GENERAL
+    PROVER_INPUT(ffe::bls381_base::component_14)
+    DUP3
+    %add_const(14)
+    SWAP1
+    MSTORE_GENERAL
+    PROVER_INPUT(ffe::bls381_base::component_15)
+    DUP3
+    %add_const(15)
+    SWAP1
+    MSTORE_GENERAL
+    PROVER_INPUT(ffe::bls381_base::component_16)
+    DUP3
+    %add_const(16)
+    SWAP1
+    MSTORE_GENERAL
+    PROVER_INPUT(ffe::bls381_base::component_17)
+    DUP3
+    %add_const(17)
+    SWAP1
+    MSTORE_GENERAL
+    PROVER_INPUT(ffe::bls381_base::component_18)
+    DUP3
+    %add_const(18)
+    SWAP1
+    MSTORE_GENERAL
+    PROVER_INPUT(ffe::bls381_base::component_19)
+    DUP3
+    %add_const(19)
+    SWAP1
+    MSTORE_GENERAL
+    PROVER_INPUT(ffe::bls381_base::component_20)
+    DUP3
+    %add_const(20)
+    SWAP1
+    MSTORE_GENERAL
+    PROVER_INPUT(ffe::bls381_base::component_21)
+    DUP3
+    %add_const(21)
+    SWAP1
+    MSTORE_GENERAL
+    PROVER_INPUT(ffe::bls381_base::component_22)
+    DUP3
+    %add_const(22)
+    SWAP1
+    MSTORE_GENERAL
+    PROVER_INPUT(ffe::bls381_base::component_23)
+    DUP3
+    %add_const(23)
+    SWAP1
+    MSTORE_GENERAL
+    // stack: inp, out, retdest
+    DUP2
+    %assert_canonical_fp381_12
+    PUSH 500
+    %build_current_general_address
+    DUP3
+    DUP3
+    // stack: inp, out, tmp, inp, out, retdest
+    %mul_fp381_12
+    PUSH 500
+    %build_current_general_address
+    %is_unit_fp381_12
+    %assert_nonzero
+    // stack: inp, out, retdest
+    %pop2
+    JUMP
+
+%macro inv_fp381_12
+    // stack: inp, out
+    %stack (inp, out) -> (inp, out, %%after)
+    %jump(inv_fp381_12)
+%%after:
+%endmacro
+
+// Checks that the 12 elements of Fp stored from addr onwards are canonical.
+%macro assert_canonical_fp381_12
+    // stack: addr
+    %rep 12
+        DUP1
+        %load_fp381
+        %is_canonical_fp381
+        %assert_nonzero
+        %add_const(2)
+    %endrep
+    POP
+%endmacro
@@ -0,0 +1,558 @@
+// Arithmetic over the base field Fp of BLS12-381 and its quadratic extension Fp2 = Fp[i]/(i^2 + 1).
+//
+// As p has 381 bits, an element of Fp takes two words (lo, hi), with lo on top of the stack. The
+// kernel only ever handles canonical elements, i.e. with (lo, hi) < p, which the multiplication
+// below relies upon. An element of Fp2 takes four words (re, im), with re on top of the stack.
+
+// Returns x + y.
+%macro add_fp381
+    // stack: x0, x1, y0, y1
+    SWAP3
+    ADD
+    // stack: x1 + y1, y0, x0
+    SWAP2
+    DUP2
+    ADD
+    // stack: x0 + y0, y0, x1 + y1
+    SWAP1
+    DUP2
+    LT
+    // stack: carry, x0 + y0, x1 + y1
+    SWAP1
+    SWAP2
+    ADD
+    SWAP1
+    // stack: s0, s1
+    %reduce_once_fp381
+    // stack: z0, z1
+%endmacro
+
+// Subtracts p from s if s >= p, where s < 2p.
+%macro reduce_once_fp381
+    // stack: s0, s1
+    DUP1
+    %lt_const(@BLS_BASE_LO)
+    %add_const(@BLS_BASE_HI)
+    DUP3
+    SUB
+    // stack: t1, s0, s1
+    DUP1
+    %shr_const(128)
+    %jumpi(%%keep)
+    // stack: t1, s0, s1
+    SWAP2
+    POP
+    %sub_const(@BLS_BASE_LO)
+    // stack: t0, t1
+    %jump(%%end)
+%%keep:
+    // stack: t1, s0, s1
+    POP
+%%end:
+    // stack: z0, z1
+%endmacro
+
+// Returns x - y.
+%macro sub_fp381
+    // stack: x0, x1, y0, y1
+    DUP3
+    DUP2
+    LT
+    // stack: borrow, x0, x1, y0, y1
+    SWAP3
+    SWAP1
+    SUB
+    // stack: x0 - y0, x1, borrow, y1
+    SWAP3
+    SWAP1
+    SUB
+    SUB
+    // stack: d1, d0
+    SWAP1
+    DUP2
+    %shr_const(128)
+    ISZERO
+    %jumpi(%%end)
+    // stack: d0, d1
+    // The subtraction underflowed, so we add p back.
+    %add_const(@BLS_BASE_LO)
+    DUP1
+    %lt_const(@BLS_BASE_LO)
+    // stack: carry, d0 + p0, d1
+    SWAP1
+    SWAP2
+    ADD
+    %add_const(@BLS_BASE_HI)
+    SWAP1
+%%end:
+    // stack: z0, z1
+%endmacro
+
+// Returns -x.
+%macro neg_fp381
+    // stack: x0, x1
+    PUSH 0
+    PUSH 0
+    %sub_fp381
+    // stack: z0, z1
+%endmacro
+
+%macro mul_fp381
+    // stack: x0, x1, y0, y1
+    %stack (x: 2, y: 2) -> (x, y, %%after)
+    %jump(mul_fp381)
+%%after:
+    // stack: z0, z1
+%endmacro
+
+// Returns x * y, where the prover provides z = x * y mod p and q = (x * y - z) / p. The identity
+//     x * y = q * p + z
+// holds over the integers if z < p, q < 2^382, and the identity holds modulo 2^256 and modulo the
+// two primes M1 = 2^256 - 189 and M2 = 2^256 - 357. Indeed, both sides are then smaller than 2^764,
+// while 2^256 * M1 * M2 > 2^767.
+global mul_fp381:
+    // stack: x0, x1, y0, y1, retdest
+    PROVER_INPUT(sf::bls381_base::mul_3)
+    PROVER_INPUT(sf::bls381_base::mul_2)
+    PROVER_INPUT(sf::bls381_base::mul_1)
+    PROVER_INPUT(sf::bls381_base::mul_0)
+    // stack: z0, z1, q0, q1, x0, x1, y0, y1, retdest
+    %assert_canonical_fp381
+    DUP4
+    %shr_const(126)
+    %assert_zero
+    // Check the identity modulo 2^256.
+    DUP7
+    DUP6
+    MUL
+    PUSH @BLS_BASE_LO
+    DUP5
+    MUL
+    DUP3
+    ADD
+    // stack: q0 * p0 + z0, x0 * y0, z0, z1, q0, q1, x0, x1, y0, y1, retdest
+    %assert_eq
+    %check_mul_fp381_mod(@BLS_CRT_MODULUS_1, @BLS_CRT_SHIFT_1, @BLS_CRT_BASE_1)
+    %check_mul_fp381_mod(@BLS_CRT_MODULUS_2, @BLS_CRT_SHIFT_2, @BLS_CRT_BASE_2)
+    // stack: z0, z1, q0, q1, x0, x1, y0, y1, retdest
+    %stack (z: 2, q: 2, x: 2, y: 2, retdest) -> (retdest, z)
+    JUMP
+
+// Checks the identity x * y = q * p + z modulo m, where shift = 2^256 mod m and base = p mod m.
+%macro check_mul_fp381_mod(m, shift, base)
+    // stack: z0, z1, q0, q1, x0, x1, y0, y1
+    PUSH $m
+    DUP9
+    DUP9
+    %reduce_fp381_mod($m, $shift)
+    DUP8
+    DUP8
+    %reduce_fp381_mod($m, $shift)
+    MULMOD
+    // stack: x * y mod m, z0, z1, q0, q1, x0, x1, y0, y1
+    PUSH $m
+    PUSH $base
+    DUP7
+    DUP7
+    %reduce_fp381_mod($m, $shift)
+    MULMOD
+    // stack: q * p mod m, x * y mod m, z0, z1, q0, q1, x0, x1, y0, y1
+    PUSH $m
+    DUP5
+    DUP5
+    %reduce_fp381_mod($m, $shift)
+    // stack: z mod m, m, q * p mod m, x * y mod m, z0, z1, q0, q1, x0, x1, y0, y1
+    SWAP1
+    SWAP2
+    ADDMOD
+    %assert_eq
+    // stack: z0, z1, q0, q1, x0, x1, y0, y1
+%endmacro
+
+// Returns v mod m for a two-word value v whose high word is small enough for hi * shift to fit in
+// a word, where shift = 2^256 mod m.
+%macro reduce_fp381_mod(m, shift)
+    // stack: v0, v1
+    SWAP1
+    %mul_const($shift)
+    PUSH $m
+    SWAP2
+    ADDMOD
+    // stack: v mod m
+%endmacro
+
+// Returns whether x < p.
+%macro is_canonical_fp381
+    // stack: x0, x1
+    DUP1
+    %lt_const(@BLS_BASE_LO)
+    DUP3
+    %eq_const(@BLS_BASE_HI)
+    MUL
+    DUP3
+    %lt_const(@BLS_BASE_HI)
+    ADD
+    // stack: x < p, x0, x1
+    %stack (is_canonical, x: 2) -> (is_canonical)
+%endmacro
+
+// Panics unless x < p, leaving x on the stack.
+%macro assert_canonical_fp381
+    // stack: x0, x1
+    DUP2
+    DUP2
+    %is_canonical_fp381
+    %assert_nonzero
+    // stack: x0, x1
+%endmacro
+
+// Returns whether x = 0.
+%macro is_zero_fp381
+    // stack: x0, x1
+    OR
+    ISZERO
+%endmacro
+
+// Loads an element of Fp stored at addr as (lo, hi).
+%macro load_fp381
+    // stack: addr
+    DUP1
+    %increment
+    MLOAD_GENERAL
+    SWAP1
+    MLOAD_GENERAL
+    // stack: x0, x1
+%endmacro
+
+// Stores an element of Fp at addr as (lo, hi).
+%macro store_fp381
+    // stack: addr, x0, x1
+    DUP1
+    SWAP2
+    MSTORE_GENERAL
+    // stack: addr, x1
+    %increment
+    SWAP1
+    MSTORE_GENERAL
+%endmacro
+
+// Loads an element of Fp at the given offset of the current kernel general memory.
+%macro load_fp381(offset)
+    PUSH $offset
+    %build_current_general_address
+    %load_fp381
+%endmacro
+
+// Loads an element of Fp from the kernel code, where it is stored as 48 big-endian bytes.
+%macro load_fp381_code
+    // stack: addr
+    PUSH 16
+    DUP2
+    MLOAD_32BYTES
+    // stack: x1, addr
+    SWAP1
+    %add_const(16)
+    PUSH 32
+    SWAP1
+    MLOAD_32BYTES
+    // stack: x0, x1
+%endmacro
+
+// fp381_2 macros
+
+%macro add_fp381_2
+    // stack: x_re: 2, x_im: 2, y_re: 2, y_im: 2
+    %stack (x_re: 2, x_im: 2, y_re: 2, y_im: 2) -> (x_re, y_re, x_im, y_im)
+    %add_fp381
+    %stack (z_re: 2, x_im: 2, y_im: 2) -> (x_im, y_im, z_re)
+    %add_fp381
+    %stack (z_im: 2, z_re: 2) -> (z_re, z_im)
+    // stack: z_re: 2, z_im: 2
+%endmacro
+
+%macro sub_fp381_2
+    // stack: x_re: 2, x_im: 2, y_re: 2, y_im: 2
+    %stack (x_re: 2, x_im: 2, y_re: 2, y_im: 2) -> (x_re, y_re, x_im, y_im)
+    %sub_fp381
+    %stack (z_re: 2, x_im: 2, y_im: 2) -> (x_im, y_im, z_re)
+    %sub_fp381
+    %stack (z_im: 2, z_re: 2) -> (z_re, z_im)
+    // stack: z_re: 2, z_im: 2
+%endmacro
+
+%macro neg_fp381_2
+    // stack: x_re: 2, x_im: 2
+    %neg_fp381
+    %stack (z_re: 2, x_im: 2) -> (x_im, z_re)
+    %neg_fp381
+    %stack (z_im: 2, z_re: 2) -> (z_re, z_im)
+    // stack: z_re: 2, z_im: 2
+%endmacro
+
+// Returns twice x.
+%macro double_fp381_2
+    // stack: x: 4
+    %dup_fp381_2_0
+    %add_fp381_2
+    // stack: 2x: 4
+%endmacro
+
+// Complex conjugation.
+%macro conj_fp381_2
+    // stack: x_re: 2, x_im: 2
+    %stack (x_re: 2, x_im: 2) -> (x_im, x_re)
+    %neg_fp381
+    %stack (z_im: 2, x_re: 2) -> (x_re, z_im)
+    // stack: x_re: 2, -x_im: 2
+%endmacro
+
+// Multiplication by xi = 1 + i, i.e. (a + bi)(1 + i) = (a - b) + (a + b)i.
+%macro mul_fp381_2_by_xi
+    // stack: a: 2, b: 2
+    DUP4
+    DUP4
+    DUP4
+    DUP4
+    %sub_fp381
+    // stack: a - b: 2, a: 2, b: 2
+    %stack (d: 2, a: 2, b: 2) -> (a, b, d)
+    %add_fp381
+    %stack (s: 2, d: 2) -> (d, s)
+    // stack: a - b: 2, a + b: 2
+%endmacro
+
+// Multiplication by an element of the base field.
+%macro mul_fp381_2_by_fp381
+    // stack: x_re: 2, x_im: 2, c: 2
+    DUP6
+    DUP6
+    %mul_fp381
+    // stack: c * x_re: 2, x_im: 2, c: 2
+    %stack (z_re: 2, x_im: 2, c: 2) -> (x_im, c, z_re)
+    %mul_fp381
+    %stack (z_im: 2, z_re: 2) -> (z_re, z_im)
+    // stack: z_re: 2, z_im: 2
+%endmacro
+
+%macro mul_fp381_2
+    // stack: x: 4, y: 4
+    %stack (x: 4, y: 4) -> (x, y, %%after)
+    %jump(mul_fp381_2)
+%%after:
+    // stack: z: 4
+%endmacro
+
+%macro square_fp381_2
+    // stack: x: 4
+    %dup_fp381_2_0
+    %mul_fp381_2
+    // stack: x^2: 4
+%endmacro
+
+// Karatsuba multiplication, with x = a + bi and y = c + di:
+//     x * y = (ac - bd) + ((a + b)(c + d) - ac - bd)i
+global mul_fp381_2:
+    // stack: a: 2, b: 2, c: 2, d: 2, retdest
+    DUP6
+    DUP6
+    DUP4
+    DUP4
+    %mul_fp381
+    // stack: ac: 2, a: 2, b: 2, c: 2, d: 2, retdest
+    DUP10
+    DUP10
+    DUP8
+    DUP8
+    %mul_fp381
+    // stack: bd: 2, ac: 2, a: 2, b: 2, c: 2, d: 2, retdest
+    %stack (bd: 2, ac: 2, a: 2, b: 2) -> (a, b, bd, ac)
+    %add_fp381
+    %stack (s: 2, bd: 2, ac: 2, c: 2, d: 2) -> (c, d, s, bd, ac)
+    %add_fp381
+    %mul_fp381
+    // stack: (a + b)(c + d): 2, bd: 2, ac: 2, retdest
+    %stack (t: 2, bd: 2, ac: 2) -> (t, ac, bd, ac, bd)
+    %sub_fp381
+    %sub_fp381
+    // stack: z_im: 2, ac: 2, bd: 2, retdest
+    %stack (z_im: 2, ac: 2, bd: 2) -> (ac, bd, z_im)
+    %sub_fp381
+    // stack: z_re: 2, z_im: 2, retdest
+    %stack (z_re: 2, z_im: 2, retdest) -> (retdest, z_re, z_im)
+    JUMP
+
+// Returns x / y for y != 0, where the prover provides the quotient z, and the kernel checks that
+// z is canonical and y * z = x.
+global div_fp381_2:
+    // stack: x: 4, y: 4, retdest
+    DUP8
+    DUP8
+    DUP8
+    DUP8
+    %is_zero_fp381_2
+    %assert_zero
+    PROVER_INPUT(sf::bls381_base::fp2_div_3)
+    PROVER_INPUT(sf::bls381_base::fp2_div_2)
+    PROVER_INPUT(sf::bls381_base::fp2_div_1)
+    PROVER_INPUT(sf::bls381_base::fp2_div_0)
+    // stack: z: 4, x: 4, y: 4, retdest
+    %assert_canonical_fp381_2
+    %stack (z: 4, x: 4, y: 4) -> (y, z, x, z)
+    %mul_fp381_2
+    // stack: y * z: 4, x: 4, z: 4, retdest
+    %eq_fp381_2
+    %assert_nonzero
+    // stack: z: 4, retdest
+    %stack (z: 4, retdest) -> (retdest, z)
+    JUMP
+
+%macro div_fp381_2
+    // stack: x: 4, y: 4
+    %stack (x: 4, y: 4) -> (x, y, %%after)
+    %jump(div_fp381_2)
+%%after:
+    // stack: x / y: 4
+%endmacro
+
+// Panics unless both coordinates of x are canonical, leaving x on the stack.
+%macro assert_canonical_fp381_2
+    // stack: x_re: 2, x_im: 2
+    %assert_canonical_fp381
+    DUP4
+    DUP4
+    %is_canonical_fp381
+    %assert_nonzero
+    // stack: x_re: 2, x_im: 2
+%endmacro
+
+// Returns whether x = y.
+%macro eq_fp381_2
+    // stack: x0, x1, x2, x3, y0, y1, y2, y3
+    DUP5
+    XOR
+    SWAP1
+    DUP6
+    XOR
+    OR
+    SWAP1
+    DUP6
+    XOR
+    OR
+    SWAP1
+    DUP6
+    XOR
+    OR
+    // stack: diff, y0, y1, y2, y3
+    SWAP4
+    %pop4
+    ISZERO
+    // stack: x == y
+%endmacro
+
+// Returns whether x = 0.
+%macro is_zero_fp381_2
+    // stack: x0, x1, x2, x3
+    OR
+    OR
+    OR
+    ISZERO
+%endmacro
+
+%macro dup_fp381_2_0
+    DUP4
+    DUP4
+    DUP4
+    DUP4
+%endmacro
+
+%macro dup_fp381_2_4
+    DUP8
+    DUP8
+    DUP8
+    DUP8
+%endmacro
+
+%macro dup_fp381_2_8
+    DUP12
+    DUP12
+    DUP12
+    DUP12
+%endmacro
+
+%macro dup_fp381_2_12
+    DUP16
+    DUP16
+    DUP16
+    DUP16
+%endmacro
+
+%macro swap_fp381_2
+    %stack (x: 4, y: 4) -> (y, x)
+%endmacro
+
+// Loads an element of Fp2 stored at addr as (re, im).
+%macro load_fp381_2
+    // stack: addr
+    DUP1
+    %add_const(3)
+    MLOAD_GENERAL
+    SWAP1
+    DUP1
+    %add_const(2)
+    MLOAD_GENERAL
+    SWAP1
+    DUP1
+    %increment
+    MLOAD_GENERAL
+    SWAP1
+    MLOAD_GENERAL
+    // stack: x0, x1, x2, x3
+%endmacro
+
+// Stores an element of Fp2 at addr as (re, im).
+%macro store_fp381_2
+    // stack: addr, x0, x1, x2, x3
+    %rep 3
+        DUP1
+        SWAP2
+        MSTORE_GENERAL
+        %increment
+    %endrep
+    // stack: addr + 3, x3
+    SWAP1
+    MSTORE_GENERAL
+%endmacro
+
+// Loads an element of Fp2 at the given offset of the current kernel general memory.
+%macro load_fp381_2(offset)
+    PUSH $offset
+    %build_current_general_address
+    %load_fp381_2
+%endmacro
+
+// Stores an element of Fp2 at the given offset of the current kernel general memory.
+%macro store_fp381_2(offset)
+    // stack: x: 4
+    PUSH $offset
+    %build_current_general_address
+    %store_fp381_2
+%endmacro
+
+// Loads an element of Fp2 from the kernel code, where it is stored as 96 big-endian bytes, the
+// real part first.
+%macro load_fp381_2_code
+    // stack: addr
+    DUP1
+    %add_const(48)
+    %load_fp381_code
+    // stack: x_im: 2, addr
+    DUP3
+    %load_fp381_code
+    %stack (x_re: 2, x_im: 2, addr) -> (x_re, x_im)
+    // stack: x_re: 2, x_im: 2
+%endmacro
+
+%macro load_fp381_2_code(label)
+    PUSH $label
+    %load_fp381_2_code
+%endmacro
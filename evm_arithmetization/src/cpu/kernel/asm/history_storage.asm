@@ -0,0 +1,70 @@
+/// EIP-2935: Serve historical block hashes from state
+/// <https://eips.ethereum.org/EIPS/eip-2935#specification>
+///
+/// Stores the parent block hash in the history storage contract, at slot
+/// `(block_number - 1) % HISTORY_SERVE_WINDOW`.
+///
+/// *NOTE*: This is a no-op for the genesis block, or if the history storage
+/// contract hasn't been deployed yet.
+
+/// Pre-stack: (empty)
+/// Post-stack: (empty)
+global set_parent_block_hash:
+    // stack: (empty)
+    PUSH txn_loop
+    %blocknumber
+    // stack: block_number, retdest
+    DUP1 ISZERO %jumpi(skip_parent_block_hash)
+    // stack: block_number, retdest
+    PUSH set_parent_block_hash_contd
+    PUSH @HISTORY_STORAGE_STATE_KEY
+    // stack: state_key, set_parent_block_hash_contd, block_number, retdest
+    %jump(search_account)
+set_parent_block_hash_contd:
+    // stack: account_ptr, block_number, retdest
+    ISZERO %jumpi(skip_parent_block_hash)
+    // stack: block_number, retdest
+    PUSH @HISTORY_SERVE_WINDOW
+    SWAP1
+    %decrement
+    // stack: block_number - 1, 8191, retdest
+    MOD
+    // stack: slot_idx, retdest
+    %slot_to_storage_key
+    // stack: slot_key, retdest
+    // The parent block hash is the last of the 256 previous block hashes.
+    PUSH 255
+    %mload_kernel(@SEGMENT_BLOCK_HASHES)
+    // stack: parent_hash, slot_key, retdest
+    SWAP1
+    PUSH @HISTORY_STORAGE_STATE_KEY
+    // stack: state_key, slot_key, parent_hash, retdest
+    DUP3 ISZERO %jumpi(delete_parent_block_hash_slot)
+    // stack: state_key, slot_key, parent_hash, retdest
+    %insert_slot_with_value_from_keys
+    // stack: retdest
+    JUMP
+
+delete_parent_block_hash_slot:
+    // stack: state_key, slot_key, 0, retdest
+    DUP3 DUP3 DUP3
+    %search_slot
+    // stack: slot_exists, state_key, slot_key, 0, retdest
+    %jumpi(remove_parent_block_hash_slot)
+    // stack: state_key, slot_key, 0, retdest
+    %pop3
+    // stack: retdest
+    JUMP
+
+remove_parent_block_hash_slot:
+    // stack: state_key, slot_key, 0, retdest
+    %stack(state_key, storage_key, zero) -> (storage_key, state_key)
+    %remove_slot
+    // stack: retdest
+    JUMP
+
+skip_parent_block_hash:
+    // stack: block_number, retdest
+    POP
+    // stack: retdest
+    JUMP
@@ -183,6 +183,7 @@ global txn_loop_after:
     global execute_withdrawals:
         // stack: cum_gas, txn_counter, num_nibbles, txn_nb
         %withdrawals
+        %requests
 }
 
 global perform_final_checks:
@@ -282,5 +283,6 @@ global check_final_state_trie:
     #[cfg(feature = eth_mainnet)]
     {
         %reset_blob_versioned_hashes
+        PUSH 0 %mstore_global_metadata(@GLOBAL_METADATA_AUTH_LIST_LEN)
     }
 %endmacro
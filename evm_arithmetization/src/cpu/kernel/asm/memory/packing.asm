@@ -279,7 +279,7 @@ mstore_unpacking_32:
 %endmacro
 
 // Pre stack: addr, value, retdest
-// Post stack: addr'
+// Post stack: (empty)
 global mstore_unpacking_u64_LE:
     %stack (addr, value) -> (0xff, value, addr, addr, value)
     AND
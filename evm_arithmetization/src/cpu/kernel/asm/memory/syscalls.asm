@@ -82,7 +82,7 @@ calldataload_large_offset:
     %stack (kexit_info, dest_offset, offset, size) ->
         (dest_offset, size, kexit_info, dest_offset, offset, size)
     %add_or_fault
-    // stack: expanded_num_bytes, kexit_info, dest_offset, offset, size, kexit_info
+    // stack: expanded_num_bytes, kexit_info, dest_offset, offset, size
     DUP1 %ensure_reasonable_offset
     %update_mem_bytes
 
@@ -210,7 +210,7 @@ global sys_codecopy:
 
     %stack (kexit_info, dest_offset, offset, size) -> (dest_offset, size, kexit_info, dest_offset, offset, size)
     %add_or_fault
-    // stack: expanded_num_bytes, kexit_info, dest_offset, offset, size, kexit_info
+    // stack: expanded_num_bytes, kexit_info, dest_offset, offset, size
     DUP1 %ensure_reasonable_offset
     %update_mem_bytes
 
@@ -270,7 +270,7 @@ global sys_mcopy:
 
     %stack (kexit_info, dest_offset, offset, size) -> (dest_offset, size, kexit_info, dest_offset, offset, size)
     %add_or_fault
-    // stack: expanded_num_bytes, kexit_info, dest_offset, offset, size, kexit_info
+    // stack: expanded_num_bytes, kexit_info, dest_offset, offset, size
     DUP1 %ensure_reasonable_offset
     %update_mem_bytes
 
@@ -17,7 +17,7 @@ global mpt_insert_state_trie:
 
 // Insert a node in the transaction trie. The payload
 // must be pointing to the rlp encoded txn
-// Pre stack: key, txn_rlp_ptr, redest
+// Pre stack: key, num_nibbles, txn_rlp_ptr, retdest
 // Post stack: (empty)
 global mpt_insert_txn_trie:
     // stack: key=rlp(key), num_nibbles, txn_rlp_ptr, retdest 
@@ -306,4 +306,52 @@ sload_with_addr:
     decode_and_store_blob_versioned_hashes_finish:
         %stack (rlp_addr, end_rlp_addr, store_addr, retdest) -> (retdest, rlp_addr)
         JUMP
-}
\ No newline at end of file
+}
+/// Type-4 transactions specific decoding helper macros.
+#[cfg(feature = eth_mainnet)]
+{
+    %macro decode_and_store_authorization_list
+        // stack: rlp_addr
+        %decode_rlp_list_len
+        %stack (rlp_addr, len) -> (len, len, rlp_addr, %%after)
+
+        // EIP-7702: Set-code transactions should have at least 1 authorization.
+        // stack: len, len, rlp_addr, %%after, chain_id_addr, retdest
+        %assert_nonzero(invalid_txn_3)
+
+        // stack: len, rlp_addr, %%after
+        %jump(decode_and_store_authorization_list)
+    %%after:
+    %endmacro
+
+    // The authorization list is of the form `[[chain_id, address, nonce, y_parity, r, s]...]`.
+    // We only store its location here and charge the intrinsic cost of each authorization,
+    // as the tuples are processed after the sender has been charged (see `process_authorization_list`).
+    global decode_and_store_authorization_list:
+        // stack: len, rlp_addr
+        DUP2 %mstore_global_metadata(@GLOBAL_METADATA_AUTH_LIST_START)
+        DUP1 %mstore_global_metadata(@GLOBAL_METADATA_AUTH_LIST_LEN)
+        // stack: len, rlp_addr
+        DUP2 ADD
+        // stack: end_rlp_addr, rlp_addr
+        SWAP1
+    decode_and_store_authorization_list_loop:
+        // stack: rlp_addr, end_rlp_addr
+        DUP2 DUP2 EQ %jumpi(decode_and_store_authorization_list_finish)
+        // stack: rlp_addr, end_rlp_addr
+        %decode_rlp_list_len // Should be a list `[chain_id, address, nonce, y_parity, r, s]`
+        // stack: rlp_addr, tuple_len, end_rlp_addr
+        ADD
+        // stack: rlp_addr', end_rlp_addr
+        %add_authorization_cost
+        %jump(decode_and_store_authorization_list_loop)
+    decode_and_store_authorization_list_finish:
+        %stack (rlp_addr, end_rlp_addr, retdest) -> (retdest, rlp_addr)
+        JUMP
+
+    %macro add_authorization_cost
+        %mload_global_metadata(@GLOBAL_METADATA_ACCESS_LIST_DATA_COST)
+        %add_const(@GAS_PER_EMPTY_ACCOUNT)
+        %mstore_global_metadata(@GLOBAL_METADATA_ACCESS_LIST_DATA_COST)
+    %endmacro
+}
@@ -14,7 +14,7 @@ read_txn_from_memory:
     // stack: retdest
 
     // We will peak at the first byte to determine what type of transaction this is.
-    // Note that type 1, 2 and 3 transactions have a first byte of 1, 2 and 3, respectively.
+    // Note that type 1, 2, 3 and 4 transactions have a first byte of 1, 2, 3 and 4, respectively.
     // Type 0 (legacy) transactions have no such prefix, but their RLP will have a
     // first byte >= 0xc0, so there is no overlap.
 
@@ -33,7 +33,7 @@ read_txn_from_memory:
     %jumpi(process_type_2_txn)
     // stack: rlp_start_addr, retdest
 
-    // Only Ethereum mainnet supports Blob-transactions.
+    // Only Ethereum mainnet supports Blob-transactions and set-code transactions.
     #[cfg(feature = eth_mainnet)]
     {
         DUP1
@@ -42,6 +42,13 @@ read_txn_from_memory:
         // stack: first_byte == 3, rlp_start_addr, retdest
         %jumpi(process_type_3_txn)
         // stack: rlp_start_addr, retdest
+
+        DUP1
+        MLOAD_GENERAL
+        %eq_const(4)
+        // stack: first_byte == 4, rlp_start_addr, retdest
+        %jumpi(process_type_4_txn)
+        // stack: rlp_start_addr, retdest
     }
 
     // At this point, since it's not a typed transaction,
@@ -0,0 +1,262 @@
+// Type 4 transactions, introduced by EIP 7702, have the format
+//     0x04 || rlp([chain_id, nonce, max_priority_fee_per_gas, max_fee_per_gas, gas_limit, destination,
+//                  value, data, access_list, authorization_list, y_parity, r, s])
+//
+// The signed data is
+//     keccak256(0x04 || rlp([chain_id, nonce, max_priority_fee_per_gas, max_fee_per_gas, gas_limit,
+//                            destination, value, data, access_list, authorization_list]))
+//
+// where `authorization_list` is a list of tuples `[chain_id, address, nonce, y_parity, r, s]`.
+
+global process_type_4_txn:
+    // stack: rlp_addr, retdest
+    // Store txn type.
+    PUSH 4
+    %mstore_txn_field(@TXN_FIELD_TYPE)
+
+    // stack: rlp_addr, retdest
+    // Initial rlp address offset of 1 (skipping over the 0x04 byte)
+    %add_const(1)
+    // stack: rlp_addr, retdest
+    %decode_rlp_list_len
+    // We don't actually need the length.
+    %stack (rlp_addr, len) -> (rlp_addr)
+
+    // stack: rlp_addr, retdest
+    %store_chain_id_present_true
+    // stack: rlp_addr, retdest
+    // Keep track of the chain id position.
+    DUP1
+    // stack: rlp_addr, chain_id_addr, retdest
+    %decode_and_store_chain_id
+    %decode_and_store_nonce
+    %decode_and_store_max_priority_fee
+    %decode_and_store_max_fee
+    %decode_and_store_gas_limit
+    %decode_and_store_to
+
+    // EIP-7702: Set-code transactions cannot be contract creations.
+    %is_contract_creation
+    // stack: is_contract_creation, rlp_addr, chain_id_addr, retdest
+    %jumpi(invalid_txn_2)
+
+    %decode_and_store_value
+    %decode_and_store_data
+    %decode_and_store_access_list
+    %decode_and_store_authorization_list
+    // stack: rlp_addr, chain_id_addr, retdest
+    DUP1
+    // stack: rlp_addr, after_auth_list_addr, chain_id_addr, retdest
+    %decode_and_store_y_parity
+    %decode_and_store_r
+    %decode_and_store_s
+
+    // stack: rlp_addr, after_auth_list_addr, chain_id_addr, retdest
+    POP
+    // stack: after_auth_list_addr, chain_id_addr, retdest
+
+// From EIP-7702:
+// The signature_y_parity, signature_r, signature_s elements of this transaction represent a secp256k1 signature over
+// keccak256(0x04 || rlp([chain_id, nonce, max_priority_fee_per_gas, max_fee_per_gas, gas_limit, destination, value, data, access_list, authorization_list]))
+// We know that [chain_id, nonce, max_priority_fee_per_gas, max_fee_per_gas, gas_limit, destination, value, data, access_list, authorization_list] is already encoded
+// at `chain_id_addr`; we just need to overwrite the existing RLP prefix. This is fine since we don't need the original encoding anymore.
+// Note that the authorization list itself, which lies after `chain_id_addr`, is left untouched.
+type_4_compute_signed_data:
+    // stack: after_auth_list_addr, chain_id_addr, retdest
+    %prepend_rlp_list_prefix
+    // stack: prefix_start_pos, rlp_len, retdest
+
+    // Store a `4` in front of the RLP
+    %decrement
+    %stack (rlp_addr) -> (4, rlp_addr, rlp_addr)
+    MSTORE_GENERAL
+    // stack: rlp_addr, rlp_len, retdest
+
+    // Hash the RLP + the leading `4`
+    SWAP1 %increment SWAP1
+    // stack: ADDR, len, retdest
+    KECCAK_GENERAL
+    // stack: hash, retdest
+
+    %mload_txn_field(@TXN_FIELD_S)
+    %mload_txn_field(@TXN_FIELD_R)
+    %mload_txn_field(@TXN_FIELD_Y_PARITY) %add_const(27) // ecrecover interprets v as y_parity + 27
+
+    PUSH store_origin
+    // stack: store_origin, v, r, s, hash, retdest
+    SWAP4
+    // stack: hash, v, r, s, store_origin, retdest
+    %jump(ecrecover)
+
+store_origin:
+    // stack: address, retdest
+    // If ecrecover returned u256::MAX, that indicates failure.
+    DUP1
+    %eq_const(0xffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff)
+    %jumpi(panic)
+
+    // stack: address, retdest
+    %mstore_txn_field(@TXN_FIELD_ORIGIN)
+    // stack: retdest
+    %jump(process_normalized_txn)
+
+// Processes the authorization list of the current type-4 transaction, as per EIP-7702.
+// This is called after the sender has been charged, and before the message call is executed,
+// so that authorizations persist even if the transaction reverts.
+// Invalid authorizations are skipped, they do not invalidate the transaction.
+//
+// Pre stack: retdest
+// Post stack: (empty)
+global process_authorization_list:
+    // stack: retdest
+    %mload_global_metadata(@GLOBAL_METADATA_AUTH_LIST_START)
+    %mload_global_metadata(@GLOBAL_METADATA_AUTH_LIST_LEN)
+    DUP2 ADD
+    // stack: end_rlp_addr, rlp_addr, retdest
+    SWAP1
+process_authorization_list_loop:
+    // stack: rlp_addr, end_rlp_addr, retdest
+    DUP2 DUP2 EQ %jumpi(process_authorization_list_finish)
+    // stack: rlp_addr, end_rlp_addr, retdest
+    %decode_rlp_list_len
+    // stack: rlp_addr, tuple_len, end_rlp_addr, retdest
+    DUP1 SWAP2 ADD
+    // stack: next_rlp_addr, rlp_addr, end_rlp_addr, retdest
+    SWAP1
+    // stack: rlp_addr, next_rlp_addr, end_rlp_addr, retdest
+    // Keep track of the tuple's chain id position.
+    DUP1
+    %decode_rlp_scalar
+    %decode_rlp_scalar
+    %decode_rlp_scalar
+    // stack: rlp_addr, nonce, address, chain_id, chain_id_addr, next_rlp_addr, end_rlp_addr, retdest
+    DUP1
+    %decode_rlp_scalar
+    %decode_rlp_scalar
+    %decode_rlp_scalar
+    // stack: rlp_addr, s, r, y_parity, after_nonce_addr, nonce, address, chain_id, chain_id_addr, next_rlp_addr, end_rlp_addr, retdest
+    POP
+
+    // The signed message is keccak256(MAGIC || rlp([chain_id, address, nonce])).
+    // As for transactions, [chain_id, address, nonce] is already encoded at `chain_id_addr`,
+    // we just need to overwrite the bytes before it with the list prefix and the magic byte.
+    // This is fine since the authorization tuple is not needed anymore.
+    %stack (s, r, y_parity, after_nonce_addr, nonce, address, chain_id, chain_id_addr) ->
+        (after_nonce_addr, chain_id_addr, s, r, y_parity, nonce, address, chain_id)
+    %prepend_rlp_list_prefix
+    // stack: prefix_start_pos, rlp_len, s, r, y_parity, nonce, address, chain_id, next_rlp_addr, end_rlp_addr, retdest
+    %decrement
+    %stack (rlp_addr) -> (@SET_CODE_AUTH_MAGIC, rlp_addr, rlp_addr)
+    MSTORE_GENERAL
+    SWAP1 %increment SWAP1
+    // stack: ADDR, len, s, r, y_parity, nonce, address, chain_id, next_rlp_addr, end_rlp_addr, retdest
+    KECCAK_GENERAL
+    // stack: hash, s, r, y_parity, nonce, address, chain_id, next_rlp_addr, end_rlp_addr, retdest
+    %stack (hash, s, r, y_parity) -> (y_parity, hash, r, s, authority_recovered, s)
+    %add_const(27) // ecrecover interprets v as y_parity + 27
+    SWAP1
+    // stack: hash, v, r, s, authority_recovered, s, nonce, address, chain_id, next_rlp_addr, end_rlp_addr, retdest
+    %jump(ecrecover)
+
+authority_recovered:
+    // stack: authority, s, nonce, address, chain_id, next_rlp_addr, end_rlp_addr, retdest
+    // EIP-2: Check that s <= n/2. `SECP_SCALAR_HALF` is ceil(n/2), so we compare against floor(n/2).
+    SWAP1 %secp_scalar_half %decrement LT ISZERO
+    // stack: s_is_valid, authority, nonce, address, chain_id, next_rlp_addr, end_rlp_addr, retdest
+    // If ecrecover returned u256::MAX, that indicates failure.
+    DUP2 %eq_const(@U256_MAX) ISZERO AND
+    // The chain id must be either 0 or the current chain id.
+    DUP5 ISZERO
+    DUP6 %mload_global_metadata(@GLOBAL_METADATA_BLOCK_CHAIN_ID) EQ
+    OR AND
+    // EIP-2681: The nonce must be strictly less than 2^64 - 1.
+    DUP3 %lt_const(@MAX_NONCE) AND
+    // stack: is_valid, authority, nonce, address, chain_id, next_rlp_addr, end_rlp_addr, retdest
+    ISZERO %jumpi(skip_authorization)
+
+    // stack: authority, nonce, address, chain_id, next_rlp_addr, end_rlp_addr, retdest
+    DUP1 %insert_accessed_addresses_no_return
+
+    // The authority's code must be either empty or already delegated.
+    DUP1 %is_code_empty_or_delegated
+    ISZERO %jumpi(skip_authorization)
+
+    // The authority's nonce must match the authorization's nonce.
+    DUP1 %nonce
+    DUP3 EQ ISZERO %jumpi(skip_authorization)
+
+    // stack: authority, nonce, address, chain_id, next_rlp_addr, end_rlp_addr, retdest
+    DUP1 %is_non_existent
+    %jumpi(create_authority_account)
+
+    // The authority already exists: refund PER_EMPTY_ACCOUNT_COST - PER_AUTH_BASE_COST.
+    PUSH @REFUND_AUTH_EXISTING_ACCOUNT
+    %refund_gas
+    %jump(set_delegation)
+
+create_authority_account:
+    // stack: authority, nonce, address, chain_id, next_rlp_addr, end_rlp_addr, retdest
+    DUP1 PUSH 0
+    // stack: is_eoa, authority, authority, nonce, address, chain_id, next_rlp_addr, end_rlp_addr, retdest
+    %journal_add_account_created
+    %get_trie_data_size // pointer to new account we're about to insert
+    PUSH 0 %append_to_trie_data // nonce
+    PUSH 0 %append_to_trie_data // balance
+    PUSH 0 %append_to_trie_data // storage root pointer
+    PUSH @EMPTY_STRING_HASH %append_to_trie_data // code hash
+    // stack: new_account_ptr, authority, nonce, address, chain_id, next_rlp_addr, end_rlp_addr, retdest
+    DUP2 %addr_to_state_key
+    // stack: key, new_account_ptr, authority, nonce, address, chain_id, next_rlp_addr, end_rlp_addr, retdest
+    %mpt_insert_state_trie
+
+set_delegation:
+    // stack: authority, nonce, address, chain_id, next_rlp_addr, end_rlp_addr, retdest
+    // Delegating to the zero address resets the authority's code.
+    DUP3 ISZERO %jumpi(reset_delegation)
+    DUP3 %observe_new_delegation
+    // stack: delegate, authority, nonce, address, chain_id, next_rlp_addr, end_rlp_addr, retdest
+    %delegation_designator
+    %keccak256_word(23) // DELEGATION_DESIGNATOR_LEN
+    %jump(set_delegation_codehash)
+reset_delegation:
+    PUSH @EMPTY_STRING_HASH
+set_delegation_codehash:
+    // stack: codehash, authority, nonce, address, chain_id, next_rlp_addr, end_rlp_addr, retdest
+    DUP2
+    // stack: authority, codehash, authority, nonce, address, chain_id, next_rlp_addr, end_rlp_addr, retdest
+    %set_codehash
+    // stack: authority, nonce, address, chain_id, next_rlp_addr, end_rlp_addr, retdest
+    %increment_nonce
+    // stack: nonce, address, chain_id, next_rlp_addr, end_rlp_addr, retdest
+    %pop3
+    %jump(process_authorization_list_loop)
+
+skip_authorization:
+    // stack: authority, nonce, address, chain_id, next_rlp_addr, end_rlp_addr, retdest
+    %pop4
+    %jump(process_authorization_list_loop)
+
+process_authorization_list_finish:
+    // stack: rlp_addr, end_rlp_addr, retdest
+    %pop2
+    JUMP
+
+%macro process_authorization_list
+    PUSH %%after
+    %jump(process_authorization_list)
+%%after:
+%endmacro
+
+// This should be called whenever an account delegates to a new address. It does nothing, but
+// provides a single hook where code can react to new delegation designators being deployed.
+global observe_new_delegation:
+    // stack: delegate, retdest
+    SWAP1
+    // stack: retdest, delegate
+    JUMP
+
+%macro observe_new_delegation
+    %stack (delegate) -> (delegate, %%after)
+    %jump(observe_new_delegation)
+%%after:
+%endmacro
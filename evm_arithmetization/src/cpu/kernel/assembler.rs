@@ -1,4 +1,4 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::fs;
 use std::time::Instant;
 
@@ -13,6 +13,9 @@ use crate::cpu::kernel::ast::Item::LocalLabelDeclaration;
 use crate::cpu::kernel::ast::{File, Item, StackReplacement};
 use crate::cpu::kernel::opcodes::{get_opcode, get_push_opcode};
 use crate::cpu::kernel::optimizer::optimize_asm;
+use crate::cpu::kernel::stack::stack_effects::{
+    check_code, check_macro, StackAnnotations, StackEffectError,
+};
 use crate::cpu::kernel::stack::stack_manipulation::expand_stack_manipulation;
 use crate::cpu::kernel::utils::u256_to_trimmed_be_bytes;
 use crate::generation::prover_input::ProverInputFn;
@@ -157,6 +160,71 @@ pub(crate) fn assemble(
     Kernel::new(code, global_labels, prover_inputs)
 }
 
+/// Checks the stack depth through the code of `files`, with macros and stack
+/// manipulations expanded, against the stack effects documented by their
+/// comments. Documented macros are also checked on their own.
+pub(crate) fn check_stack_effects(
+    files: Vec<File>,
+    annotations: &[StackAnnotations],
+    constants: &HashMap<String, U256>,
+) -> Vec<StackEffectError> {
+    let macros = find_macros(&files);
+    let mut macro_counter = 0;
+    let mut expand = |body| {
+        let body = expand_macros(body, &macros, &mut macro_counter);
+        expand_stack_manipulation(inline_constants(body, constants))
+    };
+    let bodies = files
+        .into_iter()
+        .map(|file| expand(expand_conditional_blocks(file.body)))
+        .collect_vec();
+
+    // Global labels may be documented in another file than the one jumping
+    // to them.
+    let global_labels: HashSet<_> = bodies
+        .iter()
+        .flatten()
+        .filter_map(|item| match item {
+            Item::GlobalLabelDeclaration(label) => Some(label.as_str()),
+            _ => None,
+        })
+        .collect();
+    let global_effects: HashMap<_, _> = annotations
+        .iter()
+        .flat_map(|annotations| &annotations.labels)
+        .filter(|(label, _)| global_labels.contains(label.as_str()))
+        .map(|(label, effect)| (label.as_str(), *effect))
+        .collect();
+
+    let mut errors = vec![];
+    for (file, (body, annotations)) in bodies.iter().zip_eq(annotations).enumerate() {
+        let mut effects = global_effects.clone();
+        effects.extend(
+            annotations
+                .labels
+                .iter()
+                .map(|(label, effect)| (label.as_str(), *effect)),
+        );
+        errors.extend(check_code(file, body, &effects));
+
+        let documented_macros = annotations.macros.iter().sorted_by(|a, b| a.0.cmp(b.0));
+        for ((name, num_params), effect) in documented_macros {
+            let signature = MacroSignature {
+                name: name.clone(),
+                num_params: *num_params,
+            };
+            // The macro may be disabled by a conditional block.
+            if !macros.contains_key(&signature) {
+                continue;
+            }
+            let args = vec![PushTarget::Literal(U256::zero()); *num_params];
+            let body = expand(vec![Item::MacroCall(name.clone(), args)]);
+            errors.extend(check_macro(file, name, *effect, &body, &effects));
+        }
+    }
+    errors
+}
+
 fn find_macros(files: &[File]) -> HashMap<MacroSignature, Macro> {
     let mut macros = HashMap::new();
     for file in files {
@@ -292,6 +360,12 @@ fn expand_macro_call(
 
     // Recursively expand any macros in the expanded code.
     expand_macros(expanded_item, macros, macro_counter)
+        .into_iter()
+        .map(|item| match item {
+            Item::StackComment(stack, expansions) => Item::StackComment(stack, expansions + 1),
+            item => item,
+        })
+        .collect()
 }
 
 fn inline_constants(body: Vec<Item>, constants: &HashMap<String, U256>) -> Vec<Item> {
@@ -359,6 +433,9 @@ fn find_labels(
             | Item::MacroLabelDeclaration(_) => {
                 panic!("Item should have been expanded already: {item:?}");
             }
+            Item::StackComment(_, _) => {
+                panic!("Stack comments are only parsed to check stack effects: {item:?}");
+            }
             Item::GlobalLabelDeclaration(label) => {
                 let old = global_labels.insert(label.clone(), *offset);
                 assert!(old.is_none(), "Duplicate global label: {label}");
@@ -414,6 +491,9 @@ fn assemble_file(
             | Item::MacroLabelDeclaration(_) => {
                 panic!("Item should have been expanded already: {item:?}");
             }
+            Item::StackComment(_, _) => {
+                panic!("Stack comments are only parsed to check stack effects: {item:?}");
+            }
             Item::GlobalLabelDeclaration(_) | Item::LocalLabelDeclaration(_) => {
                 // Nothing to do; we processed labels in the prior phase.
             }
@@ -38,6 +38,9 @@ pub(crate) enum Item {
     Bytes(Vec<BytesTarget>),
     /// Creates a table of addresses from a list of labels.
     Jumptable(Vec<String>),
+    /// A `// stack:` comment, which is only kept when checking stack effects:
+    /// the listed stack, and the number of macro expansions it went through.
+    StackComment(String, usize),
 }
 
 /// The left hand side of a %stack stack-manipulation macro.
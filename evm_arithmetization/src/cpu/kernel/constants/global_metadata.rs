@@ -56,6 +56,10 @@ pub(crate) enum GlobalMetadata {
     BlockCurrentHash,
     /// EIP-4788: hash tree root of the beacon chain parent block.
     ParentBeaconBlockRoot,
+    /// EIP-7685: commitment to the execution layer requests of the block.
+    RequestsHash,
+    /// EIP-6110: address of the deposit contract of the chain.
+    DepositContractAddress,
 
     /// Gas to refund at the end of the transaction.
     RefundCounter,
@@ -74,6 +78,8 @@ pub(crate) enum GlobalMetadata {
     CurrentCheckpoint,
     TouchedAddressesLen,
     // Gas cost for the access list in type-1 txns. See EIP-2930.
+    // For type-4 txns, this also includes the authorization list cost. See
+    // EIP-7702.
     AccessListDataCost,
     // Boolean flag indicating if the txn is a contract creation txn.
     ContractCreation,
@@ -113,10 +119,17 @@ pub(crate) enum GlobalMetadata {
 
     /// Address where the base fee to be burnt is sent.
     BurnAddr,
+
+    /// Address of the authorization list of the current type-4 transaction,
+    /// within the `RlpRaw` segment.
+    AuthListStart,
+    /// Length in bytes of the authorization list of the current type-4
+    /// transaction.
+    AuthListLen,
 }
 
 impl GlobalMetadata {
-    pub(crate) const COUNT: usize = 54;
+    pub(crate) const COUNT: usize = 58;
 
     /// Unscales this virtual offset by their respective `Segment` value.
     pub(crate) const fn unscale(&self) -> usize {
@@ -151,6 +164,8 @@ impl GlobalMetadata {
             Self::BlockGasUsedBefore,
             Self::BlockGasUsedAfter,
             Self::ParentBeaconBlockRoot,
+            Self::RequestsHash,
+            Self::DepositContractAddress,
             Self::RefundCounter,
             Self::AccessedAddressesLen,
             Self::AccessedStorageKeysLen,
@@ -179,6 +194,8 @@ impl GlobalMetadata {
             Self::TransientStorageLen,
             Self::BlobVersionedHashesLen,
             Self::BurnAddr,
+            Self::AuthListStart,
+            Self::AuthListLen,
         ]
     }
 
@@ -212,6 +229,8 @@ impl GlobalMetadata {
             Self::BlockGasUsedAfter => "GLOBAL_METADATA_BLOCK_GAS_USED_AFTER",
             Self::BlockCurrentHash => "GLOBAL_METADATA_BLOCK_CURRENT_HASH",
             Self::ParentBeaconBlockRoot => "GLOBAL_METADATA_PARENT_BEACON_BLOCK_ROOT",
+            Self::RequestsHash => "GLOBAL_METADATA_REQUESTS_HASH",
+            Self::DepositContractAddress => "GLOBAL_METADATA_DEPOSIT_CONTRACT_ADDRESS",
             Self::RefundCounter => "GLOBAL_METADATA_REFUND_COUNTER",
             Self::AccessedAddressesLen => "GLOBAL_METADATA_ACCESSED_ADDRESSES_LEN",
             Self::AccessedStorageKeysLen => "GLOBAL_METADATA_ACCESSED_STORAGE_KEYS_LEN",
@@ -245,6 +264,8 @@ impl GlobalMetadata {
             Self::TransientStorageLen => "GLOBAL_METADATA_TRANSIENT_STORAGE_LEN",
             Self::BlobVersionedHashesLen => "GLOBAL_METADATA_BLOB_VERSIONED_HASHES_LEN",
             Self::BurnAddr => "GLOBAL_METADATA_BURN_ADDR",
+            Self::AuthListStart => "GLOBAL_METADATA_AUTH_LIST_START",
+            Self::AuthListLen => "GLOBAL_METADATA_AUTH_LIST_LEN",
         }
     }
 }
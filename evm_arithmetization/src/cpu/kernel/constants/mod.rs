@@ -26,7 +26,7 @@ pub struct Named<'a, T> {
 }
 
 /// Constants that are accessible to our kernel assembly code.
-pub(crate) fn evm_constants() -> HashMap<String, U256> {
+pub fn evm_constants() -> HashMap<String, U256> {
     let mut c = HashMap::new();
 
     let hex_constants = MISC_CONSTANTS
@@ -62,6 +62,10 @@ pub(crate) fn evm_constants() -> HashMap<String, U256> {
         c.insert(name.into(), U256::from(value));
     }
 
+    for (name, value) in DELEGATION_CONSTANTS {
+        c.insert(name.into(), U256::from(value));
+    }
+
     for (name, value) in LINKED_LISTS_CONSTANTS {
         c.insert(name.into(), U256::from(value));
     }
@@ -81,6 +85,54 @@ pub(crate) fn evm_constants() -> HashMap<String, U256> {
         cancun_constants::HISTORY_BUFFER_LENGTH.name.into(),
         cancun_constants::HISTORY_BUFFER_LENGTH.value,
     );
+    c.insert(
+        prague_constants::HISTORY_STORAGE_STATE_KEY.0.into(),
+        U256::from_big_endian(&prague_constants::HISTORY_STORAGE_STATE_KEY.1),
+    );
+    c.insert(
+        prague_constants::HISTORY_SERVE_WINDOW.name.into(),
+        prague_constants::HISTORY_SERVE_WINDOW.value,
+    );
+    c.insert(
+        "SYSTEM_ADDRESS".into(),
+        U256::from_big_endian(prague_constants::SYSTEM_ADDRESS.as_bytes()),
+    );
+    c.insert(
+        "SYSTEM_CALL_GAS_LIMIT".into(),
+        prague_constants::SYSTEM_CALL_GAS_LIMIT.into(),
+    );
+    c.insert(
+        "WITHDRAWAL_REQUEST_PREDEPLOY_ADDRESS".into(),
+        U256::from_big_endian(prague_constants::WITHDRAWAL_REQUEST_PREDEPLOY_ADDRESS.as_bytes()),
+    );
+    c.insert(
+        "CONSOLIDATION_REQUEST_PREDEPLOY_ADDRESS".into(),
+        U256::from_big_endian(prague_constants::CONSOLIDATION_REQUEST_PREDEPLOY_ADDRESS.as_bytes()),
+    );
+    for (name, value) in [
+        (
+            "DEPOSIT_REQUEST_TYPE",
+            prague_constants::DEPOSIT_REQUEST_TYPE,
+        ),
+        (
+            "WITHDRAWAL_REQUEST_TYPE",
+            prague_constants::WITHDRAWAL_REQUEST_TYPE,
+        ),
+        (
+            "CONSOLIDATION_REQUEST_TYPE",
+            prague_constants::CONSOLIDATION_REQUEST_TYPE,
+        ),
+    ] {
+        c.insert(name.into(), value.into());
+    }
+    c.insert(
+        "DEPOSIT_EVENT_SIGNATURE_HASH".into(),
+        U256::from_big_endian(prague_constants::DEPOSIT_EVENT_SIGNATURE_HASH.as_bytes()),
+    );
+    c.insert(
+        "DEPOSIT_EVENT_DATA_LEN".into(),
+        prague_constants::DEPOSIT_EVENT_DATA_LEN.into(),
+    );
 
     c.insert(
         global_exit_root::GLOBAL_EXIT_ROOT_MANAGER_L2_STATE_KEY
@@ -190,7 +242,7 @@ const HASH_CONSTANTS: [(&str, [u8; 32]); 2] = [
     ),
 ];
 
-const EC_CONSTANTS: [(&str, [u8; 32]); 25] = [
+const EC_CONSTANTS: [(&str, [u8; 32]); 33] = [
     (
         "U256_MAX",
         hex!("ffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff"),
@@ -294,9 +346,46 @@ const EC_CONSTANTS: [(&str, [u8; 32]); 25] = [
         "SECP_GLV_B2",
         hex!("000000000000000000000000000000003086d221a7d46bcde86c90e49284eb15"),
     ),
+    (
+        "BLS_BASE_LO",
+        hex!("64774b84f38512bf6730d2a0f6b0f6241eabfffeb153ffffb9feffffffffaaab"),
+    ),
+    (
+        "BLS_BASE_HI",
+        hex!("000000000000000000000000000000001a0111ea397fe69a4b1ba7b6434bacd7"),
+    ),
+    (
+        // The moduli used to check BLS12-381 base field multiplications, see `mul_fp381`.
+        "BLS_CRT_MODULUS_1",
+        hex!("ffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff43"),
+    ),
+    (
+        "BLS_CRT_MODULUS_2",
+        hex!("fffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffe9b"),
+    ),
+    (
+        // Corresponds to `2^256 mod BLS_CRT_MODULUS_1`.
+        "BLS_CRT_SHIFT_1",
+        hex!("00000000000000000000000000000000000000000000000000000000000000bd"),
+    ),
+    (
+        // Corresponds to `2^256 mod BLS_CRT_MODULUS_2`.
+        "BLS_CRT_SHIFT_2",
+        hex!("0000000000000000000000000000000000000000000000000000000000000165"),
+    ),
+    (
+        // Corresponds to `BLS_BASE mod BLS_CRT_MODULUS_1`.
+        "BLS_CRT_BASE_1",
+        hex!("64774b84f38512bf6730d2a0f6b0f637517639eb24c13fe92d69d18faede4566"),
+    ),
+    (
+        // Corresponds to `BLS_BASE mod BLS_CRT_MODULUS_2`.
+        "BLS_CRT_BASE_2",
+        hex!("64774b84f38512bf6730d2a0f6b0f6486229fba0e0b0952a778fe12bd887b27e"),
+    ),
 ];
 
-const GAS_CONSTANTS: [(&str, u32); 38] = [
+const GAS_CONSTANTS: [(&str, u32); 40] = [
     ("GAS_ZERO", 0),
     ("GAS_JUMPDEST", 1),
     ("GAS_BASE", 2),
@@ -335,11 +424,18 @@ const GAS_CONSTANTS: [(&str, u32); 38] = [
     ("GAS_BLOCKHASH", 20),
     ("GAS_HASH_OPCODE", 3),
     ("GAS_PER_BLOB", 131_072),
+    ("GAS_PER_EMPTY_ACCOUNT", 25_000),
+    ("GAS_PER_AUTH_BASE", 12_500),
 ];
 
-const REFUND_CONSTANTS: [(&str, u16); 2] = [("REFUND_SCLEAR", 4_800), ("MAX_REFUND_QUOTIENT", 5)];
+const REFUND_CONSTANTS: [(&str, u16); 3] = [
+    ("REFUND_SCLEAR", 4_800),
+    ("MAX_REFUND_QUOTIENT", 5),
+    // GAS_PER_EMPTY_ACCOUNT - GAS_PER_AUTH_BASE, see EIP-7702.
+    ("REFUND_AUTH_EXISTING_ACCOUNT", 12_500),
+];
 
-const PRECOMPILES: [(&str, u16); 10] = [
+const PRECOMPILES: [(&str, u16); 17] = [
     ("ECREC", 1),
     ("SHA256", 2),
     ("RIP160", 3),
@@ -350,9 +446,16 @@ const PRECOMPILES: [(&str, u16); 10] = [
     ("SNARKV", 8),
     ("BLAKE2_F", 9),
     ("KZG_PEVAL", 10),
+    ("BLS12_G1ADD", 11),
+    ("BLS12_G1MSM", 12),
+    ("BLS12_G2ADD", 13),
+    ("BLS12_G2MSM", 14),
+    ("BLS12_PAIRING", 15),
+    ("BLS12_MAP_FP_TO_G1", 16),
+    ("BLS12_MAP_FP2_TO_G2", 17),
 ];
 
-const PRECOMPILES_GAS: [(&str, u16); 14] = [
+const PRECOMPILES_GAS: [(&str, u16); 22] = [
     ("ECREC_GAS", 3_000),
     ("SHA256_STATIC_GAS", 60),
     ("SHA256_DYNAMIC_GAS", 12),
@@ -367,6 +470,14 @@ const PRECOMPILES_GAS: [(&str, u16); 14] = [
     ("SNARKV_DYNAMIC_GAS", 34_000),
     ("BLAKE2_F__GAS", 1),
     ("KZG_PEVAL_GAS", 50_000),
+    ("BLS12_G1ADD_GAS", 375),
+    ("BLS12_G1MSM_MUL_GAS", 12_000),
+    ("BLS12_G2ADD_GAS", 600),
+    ("BLS12_G2MSM_MUL_GAS", 22_500),
+    ("BLS12_PAIRING_STATIC_GAS", 37_700),
+    ("BLS12_PAIRING_DYNAMIC_GAS", 32_600),
+    ("BLS12_MAP_FP_TO_G1_GAS", 5_500),
+    ("BLS12_MAP_FP2_TO_G2_GAS", 23_800),
 ];
 
 const SNARKV_POINTERS: [(&str, u64); 2] = [("SNARKV_INP", 112), ("SNARKV_OUT", 100)];
@@ -394,6 +505,17 @@ const MAX_RLP_PREFIX_SIZE: (&str, u8) = ("MAX_RLP_PREFIX_SIZE", 9);
 pub(crate) const INITIAL_RLP_ADDR: (&str, usize) =
     ("INITIAL_RLP_ADDR", Segment::RlpRaw as usize + 1);
 
+const DELEGATION_CONSTANTS: [(&str, u32); 3] = [
+    // Prefix of the delegation designator `0xef0100 || address`, see EIP-7702.
+    (
+        "DELEGATION_DESIGNATOR_PREFIX",
+        eip7702_constants::DELEGATION_DESIGNATOR_PREFIX_U32,
+    ),
+    ("DELEGATION_DESIGNATOR_LEN", 23),
+    // Byte prepended to the RLP of an authorization tuple before signing.
+    ("SET_CODE_AUTH_MAGIC", 0x05),
+];
+
 const LINKED_LISTS_CONSTANTS: [(&str, u16); 5] = [
     ("ACCOUNTS_LINKED_LISTS_NODE_SIZE", 4),
     ("STORAGE_LINKED_LISTS_NODE_SIZE", 5),
@@ -402,6 +524,16 @@ const LINKED_LISTS_CONSTANTS: [(&str, u16); 5] = [
     ("STORAGE_COPY_PAYLOAD_PTR", 3),
 ];
 
+/// Constants related to set-code transactions.
+/// See <https://eips.ethereum.org/EIPS/eip-7702>.
+pub mod eip7702_constants {
+    pub const DELEGATION_DESIGNATOR_PREFIX: [u8; 3] = [0xef, 0x01, 0x00];
+
+    pub(crate) const DELEGATION_DESIGNATOR_PREFIX_U32: u32 = 0xef0100;
+
+    pub const DELEGATION_DESIGNATOR_LEN: usize = 23;
+}
+
 /// Cancun-related constants
 /// See <https://eips.ethereum.org/EIPS/eip-4788> and
 /// <https://eips.ethereum.org/EIPS/eip-4844>.
@@ -472,6 +604,115 @@ pub mod cancun_constants {
     }
 }
 
+/// Prague-related constants
+/// See <https://eips.ethereum.org/EIPS/eip-2935>,
+/// <https://eips.ethereum.org/EIPS/eip-6110>,
+/// <https://eips.ethereum.org/EIPS/eip-7002>,
+/// <https://eips.ethereum.org/EIPS/eip-7251> and
+/// <https://eips.ethereum.org/EIPS/eip-7685>.
+pub mod prague_constants {
+    use super::*;
+
+    // Block hash history constants
+    ///////////////////////////////
+
+    /// Number of previous block hashes stored in the history storage contract,
+    /// as per <https://eips.ethereum.org/EIPS/eip-2935>.
+    pub const HISTORY_SERVE_WINDOW: Named<U256> = Named {
+        name: "HISTORY_SERVE_WINDOW",
+        value: U256([8191, 0, 0, 0]),
+    };
+
+    pub const HISTORY_STORAGE_ADDRESS: Address =
+        H160(hex!("0000F90827F1C53a10cb7A02335B175320002935"));
+
+    pub const HISTORY_STORAGE_ADDRESS_HASHED: H256 = H256(hex!(
+        "6c9d57be05dd69371c4dd2e871bce6e9f4124236825bb612ee18a45e5675be51"
+    ));
+
+    pub const HISTORY_STORAGE_STATE_KEY: (&str, [u8; 32]) = (
+        "HISTORY_STORAGE_STATE_KEY",
+        *HISTORY_STORAGE_ADDRESS_HASHED.as_fixed_bytes(),
+    );
+    pub const HISTORY_STORAGE_CODE: [u8; 83] = hex!("3373fffffffffffffffffffffffffffffffffffffffe14604657602036036042575f35600143038111604257611fff81430311604257611fff9006545f5260205ff35b5f5ffd5b5f35611fff60014303065500");
+    pub const HISTORY_STORAGE_CODE_HASH: [u8; 32] =
+        hex!("6e49e66782037c0555897870e29fa5e552daf4719552131a0abce779daec0a5d");
+
+    pub const HISTORY_STORAGE_ACCOUNT: AccountRlp = AccountRlp {
+        nonce: U256::zero(),
+        balance: U256::zero(),
+        // Storage root for this account at genesis.
+        storage_root: H256(hex!(
+            "56e81f171bcc55a6ff8345e692c0f86e5b48e01b996cadc001622fb5e363b421"
+        )),
+        code_hash: H256(HISTORY_STORAGE_CODE_HASH),
+    };
+
+    #[test]
+    fn hashed() {
+        assert_eq!(
+            keccak_hash::keccak(HISTORY_STORAGE_ADDRESS),
+            HISTORY_STORAGE_ADDRESS_HASHED
+        );
+        assert_eq!(
+            keccak_hash::keccak(HISTORY_STORAGE_CODE),
+            H256(HISTORY_STORAGE_CODE_HASH)
+        );
+    }
+
+    // Execution layer requests constants
+    /////////////////////////////////////
+
+    /// Caller of the system calls performed at the end of a block.
+    pub const SYSTEM_ADDRESS: Address = H160(hex!("fffffffffffffffffffffffffffffffffffffffe"));
+
+    /// Gas limit of the system calls. It doesn't count against the block gas
+    /// limit.
+    pub const SYSTEM_CALL_GAS_LIMIT: u64 = 30_000_000;
+
+    /// Topic of the `DepositEvent` logs emitted by the deposit contract, whose
+    /// address is part of the block metadata.
+    pub const DEPOSIT_EVENT_SIGNATURE_HASH: H256 = H256(hex!(
+        "649bbc62d0e31342afea4e5cd82d4049e7e1ee912fc0889aa790803be39038c5"
+    ));
+
+    pub const WITHDRAWAL_REQUEST_PREDEPLOY_ADDRESS: Address =
+        H160(hex!("00000961Ef480Eb55e80D19ad83579A64c007002"));
+
+    pub const CONSOLIDATION_REQUEST_PREDEPLOY_ADDRESS: Address =
+        H160(hex!("0000BBdDc7CE488642fb579F8B00f3a590007251"));
+
+    // Storage layout of the EIP-7002 and EIP-7251 request queue predeploys.
+    pub const EXCESS_REQUESTS_SLOT: u64 = 0;
+    pub const REQUESTS_COUNT_SLOT: u64 = 1;
+    pub const REQUESTS_QUEUE_HEAD_SLOT: u64 = 2;
+    pub const REQUESTS_QUEUE_TAIL_SLOT: u64 = 3;
+    pub const REQUESTS_QUEUE_OFFSET: u64 = 4;
+
+    /// Number of storage slots taken by a queued withdrawal request.
+    pub const WITHDRAWAL_REQUEST_SLOTS: u64 = 3;
+    pub const MAX_WITHDRAWAL_REQUESTS_PER_BLOCK: u64 = 16;
+    pub const TARGET_WITHDRAWAL_REQUESTS_PER_BLOCK: u64 = 2;
+
+    /// Number of storage slots taken by a queued consolidation request.
+    pub const CONSOLIDATION_REQUEST_SLOTS: u64 = 4;
+    pub const MAX_CONSOLIDATION_REQUESTS_PER_BLOCK: u64 = 2;
+    pub const TARGET_CONSOLIDATION_REQUESTS_PER_BLOCK: u64 = 1;
+
+    pub const DEPOSIT_REQUEST_TYPE: u8 = 0x00;
+    pub const WITHDRAWAL_REQUEST_TYPE: u8 = 0x01;
+    pub const CONSOLIDATION_REQUEST_TYPE: u8 = 0x02;
+
+    /// Length in bytes of a deposit request, i.e. `pubkey (48) ||
+    /// withdrawal_credentials (32) || amount (8) || signature (96) || index
+    /// (8)`.
+    pub const DEPOSIT_REQUEST_LEN: usize = 192;
+
+    /// Length in bytes of the data of a `DepositEvent` log, i.e. the ABI
+    /// encoding of its five `bytes` fields.
+    pub const DEPOSIT_EVENT_DATA_LEN: usize = 576;
+}
+
 pub mod global_exit_root {
     use super::*;
 
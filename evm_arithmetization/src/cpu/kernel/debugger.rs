@@ -0,0 +1,288 @@
+//! A debugger for the kernel, stepping through the execution of the
+//! interpreter and stopping at breakpoints on kernel labels or user code
+//! program counters.
+
+use std::collections::BTreeSet;
+use std::fmt;
+use std::ops::Range;
+
+use anyhow::{anyhow, ensure, Result};
+use ethereum_types::U256;
+use plonky2::hash::hash_types::RichField;
+
+use crate::cpu::kernel::aggregator::KERNEL;
+use crate::cpu::kernel::constants::global_metadata::GlobalMetadata;
+use crate::cpu::kernel::interpreter::{set_registers, Interpreter};
+use crate::generation::state::State;
+use crate::generation::GenerationInputs;
+use crate::memory::segments::Segment;
+use crate::witness::memory::MemoryAddress;
+use crate::witness::state::RegistersState;
+use crate::witness::transition::decode;
+use crate::witness::util::stack_peek;
+
+/// A location at which the execution stops.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Breakpoint {
+    /// An offset in the kernel code.
+    Kernel(usize),
+    /// A program counter in user code, in any context.
+    User(usize),
+}
+
+impl Breakpoint {
+    /// Returns a breakpoint on the given label of the kernel.
+    pub fn kernel_label(label: &str) -> Result<Self> {
+        KERNEL
+            .global_labels
+            .get(label)
+            .map(|&offset| Self::Kernel(offset))
+            .ok_or_else(|| anyhow!("unknown kernel label {label}"))
+    }
+}
+
+impl fmt::Display for Breakpoint {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Kernel(offset) => write!(f, "kernel {}", KERNEL.offset_name(*offset)),
+            Self::User(pc) => write!(f, "user pc {pc}"),
+        }
+    }
+}
+
+/// Returns the names of the memory segments, as used in kernel assembly.
+pub fn segment_names() -> impl Iterator<Item = &'static str> {
+    Segment::all().into_iter().map(|segment| segment.var_name())
+}
+
+/// Why the debugger stopped executing instructions.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum StopReason {
+    /// The requested number of instructions was executed.
+    Stepped,
+    /// A breakpoint was reached.
+    Breakpoint(Breakpoint),
+    /// The kernel reached its `halt` label.
+    Halted,
+}
+
+/// Runs the kernel on some [`GenerationInputs`] one instruction at a time.
+pub struct KernelDebugger<F: RichField> {
+    interpreter: Interpreter<F>,
+    breakpoints: BTreeSet<Breakpoint>,
+}
+
+impl<F: RichField> KernelDebugger<F> {
+    /// Returns a debugger about to execute the first instruction of the
+    /// kernel on `inputs`.
+    pub fn new(inputs: &GenerationInputs<F>) -> Self {
+        let mut interpreter = Interpreter::new_with_generation_inputs(
+            KERNEL.global_labels["init"],
+            vec![],
+            inputs,
+            None,
+        );
+        set_registers(RegistersState::new(), &mut interpreter);
+
+        Self {
+            interpreter,
+            breakpoints: BTreeSet::new(),
+        }
+    }
+
+    /// Adds a breakpoint, returning whether it wasn't already set.
+    pub fn add_breakpoint(&mut self, breakpoint: Breakpoint) -> bool {
+        self.breakpoints.insert(breakpoint)
+    }
+
+    /// Removes a breakpoint, returning whether it was set.
+    pub fn remove_breakpoint(&mut self, breakpoint: Breakpoint) -> bool {
+        self.breakpoints.remove(&breakpoint)
+    }
+
+    pub fn breakpoints(&self) -> impl Iterator<Item = &Breakpoint> {
+        self.breakpoints.iter()
+    }
+
+    /// Executes up to `n` instructions, stopping early at breakpoints.
+    ///
+    /// On error, the state in which the instruction failed can still be
+    /// inspected.
+    pub fn step(&mut self, n: usize) -> Result<StopReason> {
+        for i in 0..n {
+            if self.is_halted() {
+                return Ok(StopReason::Halted);
+            }
+            if i > 0 {
+                if let Some(breakpoint) = self.current_breakpoint() {
+                    return Ok(StopReason::Breakpoint(breakpoint));
+                }
+            }
+            self.interpreter.transition()?;
+        }
+
+        Ok(if self.is_halted() {
+            StopReason::Halted
+        } else {
+            StopReason::Stepped
+        })
+    }
+
+    /// Executes instructions until a breakpoint is reached or the kernel
+    /// halts.
+    pub fn resume(&mut self) -> Result<StopReason> {
+        self.step(usize::MAX).map(|reason| match reason {
+            StopReason::Stepped => unreachable!("the kernel can't run for usize::MAX cycles"),
+            reason => reason,
+        })
+    }
+
+    /// Returns whether the kernel reached its `halt` label.
+    pub fn is_halted(&self) -> bool {
+        self.interpreter.at_halt()
+    }
+
+    pub fn clock(&self) -> usize {
+        self.interpreter.get_clock()
+    }
+
+    pub fn program_counter(&self) -> usize {
+        self.registers().program_counter
+    }
+
+    pub fn is_kernel(&self) -> bool {
+        self.registers().is_kernel
+    }
+
+    pub fn context(&self) -> usize {
+        self.registers().context
+    }
+
+    pub fn gas_used(&self) -> u64 {
+        self.registers().gas_used
+    }
+
+    /// Returns a description of the current location, with the closest
+    /// kernel label in kernel mode.
+    pub fn location(&self) -> String {
+        let pc = self.program_counter();
+        match self.is_kernel() {
+            true => format!("kernel {}", KERNEL.offset_name(pc)),
+            false => format!("user pc {pc}, context {}", self.context()),
+        }
+    }
+
+    /// Returns the instruction about to be executed.
+    pub fn next_instruction(&self) -> String {
+        let opcode = self.interpreter.code().get(self.program_counter()).byte(0);
+        match decode(self.registers(), opcode) {
+            Ok(op) => format!("{op:?}"),
+            Err(e) => format!("{opcode:#04x} ({e:?})"),
+        }
+    }
+
+    /// Returns the stack of the current context, from its top.
+    pub fn stack(&self) -> Vec<U256> {
+        let state = &self.interpreter.generation_state;
+        (0..state.registers.stack_len)
+            .map(|i| stack_peek(state, i).expect("the index is below the stack length"))
+            .collect()
+    }
+
+    /// Reads the values in `range` of a memory segment of `context`, given by
+    /// its name, with or without its `SEGMENT_` prefix.
+    pub fn memory(&self, context: usize, segment: &str, range: Range<usize>) -> Result<Vec<U256>> {
+        let name = segment.to_uppercase();
+        let segment = Segment::all()
+            .into_iter()
+            .find(|s| {
+                s.var_name() == name || s.var_name().strip_prefix("SEGMENT_") == Some(name.as_str())
+            })
+            .ok_or_else(|| anyhow!("unknown segment {segment}"))?;
+        let memory = &self.interpreter.generation_state.memory;
+        ensure!(context < memory.contexts.len(), "unknown context {context}");
+
+        Ok(range
+            .map(|virt| memory.get_with_init(MemoryAddress::new(context, segment, virt)))
+            .collect())
+    }
+
+    /// Returns the global metadata, by name.
+    pub fn global_metadata(&self) -> Vec<(&'static str, U256)> {
+        let memory = &self.interpreter.generation_state.memory;
+        GlobalMetadata::all()
+            .into_iter()
+            .map(|field| {
+                let address = MemoryAddress::new(0, Segment::GlobalMetadata, field.unscale());
+                (field.var_name(), memory.get_with_init(address))
+            })
+            .collect()
+    }
+
+    fn registers(&self) -> RegistersState {
+        self.interpreter.generation_state.registers
+    }
+
+    fn current_breakpoint(&self) -> Option<Breakpoint> {
+        let registers = self.registers();
+        let breakpoint = match registers.is_kernel {
+            true => Breakpoint::Kernel(registers.program_counter),
+            false => Breakpoint::User(registers.program_counter),
+        };
+        self.breakpoints.contains(&breakpoint).then_some(breakpoint)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use plonky2::field::goldilocks_field::GoldilocksField as F;
+
+    use super::*;
+
+    #[test]
+    fn stops_at_kernel_label_breakpoints() -> Result<()> {
+        let mut debugger = KernelDebugger::<F>::new(&GenerationInputs::default());
+        assert_eq!(debugger.program_counter(), KERNEL.global_labels["init"]);
+
+        let breakpoint = Breakpoint::kernel_label("main")?;
+        assert!(debugger.add_breakpoint(breakpoint));
+        assert!(!debugger.add_breakpoint(breakpoint));
+        assert_eq!(debugger.resume()?, StopReason::Breakpoint(breakpoint));
+        assert_eq!(debugger.program_counter(), KERNEL.global_labels["main"]);
+        assert!(debugger.is_kernel());
+
+        let clock = debugger.clock();
+        assert_eq!(debugger.step(1)?, StopReason::Stepped);
+        assert_eq!(debugger.clock(), clock + 1);
+
+        assert!(debugger.remove_breakpoint(breakpoint));
+        assert!(Breakpoint::kernel_label("not_a_label").is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn inspects_stack_and_memory() -> Result<()> {
+        let mut debugger = KernelDebugger::<F>::new(&GenerationInputs::default());
+        assert!(debugger.stack().is_empty());
+
+        // `init` starts by pushing the address of the registers.
+        debugger.step(1)?;
+        assert_eq!(
+            debugger.stack(),
+            vec![(Segment::RegistersStates as usize).into()]
+        );
+
+        assert_eq!(
+            debugger.memory(0, "registers_states", 0..1)?,
+            vec![KERNEL.global_labels["main"].into()]
+        );
+        assert!(debugger.memory(0, "not_a_segment", 0..1).is_err());
+        assert!(debugger
+            .global_metadata()
+            .iter()
+            .any(|(name, _)| *name == "GLOBAL_METADATA_TRIE_DATA_SIZE"));
+
+        Ok(())
+    }
+}
@@ -23,8 +23,8 @@ use crate::generation::linked_list::LinkedListsPtrs;
 use crate::generation::mpt::{load_linked_lists_and_txn_and_receipt_mpts, TrieRootPtrs};
 use crate::generation::rlp::all_rlp_prover_inputs_reversed;
 use crate::generation::state::{
-    all_ger_prover_inputs, all_withdrawals_prover_inputs_reversed, GenerationState,
-    GenerationStateCheckpoint,
+    all_ger_prover_inputs, all_requests_prover_inputs,
+    all_withdrawals_prover_inputs_reversed, GenerationState, GenerationStateCheckpoint,
 };
 use crate::generation::{state::State, GenerationInputs};
 use crate::keccak_sponge::columns::KECCAK_WIDTH_BYTES;
@@ -114,6 +114,7 @@ pub(crate) struct ExtraSegmentData {
     pub(crate) bignum_modmul_result_limbs: Vec<U256>,
     pub(crate) rlp_prover_inputs: Vec<U256>,
     pub(crate) withdrawal_prover_inputs: Vec<U256>,
+    pub(crate) requests_prover_inputs: Vec<U256>,
     pub(crate) ger_prover_inputs: Vec<U256>,
     pub(crate) trie_root_ptrs: TrieRootPtrs,
     pub(crate) jumpdest_table: Option<HashMap<usize, Vec<usize>>>,
@@ -126,6 +127,16 @@ pub(crate) fn set_registers_and_run<F: RichField>(
     registers: RegistersState,
     interpreter: &mut Interpreter<F>,
 ) -> anyhow::Result<(RegistersState, Option<MemoryState>)> {
+    set_registers(registers, interpreter);
+    interpreter.run()
+}
+
+/// Sets the registers of the interpreter to `registers`, and prepares it to
+/// start executing the kernel from its `init` label.
+pub(crate) fn set_registers<F: RichField>(
+    registers: RegistersState,
+    interpreter: &mut Interpreter<F>,
+) {
     interpreter.generation_state.registers = registers;
     interpreter.generation_state.registers.program_counter = KERNEL.global_labels["init"];
     interpreter.generation_state.registers.is_kernel = true;
@@ -148,8 +159,6 @@ pub(crate) fn set_registers_and_run<F: RichField>(
             *reg_content,
         )
     });
-
-    interpreter.run()
 }
 
 impl<F: RichField> Interpreter<F> {
@@ -263,12 +272,14 @@ impl<F: RichField> Interpreter<F> {
         );
         self.insert_preinitialized_segment(Segment::StorageLinkedList, preinit_storage_ll_segment);
 
-        // Update the RLP and withdrawal prover inputs.
+        // Update the RLP, withdrawal and requests prover inputs.
         let rlp_prover_inputs = all_rlp_prover_inputs_reversed(&inputs.signed_txns);
         let withdrawal_prover_inputs = all_withdrawals_prover_inputs_reversed(&inputs.withdrawals);
+        let requests_prover_inputs = all_requests_prover_inputs(inputs.process_requests);
         let ger_prover_inputs = all_ger_prover_inputs(inputs.ger_data);
         self.generation_state.rlp_prover_inputs = rlp_prover_inputs;
         self.generation_state.withdrawal_prover_inputs = withdrawal_prover_inputs;
+        self.generation_state.requests_prover_inputs = requests_prover_inputs;
         self.generation_state.ger_prover_inputs = ger_prover_inputs;
 
         // Set `GlobalMetadata` values.
@@ -308,6 +319,13 @@ impl<F: RichField> Interpreter<F> {
                 GlobalMetadata::ParentBeaconBlockRoot,
                 h2u(metadata.parent_beacon_block_root),
             ),
+            #[cfg(feature = "eth_mainnet")]
+            (GlobalMetadata::RequestsHash, h2u(metadata.requests_hash)),
+            #[cfg(feature = "eth_mainnet")]
+            (
+                GlobalMetadata::DepositContractAddress,
+                U256::from_big_endian(&metadata.deposit_contract_address.0),
+            ),
             (GlobalMetadata::BlockGasUsedBefore, inputs.gas_used_before),
             (GlobalMetadata::BlockGasUsedAfter, inputs.gas_used_after),
             (GlobalMetadata::TxnNumberBefore, inputs.txn_number_before),
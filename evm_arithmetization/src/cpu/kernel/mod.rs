@@ -3,6 +3,7 @@ pub mod assembler;
 mod ast;
 pub(crate) mod constants;
 mod cost_estimator;
+pub mod debugger;
 pub(crate) mod keccak_util;
 pub mod opcodes;
 mod optimizer;
@@ -15,15 +16,19 @@ pub(crate) mod interpreter;
 use std::collections::HashSet;
 
 pub use constants::cancun_constants;
+pub use constants::eip7702_constants;
+pub use constants::evm_constants;
 pub use constants::global_exit_root;
+pub use constants::prague_constants;
 
 #[cfg(test)]
 mod tests;
 
 use assembler::assemble;
-use parser::parse;
+use parser::{parse, parse_stack_annotations, parse_with_stack_comments};
+use stack::stack_effects::StackEffectError;
 
-use crate::cpu::kernel::constants::evm_constants;
+use crate::cpu::kernel::aggregator::active_features;
 
 /// Assemble files, outputting bytes.
 /// This is for debugging the kernel only.
@@ -32,3 +37,17 @@ pub fn assemble_to_bytes(files: &[String]) -> Vec<u8> {
     let kernel = assemble(parsed_files, evm_constants(), true);
     kernel.code
 }
+
+/// Checks the stack depth through kernel assembly files against the `Pre
+/// stack` and `Post stack` comments of their labels and macros, and against
+/// the `// stack:` comments within their code.
+/// This is for debugging the kernel only.
+pub fn check_stack_effects(files: &[String]) -> Vec<StackEffectError> {
+    let active_features = active_features();
+    let parsed_files: Vec<_> = files
+        .iter()
+        .map(|f| parse_with_stack_comments(f, &active_features))
+        .collect();
+    let annotations: Vec<_> = files.iter().map(|f| parse_stack_annotations(f)).collect();
+    assembler::check_stack_effects(parsed_files, &annotations, &evm_constants())
+}
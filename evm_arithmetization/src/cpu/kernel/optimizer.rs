@@ -1,4 +1,5 @@
 use std::cmp::max;
+use std::collections::{HashMap, HashSet};
 
 use ethereum_types::U256;
 use Item::{Push, StandardOp};
@@ -8,6 +9,8 @@ use crate::cpu::kernel::ast::Item::{GlobalLabelDeclaration, LocalLabelDeclaratio
 use crate::cpu::kernel::ast::PushTarget::Label;
 use crate::cpu::kernel::ast::{Item, PushTarget};
 use crate::cpu::kernel::cost_estimator::is_code_improved;
+use crate::cpu::kernel::stack::stack_effects::opcode_stack_effect;
+use crate::cpu::kernel::stack::stack_manipulation::{shortest_path, StackItem, StackOp};
 use crate::cpu::kernel::utils::{replace_windows, u256_from_bool};
 
 pub(crate) fn optimize_asm(code: &mut Vec<Item>) {
@@ -38,6 +41,16 @@ fn optimize_asm_once(code: &mut Vec<Item>) {
     remove_swaps_commutative(code);
     remove_ignored_values(code);
     de_morgan(code);
+    // These passes save no cycles on the kernel routines they were measured on,
+    // so they stay behind a feature until they are shown to pay off.
+    if cfg!(feature = "experimental_optimizer") {
+        jump_threading(code);
+        sink_stack_operations(code);
+    }
+    schedule_stack_operations(code);
+    if cfg!(feature = "experimental_optimizer") {
+        common_subexpression_elimination(code);
+    }
 }
 
 /// Constant propagation.
@@ -208,6 +221,405 @@ fn de_morgan(code: &mut Vec<Item>) {
     });
 }
 
+/// Jump threading: `[PUSH a, JUMP], ..., [a:, PUSH b, JUMP] -> [PUSH b, JUMP],
+/// ...`, and likewise for `JUMPI`.
+fn jump_threading(code: &mut [Item]) {
+    // The labels whose code is only a jump to another label.
+    let mut forwards = HashMap::new();
+    for (i, item) in code.iter().enumerate() {
+        if let GlobalLabelDeclaration(label) | LocalLabelDeclaration(label) = item {
+            let mut rest = code[i + 1..].iter().skip_while(|item| {
+                matches!(item, GlobalLabelDeclaration(_) | LocalLabelDeclaration(_))
+            });
+            if let (Some(Push(Label(target))), Some(StandardOp(jump))) = (rest.next(), rest.next())
+                && jump == "JUMP"
+            {
+                forwards.insert(label.clone(), target.clone());
+            }
+        }
+    }
+
+    for i in 1..code.len() {
+        if let (Push(Label(label)), StandardOp(jump)) = (&code[i - 1], &code[i])
+            && (jump == "JUMP" || jump == "JUMPI")
+            && let Some(target) = final_jump_target(label, &forwards)
+        {
+            code[i - 1] = Push(Label(target));
+        }
+    }
+}
+
+/// Follows jumps from `label`, returning [`None`] if there are none, or if
+/// they loop.
+fn final_jump_target(label: &str, forwards: &HashMap<String, String>) -> Option<String> {
+    let mut visited = HashSet::from([label]);
+    let mut target = forwards.get(label)?;
+    while let Some(next) = forwards.get(target) {
+        if !visited.insert(target) {
+            return None;
+        }
+        target = next;
+    }
+    (target != label).then(|| target.clone())
+}
+
+/// Moves the stack operations before a `PUSH l, JUMP` to the local label `l`
+/// when that jump is the only way to reach `l`, so that they are scheduled
+/// with the stack operations there, across the two basic blocks, e.g.
+/// `[SWAP1, PUSH l, JUMP], ..., [JUMP, l:, SWAP1, POP] -> [PUSH l, JUMP], ...,
+/// [JUMP, l:, SWAP1, SWAP1, POP]`, which is then scheduled to `[.., l:, POP]`.
+fn sink_stack_operations(code: &mut Vec<Item>) {
+    let mut references = HashMap::<String, usize>::new();
+    for item in code.iter() {
+        match item {
+            Push(Label(label)) => *references.entry(label.clone()).or_default() += 1,
+            Item::Jumptable(labels) => {
+                for label in labels {
+                    *references.entry(label.clone()).or_default() += 1;
+                }
+            }
+            _ => {}
+        }
+    }
+
+    let mut i = 0;
+    while i + 1 < code.len() {
+        let (Push(Label(label)), StandardOp(jump)) = (&code[i], &code[i + 1]) else {
+            i += 1;
+            continue;
+        };
+        let start = i - code[..i]
+            .iter()
+            .rev()
+            .take_while(|item| stack_op(item).is_some())
+            .count();
+        // `l` must not be fallen into, nor be jumped to from elsewhere.
+        let declaration = code
+            .iter()
+            .position(|item| matches!(item, LocalLabelDeclaration(l) if l == label));
+        let Some(declaration) = declaration.filter(|&d| {
+            jump == "JUMP"
+                && references[label] == 1
+                && start < i
+                && d > 0
+                && is_terminator(&code[d - 1])
+        }) else {
+            i += 1;
+            continue;
+        };
+
+        let len = code[declaration + 1..]
+            .iter()
+            .take_while(|item| stack_op(item).is_some())
+            .count();
+        let combined = [
+            &code[start..i],
+            &code[declaration + 1..declaration + 1 + len],
+        ]
+        .concat();
+        if schedule_run(&combined).is_none() {
+            i += 1;
+            continue;
+        }
+
+        let moved = code.drain(start..i).collect::<Vec<_>>();
+        let declaration = match declaration > start {
+            true => declaration - moved.len(),
+            false => declaration,
+        };
+        code.splice(declaration + 1..declaration + 1, moved);
+        i = start + 1;
+    }
+}
+
+/// Whether the code following `item` can only be reached through a label.
+fn is_terminator(item: &Item) -> bool {
+    matches!(item, StandardOp(op) if matches!(op.as_str(), "JUMP" | "PANIC" | "EXIT_KERNEL"))
+}
+
+/// Replaces each straight-line run of `PUSH`, `DUP`, `SWAP` and `POP`
+/// operations with the cheapest sequence having the same effect on the stack.
+fn schedule_stack_operations(code: &mut Vec<Item>) {
+    let mut start = 0;
+    while start < code.len() {
+        let len = code[start..]
+            .iter()
+            .take_while(|item| stack_op(item).is_some())
+            .count();
+        let end = start + len;
+        match (len >= 2)
+            .then(|| schedule_run(&code[start..end]))
+            .flatten()
+        {
+            Some(replacement) => {
+                start += replacement.len();
+                code.splice(start - replacement.len()..end, replacement);
+            }
+            None => start = end + 1,
+        }
+    }
+}
+
+/// The largest number of stack items read by a run of stack operations that
+/// gets rescheduled. Keeps the search for the cheapest sequence fast.
+const MAX_SCHEDULED_DEPTH: usize = 5;
+
+/// The largest stack a run of stack operations may leave, relative to the
+/// items it reads, to be rescheduled.
+const MAX_SCHEDULED_STACK: usize = 8;
+
+fn schedule_run(run: &[Item]) -> Option<Vec<Item>> {
+    let ops = run
+        .iter()
+        .map(|item| stack_op(item).unwrap())
+        .collect::<Vec<_>>();
+
+    // Run the operations on the items they read, naming those as they are read.
+    let mut dst = vec![];
+    let mut depth = 0;
+    for op in &ops {
+        let reads = match op {
+            StackOp::Push(_) => 0,
+            StackOp::Pop => 1,
+            StackOp::Dup(n) => *n as usize,
+            StackOp::Swap(n) => *n as usize + 1,
+        };
+        while dst.len() < reads {
+            dst.insert(0, StackItem::NamedItem(format!("@{depth}")));
+            depth += 1;
+        }
+        dst = op.apply_to(dst)?;
+    }
+    if depth > MAX_SCHEDULED_DEPTH || dst.len() > MAX_SCHEDULED_STACK {
+        return None;
+    }
+    let src = (0..depth)
+        .rev()
+        .map(|i| StackItem::NamedItem(format!("@{i}")))
+        .collect();
+
+    let push_targets = ops
+        .iter()
+        .filter_map(|op| match op {
+            StackOp::Push(target) => Some(target.clone()),
+            _ => None,
+        })
+        .collect::<HashSet<_>>();
+    let path = shortest_path(src, dst, push_targets.into_iter().collect());
+    let cost = |ops: &[StackOp]| ops.iter().map(StackOp::cost).sum::<u32>();
+    let replacement: Vec<_> = path.iter().cloned().map(StackOp::into_item).collect();
+    // Never make the code larger, so that this doesn't undo other passes.
+    (cost(&path) < cost(&ops) && !is_code_improved(&replacement, run)).then_some(replacement)
+}
+
+fn stack_op(item: &Item) -> Option<StackOp> {
+    match item {
+        Push(target @ (Literal(_) | Label(_))) => Some(StackOp::Push(target.clone())),
+        StandardOp(op) if op == "POP" => Some(StackOp::Pop),
+        StandardOp(op) => {
+            if let Some(n) = op.strip_prefix("DUP") {
+                n.parse().ok().map(StackOp::Dup)
+            } else if let Some(n) = op.strip_prefix("SWAP") {
+                n.parse().ok().map(StackOp::Swap)
+            } else {
+                None
+            }
+        }
+        _ => None,
+    }
+}
+
+/// Replaces code recomputing a value which is still on the stack with a `DUP`,
+/// e.g. `[DUP1, PUSH 5, ADD, DUP2, PUSH 5, ADD] -> [DUP1, PUSH 5, ADD, DUP1]`.
+/// Values are numbered within basic blocks, so this mostly catches repeated
+/// macro expansions.
+fn common_subexpression_elimination(code: &mut Vec<Item>) {
+    let mut numbering = ValueNumbering::default();
+    let mut stack = vec![];
+    for start in 0..code.len() {
+        // A rewrite doesn't change the values before it, so the scan resumes
+        // from the `DUP`.
+        if let Some((end, dup)) = find_common_subexpression(code, start, &mut numbering, &stack) {
+            code.splice(start..end, [StandardOp(format!("DUP{dup}"))]);
+        }
+        if start >= code.len() {
+            break;
+        }
+        numbering.apply(&code[start], &mut stack);
+    }
+}
+
+/// The longest code which could be replaced with a `DUP`.
+const MAX_SUBEXPRESSION_LEN: usize = 16;
+
+/// Returns the end of the longest code from `start` recomputing a value on
+/// `stack`, and the `DUP` to replace it with.
+fn find_common_subexpression(
+    code: &[Item],
+    start: usize,
+    numbering: &mut ValueNumbering,
+    stack: &[usize],
+) -> Option<(usize, usize)> {
+    let mut window_stack = stack.to_vec();
+    let mut best = None;
+    for (end, item) in code
+        .iter()
+        .enumerate()
+        .skip(start)
+        .take(MAX_SUBEXPRESSION_LEN)
+    {
+        // The code must not consume the items below it.
+        if !numbering.apply_pure(item, &mut window_stack, stack.len()) {
+            break;
+        }
+        if window_stack.len() != stack.len() + 1 {
+            continue;
+        }
+        let value = window_stack.last().unwrap();
+        if let Some(i) = stack.iter().rev().take(16).position(|v| v == value)
+            && is_code_improved(&code[start..=end], &[StandardOp(format!("DUP{}", i + 1))])
+        {
+            best = Some((end + 1, i + 1));
+        }
+    }
+    best
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+enum Expression {
+    Push(PushTarget),
+    Op(String, Vec<usize>),
+}
+
+/// Numbers the values on the stack, so that equal numbers are equal values.
+#[derive(Default)]
+struct ValueNumbering {
+    expressions: HashMap<Expression, usize>,
+    next: usize,
+}
+
+impl ValueNumbering {
+    fn fresh(&mut self) -> usize {
+        self.next += 1;
+        self.next - 1
+    }
+
+    fn number(&mut self, expression: Expression) -> usize {
+        if let Some(&value) = self.expressions.get(&expression) {
+            return value;
+        }
+        let value = self.fresh();
+        self.expressions.insert(expression, value);
+        value
+    }
+
+    /// Makes the stack at least `len` items long, with unknown values at the
+    /// bottom.
+    fn reserve(&mut self, stack: &mut Vec<usize>, len: usize) {
+        while stack.len() < len {
+            let value = self.fresh();
+            stack.insert(0, value);
+        }
+    }
+
+    /// Applies a pure operation, returning `false` if `item` isn't one, or if
+    /// it would consume an item below `floor`.
+    fn apply_pure(&mut self, item: &Item, stack: &mut Vec<usize>, floor: usize) -> bool {
+        let available = stack.len() - floor;
+        match item {
+            Push(target @ (Literal(_) | Label(_))) => {
+                stack.push(self.number(Expression::Push(target.clone())))
+            }
+            StandardOp(op) => match stack_op(item) {
+                Some(StackOp::Pop) if available >= 1 => {
+                    stack.pop();
+                }
+                Some(StackOp::Dup(n)) if stack.len() >= n as usize => {
+                    stack.push(stack[stack.len() - n as usize])
+                }
+                Some(StackOp::Swap(n)) if available > n as usize => {
+                    let top = stack.len() - 1;
+                    stack.swap(top, top - n as usize);
+                }
+                Some(_) => return false,
+                None if is_pure(op) => {
+                    let Some((pops, 1)) = opcode_stack_effect(op) else {
+                        return false;
+                    };
+                    if available < pops as usize {
+                        return false;
+                    }
+                    let args = stack.split_off(stack.len() - pops as usize);
+                    stack.push(self.number(Expression::Op(op.clone(), args)));
+                }
+                None => return false,
+            },
+            _ => return false,
+        }
+        true
+    }
+
+    fn apply(&mut self, item: &Item, stack: &mut Vec<usize>) {
+        if let StandardOp(op) = item
+            && let Some((pops, pushes)) = opcode_stack_effect(op)
+            && !matches!(op.as_str(), "JUMP" | "PANIC" | "EXIT_KERNEL")
+        {
+            let needed = match stack_op(item) {
+                Some(StackOp::Dup(n)) => n as usize,
+                _ => pops as usize,
+            };
+            self.reserve(stack, needed);
+            if !self.apply_pure(item, stack, 0) {
+                // Impure operations produce unknown values.
+                stack.truncate(stack.len() - pops as usize);
+                for _ in 0..pushes {
+                    let value = self.fresh();
+                    stack.push(value);
+                }
+            }
+        } else if !self.apply_pure(item, stack, 0) {
+            // Labels may be jumped to with any stack, and the rest is unknown.
+            stack.clear();
+        }
+    }
+}
+
+/// Whether an operation only computes a value from its arguments.
+fn is_pure(op: &str) -> bool {
+    matches!(
+        op,
+        "ADD"
+            | "MUL"
+            | "SUB"
+            | "DIV"
+            | "SDIV"
+            | "MOD"
+            | "SMOD"
+            | "ADDMOD"
+            | "MULMOD"
+            | "SUBMOD"
+            | "EXP"
+            | "SIGNEXTEND"
+            | "ADDFP254"
+            | "MULFP254"
+            | "SUBFP254"
+            | "LT"
+            | "GT"
+            | "SLT"
+            | "SGT"
+            | "EQ"
+            | "ISZERO"
+            | "AND"
+            | "OR"
+            | "XOR"
+            | "NOT"
+            | "BYTE"
+            | "SHL"
+            | "SHR"
+            | "SAR"
+    )
+}
+
 /// Like `replace_windows`, but specifically for code, and only makes
 /// replacements if our cost estimator thinks that the new code is more
 /// efficient.
@@ -376,4 +788,172 @@ mod tests {
         de_morgan(&mut before);
         assert_eq!(before, after);
     }
+
+    #[test]
+    fn test_jump_threading() {
+        let mut code = vec![
+            Push(Label("a".into())),
+            StandardOp("JUMPI".into()),
+            LocalLabelDeclaration("a".into()),
+            Push(Label("b".into())),
+            StandardOp("JUMP".into()),
+            LocalLabelDeclaration("b".into()),
+            Push(Label("c".into())),
+            StandardOp("JUMP".into()),
+            LocalLabelDeclaration("c".into()),
+            StandardOp("STOP".into()),
+        ];
+        jump_threading(&mut code);
+        assert_eq!(code[0], Push(Label("c".into())));
+        assert_eq!(code[3], Push(Label("c".into())));
+        assert_eq!(code[6], Push(Label("c".into())));
+    }
+
+    #[test]
+    fn test_jump_threading_loop() {
+        let original = vec![
+            LocalLabelDeclaration("a".into()),
+            Push(Label("b".into())),
+            StandardOp("JUMP".into()),
+            LocalLabelDeclaration("b".into()),
+            Push(Label("a".into())),
+            StandardOp("JUMP".into()),
+        ];
+        let mut code = original.clone();
+        jump_threading(&mut code);
+        assert_eq!(code, original);
+    }
+
+    #[test]
+    fn test_schedule_stack_operations() {
+        let mut code = vec![
+            StandardOp("ADD".into()),
+            Push(Label("a".into())),
+            StandardOp("SWAP1".into()),
+            StandardOp("POP".into()),
+            StandardOp("DUP1".into()),
+            StandardOp("SWAP1".into()),
+            StandardOp("MUL".into()),
+        ];
+        schedule_stack_operations(&mut code);
+        assert_eq!(
+            code,
+            vec![
+                StandardOp("ADD".into()),
+                StandardOp("POP".into()),
+                Push(Label("a".into())),
+                StandardOp("DUP1".into()),
+                StandardOp("MUL".into()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_sink_stack_operations() {
+        let mut code = vec![
+            StandardOp("SWAP1".into()),
+            Push(Label("a".into())),
+            StandardOp("JUMP".into()),
+            LocalLabelDeclaration("a".into()),
+            StandardOp("SWAP1".into()),
+            StandardOp("POP".into()),
+            StandardOp("STOP".into()),
+        ];
+        sink_stack_operations(&mut code);
+        schedule_stack_operations(&mut code);
+        assert_eq!(
+            code,
+            vec![
+                Push(Label("a".into())),
+                StandardOp("JUMP".into()),
+                LocalLabelDeclaration("a".into()),
+                StandardOp("POP".into()),
+                StandardOp("STOP".into()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_sink_stack_operations_other_predecessors() {
+        // `a` is also reached by falling through, and `b` by another jump.
+        let original = vec![
+            StandardOp("SWAP1".into()),
+            Push(Label("a".into())),
+            StandardOp("JUMP".into()),
+            StandardOp("ADD".into()),
+            LocalLabelDeclaration("a".into()),
+            StandardOp("SWAP1".into()),
+            StandardOp("POP".into()),
+            StandardOp("SWAP1".into()),
+            Push(Label("b".into())),
+            StandardOp("JUMP".into()),
+            Push(Label("b".into())),
+            StandardOp("JUMPI".into()),
+            StandardOp("PANIC".into()),
+            LocalLabelDeclaration("b".into()),
+            StandardOp("SWAP1".into()),
+            StandardOp("POP".into()),
+        ];
+        let mut code = original.clone();
+        sink_stack_operations(&mut code);
+        assert_eq!(code, original);
+    }
+
+    #[test]
+    fn test_common_subexpression_elimination() {
+        let mut code = vec![
+            StandardOp("DUP1".into()),
+            Push(Literal(5.into())),
+            StandardOp("ADD".into()),
+            StandardOp("DUP2".into()),
+            Push(Literal(5.into())),
+            StandardOp("ADD".into()),
+        ];
+        common_subexpression_elimination(&mut code);
+        assert_eq!(
+            code,
+            vec![
+                StandardOp("DUP1".into()),
+                Push(Literal(5.into())),
+                StandardOp("ADD".into()),
+                StandardOp("DUP1".into()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_common_subexpression_elimination_repeated() {
+        // Both recomputations are replaced in a single scan.
+        let mut code = vec![
+            StandardOp("DUP1".into()),
+            StandardOp("ISZERO".into()),
+            StandardOp("DUP2".into()),
+            StandardOp("ISZERO".into()),
+            StandardOp("DUP3".into()),
+            StandardOp("ISZERO".into()),
+        ];
+        common_subexpression_elimination(&mut code);
+        assert_eq!(
+            code,
+            vec![
+                StandardOp("DUP1".into()),
+                StandardOp("ISZERO".into()),
+                StandardOp("DUP1".into()),
+                StandardOp("DUP1".into()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_common_subexpression_elimination_impure() {
+        let original = vec![
+            StandardOp("DUP1".into()),
+            StandardOp("MLOAD_GENERAL".into()),
+            StandardOp("DUP2".into()),
+            StandardOp("MLOAD_GENERAL".into()),
+        ];
+        let mut code = original.clone();
+        common_subexpression_elimination(&mut code);
+        assert_eq!(code, original);
+    }
 }
@@ -7,6 +7,7 @@ use pest::{Parser, Span};
 
 use super::ast::{BytesTarget, StackPlaceholder};
 use crate::cpu::kernel::ast::{File, Item, PushTarget, StackReplacement};
+use crate::cpu::kernel::stack::stack_effects::{count_stack_items, StackAnnotations, StackEffect};
 
 /// Parses EVM assembly code.
 #[derive(pest_derive::Parser)]
@@ -14,16 +15,22 @@ use crate::cpu::kernel::ast::{File, Item, PushTarget, StackReplacement};
 pub struct AsmParser;
 
 pub(crate) fn parse(s: &str, active_features: &HashSet<&str>) -> File {
-    let s = strip_comments(s);
-    let file = AsmParser::parse(Rule::file, &s)
+    parse_file(&strip_comments(s, false), active_features)
+}
+
+/// Parses EVM assembly code, keeping the `// stack:` comments between items
+/// as [`Item::StackComment`]s, to check them against the stack depth.
+pub(crate) fn parse_with_stack_comments(s: &str, active_features: &HashSet<&str>) -> File {
+    parse_file(&strip_comments(s, true), active_features)
+}
+
+fn parse_file(s: &str, active_features: &HashSet<&str>) -> File {
+    let file = AsmParser::parse(Rule::file, s)
         .expect("Parsing failed")
         .next()
         .unwrap();
 
-    let body = file
-        .into_inner()
-        .map(|i| parse_item(i, active_features))
-        .collect();
+    let body = parse_items(file.into_inner(), active_features);
     File { body }
 }
 
@@ -34,12 +41,13 @@ pub(crate) fn parse(s: &str, active_features: &HashSet<&str>) -> File {
 /// - The AST doesn't track source locations, so changing positions doesn't
 ///   matter.
 ///
-/// So just strip before parsing.
-fn strip_comments(s: &str) -> String {
+/// So just strip before parsing. Only `// stack:` comments between items may
+/// be kept, which [`parse_items`] handles.
+fn strip_comments(s: &str, keep_stack_comments: bool) -> String {
     return match AsmParser::parse(Rule::file, s) {
         Ok(tree) => {
             let mut spans = vec![];
-            comment_spans(&mut spans, tree);
+            comment_spans(&mut spans, tree, false, keep_stack_comments);
             spans.sort_by_key(|it| std::cmp::Reverse(it.start()));
 
             let mut s = String::from(s);
@@ -51,16 +59,121 @@ fn strip_comments(s: &str) -> String {
         Err(_) => String::from(s), // let the rest of the compiler bail
     };
 
-    fn comment_spans<'a>(spans: &mut Vec<Span<'a>>, pairs: Pairs<'a, Rule>) {
+    fn comment_spans<'a>(
+        spans: &mut Vec<Span<'a>>,
+        pairs: Pairs<'a, Rule>,
+        between_items: bool,
+        keep_stack_comments: bool,
+    ) {
         for pair in pairs {
-            if let Rule::COMMENT = pair.as_rule() {
-                spans.push(pair.as_span());
+            let rule = pair.as_rule();
+            if let Rule::COMMENT = rule {
+                if !(between_items && keep_stack_comments && stack_comment(pair.as_str()).is_some())
+                {
+                    spans.push(pair.as_span());
+                }
             }
-            comment_spans(spans, pair.into_inner());
+            let has_items = matches!(
+                rule,
+                Rule::file | Rule::conditional_block | Rule::macro_def | Rule::repeat
+            );
+            comment_spans(spans, pair.into_inner(), has_items, keep_stack_comments);
         }
     }
 }
 
+/// Returns the stack listed by a `// stack:` comment.
+fn stack_comment(comment: &str) -> Option<&str> {
+    let comment = comment.strip_prefix("//")?.trim();
+    strip_prefixes(comment, &["stack:", "Stack:"])
+}
+
+fn strip_prefixes<'a>(s: &'a str, prefixes: &[&str]) -> Option<&'a str> {
+    prefixes.iter().find_map(|prefix| s.strip_prefix(prefix))
+}
+
+/// Parses the `Pre stack` and `Post stack` comments documenting the labels and
+/// macros declared right after them.
+pub(crate) fn parse_stack_annotations(s: &str) -> StackAnnotations {
+    let mut annotations = StackAnnotations::default();
+    if let Ok(mut tree) = AsmParser::parse(Rule::file, s) {
+        let file = tree.next().unwrap();
+        collect_stack_annotations(&mut annotations, &mut (None, None), file.into_inner());
+    }
+    return annotations;
+
+    fn collect_stack_annotations(
+        annotations: &mut StackAnnotations,
+        pending: &mut (Option<usize>, Option<usize>),
+        pairs: Pairs<Rule>,
+    ) {
+        for pair in pairs {
+            match pair.as_rule() {
+                Rule::COMMENT => {
+                    let comment = pair.as_str().trim_start_matches('/').trim();
+                    if let Some(pre) = strip_prefixes(comment, &["Pre stack:", "Pre-stack:"]) {
+                        pending.0 = count_stack_items(pre);
+                    } else if let Some(post) =
+                        strip_prefixes(comment, &["Post stack:", "Post-stack:"])
+                    {
+                        pending.1 = count_stack_items(post);
+                    }
+                }
+                Rule::item => {
+                    let item = pair.into_inner().next().unwrap();
+                    let effect = pending.0.map(|pre| StackEffect {
+                        pre,
+                        post: pending.1,
+                    });
+                    match item.as_rule() {
+                        Rule::conditional_block => {
+                            collect_stack_annotations(annotations, pending, item.into_inner());
+                            continue;
+                        }
+                        Rule::global_label_decl | Rule::local_label_decl => {
+                            let label = item.into_inner().next().unwrap().as_str();
+                            if let Some(effect) = effect {
+                                annotations.labels.insert(label.into(), effect);
+                            }
+                        }
+                        Rule::macro_def => {
+                            let mut inner = item.into_inner();
+                            let name = inner.next().unwrap().as_str();
+                            let num_params = inner
+                                .next()
+                                .filter(|pair| pair.as_rule() == Rule::paramlist)
+                                .map_or(0, |params| params.into_inner().count());
+                            if let Some(effect) = effect {
+                                annotations.macros.insert((name.into(), num_params), effect);
+                            }
+                        }
+                        _ => {}
+                    }
+                    *pending = (None, None);
+                }
+                _ => {}
+            }
+        }
+    }
+}
+
+/// Parses the items of a file or block, along with the `// stack:` comments
+/// left between them.
+fn parse_items<'a>(
+    pairs: impl Iterator<Item = Pair<'a, Rule>>,
+    active_features: &HashSet<&str>,
+) -> Vec<Item> {
+    pairs
+        .map(|pair| match pair.as_rule() {
+            Rule::COMMENT => {
+                let stack = stack_comment(pair.as_str()).expect("Only stack comments are kept");
+                Item::StackComment(stack.trim().into(), 0)
+            }
+            _ => parse_item(pair, active_features),
+        })
+        .collect()
+}
+
 fn parse_item(item: Pair<Rule>, active_features: &HashSet<&str>) -> Item {
     assert_eq!(item.as_rule(), Rule::item);
     let item = item.into_inner().next().unwrap();
@@ -172,10 +285,7 @@ fn parse_conditional_block(item: Pair<Rule>, active_features: &HashSet<&str>) ->
     let feature_supported = is_supported(active_features, features, group_rule);
 
     if feature_supported {
-        Item::ConditionalBlock(
-            name.into(),
-            inner.map(|i| parse_item(i, active_features)).collect(),
-        )
+        Item::ConditionalBlock(name.into(), parse_items(inner, active_features))
     } else {
         Item::ConditionalBlock(name.into(), vec![])
     }
@@ -195,11 +305,7 @@ fn parse_macro_def(item: Pair<Rule>, active_features: &HashSet<&str>) -> Item {
         vec![]
     };
 
-    Item::MacroDef(
-        name,
-        params,
-        inner.map(|i| parse_item(i, active_features)).collect(),
-    )
+    Item::MacroDef(name, params, parse_items(inner, active_features))
 }
 
 fn parse_macro_call(item: Pair<Rule>) -> Item {
@@ -223,10 +329,7 @@ fn parse_repeat(item: Pair<Rule>, active_features: &HashSet<&str>) -> Item {
     assert_eq!(item.as_rule(), Rule::repeat);
     let mut inner = item.into_inner();
     let count = parse_literal_u256(inner.next().unwrap());
-    Item::Repeat(
-        count,
-        inner.map(|i| parse_item(i, active_features)).collect(),
-    )
+    Item::Repeat(count, parse_items(inner, active_features))
 }
 
 fn parse_stack(item: Pair<Rule>) -> Item {
@@ -1,2 +1,3 @@
 mod permutations;
+pub mod stack_effects;
 pub mod stack_manipulation;
@@ -0,0 +1,677 @@
+//! A static check of the stack depth of kernel code against the `Pre stack`
+//! and `Post stack` comments documenting its labels and macros.
+//!
+//! Depths are only tracked from documented labels and macros, through
+//! fall-through and jumps to labels pushed right before `JUMP` or `JUMPI`.
+//! A jump to a documented label is treated as a call, returning to the label
+//! pushed before its target, if any. A `JUMP` to any other target ends the
+//! path, and is treated as a return unless the target could be computed from
+//! a label pushed on the way. Undocumented local labels jumped to with a
+//! return label pushed below them are subroutines, whose depth depends on
+//! their caller, and aren't checked.
+//!
+//! The `// stack:` comments listing the stack within the code of a label or
+//! macro are checked against the depth at their position, unless they list a
+//! stack of unknown length. They may leave out the bottom of the stack, e.g.
+//! the return address, but can't list more items than it holds. Those of
+//! expanded macros are only checked along with the macro on its own.
+
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+
+use crate::cpu::kernel::ast::{Item, PushTarget};
+
+/// The number of stack items documented before and after a label or macro.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) struct StackEffect {
+    pub(crate) pre: usize,
+    pub(crate) post: Option<usize>,
+}
+
+/// The stack effects documented in a file.
+#[derive(Debug, Default)]
+pub(crate) struct StackAnnotations {
+    pub(crate) labels: HashMap<String, StackEffect>,
+    /// By name and number of parameters.
+    pub(crate) macros: HashMap<(String, usize), StackEffect>,
+}
+
+/// The documented label or macro whose code has a stack error.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum StackScope {
+    Label(String),
+    Macro(String),
+}
+
+impl fmt::Display for StackScope {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Label(label) => write!(f, "{label}"),
+            Self::Macro(name) => write!(f, "%{name}"),
+        }
+    }
+}
+
+/// A mismatch between the stack depth of some code and its documentation.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct StackEffectError {
+    /// The index of the file, in the checked files.
+    pub file: usize,
+    pub scope: StackScope,
+    pub message: String,
+}
+
+impl fmt::Display for StackEffectError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.scope, self.message)
+    }
+}
+
+/// Checks the expanded code of a file, given the stack effects of the labels
+/// it may jump to.
+pub(crate) fn check_code(
+    file: usize,
+    body: &[Item],
+    effects: &HashMap<&str, StackEffect>,
+) -> Vec<StackEffectError> {
+    let mut checker = Checker::new(file, body, effects, 0);
+    checker.run_to_fixpoint(body, None, None);
+    checker.errors
+}
+
+/// Checks the expanded body of a documented macro.
+pub(crate) fn check_macro(
+    file: usize,
+    name: &str,
+    effect: StackEffect,
+    body: &[Item],
+    effects: &HashMap<&str, StackEffect>,
+) -> Vec<StackEffectError> {
+    let mut checker = Checker::new(file, body, effects, 1);
+    let scope = (StackScope::Macro(name.into()), effect);
+    let depth = checker.run_to_fixpoint(body, Some(scope.clone()), Some(effect.pre as isize));
+    if let (Some(depth), Some(post)) = (depth, effect.post) {
+        if depth != post as isize {
+            checker.error(
+                &Some(scope),
+                format!("ends with {depth} stack items, but its Post stack has {post}"),
+            );
+        }
+    }
+    checker.errors
+}
+
+type Scope = Option<(StackScope, StackEffect)>;
+
+struct Checker<'a> {
+    file: usize,
+    effects: &'a HashMap<&'a str, StackEffect>,
+    /// The number of macro expansions of the `// stack:` comments to check.
+    expansions: usize,
+    local_labels: HashSet<&'a str>,
+    /// The stack depth at each undocumented local label, once known.
+    label_depths: HashMap<&'a str, isize>,
+    /// The labels pushed below the return label of a call, which are still on
+    /// the stack when the call returns to it.
+    pushed_below: HashMap<&'a str, Vec<&'a str>>,
+    /// The undocumented local labels called with a return label.
+    subroutines: HashSet<&'a str>,
+    errors: Vec<StackEffectError>,
+}
+
+impl<'a> Checker<'a> {
+    fn new(
+        file: usize,
+        body: &'a [Item],
+        effects: &'a HashMap<&'a str, StackEffect>,
+        expansions: usize,
+    ) -> Self {
+        let local_labels = body
+            .iter()
+            .filter_map(|item| match item {
+                Item::LocalLabelDeclaration(label) => Some(label.as_str()),
+                _ => None,
+            })
+            .collect();
+        Self {
+            file,
+            effects,
+            expansions,
+            local_labels,
+            label_depths: HashMap::new(),
+            pushed_below: HashMap::new(),
+            subroutines: HashSet::new(),
+            errors: vec![],
+        }
+    }
+
+    /// Runs over `body` until the depths at labels reached by backward jumps
+    /// are known, returning the depth at its end.
+    fn run_to_fixpoint(
+        &mut self,
+        body: &'a [Item],
+        scope: Scope,
+        depth: Option<isize>,
+    ) -> Option<isize> {
+        loop {
+            self.errors.clear();
+            let known_labels = self.label_depths.len();
+            let known_subroutines = self.subroutines.len();
+            let end_depth = self.run(body, scope.clone(), depth);
+            if self.subroutines.len() != known_subroutines {
+                // The depths found by falling through into a subroutine are
+                // those of a single caller.
+                self.label_depths.clear();
+            } else if self.label_depths.len() == known_labels {
+                return end_depth;
+            }
+        }
+    }
+
+    fn run(
+        &mut self,
+        body: &'a [Item],
+        mut scope: Scope,
+        mut depth: Option<isize>,
+    ) -> Option<isize> {
+        // The labels pushed since the start of the current path, and not jumped
+        // to by a `JUMPI` yet.
+        let mut pushed_labels: Vec<&'a str> = vec![];
+        // Whether the previous item pushed a label.
+        let mut pushed_label = false;
+
+        for item in body {
+            let jump_target = pushed_label.then(|| *pushed_labels.last().unwrap());
+            pushed_label = false;
+
+            match item {
+                Item::GlobalLabelDeclaration(label) | Item::LocalLabelDeclaration(label) => {
+                    if let Some(&effect) = self.effects.get(label.as_str()) {
+                        let label_scope = Some((StackScope::Label(label.clone()), effect));
+                        if let Some(depth) = depth {
+                            if depth != effect.pre as isize {
+                                self.error(
+                                    &label_scope,
+                                    format!(
+                                        "is reached by fall-through with {depth} stack items, \
+                                        but its Pre stack has {}",
+                                        effect.pre
+                                    ),
+                                );
+                            }
+                        }
+                        scope = label_scope;
+                        depth = Some(effect.pre as isize);
+                        pushed_labels.clear();
+                    } else if self.subroutines.contains(label.as_str()) {
+                        depth = None;
+                        pushed_labels.clear();
+                    } else if self.local_labels.contains(label.as_str()) {
+                        depth = match depth {
+                            Some(depth) => {
+                                self.reach(label, depth, &scope);
+                                Some(depth)
+                            }
+                            None => {
+                                pushed_labels = self
+                                    .pushed_below
+                                    .get(label.as_str())
+                                    .cloned()
+                                    .unwrap_or_default();
+                                self.label_depths.get(label.as_str()).copied()
+                            }
+                        };
+                    }
+                }
+                Item::Push(target) => {
+                    if let PushTarget::Label(label) = target {
+                        pushed_labels.push(label);
+                        pushed_label = true;
+                    }
+                    depth = depth.map(|depth| depth + 1);
+                }
+                Item::ProverInput(_) => depth = depth.map(|depth| depth + 1),
+                Item::StandardOp(op) if op == "JUMP" => {
+                    if let Some(depth) = self.pop(depth, 1, op, &scope) {
+                        match jump_target {
+                            Some(target) => {
+                                let return_label =
+                                    pushed_labels.len().checked_sub(2).map(|i| pushed_labels[i]);
+                                if let Some(label) = return_label {
+                                    let below = pushed_labels[..pushed_labels.len() - 2].to_vec();
+                                    self.pushed_below.insert(label, below);
+                                }
+                                self.jump(target, depth, return_label, &scope);
+                            }
+                            // The target may be computed from a pushed label.
+                            None if !pushed_labels.is_empty() => {}
+                            // Otherwise, only a return address is jumped to without
+                            // being pushed.
+                            None => self.check_return(depth, &scope),
+                        }
+                    }
+                    depth = None;
+                    pushed_labels.clear();
+                }
+                Item::StandardOp(op) if op == "JUMPI" => {
+                    depth = self.pop(depth, 2, op, &scope);
+                    if let Some(target) = jump_target {
+                        pushed_labels.pop();
+                        if let Some(depth) = depth {
+                            if self.is_undocumented_local(target) {
+                                self.reach(target, depth, &scope);
+                            }
+                        }
+                    }
+                }
+                Item::StandardOp(op) => match opcode_stack_effect(op) {
+                    Some((pops, pushes)) => {
+                        depth = self
+                            .pop(depth, pops, op, &scope)
+                            .map(|depth| depth + pushes);
+                        if is_terminal(op) {
+                            depth = None;
+                            pushed_labels.clear();
+                        }
+                    }
+                    None => depth = None,
+                },
+                Item::Bytes(_) | Item::Jumptable(_) => {
+                    depth = None;
+                    pushed_labels.clear();
+                }
+                Item::StackComment(stack, expansions) if *expansions == self.expansions => {
+                    if let (Some(depth), Some(items)) = (depth, count_stack_items(stack)) {
+                        if depth < items as isize {
+                            self.error(
+                                &scope,
+                                format!(
+                                    "has {depth} stack items, but `// stack: {stack}` lists {items}"
+                                ),
+                            );
+                        }
+                    }
+                }
+                Item::StackComment(_, _) => {}
+                Item::ConditionalBlock(_, _)
+                | Item::MacroDef(_, _, _)
+                | Item::MacroCall(_, _)
+                | Item::Repeat(_, _)
+                | Item::StackManipulation(_, _)
+                | Item::MacroLabelDeclaration(_) => {
+                    panic!("Item should have been expanded already: {item:?}");
+                }
+            }
+        }
+
+        depth
+    }
+
+    /// Pops `n` items for `op`, flagging an underflow of the documented stack.
+    fn pop(&mut self, depth: Option<isize>, n: isize, op: &str, scope: &Scope) -> Option<isize> {
+        let depth = depth?;
+        if depth < n {
+            self.error(
+                scope,
+                format!("{op} needs {n} stack items, but only {depth} are documented"),
+            );
+            return None;
+        }
+        Some(depth - n)
+    }
+
+    /// Jumps to a pushed label, with `depth` items left on the stack.
+    fn jump(
+        &mut self,
+        target: &'a str,
+        depth: isize,
+        return_label: Option<&'a str>,
+        scope: &Scope,
+    ) {
+        if let Some(&effect) = self.effects.get(target) {
+            let pre = effect.pre as isize;
+            if depth < pre {
+                self.error(
+                    scope,
+                    format!(
+                        "jumps to {target} with {depth} stack items, but its Pre stack has {pre}"
+                    ),
+                );
+                return;
+            }
+            let Some(post) = effect.post else {
+                return;
+            };
+            let after = depth - pre + post as isize;
+            match return_label {
+                Some(label) if self.is_undocumented_local(label) => self.reach(label, after, scope),
+                Some(_) => {}
+                // A tail call returns to our own caller.
+                None => self.check_return(after, scope),
+            }
+        } else if self.is_undocumented_local(target) {
+            if return_label.is_some() {
+                self.subroutines.insert(target);
+            } else {
+                self.reach(target, depth, scope);
+            }
+        }
+    }
+
+    /// Checks the depth when returning from a documented label.
+    fn check_return(&mut self, depth: isize, scope: &Scope) {
+        if let Some((
+            StackScope::Label(_),
+            StackEffect {
+                post: Some(post), ..
+            },
+        )) = scope
+        {
+            if depth != *post as isize {
+                let post = *post;
+                self.error(
+                    scope,
+                    format!("returns with {depth} stack items, but its Post stack has {post}"),
+                );
+            }
+        }
+    }
+
+    /// Records the depth at a local label, flagging inconsistent merge points.
+    fn reach(&mut self, label: &'a str, depth: isize, scope: &Scope) {
+        if self.subroutines.contains(label) {
+            return;
+        }
+        match self.label_depths.get(label) {
+            Some(&known) if known != depth => self.error(
+                scope,
+                format!(
+                    "reaches {label} with {depth} stack items, but it is also reached with {known}"
+                ),
+            ),
+            Some(_) => {}
+            None => {
+                self.label_depths.insert(label, depth);
+            }
+        }
+    }
+
+    fn is_undocumented_local(&self, label: &str) -> bool {
+        self.local_labels.contains(label) && !self.effects.contains_key(label)
+    }
+
+    fn error(&mut self, scope: &Scope, message: String) {
+        if let Some((scope, _)) = scope {
+            self.errors.push(StackEffectError {
+                file: self.file,
+                scope: scope.clone(),
+                message,
+            });
+        }
+    }
+}
+
+/// Counts the items of a documented stack, like `x, f(y, z), retdest`.
+/// Returns [`None`] for stacks of unknown length, like `x, ...`.
+pub(crate) fn count_stack_items(stack: &str) -> Option<usize> {
+    let stack = stack.trim();
+    if stack.is_empty() || stack == "(empty)" {
+        return Some(0);
+    }
+    if stack.contains("...") {
+        return None;
+    }
+    // Commas within brackets separate arguments, not stack items.
+    let mut nesting = 0;
+    let mut items = 1;
+    for c in stack.chars() {
+        match c {
+            '(' | '[' | '{' => nesting += 1,
+            ')' | ']' | '}' => nesting -= 1,
+            ',' if nesting == 0 => items += 1,
+            _ => {}
+        }
+    }
+    Some(items)
+}
+
+/// The number of items popped and pushed by an opcode other than a `PUSH`.
+pub(crate) fn opcode_stack_effect(op: &str) -> Option<(isize, isize)> {
+    if let Some(n) = op.strip_prefix("DUP") {
+        let n = n.parse().ok()?;
+        return Some((n, n + 1));
+    }
+    if let Some(n) = op.strip_prefix("SWAP") {
+        let n: isize = n.parse().ok()?;
+        return Some((n + 1, n + 1));
+    }
+    if let Some(n) = op.strip_prefix("LOG") {
+        let n: isize = n.parse().ok()?;
+        return Some((n + 2, 0));
+    }
+    if op.starts_with("MSTORE_32BYTES_") {
+        return Some((2, 1));
+    }
+    Some(match op {
+        "STOP" | "JUMPDEST" | "PANIC" | "INVALID" => (0, 0),
+        "ADDRESS" | "ORIGIN" | "CALLER" | "CALLVALUE" | "CALLDATASIZE" | "CODESIZE"
+        | "GASPRICE" | "RETURNDATASIZE" | "COINBASE" | "TIMESTAMP" | "NUMBER" | "DIFFICULTY"
+        | "GASLIMIT" | "CHAINID" | "BASEFEE" | "BLOBBASEFEE" | "GETPC" | "MSIZE" | "GAS"
+        | "GET_CONTEXT" => (0, 1),
+        "ISZERO" | "NOT" | "BALANCE" | "CALLDATALOAD" | "EXTCODESIZE" | "EXTCODEHASH"
+        | "BLOCKHASH" | "MLOAD" | "SLOAD" | "TLOAD" | "MLOAD_GENERAL" => (1, 1),
+        "POP" | "SELFDESTRUCT" | "SET_CONTEXT" | "EXIT_KERNEL" => (1, 0),
+        "ADD" | "MUL" | "SUB" | "DIV" | "SDIV" | "MOD" | "SMOD" | "EXP" | "SIGNEXTEND"
+        | "ADDFP254" | "MULFP254" | "SUBFP254" | "LT" | "GT" | "SLT" | "SGT" | "EQ" | "AND"
+        | "OR" | "XOR" | "BYTE" | "SHL" | "SHR" | "SAR" | "KECCAK256" | "KECCAK_GENERAL"
+        | "MLOAD_32BYTES" => (2, 1),
+        "MSTORE" | "MSTORE8" | "SSTORE" | "TSTORE" | "RETURN" | "REVERT" | "MSTORE_GENERAL" => {
+            (2, 0)
+        }
+        "ADDMOD" | "MULMOD" | "SUBMOD" => (3, 1),
+        "CALLDATACOPY" | "CODECOPY" | "RETURNDATACOPY" | "MCOPY" => (3, 0),
+        "CREATE" => (3, 1),
+        "CREATE2" => (4, 1),
+        "EXTCODECOPY" => (4, 0),
+        "DELEGATECALL" | "STATICCALL" => (6, 1),
+        "CALL" | "CALLCODE" => (7, 1),
+        _ => return None,
+    })
+}
+
+/// Whether execution never continues after an opcode.
+fn is_terminal(op: &str) -> bool {
+    matches!(
+        op,
+        "STOP" | "RETURN" | "REVERT" | "INVALID" | "PANIC" | "SELFDESTRUCT" | "EXIT_KERNEL"
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cpu::kernel::aggregator::KERNEL_FILES;
+    use crate::cpu::kernel::check_stack_effects;
+
+    /// The `%jump` and `%jumpi` macros of `basic_macros.asm`.
+    const JUMP_MACROS: &str = "
+        %macro jump(dst)
+            PUSH $dst
+            jump
+        %endmacro
+
+        %macro jumpi(dst)
+            PUSH $dst
+            jumpi
+        %endmacro
+    ";
+
+    fn check(files: &[&str]) -> Vec<String> {
+        let files = [JUMP_MACROS]
+            .iter()
+            .chain(files)
+            .map(|f| f.to_string())
+            .collect::<Vec<_>>();
+        check_stack_effects(&files)
+            .iter()
+            .map(|error| error.to_string())
+            .collect()
+    }
+
+    #[test]
+    fn consistent_code() {
+        let errors = check(&["
+            // Pre stack: x, y
+            // Post stack: sum
+            %macro add_pair
+                ADD
+            %endmacro
+
+            // Pre stack: x, retdest
+            // Post stack: double
+            global double:
+                DUP1 %add_pair
+                SWAP1 JUMP
+
+            // Pre stack: x, retdest
+            // Post stack: quadruple
+            global quadruple:
+                %stack (x) -> (x, after_first)
+                %jump(double)
+            after_first:
+                %stack (x) -> (x, after_second)
+                %jump(double)
+            after_second:
+                SWAP1 JUMP
+
+            // Pre stack: n, retdest
+            // Post stack: (empty)
+            global count_down:
+                DUP1 ISZERO %jumpi(done)
+                PUSH 1 SWAP1 SUB
+                %jump(count_down_loop)
+            count_down_loop:
+                DUP1 ISZERO %jumpi(done)
+                PUSH 1 SWAP1 SUB
+                %jump(count_down_loop)
+            done:
+                POP JUMP
+
+            // Pre stack: x, y, retdest
+            // Post stack: sum
+            global add_or_double_first:
+                DUP1 %jumpi(double_first)
+            add_pair:
+                // stack: x, y, retdest
+                ADD SWAP1 JUMP
+            double_first:
+                %stack (x, y) -> (x, x, after_double, y)
+                %jump(add_pair)
+            after_double:
+                // stack: double, y, retdest
+                %jump(add_pair)
+            "]);
+        assert_eq!(errors, Vec::<String>::new());
+    }
+
+    #[test]
+    fn flags_mismatches() {
+        let errors = check(&["
+            // Pre stack: x, y
+            // Post stack: sum
+            %macro bad_add
+                ADD POP
+            %endmacro
+
+            // Pre stack: x, retdest
+            // Post stack: (empty)
+            global bad_return:
+                SWAP1 JUMP
+
+            // Pre stack: retdest
+            global bad_merge:
+                PUSH 0 %jumpi(merge)
+                PUSH 1
+            merge:
+                JUMP
+
+            // Pre stack: retdest
+            global underflow:
+                SWAP1 JUMP
+            "]);
+        assert_eq!(
+            errors,
+            [
+                "bad_return: returns with 1 stack items, but its Post stack has 0",
+                "bad_merge: reaches merge with 2 stack items, but it is also reached with 1",
+                "underflow: SWAP1 needs 2 stack items, but only 1 are documented",
+                "%bad_add: ends with 0 stack items, but its Post stack has 1",
+            ]
+        );
+    }
+
+    #[test]
+    fn stack_comments() {
+        let errors = check(&["
+            // Pre stack: x
+            // Post stack: x, x
+            %macro dup
+                // stack: x
+                DUP1
+                // stack: x, x
+            %endmacro
+
+            // Pre stack: x
+            // Post stack: x, x
+            %macro bad_dup
+                DUP1
+                // stack: x, x, x
+            %endmacro
+
+            // Pre stack: x, y, retdest
+            // Post stack: x + y, x + y
+            global add_twice:
+                // stack: x, y, retdest
+                ADD
+                // stack: x + y
+                %dup
+                // stack: x + y, x + y, ...
+                %stack (sum, sum_again, retdest) -> (retdest, sum, sum_again)
+                // stack: retdest, x + y, x + y
+                JUMP
+
+            // Pre stack: x, y, retdest
+            // Post stack: max(x, y)
+            global bad_max:
+                // stack: x, y, retdest
+                DUP2 DUP2 LT
+                // stack: x < y, x, y, retdest
+                %jumpi(bad_max_y)
+                // stack: x, y, retdest
+                SWAP1 POP SWAP1 JUMP
+            bad_max_y:
+                // stack: y, x, y, retdest
+                POP SWAP1 JUMP
+            "]);
+        assert_eq!(
+            errors,
+            [
+                "bad_max: has 3 stack items, but `// stack: y, x, y, retdest` lists 4",
+                "%bad_dup: has 2 stack items, but `// stack: x, x, x` lists 3",
+            ]
+        );
+    }
+
+    #[test]
+    fn stack_items() {
+        assert_eq!(count_stack_items("(empty)"), Some(0));
+        assert_eq!(count_stack_items("x, f(y, z), a[i, j], retdest"), Some(4));
+        assert_eq!(count_stack_items("x, ..."), None);
+    }
+
+    #[test]
+    fn kernel_files() {
+        let files = KERNEL_FILES.map(String::from);
+        assert_eq!(check_stack_effects(&files), vec![]);
+    }
+}
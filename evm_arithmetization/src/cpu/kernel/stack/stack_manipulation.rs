@@ -89,7 +89,7 @@ fn expand(names: Vec<StackPlaceholder>, replacements: Vec<StackReplacement>) ->
 
 /// Finds the lowest-cost sequence of `StackOp`s that transforms `src` to `dst`.
 /// Uses a variant of Dijkstra's algorithm.
-fn shortest_path(
+pub(crate) fn shortest_path(
     src: Vec<StackItem>,
     dst: Vec<StackItem>,
     unique_push_targets: Vec<PushTarget>,
@@ -282,7 +282,7 @@ fn should_try_swap(src: &[StackItem], dst: &[StackItem], i: u8) -> bool {
 }
 
 impl StackOp {
-    fn cost(&self) -> u32 {
+    pub(crate) fn cost(&self) -> u32 {
         let (cpu_rows, memory_rows) = match self {
             StackOp::Push(target) => {
                 let bytes = match target {
@@ -312,7 +312,7 @@ impl StackOp {
 
     /// Returns an updated stack after this operation is performed, or `None` if
     /// this operation would not be valid on the given stack.
-    fn apply_to(&self, mut stack: Vec<StackItem>) -> Option<Vec<StackItem>> {
+    pub(crate) fn apply_to(&self, mut stack: Vec<StackItem>) -> Option<Vec<StackItem>> {
         let len = stack.len();
         match self {
             StackOp::Push(target) => {
@@ -334,7 +334,7 @@ impl StackOp {
         Some(stack)
     }
 
-    fn into_item(self) -> Item {
+    pub(crate) fn into_item(self) -> Item {
         match self {
             StackOp::Push(target) => Item::Push(target),
             Pop => Item::StandardOp("POP".into()),
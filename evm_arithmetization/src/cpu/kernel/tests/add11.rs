@@ -5,14 +5,20 @@ use std::str::FromStr;
 
 use ethereum_types::{Address, BigEndianHash, H256};
 use hex_literal::hex;
+use itertools::Itertools;
 use keccak_hash::keccak;
 use mpt_trie::nibbles::Nibbles;
 use mpt_trie::partial_trie::{HashedPartialTrie, Node, PartialTrie};
+use once_cell::sync::Lazy;
 use plonky2::field::goldilocks_field::GoldilocksField as F;
 use plonky2::field::types::Field;
 
-use crate::cpu::kernel::aggregator::KERNEL;
+use crate::cpu::kernel::aggregator::{active_features, with_kernel, KERNEL, KERNEL_FILES};
+use crate::cpu::kernel::assembler::{assemble, Kernel};
+use crate::cpu::kernel::constants::evm_constants;
+use crate::cpu::kernel::constants::global_metadata::GlobalMetadata;
 use crate::cpu::kernel::interpreter::Interpreter;
+use crate::cpu::kernel::parser::parse;
 use crate::generation::mpt::{AccountRlp, LegacyReceiptRlp};
 use crate::generation::TrieInputs;
 use crate::proof::{BlockHashes, BlockMetadata, TrieRoots};
@@ -24,6 +30,63 @@ use crate::{GenerationInputs, EMPTY_CONSOLIDATED_BLOCKHASH};
 
 #[test]
 fn test_add11_yml() {
+    let inputs = add11_yml_inputs();
+
+    let initial_stack = vec![];
+    let initial_offset = KERNEL.global_labels["init"];
+    let mut interpreter: Interpreter<F> =
+        Interpreter::new_with_generation_inputs(initial_offset, initial_stack, &inputs, None);
+
+    interpreter.set_is_kernel(true);
+    interpreter.run().expect("Proving add11 failed.");
+}
+
+/// Runs the add11 block on the kernel assembled without and with the
+/// optimizer, which must reach the same final state, the optimized kernel in
+/// fewer cycles.
+#[test]
+fn test_add11_yml_optimizer() {
+    static UNOPTIMIZED_KERNEL: Lazy<Kernel> = Lazy::new(|| {
+        let active_features = active_features();
+        let parsed_files = KERNEL_FILES
+            .iter()
+            .map(|f| parse(f, &active_features))
+            .collect_vec();
+        assemble(parsed_files, evm_constants(), false)
+    });
+
+    let inputs = add11_yml_inputs();
+    let run = || {
+        let initial_offset = KERNEL.global_labels["init"];
+        let mut interpreter: Interpreter<F> =
+            Interpreter::new_with_generation_inputs(initial_offset, vec![], &inputs, None);
+        interpreter.set_is_kernel(true);
+        interpreter.run().expect("Proving add11 failed.");
+
+        // The kernel's own length and hash differ between the two runs.
+        let metadata = GlobalMetadata::all()
+            .into_iter()
+            .filter(|&field| {
+                field != GlobalMetadata::KernelHash && field != GlobalMetadata::KernelLen
+            })
+            .map(|field| interpreter.get_global_metadata_field(field))
+            .collect_vec();
+        let gas_used = interpreter.generation_state.registers.gas_used;
+        (interpreter.clock, gas_used, metadata)
+    };
+
+    let (unoptimized_cycles, unoptimized_gas_used, unoptimized_metadata) =
+        with_kernel(&UNOPTIMIZED_KERNEL, run);
+    let (cycles, gas_used, metadata) = run();
+    assert_eq!(gas_used, unoptimized_gas_used);
+    assert_eq!(metadata, unoptimized_metadata);
+    assert!(
+        cycles < unoptimized_cycles,
+        "the optimized kernel took {cycles} cycles, against {unoptimized_cycles} unoptimized"
+    );
+}
+
+fn add11_yml_inputs() -> GenerationInputs<F> {
     let beneficiary = hex!("2adc25665018aa1fe0e6bc666dac8fc2697ff9ba");
     let sender = hex!("a94f5374fce5edbc8e2a8697c15331677e6ebf0b");
     let to = hex!("095e7baea6a6c7c4c2dfeb977efac326af552d87");
@@ -175,14 +238,15 @@ fn test_add11_yml() {
         receipts_root: receipts_trie.hash(),
     };
 
-    let inputs = GenerationInputs {
+    GenerationInputs {
         signed_txns: vec![txn.to_vec()],
         burn_addr: None,
         withdrawals: vec![],
+        process_requests: false,
         ger_data: None,
         tries: tries_before,
         trie_roots_after,
-        contract_code: contract_code.clone(),
+        contract_code,
         block_metadata,
         checkpoint_state_trie_root: HashedPartialTrie::from(Node::Empty).hash(),
         checkpoint_consolidated_hash: EMPTY_CONSOLIDATED_BLOCKHASH.map(F::from_canonical_u64),
@@ -193,15 +257,7 @@ fn test_add11_yml() {
             prev_hashes: vec![H256::default(); 256],
             cur_hash: H256::default(),
         },
-    };
-
-    let initial_stack = vec![];
-    let initial_offset = KERNEL.global_labels["init"];
-    let mut interpreter: Interpreter<F> =
-        Interpreter::new_with_generation_inputs(initial_offset, initial_stack, &inputs, None);
-
-    interpreter.set_is_kernel(true);
-    interpreter.run().expect("Proving add11 failed.");
+    }
 }
 
 #[test]
@@ -356,6 +412,7 @@ fn test_add11_yml_with_exception() {
         signed_txns: vec![txn.to_vec()],
         burn_addr: None,
         withdrawals: vec![],
+        process_requests: false,
         ger_data: None,
         tries: tries_before,
         trie_roots_after,
@@ -1,12 +1,17 @@
 use anyhow::Result;
-use ethereum_types::U256;
+use ethereum_types::{U256, U512};
 use hex_literal::hex;
 use plonky2::field::goldilocks_field::GoldilocksField as F;
+use rand::Rng;
 
 use crate::cpu::kernel::aggregator::KERNEL;
 use crate::cpu::kernel::cancun_constants::POINT_EVALUATION_PRECOMPILE_RETURN_VALUE;
 use crate::cpu::kernel::constants::cancun_constants::KZG_VERSIONED_HASH;
+use crate::cpu::kernel::constants::context_metadata::ContextMetadata;
 use crate::cpu::kernel::interpreter::Interpreter;
+use crate::curve_pairings::{bls381, CurveAff, CyclicGroup};
+use crate::extension_tower::{FieldExt, Fp2, BLS381, BLS_BASE, BLS_SCALAR};
+use crate::memory::segments::Segment;
 use crate::util::sha2;
 
 /// A KZG point evaluation precompile payload consists in:
@@ -147,3 +152,278 @@ fn test_kzg_peval_precompile() -> Result<()> {
 
     Ok(())
 }
+
+/// Runs the BLS12-381 precompile starting at the given label, right after its
+/// prelude, on `input`. Returns the precompile output, or `None` if the kernel
+/// faulted.
+fn run_bls12_381_precompile(
+    label: &str,
+    output_len: usize,
+    input: &[u8],
+) -> Result<Option<Vec<u8>>> {
+    let kexit_info = U256::from(0xdeadbeefu32);
+    let initial_stack = vec![kexit_info];
+
+    let bls12_381_precompile = KERNEL.global_labels[label];
+    let mut interpreter: Interpreter<F> =
+        Interpreter::new(bls12_381_precompile, initial_stack, None);
+    interpreter.set_memory_segment_bytes(Segment::Calldata, input.to_vec());
+    interpreter.set_context_metadata_field(0, ContextMetadata::CalldataSize, input.len().into());
+    interpreter.set_context_metadata_field(0, ContextMetadata::GasLimit, u64::MAX.into());
+    interpreter.halt_offsets = vec![
+        KERNEL.global_labels["pop_and_return_success"],
+        KERNEL.global_labels["fault_exception"],
+    ];
+    interpreter.run()?;
+
+    if interpreter.generation_state.registers.program_counter
+        == KERNEL.global_labels["fault_exception"]
+    {
+        return Ok(None);
+    }
+    let returndata = interpreter.get_memory_segment_bytes(Segment::Returndata);
+    Ok(Some(returndata[..output_len].to_vec()))
+}
+
+fn fp_to_bytes(x: BLS381) -> Vec<u8> {
+    let mut bytes = [0u8; 64];
+    x.val.to_big_endian(&mut bytes);
+    bytes.to_vec()
+}
+
+fn g1_to_bytes(p: CurveAff<BLS381>) -> Vec<u8> {
+    [fp_to_bytes(p.x), fp_to_bytes(p.y)].concat()
+}
+
+fn g2_to_bytes(p: CurveAff<Fp2<BLS381>>) -> Vec<u8> {
+    [
+        fp_to_bytes(p.x.re),
+        fp_to_bytes(p.x.im),
+        fp_to_bytes(p.y.re),
+        fp_to_bytes(p.y.im),
+    ]
+    .concat()
+}
+
+fn scalar_to_bytes(s: U256) -> Vec<u8> {
+    let mut bytes = [0u8; 32];
+    s.to_big_endian(&mut bytes);
+    bytes.to_vec()
+}
+
+/// A point of order 3 of the BLS12-381 curve, hence outside of G1.
+const G1_NON_SUBGROUP_POINT: CurveAff<BLS381> = CurveAff {
+    x: BLS381::ZERO,
+    y: BLS381 {
+        val: U512([2, 0, 0, 0, 0, 0, 0, 0]),
+    },
+};
+
+#[test]
+fn test_bls12_g1add_precompile() -> Result<()> {
+    let p = CurveAff::<BLS381>::int(2);
+    let q = CurveAff::<BLS381>::int(5);
+
+    let input = [g1_to_bytes(p), g1_to_bytes(q)].concat();
+    let output = run_bls12_381_precompile("bls12_g1add", 128, &input)?;
+    assert_eq!(output, Some(g1_to_bytes(CurveAff::<BLS381>::int(7))));
+
+    // The point at infinity is encoded as zeros.
+    let input = [g1_to_bytes(p), g1_to_bytes(-p)].concat();
+    let output = run_bls12_381_precompile("bls12_g1add", 128, &input)?;
+    assert_eq!(output, Some(vec![0; 128]));
+
+    // Points outside of G1 are accepted by G1ADD.
+    let r = G1_NON_SUBGROUP_POINT;
+    let input = [g1_to_bytes(r), g1_to_bytes(r)].concat();
+    let output = run_bls12_381_precompile("bls12_g1add", 128, &input)?;
+    assert_eq!(output, Some(g1_to_bytes(-r)));
+
+    Ok(())
+}
+
+#[test]
+fn test_bls12_g1msm_precompile() -> Result<()> {
+    let g = CurveAff::<BLS381>::GENERATOR;
+    let input = [
+        g1_to_bytes(g),
+        scalar_to_bytes(U256::from(2)),
+        g1_to_bytes(CurveAff::<BLS381>::int(3)),
+        scalar_to_bytes(U256::from(4)),
+        // Scalars aren't required to be reduced.
+        g1_to_bytes(g),
+        scalar_to_bytes(BLS_SCALAR + 1),
+    ]
+    .concat();
+    let output = run_bls12_381_precompile("bls12_g1msm", 128, &input)?;
+    assert_eq!(output, Some(g1_to_bytes(CurveAff::<BLS381>::int(15))));
+
+    Ok(())
+}
+
+#[test]
+fn test_bls12_g2add_precompile() -> Result<()> {
+    let p = CurveAff::<Fp2<BLS381>>::int(3);
+    let q = CurveAff::<Fp2<BLS381>>::int(4);
+
+    let input = [g2_to_bytes(p), g2_to_bytes(q)].concat();
+    let output = run_bls12_381_precompile("bls12_g2add", 256, &input)?;
+    assert_eq!(output, Some(g2_to_bytes(CurveAff::<Fp2<BLS381>>::int(7))));
+
+    Ok(())
+}
+
+#[test]
+fn test_bls12_g2msm_precompile() -> Result<()> {
+    let input = [
+        g2_to_bytes(CurveAff::<Fp2<BLS381>>::GENERATOR),
+        scalar_to_bytes(U256::from(3)),
+        g2_to_bytes(CurveAff::<Fp2<BLS381>>::int(2)),
+        scalar_to_bytes(U256::zero()),
+    ]
+    .concat();
+    let output = run_bls12_381_precompile("bls12_g2msm", 256, &input)?;
+    assert_eq!(output, Some(g2_to_bytes(CurveAff::<Fp2<BLS381>>::int(3))));
+
+    Ok(())
+}
+
+#[test]
+fn test_bls12_pairing_precompile() -> Result<()> {
+    let g1 = CurveAff::<BLS381>::GENERATOR;
+    let g2 = CurveAff::<Fp2<BLS381>>::GENERATOR;
+
+    // e(2 * g1, g2) * e(-g1, 2 * g2) == 1
+    let input = [
+        g1_to_bytes(CurveAff::<BLS381>::int(2)),
+        g2_to_bytes(g2),
+        g1_to_bytes(-g1),
+        g2_to_bytes(CurveAff::<Fp2<BLS381>>::int(2)),
+    ]
+    .concat();
+    let output = run_bls12_381_precompile("bls12_pairing", 32, &input)?;
+    assert_eq!(output, Some(scalar_to_bytes(U256::one())));
+
+    let input = [g1_to_bytes(g1), g2_to_bytes(g2)].concat();
+    let output = run_bls12_381_precompile("bls12_pairing", 32, &input)?;
+    assert_eq!(output, Some(scalar_to_bytes(U256::zero())));
+
+    // Pairs involving the point at infinity are ignored.
+    let input = [g1_to_bytes(CurveAff::<BLS381>::unit()), g2_to_bytes(g2)].concat();
+    let output = run_bls12_381_precompile("bls12_pairing", 32, &input)?;
+    assert_eq!(output, Some(scalar_to_bytes(U256::one())));
+
+    Ok(())
+}
+
+#[test]
+fn test_bls12_map_fp_to_g1_precompile() -> Result<()> {
+    // Test vector taken from <https://eips.ethereum.org/EIPS/eip-2537>.
+    let input = hex!("00000000000000000000000000000000156c8a6a2c184569d69a76be144b5cdc5141d2d2ca4fe341f011e25e3969c55ad9e9b9ce2eb833c81a908e5fa4ac5f03");
+    let expected = hex!("00000000000000000000000000000000184bb665c37ff561a89ec2122dd343f20e0f4cbcaec84e3c3052ea81d1834e192c426074b02ed3dca4e7676ce4ce48ba0000000000000000000000000000000004407b8d35af4dacc809927071fc0405218f1401a6d15af775810e4e460064bcc9468beeba82fdc751be70476c888bf3");
+
+    let output = run_bls12_381_precompile("bls12_map_fp_to_g1", 128, &input)?;
+    assert_eq!(output, Some(expected.to_vec()));
+
+    Ok(())
+}
+
+#[test]
+fn test_bls12_map_fp2_to_g2_precompile() -> Result<()> {
+    // The field elements and the resulting point of the `hash_to_curve` test
+    // vector for the empty message, taken from
+    // <https://www.rfc-editor.org/rfc/rfc9380#appendix-J.10.1>. Since clearing the
+    // cofactor is linear, the point is the sum of the images of both elements.
+    let u0 = hex!("0000000000000000000000000000000003dbc2cce174e91ba93cbb08f26b917f98194a2ea08d1cce75b2b9cc9f21689d80bd79b594a613d0a68eb807dfdc1cf80000000000000000000000000000000005a2acec64114845711a54199ea339abd125ba38253b70a92c876df10598bd1986b739cad67961eb94f7076511b3b39a");
+    let u1 = hex!("0000000000000000000000000000000002f99798e8a5acdeed60d7e18e9120521ba1f47ec090984662846bc825de191b5b7641148c0dbc237726a334473eee9400000000000000000000000000000000145a81e418d4010cc027a68f14391b30074e89e60ee7a22f87217b2f6eb0c4b94c9115b436e6fa4607e95a98de30a435");
+    let expected = hex!("000000000000000000000000000000000141ebfbdca40eb85b87142e130ab689c673cf60f1a3e98d69335266f30d9b8d4ac44c1038e9dcdd5393faf5c41fb78a0000000000000000000000000000000005cb8437535e20ecffaef7752baddf98034139c38452458baeefab379ba13dff5bf5dd71b72418717047f5b0f37da03d000000000000000000000000000000000503921d7f6a12805e72940b963c0cf3471c7b2a524950ca195d11062ee75ec076daf2d4bc358c4b190c0c98064fdd920000000000000000000000000000000012424ac32561493f3fe3c260708a12b7c620e7be00099a974e259ddc7d1f6395c3c811cdd19f1e8dbf3e9ecfdcbab8d6");
+
+    let q0 = run_bls12_381_precompile("bls12_map_fp2_to_g2", 256, &u0)?.expect("valid input");
+    let q1 = run_bls12_381_precompile("bls12_map_fp2_to_g2", 256, &u1)?.expect("valid input");
+    let output = run_bls12_381_precompile("bls12_g2add", 256, &[q0, q1].concat())?;
+    assert_eq!(output, Some(expected.to_vec()));
+
+    Ok(())
+}
+
+#[test]
+fn test_bls12_precompiles_against_reference() -> Result<()> {
+    let mut rng = rand::thread_rng();
+
+    let u: BLS381 = rng.gen();
+    let p = bls381::map_fp_to_g1(u);
+    assert!(bls381::g1_is_on_curve(p) && bls381::is_in_subgroup(p));
+    let output = run_bls12_381_precompile("bls12_map_fp_to_g1", 128, &fp_to_bytes(u))?;
+    assert_eq!(output, Some(g1_to_bytes(p)));
+
+    let u: Fp2<BLS381> = rng.gen();
+    let q = bls381::map_fp2_to_g2(u);
+    assert!(bls381::g2_is_on_curve(q) && bls381::is_in_subgroup(q));
+    let input = [fp_to_bytes(u.re), fp_to_bytes(u.im)].concat();
+    let output = run_bls12_381_precompile("bls12_map_fp2_to_g2", 256, &input)?;
+    assert_eq!(output, Some(g2_to_bytes(q)));
+
+    let g = CurveAff::<BLS381>::GENERATOR;
+    let (s, t) = (U256(rng.gen()), U256(rng.gen()));
+    let input = [
+        g1_to_bytes(p),
+        scalar_to_bytes(s),
+        g1_to_bytes(g),
+        scalar_to_bytes(t),
+    ]
+    .concat();
+    let output = run_bls12_381_precompile("bls12_g1msm", 128, &input)?;
+    assert_eq!(output, Some(g1_to_bytes(p * s + g * t)));
+
+    Ok(())
+}
+
+#[test]
+fn test_bls12_precompiles_invalid_input() -> Result<()> {
+    let g1 = CurveAff::<BLS381>::GENERATOR;
+    let g2 = CurveAff::<Fp2<BLS381>>::GENERATOR;
+
+    // Point not on the curve.
+    let not_on_curve = CurveAff {
+        x: g1.x,
+        y: g1.y + BLS381::UNIT,
+    };
+    let input = [g1_to_bytes(g1), g1_to_bytes(not_on_curve)].concat();
+    assert_eq!(run_bls12_381_precompile("bls12_g1add", 128, &input)?, None);
+
+    // Non-canonical field element.
+    let mut input = [g1_to_bytes(g1), g1_to_bytes(g1)].concat();
+    let x_plus_modulus = g1.x.val + BLS_BASE;
+    x_plus_modulus.to_big_endian(&mut input[128..192]);
+    assert_eq!(run_bls12_381_precompile("bls12_g1add", 128, &input)?, None);
+
+    // Non-zero top bytes.
+    let mut input = fp_to_bytes(BLS381::UNIT);
+    input[0] = 1;
+    assert_eq!(
+        run_bls12_381_precompile("bls12_map_fp_to_g1", 128, &input)?,
+        None
+    );
+    let mut input = [fp_to_bytes(BLS381::UNIT), fp_to_bytes(BLS381::UNIT)].concat();
+    input[64] = 1;
+    assert_eq!(
+        run_bls12_381_precompile("bls12_map_fp2_to_g2", 256, &input)?,
+        None
+    );
+
+    // Point outside of G1.
+    let input = [
+        g1_to_bytes(G1_NON_SUBGROUP_POINT),
+        scalar_to_bytes(U256::one()),
+    ]
+    .concat();
+    assert_eq!(run_bls12_381_precompile("bls12_g1msm", 128, &input)?, None);
+    let input = [g1_to_bytes(G1_NON_SUBGROUP_POINT), g2_to_bytes(g2)].concat();
+    assert_eq!(run_bls12_381_precompile("bls12_pairing", 32, &input)?, None);
+
+    // Invalid input length.
+    let input = [g1_to_bytes(g1), g2_to_bytes(g2), vec![0]].concat();
+    assert_eq!(run_bls12_381_precompile("bls12_pairing", 32, &input)?, None);
+
+    Ok(())
+}
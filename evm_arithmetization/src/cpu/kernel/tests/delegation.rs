@@ -0,0 +1,229 @@
+use std::collections::HashMap;
+
+use anyhow::Result;
+use ethereum_types::{Address, U256};
+use hex_literal::hex;
+use keccak_hash::keccak;
+use mpt_trie::partial_trie::{HashedPartialTrie, Node, PartialTrie};
+use plonky2::field::goldilocks_field::GoldilocksField as F;
+use rand::{thread_rng, Rng};
+
+use crate::cpu::kernel::aggregator::KERNEL;
+use crate::cpu::kernel::constants::context_metadata::ContextMetadata::GasLimit;
+use crate::cpu::kernel::constants::global_metadata::GlobalMetadata;
+use crate::cpu::kernel::eip7702_constants::DELEGATION_DESIGNATOR_PREFIX;
+use crate::cpu::kernel::interpreter::Interpreter;
+use crate::cpu::kernel::tests::account_code::prepare_interpreter;
+use crate::cpu::kernel::tests::transaction_parsing::prepare_interpreter_for_txn_parsing;
+use crate::generation::mpt::AccountRlp;
+use crate::memory::segments::Segment;
+use crate::witness::operation::CONTEXT_SCALING_FACTOR;
+
+fn designator(delegate: Address) -> Vec<u8> {
+    [&DELEGATION_DESIGNATOR_PREFIX[..], delegate.as_bytes()].concat()
+}
+
+fn test_account(code: &[u8]) -> AccountRlp {
+    AccountRlp {
+        nonce: U256::from(1111),
+        balance: U256::from(2222),
+        storage_root: HashedPartialTrie::from(Node::Empty).hash(),
+        code_hash: keccak(code),
+    }
+}
+
+/// Inserts an account with the given code at a random address, and returns
+/// the interpreter with an empty stack, along with the address.
+fn interpreter_with_code(code: &[u8]) -> Result<(Interpreter<F>, Address)> {
+    let mut interpreter: Interpreter<F> = Interpreter::new(0, vec![], None);
+    let address: Address = thread_rng().gen();
+    prepare_interpreter(&mut interpreter, address, &test_account(code))?;
+    interpreter.pop().expect("The stack should not be empty");
+    interpreter.pop().expect("The stack should not be empty");
+    assert!(interpreter.stack().is_empty());
+    interpreter.generation_state.inputs.contract_code =
+        HashMap::from([(keccak(code), code.to_vec())]);
+    Ok((interpreter, address))
+}
+
+fn run_function(interpreter: &mut Interpreter<F>, label: &str, args: &[U256]) -> Result<Vec<U256>> {
+    interpreter.generation_state.registers.program_counter = KERNEL.global_labels[label];
+    interpreter
+        .push(0xDEADBEEFu32.into())
+        .expect("The stack should not overflow");
+    for &arg in args.iter().rev() {
+        interpreter
+            .push(arg)
+            .expect("The stack should not overflow");
+    }
+    interpreter.run()?;
+    Ok(interpreter.stack())
+}
+
+fn address_to_u256(address: Address) -> U256 {
+    U256::from_big_endian(address.as_bytes())
+}
+
+#[test]
+fn test_get_delegate() -> Result<()> {
+    let delegate: Address = thread_rng().gen();
+    let (mut interpreter, address) = interpreter_with_code(&designator(delegate))?;
+
+    let stack = run_function(
+        &mut interpreter,
+        "get_delegate",
+        &[address_to_u256(address)],
+    )?;
+    assert_eq!(stack, vec![address_to_u256(delegate)]);
+
+    Ok(())
+}
+
+#[test]
+fn test_get_delegate_not_delegated() -> Result<()> {
+    // Same length as a designator, but another prefix, so that the prover's claim
+    // that the code isn't a designator is checked against the loaded code.
+    let mut code = designator(thread_rng().gen());
+    code[0] = 0x60;
+    let (mut interpreter, address) = interpreter_with_code(&code)?;
+
+    let stack = run_function(
+        &mut interpreter,
+        "get_delegate",
+        &[address_to_u256(address)],
+    )?;
+    assert_eq!(stack, vec![U256::zero()]);
+
+    Ok(())
+}
+
+#[test]
+fn test_get_delegate_empty_account() -> Result<()> {
+    let mut interpreter: Interpreter<F> = Interpreter::new(0, vec![], None);
+    let address: Address = thread_rng().gen();
+    prepare_interpreter(&mut interpreter, thread_rng().gen(), &test_account(&[]))?;
+    interpreter.pop().expect("The stack should not be empty");
+    interpreter.pop().expect("The stack should not be empty");
+
+    let stack = run_function(
+        &mut interpreter,
+        "get_delegate",
+        &[address_to_u256(address)],
+    )?;
+    assert_eq!(stack, vec![U256::zero()]);
+
+    Ok(())
+}
+
+#[test]
+fn test_extcodesize_delegated() -> Result<()> {
+    // EXTCODE* operate on the designator itself, not on the delegate's code.
+    let code = designator(thread_rng().gen());
+    let (mut interpreter, address) = interpreter_with_code(&code)?;
+
+    let stack = run_function(&mut interpreter, "extcodesize", &[address_to_u256(address)])?;
+    assert_eq!(
+        stack,
+        vec![U256::one() << CONTEXT_SCALING_FACTOR, code.len().into()]
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_extcodehash_delegated() -> Result<()> {
+    let code = designator(thread_rng().gen());
+    let (mut interpreter, address) = interpreter_with_code(&code)?;
+
+    let stack = run_function(&mut interpreter, "extcodehash", &[address_to_u256(address)])?;
+    assert_eq!(stack, vec![U256::from_big_endian(keccak(&code).as_bytes())]);
+
+    Ok(())
+}
+
+#[test]
+fn test_load_delegated_code() -> Result<()> {
+    let ctx = 1;
+
+    // A non-delegated account's own code is loaded.
+    let code = hex!("6042600052").to_vec();
+    let (mut interpreter, address) = interpreter_with_code(&code)?;
+    let stack = run_function(
+        &mut interpreter,
+        "load_delegated_code_padded",
+        &[
+            address_to_u256(address),
+            (ctx << CONTEXT_SCALING_FACTOR).into(),
+        ],
+    )?;
+    assert_eq!(stack, vec![code.len().into()]);
+
+    // A delegated account's designator is followed, to a delegate which doesn't
+    // exist here, and so has no code.
+    let (mut interpreter, address) = interpreter_with_code(&designator(thread_rng().gen()))?;
+    let stack = run_function(
+        &mut interpreter,
+        "load_delegated_code_padded",
+        &[
+            address_to_u256(address),
+            (ctx << CONTEXT_SCALING_FACTOR).into(),
+        ],
+    )?;
+    assert_eq!(stack, vec![U256::zero()]);
+
+    Ok(())
+}
+
+#[test]
+fn test_process_authorization_list() -> Result<()> {
+    // The set-code transaction of `process_type_4_txn()`, with a single
+    // authorization delegating the account of the key
+    // 0x4c0883a69102937d6231471b5dbb6204fe5129617082792ae468d01a3f362318 to 0xff,
+    // with nonce 0 on chain 1337.
+    let txn = hex!("04f8ca820539018203e88201f482ea6094000000000000000000000000000000000000000201824242c0f85ef85c8205399400000000000000000000000000000000000000ff8080a0683748c05c6f47c5cc947e6e70865eb1d00abfc1f520e557f943621f79c95ed1a019828360b8c23ba7c5a19919ecd3826365a273430ced31450a13e36801601e0601a069842b019d656ce5ce4d17a8f6a4e8614fb1e6dc644e7e9f11790489ac2d7ac7a07c3c6ee3039db74cd187a800222262c0d261dea3737f61be55bcc450e652943d").to_vec();
+    let authority = Address::from(hex!("2c7536e3605d9c16a7a3d7b1898e529396a65c23"));
+    let delegate = Address::from_low_u64_be(0xff);
+
+    // Start from a state trie with an unrelated account, so that the authority
+    // account gets created.
+    let mut interpreter: Interpreter<F> = Interpreter::new(0, vec![], None);
+    prepare_interpreter(&mut interpreter, thread_rng().gen(), &test_account(&[]))?;
+    interpreter.pop().expect("The stack should not be empty");
+    interpreter.pop().expect("The stack should not be empty");
+
+    let context = interpreter.context();
+    interpreter.generation_state.memory.contexts[context].segments
+        [Segment::ContextMetadata.unscale()]
+    .set(GasLimit.unscale(), U256::from(1000000000000u64));
+    run_function(&mut interpreter, "init_access_lists", &[])?;
+
+    prepare_interpreter_for_txn_parsing(
+        &mut interpreter,
+        KERNEL.global_labels["process_type_4_txn"],
+        KERNEL.global_labels["process_normalized_txn"],
+        txn,
+    )?;
+    interpreter.run()?;
+    while interpreter.stack_len() > 0 {
+        interpreter.pop().expect("The stack should not be empty");
+    }
+
+    interpreter.set_global_metadata_field(GlobalMetadata::BlockChainId, 1337.into());
+    let stack = run_function(&mut interpreter, "process_authorization_list", &[])?;
+    assert!(stack.is_empty());
+
+    // The authority now delegates to 0xff, and its nonce was incremented.
+    interpreter.generation_state.inputs.contract_code =
+        HashMap::from([(keccak(designator(delegate)), designator(delegate))]);
+    let stack = run_function(
+        &mut interpreter,
+        "get_delegate",
+        &[address_to_u256(authority)],
+    )?;
+    assert_eq!(stack, vec![address_to_u256(delegate)]);
+    interpreter.pop().expect("The stack should not be empty");
+    let stack = run_function(&mut interpreter, "nonce", &[address_to_u256(authority)])?;
+    assert_eq!(stack, vec![U256::one()]);
+
+    Ok(())
+}
@@ -0,0 +1,110 @@
+use anyhow::Result;
+use ethereum_types::{BigEndianHash, H256, U256};
+use keccak_hash::keccak;
+use mpt_trie::nibbles::Nibbles;
+use mpt_trie::partial_trie::{HashedPartialTrie, Node, PartialTrie};
+use plonky2::field::goldilocks_field::GoldilocksField as F;
+use rand::{thread_rng, Rng};
+
+use crate::cpu::kernel::aggregator::KERNEL;
+use crate::cpu::kernel::constants::global_metadata::GlobalMetadata;
+use crate::cpu::kernel::interpreter::Interpreter;
+use crate::cpu::kernel::prague_constants::{
+    HISTORY_SERVE_WINDOW, HISTORY_STORAGE_ACCOUNT, HISTORY_STORAGE_ADDRESS_HASHED,
+};
+use crate::cpu::kernel::tests::account_code::initialize_mpts;
+use crate::generation::TrieInputs;
+use crate::memory::segments::Segment;
+
+/// Returns an interpreter whose state only contains the history storage
+/// contract, with an empty storage, at the given block.
+fn prepare_interpreter(block_number: U256, parent_hash: U256) -> Result<Interpreter<F>> {
+    let mut state_trie = HashedPartialTrie::from(Node::Empty);
+    state_trie.insert(
+        Nibbles::from_h256_be(HISTORY_STORAGE_ADDRESS_HASHED),
+        rlp::encode(&HISTORY_STORAGE_ACCOUNT).to_vec(),
+    )?;
+    let trie_inputs = TrieInputs {
+        state_trie,
+        transactions_trie: Node::Empty.into(),
+        receipts_trie: Node::Empty.into(),
+        storage_tries: vec![(HISTORY_STORAGE_ADDRESS_HASHED, Node::Empty.into())],
+    };
+
+    let mut interpreter: Interpreter<F> = Interpreter::new(0, vec![], None);
+    initialize_mpts(&mut interpreter, &trie_inputs);
+    interpreter.set_global_metadata_field(GlobalMetadata::BlockNumber, block_number);
+    let mut block_hashes = vec![U256::zero(); 256];
+    block_hashes[255] = parent_hash;
+    interpreter.set_memory_segment(Segment::BlockHashes, block_hashes);
+
+    Ok(interpreter)
+}
+
+/// Runs `set_parent_block_hash`, which returns to `txn_loop`.
+fn run_set_parent_block_hash(interpreter: &mut Interpreter<F>) -> Result<()> {
+    interpreter.generation_state.registers.program_counter =
+        KERNEL.global_labels["set_parent_block_hash"];
+    interpreter.halt_offsets = vec![KERNEL.global_labels["txn_loop"]];
+    interpreter.run()?;
+    assert!(interpreter.stack().is_empty());
+    Ok(())
+}
+
+/// Reads the given slot of the history storage contract.
+fn read_slot(interpreter: &mut Interpreter<F>, slot: U256) -> Result<U256> {
+    let storage_key = U256::from_big_endian(keccak(H256::from_uint(&slot)).as_bytes());
+    interpreter.generation_state.registers.program_counter = KERNEL.global_labels["search_slot"];
+    interpreter.halt_offsets = vec![0xdeadbeef];
+    interpreter
+        .push(0xDEADBEEFu32.into())
+        .expect("The stack should not overflow");
+    interpreter
+        .push(U256::zero())
+        .expect("The stack should not overflow");
+    interpreter
+        .push(storage_key)
+        .expect("The stack should not overflow");
+    interpreter
+        .push(U256::from_big_endian(
+            HISTORY_STORAGE_ADDRESS_HASHED.as_bytes(),
+        ))
+        .expect("The stack should not overflow");
+    interpreter.run()?;
+    Ok(interpreter.pop().expect("The stack should not be empty"))
+}
+
+#[test]
+fn test_set_parent_block_hash() -> Result<()> {
+    // The slot of the parent of block `HISTORY_SERVE_WINDOW + 5` wraps around the
+    // ring buffer, to `(HISTORY_SERVE_WINDOW + 4) % HISTORY_SERVE_WINDOW = 4`.
+    let block_number = HISTORY_SERVE_WINDOW.value + 5;
+    let parent_hash = U256::from_big_endian(&thread_rng().gen::<H256>().0);
+    let mut interpreter = prepare_interpreter(block_number, parent_hash)?;
+
+    run_set_parent_block_hash(&mut interpreter)?;
+
+    assert_eq!(read_slot(&mut interpreter, 4.into())?, parent_hash);
+    assert_eq!(
+        read_slot(&mut interpreter, HISTORY_SERVE_WINDOW.value + 4)?,
+        U256::zero()
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_set_parent_block_hash_genesis() -> Result<()> {
+    // There is no parent to store for the genesis block.
+    let parent_hash = U256::from_big_endian(&thread_rng().gen::<H256>().0);
+    let mut interpreter = prepare_interpreter(U256::zero(), parent_hash)?;
+
+    run_set_parent_block_hash(&mut interpreter)?;
+
+    assert_eq!(
+        read_slot(&mut interpreter, HISTORY_SERVE_WINDOW.value - 1)?,
+        U256::zero()
+    );
+
+    Ok(())
+}
@@ -82,6 +82,7 @@ fn test_init_exc_stop() {
         signed_txns: vec![],
         burn_addr: None,
         withdrawals: vec![],
+        process_requests: false,
         tries: TrieInputs {
             state_trie: state_trie_before,
             transactions_trie,
@@ -11,9 +11,13 @@ mod block_hash;
 mod bls381;
 mod bn254;
 mod core;
+#[cfg(feature = "eth_mainnet")]
+mod delegation;
 mod ecc;
 mod exp;
 mod hash;
+#[cfg(feature = "eth_mainnet")]
+mod history_storage;
 mod init_exc_stop;
 mod kernel_consistency;
 mod log;
@@ -21,6 +25,8 @@ mod mcopy;
 mod mpt;
 mod packing;
 mod receipt;
+#[cfg(feature = "eth_mainnet")]
+mod requests;
 mod rlp;
 mod signed_syscalls;
 mod transaction_parsing;
@@ -11,7 +11,7 @@ use crate::cpu::kernel::constants::txn_fields::NormalizedTxnField;
 use crate::cpu::kernel::constants::INITIAL_RLP_ADDR;
 use crate::cpu::kernel::interpreter::Interpreter;
 use crate::cpu::kernel::tests::account_code::initialize_mpts;
-use crate::generation::mpt::{LegacyReceiptRlp, LogRlp};
+use crate::generation::mpt::{parse_receipts, LegacyReceiptRlp, LogRlp};
 use crate::memory::segments::Segment;
 
 #[test]
@@ -101,6 +101,42 @@ fn test_process_receipt() -> Result<()> {
     Ok(())
 }
 
+#[test]
+fn test_typed_receipt_encoding() -> Result<()> {
+    let encode_receipt = KERNEL.global_labels["encode_receipt"];
+    let retdest = 0xDEADBEEFu32.into();
+
+    let receipt = LegacyReceiptRlp {
+        status: false,
+        cum_gas_used: 0x5208.into(),
+        bloom: vec![0; 256].into(),
+        logs: vec![],
+    };
+
+    for txn_type in 1..=4 {
+        // Typed receipts are encoded as RLP(txn_type||RLP(receipt)).
+        let typed_receipt = receipt.encode(txn_type);
+        let expected_rlp = rlp::encode(&typed_receipt);
+
+        let rlp_addr = U256::from(Segment::RlpRaw as usize);
+        let initial_stack: Vec<U256> = vec![retdest, 0.into(), 0.into(), rlp_addr];
+        let mut interpreter: Interpreter<F> = Interpreter::new(encode_receipt, initial_stack, None);
+        let trie_data = parse_receipts(&typed_receipt).expect("The receipt should be valid");
+        assert_eq!(trie_data[0], txn_type.into());
+        interpreter.set_memory_segment(Segment::TrieData, trie_data);
+
+        interpreter.run()?;
+        let rlp_pos = interpreter.pop().expect("The stack should not be empty");
+
+        let rlp_read: &[u8] = &interpreter.get_rlp_memory();
+
+        assert_eq!((rlp_pos - rlp_addr).as_usize(), expected_rlp.len());
+        assert_eq!(&rlp_read[..expected_rlp.len()], &expected_rlp[..]);
+    }
+
+    Ok(())
+}
+
 /// Values taken from the block 1000000 of Goerli: https://goerli.etherscan.io/txs?block=1000000
 #[test]
 fn test_receipt_encoding() -> Result<()> {
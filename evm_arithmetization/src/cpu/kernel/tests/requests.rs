@@ -0,0 +1,355 @@
+use std::collections::HashMap;
+
+use anyhow::Result;
+use ethereum_types::{Address, H256, U256};
+use hex_literal::hex;
+use keccak_hash::keccak;
+use mpt_trie::nibbles::Nibbles;
+use mpt_trie::partial_trie::{HashedPartialTrie, Node, PartialTrie};
+use plonky2::field::goldilocks_field::GoldilocksField as F;
+use rand::{thread_rng, Rng};
+
+use crate::cpu::kernel::aggregator::KERNEL;
+use crate::cpu::kernel::constants::context_metadata::ContextMetadata;
+use crate::cpu::kernel::constants::global_metadata::GlobalMetadata;
+use crate::cpu::kernel::interpreter::Interpreter;
+use crate::cpu::kernel::prague_constants::{
+    CONSOLIDATION_REQUEST_PREDEPLOY_ADDRESS, CONSOLIDATION_REQUEST_TYPE, DEPOSIT_EVENT_DATA_LEN,
+    DEPOSIT_EVENT_SIGNATURE_HASH, DEPOSIT_REQUEST_LEN, DEPOSIT_REQUEST_TYPE, SYSTEM_ADDRESS,
+    WITHDRAWAL_REQUEST_PREDEPLOY_ADDRESS, WITHDRAWAL_REQUEST_TYPE,
+};
+use crate::cpu::kernel::tests::account_code::initialize_mpts;
+use crate::cpu::kernel::tests::{run_interpreter_with_memory, InterpreterMemoryInitialization};
+use crate::generation::mpt::{AccountRlp, LegacyReceiptRlp, LogRlp};
+use crate::generation::state::all_requests_prover_inputs;
+use crate::generation::TrieInputs;
+use crate::memory::segments::Segment;
+use crate::util::sha2;
+
+/// Returns its caller as a 32-byte word:
+/// `CALLER PUSH0 MSTORE PUSH1 32 PUSH0 RETURN`.
+const RETURN_CALLER_CODE: [u8; 7] = hex!("335f5260205ff3");
+
+/// Returns nothing: `STOP`.
+const STOP_CODE: [u8; 1] = hex!("00");
+
+/// Returns an interpreter whose state contains the withdrawal requests
+/// predeploy with [`RETURN_CALLER_CODE`], and the consolidation requests
+/// predeploy with [`STOP_CODE`], and whose block has the given typed receipts.
+fn prepare_interpreter(receipts: &[(u8, LegacyReceiptRlp)]) -> Result<Interpreter<F>> {
+    let mut receipts_trie = HashedPartialTrie::from(Node::Empty);
+    for (i, (txn_type, receipt)) in receipts.iter().enumerate() {
+        receipts_trie.insert(
+            Nibbles::from_bytes_be(&rlp::encode(&i))?,
+            receipt.encode(*txn_type),
+        )?;
+    }
+
+    let mut state_trie = HashedPartialTrie::from(Node::Empty);
+    let mut storage_tries = vec![];
+    let mut contract_code = HashMap::new();
+    for (address, code) in [
+        (
+            WITHDRAWAL_REQUEST_PREDEPLOY_ADDRESS,
+            &RETURN_CALLER_CODE[..],
+        ),
+        (CONSOLIDATION_REQUEST_PREDEPLOY_ADDRESS, &STOP_CODE[..]),
+    ] {
+        let account = AccountRlp {
+            nonce: U256::one(),
+            balance: U256::zero(),
+            storage_root: HashedPartialTrie::from(Node::Empty).hash(),
+            code_hash: keccak(code),
+        };
+        state_trie.insert(
+            Nibbles::from_h256_be(keccak(address)),
+            rlp::encode(&account).to_vec(),
+        )?;
+        storage_tries.push((keccak(address), Node::Empty.into()));
+        contract_code.insert(keccak(code), code.to_vec());
+    }
+    let trie_inputs = TrieInputs {
+        state_trie,
+        transactions_trie: Node::Empty.into(),
+        receipts_trie,
+        storage_tries,
+    };
+
+    let mut interpreter: Interpreter<F> = Interpreter::new(0, vec![], None);
+    initialize_mpts(&mut interpreter, &trie_inputs);
+    interpreter.generation_state.inputs.contract_code = contract_code;
+    interpreter.set_global_metadata_field(GlobalMetadata::TxnNumberAfter, receipts.len().into());
+
+    Ok(interpreter)
+}
+
+fn run_function(interpreter: &mut Interpreter<F>, label: &str, args: &[U256]) -> Result<Vec<U256>> {
+    interpreter.generation_state.registers.program_counter = KERNEL.global_labels[label];
+    interpreter.halt_offsets = vec![0xdeadbeef];
+    interpreter
+        .push(0xDEADBEEFu32.into())
+        .expect("The stack should not overflow");
+    for &arg in args.iter().rev() {
+        interpreter
+            .push(arg)
+            .expect("The stack should not overflow");
+    }
+    interpreter.run()?;
+    Ok(interpreter.stack())
+}
+
+fn address_to_u256(address: Address) -> U256 {
+    U256::from_big_endian(address.as_bytes())
+}
+
+fn hash_to_bytes(hash: U256) -> [u8; 32] {
+    let mut bytes = [0; 32];
+    hash.to_big_endian(&mut bytes);
+    bytes
+}
+
+/// Returns the ABI encoding of the `DepositEvent` emitted for the given
+/// deposit request, i.e. of its `pubkey`, `withdrawal_credentials`, `amount`,
+/// `signature` and `index` fields, as `bytes`.
+fn deposit_event_data(request: &[u8]) -> Vec<u8> {
+    let mut heads = vec![];
+    let mut tails = vec![];
+    let mut start = 0;
+    for len in [48, 32, 8, 96, 8] {
+        heads.extend(hash_to_bytes((5 * 32 + tails.len()).into()));
+        tails.extend(hash_to_bytes(len.into()));
+        tails.extend(&request[start..start + len]);
+        tails.resize(tails.len().next_multiple_of(32), 0);
+        start += len;
+    }
+    [heads, tails].concat()
+}
+
+fn deposit_log(address: Address, request: &[u8]) -> LogRlp {
+    LogRlp {
+        address,
+        topics: vec![DEPOSIT_EVENT_SIGNATURE_HASH, thread_rng().gen()],
+        data: deposit_event_data(request).into(),
+    }
+}
+
+fn receipt(logs: Vec<LogRlp>) -> LegacyReceiptRlp {
+    LegacyReceiptRlp {
+        status: true,
+        cum_gas_used: 21_000.into(),
+        bloom: vec![0; 256].into(),
+        logs,
+    }
+}
+
+#[test]
+fn test_hash_requests() -> Result<()> {
+    let data: Vec<u8> = (0..3 * DEPOSIT_REQUEST_LEN)
+        .map(|_| thread_rng().gen())
+        .collect();
+
+    // The requests lie at @SEGMENT_KERNEL_GENERAL[2..].
+    let interpreter: Interpreter<F> =
+        run_interpreter_with_memory(InterpreterMemoryInitialization {
+            label: "hash_requests".to_string(),
+            stack: vec![
+                U256::from(WITHDRAWAL_REQUEST_TYPE),
+                data.len().into(),
+                0xdeadbeefu32.into(),
+            ],
+            segment: Segment::KernelGeneral,
+            memory: vec![(2, data.iter().map(|&byte| byte.into()).collect())],
+        })?;
+    assert_eq!(
+        interpreter.stack(),
+        vec![sha2([&[WITHDRAWAL_REQUEST_TYPE][..], &data].concat())]
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_hash_requests_empty() -> Result<()> {
+    // Empty requests are left out of the commitment, which is marked by a zero
+    // hash.
+    let interpreter: Interpreter<F> =
+        run_interpreter_with_memory(InterpreterMemoryInitialization {
+            label: "hash_requests".to_string(),
+            stack: vec![
+                U256::from(CONSOLIDATION_REQUEST_TYPE),
+                U256::zero(),
+                0xdeadbeefu32.into(),
+            ],
+            segment: Segment::KernelGeneral,
+            memory: vec![],
+        })?;
+    assert_eq!(interpreter.stack(), vec![U256::zero()]);
+
+    Ok(())
+}
+
+#[test]
+fn test_system_call() -> Result<()> {
+    let mut interpreter = prepare_interpreter(&[])?;
+
+    let stack = run_function(
+        &mut interpreter,
+        "system_call",
+        &[address_to_u256(WITHDRAWAL_REQUEST_PREDEPLOY_ADDRESS)],
+    )?;
+    assert!(stack.is_empty());
+
+    // The predeploy was called by the system address, and returned it.
+    let returndata_size = interpreter.generation_state.memory.contexts[0].segments
+        [Segment::ContextMetadata.unscale()]
+    .get(ContextMetadata::ReturndataSize.unscale());
+    assert_eq!(returndata_size, 32.into());
+    assert_eq!(
+        interpreter.get_memory_segment_bytes(Segment::Returndata)[..32],
+        H256::from(SYSTEM_ADDRESS).0
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_system_call_no_code() -> Result<()> {
+    // Calling an account without code invalidates the block.
+    let mut interpreter = prepare_interpreter(&[])?;
+
+    assert!(run_function(
+        &mut interpreter,
+        "system_call",
+        &[address_to_u256(thread_rng().gen())],
+    )
+    .is_err());
+
+    Ok(())
+}
+
+#[test]
+fn test_deposit_event_data() {
+    let request: Vec<u8> = (0..DEPOSIT_REQUEST_LEN)
+        .map(|_| thread_rng().gen())
+        .collect();
+    assert_eq!(deposit_event_data(&request).len(), DEPOSIT_EVENT_DATA_LEN);
+}
+
+#[test]
+fn test_requests() -> Result<()> {
+    let deposit_contract: Address = thread_rng().gen();
+    let deposits: Vec<u8> = (0..2 * DEPOSIT_REQUEST_LEN)
+        .map(|_| thread_rng().gen())
+        .collect();
+    let (first, second) = deposits.split_at(DEPOSIT_REQUEST_LEN);
+
+    // Only the `DepositEvent` logs of the deposit contract are deposit
+    // requests, and they may lie in receipts of any type.
+    let other_log = LogRlp {
+        address: thread_rng().gen(),
+        ..deposit_log(deposit_contract, first)
+    };
+    let receipts = [
+        (
+            0,
+            receipt(vec![other_log, deposit_log(deposit_contract, first)]),
+        ),
+        (2, receipt(vec![])),
+        (2, receipt(vec![deposit_log(deposit_contract, second)])),
+    ];
+
+    // The withdrawal requests are the returned system address, and there are no
+    // consolidation requests, so their hash is left out.
+    let deposits_hash = sha2([&[DEPOSIT_REQUEST_TYPE][..], &deposits].concat());
+    let withdrawals_hash = sha2(
+        [
+            &[WITHDRAWAL_REQUEST_TYPE][..],
+            &H256::from(SYSTEM_ADDRESS).0[..],
+        ]
+        .concat(),
+    );
+    let requests_hash = sha2(
+        [
+            hash_to_bytes(deposits_hash),
+            hash_to_bytes(withdrawals_hash),
+        ]
+        .concat(),
+    );
+
+    let mut interpreter = prepare_interpreter(&receipts)?;
+    interpreter.generation_state.requests_prover_inputs = all_requests_prover_inputs(true);
+    interpreter.set_global_metadata_field(
+        GlobalMetadata::DepositContractAddress,
+        address_to_u256(deposit_contract),
+    );
+    interpreter.set_global_metadata_field(GlobalMetadata::RequestsHash, requests_hash);
+    let stack = run_function(&mut interpreter, "requests", &[])?;
+    assert!(stack.is_empty());
+
+    // A mismatching commitment invalidates the block.
+    let mut interpreter = prepare_interpreter(&receipts[1..])?;
+    interpreter.generation_state.requests_prover_inputs = all_requests_prover_inputs(true);
+    interpreter.set_global_metadata_field(
+        GlobalMetadata::DepositContractAddress,
+        address_to_u256(deposit_contract),
+    );
+    interpreter.set_global_metadata_field(GlobalMetadata::RequestsHash, requests_hash);
+    assert!(run_function(&mut interpreter, "requests", &[]).is_err());
+
+    Ok(())
+}
+
+#[test]
+fn test_deposit_requests_malformed_log() -> Result<()> {
+    // A `DepositEvent` of the deposit contract which isn't laid out as expected
+    // invalidates the block.
+    let deposit_contract: Address = thread_rng().gen();
+    let request: Vec<u8> = (0..DEPOSIT_REQUEST_LEN)
+        .map(|_| thread_rng().gen())
+        .collect();
+
+    let mut truncated = deposit_log(deposit_contract, &request);
+    truncated.data = truncated.data[..DEPOSIT_EVENT_DATA_LEN - 32]
+        .to_vec()
+        .into();
+    let mut misplaced = deposit_log(deposit_contract, &request);
+    let mut data = misplaced.data.to_vec();
+    data[31] += 32;
+    misplaced.data = data.into();
+
+    for log in [truncated, misplaced] {
+        let mut interpreter = prepare_interpreter(&[(0, receipt(vec![log]))])?;
+        interpreter.set_global_metadata_field(
+            GlobalMetadata::DepositContractAddress,
+            address_to_u256(deposit_contract),
+        );
+        assert!(run_function(&mut interpreter, "deposit_requests", &[]).is_err());
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_deposit_requests_missing_receipt() -> Result<()> {
+    // Every transaction of the block must have a receipt.
+    let mut interpreter = prepare_interpreter(&[(0, receipt(vec![]))])?;
+    interpreter.set_global_metadata_field(GlobalMetadata::TxnNumberAfter, 2.into());
+    assert!(run_function(&mut interpreter, "deposit_requests", &[]).is_err());
+
+    Ok(())
+}
+
+#[test]
+fn test_requests_not_provided() -> Result<()> {
+    // In all but the last batch of a block, nothing is checked.
+    let mut interpreter = prepare_interpreter(&[])?;
+    interpreter.generation_state.requests_prover_inputs = all_requests_prover_inputs(false);
+    interpreter.set_global_metadata_field(
+        GlobalMetadata::RequestsHash,
+        U256::from_big_endian(&thread_rng().gen::<H256>().0),
+    );
+    let stack = run_function(&mut interpreter, "requests", &[])?;
+    assert!(stack.is_empty());
+
+    Ok(())
+}
@@ -11,6 +11,8 @@ mod parse_type_1_txn;
 mod parse_type_2_txn;
 #[cfg(feature = "eth_mainnet")]
 mod parse_type_3_txn;
+#[cfg(feature = "eth_mainnet")]
+mod parse_type_4_txn;
 
 pub(crate) fn prepare_interpreter_for_txn_parsing<F: RichField>(
     interpreter: &mut Interpreter<F>,
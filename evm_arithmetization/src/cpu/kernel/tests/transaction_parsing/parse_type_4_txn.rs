@@ -0,0 +1,94 @@
+use anyhow::Result;
+use ethereum_types::U256;
+use hex_literal::hex;
+use plonky2::field::goldilocks_field::GoldilocksField as F;
+use NormalizedTxnField::*;
+
+use crate::cpu::kernel::aggregator::KERNEL;
+use crate::cpu::kernel::constants::global_metadata::GlobalMetadata;
+use crate::cpu::kernel::constants::txn_fields::NormalizedTxnField;
+use crate::cpu::kernel::interpreter::Interpreter;
+use crate::cpu::kernel::tests::transaction_parsing::prepare_interpreter_for_txn_parsing;
+
+#[test]
+fn process_type_4_txn() -> Result<()> {
+    // Set-code transaction with chain_id=1337, nonce=1, max_priority_fee_per_gas=1000,
+    // max_fee_per_gas=500, gas=60000, to=0x02, value=1, data=0x4242, access_list=[],
+    // signed with the key 0x45a915e4d060149eb4365960e6a7a45f334393093061116b197e3240065ff2d8.
+    // The authorization list contains a single tuple [1337, 0xff, 0, y_parity, r, s],
+    // signed with the key 0x4c0883a69102937d6231471b5dbb6204fe5129617082792ae468d01a3f362318.
+    let txn = hex!("04f8ca820539018203e88201f482ea6094000000000000000000000000000000000000000201824242c0f85ef85c8205399400000000000000000000000000000000000000ff8080a0683748c05c6f47c5cc947e6e70865eb1d00abfc1f520e557f943621f79c95ed1a019828360b8c23ba7c5a19919ecd3826365a273430ced31450a13e36801601e0601a069842b019d656ce5ce4d17a8f6a4e8614fb1e6dc644e7e9f11790489ac2d7ac7a07c3c6ee3039db74cd187a800222262c0d261dea3737f61be55bcc450e652943d").to_vec();
+
+    let mut interpreter = Interpreter::<F>::new(0, vec![], None);
+    prepare_interpreter_for_txn_parsing(
+        &mut interpreter,
+        KERNEL.global_labels["process_type_4_txn"],
+        KERNEL.global_labels["process_normalized_txn"],
+        txn,
+    )?;
+
+    interpreter.run()?;
+
+    assert_eq!(interpreter.get_txn_field(ChainIdPresent), 1.into());
+    assert_eq!(interpreter.get_txn_field(ChainId), 1337.into());
+    assert_eq!(interpreter.get_txn_field(Nonce), 1.into());
+    assert_eq!(interpreter.get_txn_field(MaxPriorityFeePerGas), 1000.into());
+    assert_eq!(interpreter.get_txn_field(MaxFeePerGas), 500.into());
+    assert_eq!(interpreter.get_txn_field(GasLimit), 60000.into());
+    assert_eq!(interpreter.get_txn_field(To), 0x02.into());
+    assert_eq!(interpreter.get_txn_field(Value), 1.into());
+    assert_eq!(interpreter.get_txn_field(DataLen), 2.into());
+    assert_eq!(interpreter.get_txn_data(), &[0x42.into(), 0x42.into()]);
+    assert_eq!(interpreter.get_txn_field(YParity), 1.into());
+    assert_eq!(
+        interpreter.get_txn_field(R),
+        U256::from_big_endian(&hex!(
+            "69842b019d656ce5ce4d17a8f6a4e8614fb1e6dc644e7e9f11790489ac2d7ac7"
+        ))
+    );
+    assert_eq!(
+        interpreter.get_txn_field(S),
+        U256::from_big_endian(&hex!(
+            "7c3c6ee3039db74cd187a800222262c0d261dea3737f61be55bcc450e652943d"
+        ))
+    );
+    assert_eq!(
+        interpreter.get_txn_field(Origin),
+        U256::from_big_endian(&hex!("a94f5374fce5edbc8e2a8697c15331677e6ebf0b"))
+    );
+
+    // The authorization list is only processed after the sender has been charged,
+    // but its intrinsic cost is accounted for during parsing.
+    assert_eq!(
+        interpreter.get_global_metadata_field(GlobalMetadata::AuthListLen),
+        0x5e.into()
+    );
+    assert_eq!(
+        interpreter.get_global_metadata_field(GlobalMetadata::AccessListDataCost),
+        25_000.into()
+    );
+
+    Ok(())
+}
+
+#[test]
+fn process_type_4_txn_invalid_sig() -> Result<()> {
+    // Same transaction as `process_type_4_txn()`, with the exception that the `s`
+    // component in the signature is flipped (i.e. `s' = N - s`, where `N` is the
+    // order of the SECP256k1 prime subgroup).
+    // It should fail according to EIP-2 (`s` must be no greater than `N/2`).
+    let txn = hex!("04f8ca820539018203e88201f482ea6094000000000000000000000000000000000000000201824242c0f85ef85c8205399400000000000000000000000000000000000000ff8080a0683748c05c6f47c5cc947e6e70865eb1d00abfc1f520e557f943621f79c95ed1a019828360b8c23ba7c5a19919ecd3826365a273430ced31450a13e36801601e0601a069842b019d656ce5ce4d17a8f6a4e8614fb1e6dc644e7e9f11790489ac2d7ac7a083c3911cfc6248b32e7857ffdddd9d3de84cfe433bc93e7d6a159a3be9e3ad04").to_vec();
+
+    let mut interpreter = Interpreter::<F>::new(0, vec![], None);
+    prepare_interpreter_for_txn_parsing(
+        &mut interpreter,
+        KERNEL.global_labels["process_type_4_txn"],
+        KERNEL.global_labels["process_normalized_txn"],
+        txn,
+    )?;
+
+    let result = interpreter.run();
+    assert!(result.is_err());
+
+    Ok(())
+}
@@ -142,6 +142,139 @@ where
     }
 }
 
+/// Multiplication by an arbitrary 256-bit scalar.
+impl<T: FieldExt> Mul<U256> for CurveAff<T> {
+    type Output = CurveAff<T>;
+
+    fn mul(self, other: U256) -> Self {
+        self.mul_bits(other.bits(), |i| other.bit(i))
+    }
+}
+
+impl<T: FieldExt> CurveAff<T> {
+    /// Multiplication by a scalar given as big-endian bytes, for scalars which
+    /// don't fit in 256 bits.
+    #[cfg(test)]
+    pub(crate) fn mul_be_bytes(self, scalar: &[u8]) -> Self {
+        self.mul_bits(scalar.len() * 8, |i| {
+            (scalar[scalar.len() - 1 - i / 8] >> (i % 8)) & 1 == 1
+        })
+    }
+
+    /// Multiplication by the scalar whose `i`-th bit is `bit(i)`, for `i` below
+    /// `num_bits`. The intermediate points are kept in Jacobian coordinates,
+    /// which avoids an inversion per group operation.
+    fn mul_bits(self, num_bits: usize, bit: impl Fn(usize) -> bool) -> Self {
+        if self == CurveAff::<T>::unit() {
+            return self;
+        }
+
+        let mut acc = CurveJac::<T>::unit();
+        for i in (0..num_bits).rev() {
+            acc = acc.double();
+            if bit(i) {
+                acc = acc.add_affine(self);
+            }
+        }
+        acc.to_affine()
+    }
+}
+
+/// A point in Jacobian coordinates, representing the affine point
+/// (x / z^2, y / z^3). The point at infinity is any point with z = 0.
+/// Only valid for curves of the form y^2 = x^3 + b.
+#[derive(Debug, Copy, Clone, PartialEq)]
+struct CurveJac<T>
+where
+    T: FieldExt,
+{
+    x: T,
+    y: T,
+    z: T,
+}
+
+impl<T: FieldExt> CurveJac<T> {
+    const fn unit() -> Self {
+        CurveJac {
+            x: T::UNIT,
+            y: T::UNIT,
+            z: T::ZERO,
+        }
+    }
+
+    fn is_unit(&self) -> bool {
+        self.z == T::ZERO
+    }
+
+    /// Doubling formula `dbl-2009-l` from the Explicit-Formulas Database.
+    fn double(self) -> Self {
+        if self.is_unit() || self.y == T::ZERO {
+            return CurveJac::<T>::unit();
+        }
+        let a = self.x * self.x;
+        let b = self.y * self.y;
+        let c = b * b;
+        let t = self.x + b;
+        let d = T::new(2) * (t * t - a - c);
+        let e = T::new(3) * a;
+        let f = e * e;
+        let x = f - T::new(2) * d;
+        CurveJac {
+            x,
+            y: e * (d - x) - T::new(8) * c,
+            z: T::new(2) * self.y * self.z,
+        }
+    }
+
+    /// Mixed addition formula `madd-2004-hmv` from the Explicit-Formulas
+    /// Database, falling back to doubling when both points are equal.
+    fn add_affine(self, other: CurveAff<T>) -> Self {
+        if other == CurveAff::<T>::unit() {
+            return self;
+        }
+        if self.is_unit() {
+            return CurveJac {
+                x: other.x,
+                y: other.y,
+                z: T::UNIT,
+            };
+        }
+        let z1z1 = self.z * self.z;
+        let u2 = other.x * z1z1;
+        let s2 = other.y * self.z * z1z1;
+        let h = u2 - self.x;
+        let r = s2 - self.y;
+        if h == T::ZERO {
+            return if r == T::ZERO {
+                self.double()
+            } else {
+                CurveJac::<T>::unit()
+            };
+        }
+        let hh = h * h;
+        let hhh = h * hh;
+        let v = self.x * hh;
+        let x = r * r - hhh - T::new(2) * v;
+        CurveJac {
+            x,
+            y: r * (v - x) - self.y * hhh,
+            z: self.z * h,
+        }
+    }
+
+    fn to_affine(self) -> CurveAff<T> {
+        if self.is_unit() {
+            return CurveAff::<T>::unit();
+        }
+        let z_inv = self.z.inv();
+        let z_inv_sq = z_inv * z_inv;
+        CurveAff {
+            x: self.x * z_inv_sq,
+            y: self.y * z_inv_sq * z_inv,
+        }
+    }
+}
+
 #[derive(Debug, Copy, Clone, PartialEq)]
 pub(crate) struct CurveProj<T>
 where
@@ -568,9 +701,13 @@ pub mod bn254 {
 // <https://github.com/zkcrypto/bls12_381>.
 pub mod bls381 {
     use anyhow::{anyhow, Result};
+    #[cfg(test)]
+    use hex_literal::hex;
 
     use super::*;
     use crate::extension_tower::BLS_BASE;
+    #[cfg(test)]
+    use crate::extension_tower::BLS_SCALAR;
 
     const B_G1: BLS381 = BLS381 {
         val: U512([4, 0, 0, 0, 0, 0, 0, 0]),
@@ -733,6 +870,349 @@ pub mod bls381 {
         }
     }
 
+    #[cfg(test)]
+    const B_G2: Fp2<BLS381> = Fp2 { re: B_G1, im: B_G1 };
+
+    /// Returns whether the given point lies on the BLS12-381 curve. The point
+    /// at infinity, encoded as (0, 0), is considered to be on the curve.
+    #[cfg(test)]
+    pub(crate) fn g1_is_on_curve(p: CurveAff<BLS381>) -> bool {
+        p == CurveAff::<BLS381>::unit() || p.y * p.y == p.x * p.x * p.x + B_G1
+    }
+
+    /// Returns whether the given point lies on the twisted curve. The point
+    /// at infinity, encoded as (0, 0), is considered to be on the curve.
+    #[cfg(test)]
+    pub(crate) fn g2_is_on_curve(p: CurveAff<Fp2<BLS381>>) -> bool {
+        p == CurveAff::<Fp2<BLS381>>::unit() || p.y * p.y == p.x * p.x * p.x + B_G2
+    }
+
+    /// Returns whether the given point, assumed to be on the curve or on its
+    /// twist, belongs to the subgroup of order `BLS_SCALAR`.
+    #[cfg(test)]
+    pub(crate) fn is_in_subgroup<T: FieldExt>(p: CurveAff<T>) -> bool {
+        p * BLS_SCALAR == CurveAff::<T>::unit()
+    }
+
+    /// Maps a base field element to a point of G1, as specified in
+    /// <https://www.rfc-editor.org/rfc/rfc9380#section-6.6.3>: the simplified
+    /// SWU map onto an 11-isogenous curve, followed by the isogeny map and the
+    /// clearing of the cofactor.
+    #[cfg(test)]
+    pub(crate) fn map_fp_to_g1(u: BLS381) -> CurveAff<BLS381> {
+        let a = fp_from_bytes(&SSWU_A);
+        let b = fp_from_bytes(&SSWU_B);
+        let z = BLS381::new(SSWU_Z);
+
+        let u_sq = u * u;
+        let tv1 = z * z * u_sq * u_sq + z * u_sq;
+        let x1 = if tv1 == BLS381::ZERO {
+            b / (z * a)
+        } else {
+            (-b / a) * (BLS381::UNIT + tv1.inv())
+        };
+        let gx1 = x1 * x1 * x1 + a * x1 + b;
+        let (x, mut y) = if let Ok(y1) = gx1.sqrt() {
+            (x1, y1)
+        } else {
+            let x2 = z * u_sq * x1;
+            let gx2 = x2 * x2 * x2 + a * x2 + b;
+            // When gx1 isn't a square, gx2 = Z^3 * u^6 * gx1 is.
+            let y2 = gx2.sqrt().expect("gx2 should be a quadratic residue");
+            (x2, y2)
+        };
+        if u.val.bit(0) != y.val.bit(0) {
+            y = -y;
+        }
+
+        let eval = |coeffs: &[[u8; 48]]| {
+            coeffs
+                .iter()
+                .rev()
+                .fold(BLS381::ZERO, |acc, coeff| acc * x + fp_from_bytes(coeff))
+        };
+        let p = CurveAff::<BLS381> {
+            x: eval(&ISO_X_NUM) / eval(&ISO_X_DEN),
+            y: y * eval(&ISO_Y_NUM) / eval(&ISO_Y_DEN),
+        };
+
+        p * U256::from(H_EFF_G1)
+    }
+
+    #[cfg(test)]
+    fn fp_from_bytes(bytes: &[u8; 48]) -> BLS381 {
+        BLS381 {
+            val: U512::from_big_endian(bytes),
+        }
+    }
+
+    /// Effective cofactor used to clear the cofactor of G1, i.e. `1 - x`.
+    #[cfg(test)]
+    const H_EFF_G1: u64 = 0xd201000000010001;
+
+    /// Constants of the 11-isogenous curve y^2 = x^3 + A' * x + B' used by
+    /// the simplified SWU map, see section 8.8.1 of RFC 9380.
+    #[cfg(test)]
+    const SSWU_Z: usize = 11;
+    #[cfg(test)]
+    const SSWU_A: [u8; 48] = hex!("00144698a3b8e9433d693a02c96d4982b0ea985383ee66a8d8e8981aefd881ac98936f8da0e0f97f5cf428082d584c1d");
+    #[cfg(test)]
+    const SSWU_B: [u8; 48] = hex!("12e2908d11688030018b12e8753eee3b2016c1f0f24f4070a0b9c14fcef35ef55a23215a316ceaa5d1cc48e98e172be0");
+
+    // Coefficients of the 11-isogeny map, in increasing degree order, as
+    // listed in <https://www.rfc-editor.org/rfc/rfc9380#appendix-E.2>.
+    #[cfg(test)]
+    const ISO_X_NUM: [[u8; 48]; 12] = [
+        hex!("11a05f2b1e833340b809101dd99815856b303e88a2d7005ff2627b56cdb4e2c85610c2d5f2e62d6eaeac1662734649b7"),
+        hex!("17294ed3e943ab2f0588bab22147a81c7c17e75b2f6a8417f565e33c70d1e86b4838f2a6f318c356e834eef1b3cb83bb"),
+        hex!("0d54005db97678ec1d1048c5d10a9a1bce032473295983e56878e501ec68e25c958c3e3d2a09729fe0179f9dac9edcb0"),
+        hex!("1778e7166fcc6db74e0609d307e55412d7f5e4656a8dbf25f1b33289f1b330835336e25ce3107193c5b388641d9b6861"),
+        hex!("0e99726a3199f4436642b4b3e4118e5499db995a1257fb3f086eeb65982fac18985a286f301e77c451154ce9ac8895d9"),
+        hex!("1630c3250d7313ff01d1201bf7a74ab5db3cb17dd952799b9ed3ab9097e68f90a0870d2dcae73d19cd13c1c66f652983"),
+        hex!("0d6ed6553fe44d296a3726c38ae652bfb11586264f0f8ce19008e218f9c86b2a8da25128c1052ecaddd7f225a139ed84"),
+        hex!("17b81e7701abdbe2e8743884d1117e53356de5ab275b4db1a682c62ef0f2753339b7c8f8c8f475af9ccb5618e3f0c88e"),
+        hex!("080d3cf1f9a78fc47b90b33563be990dc43b756ce79f5574a2c596c928c5d1de4fa295f296b74e956d71986a8497e317"),
+        hex!("169b1f8e1bcfa7c42e0c37515d138f22dd2ecb803a0c5c99676314baf4bb1b7fa3190b2edc0327797f241067be390c9e"),
+        hex!("10321da079ce07e272d8ec09d2565b0dfa7dccdde6787f96d50af36003b14866f69b771f8c285decca67df3f1605fb7b"),
+        hex!("06e08c248e260e70bd1e962381edee3d31d79d7e22c837bc23c0bf1bc24c6b68c24b1b80b64d391fa9c8ba2e8ba2d229"),
+    ];
+    #[cfg(test)]
+    const ISO_X_DEN: [[u8; 48]; 11] = [
+        hex!("08ca8d548cff19ae18b2e62f4bd3fa6f01d5ef4ba35b48ba9c9588617fc8ac62b558d681be343df8993cf9fa40d21b1c"),
+        hex!("12561a5deb559c4348b4711298e536367041e8ca0cf0800c0126c2588c48bf5713daa8846cb026e9e5c8276ec82b3bff"),
+        hex!("0b2962fe57a3225e8137e629bff2991f6f89416f5a718cd1fca64e00b11aceacd6a3d0967c94fedcfcc239ba5cb83e19"),
+        hex!("03425581a58ae2fec83aafef7c40eb545b08243f16b1655154cca8abc28d6fd04976d5243eecf5c4130de8938dc62cd8"),
+        hex!("13a8e162022914a80a6f1d5f43e7a07dffdfc759a12062bb8d6b44e833b306da9bd29ba81f35781d539d395b3532a21e"),
+        hex!("0e7355f8e4e667b955390f7f0506c6e9395735e9ce9cad4d0a43bcef24b8982f7400d24bc4228f11c02df9a29f6304a5"),
+        hex!("0772caacf16936190f3e0c63e0596721570f5799af53a1894e2e073062aede9cea73b3538f0de06cec2574496ee84a3a"),
+        hex!("14a7ac2a9d64a8b230b3f5b074cf01996e7f63c21bca68a81996e1cdf9822c580fa5b9489d11e2d311f7d99bbdcc5a5e"),
+        hex!("0a10ecf6ada54f825e920b3dafc7a3cce07f8d1d7161366b74100da67f39883503826692abba43704776ec3a79a1d641"),
+        hex!("095fc13ab9e92ad4476d6e3eb3a56680f682b4ee96f7d03776df533978f31c1593174e4b4b7865002d6384d168ecdd0a"),
+        hex!("000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000001"),
+    ];
+    #[cfg(test)]
+    const ISO_Y_NUM: [[u8; 48]; 16] = [
+        hex!("090d97c81ba24ee0259d1f094980dcfa11ad138e48a869522b52af6c956543d3cd0c7aee9b3ba3c2be9845719707bb33"),
+        hex!("134996a104ee5811d51036d776fb46831223e96c254f383d0f906343eb67ad34d6c56711962fa8bfe097e75a2e41c696"),
+        hex!("00cc786baa966e66f4a384c86a3b49942552e2d658a31ce2c344be4b91400da7d26d521628b00523b8dfe240c72de1f6"),
+        hex!("01f86376e8981c217898751ad8746757d42aa7b90eeb791c09e4a3ec03251cf9de405aba9ec61deca6355c77b0e5f4cb"),
+        hex!("08cc03fdefe0ff135caf4fe2a21529c4195536fbe3ce50b879833fd221351adc2ee7f8dc099040a841b6daecf2e8fedb"),
+        hex!("16603fca40634b6a2211e11db8f0a6a074a7d0d4afadb7bd76505c3d3ad5544e203f6326c95a807299b23ab13633a5f0"),
+        hex!("04ab0b9bcfac1bbcb2c977d027796b3ce75bb8ca2be184cb5231413c4d634f3747a87ac2460f415ec961f8855fe9d6f2"),
+        hex!("0987c8d5333ab86fde9926bd2ca6c674170a05bfe3bdd81ffd038da6c26c842642f64550fedfe935a15e4ca31870fb29"),
+        hex!("09fc4018bd96684be88c9e221e4da1bb8f3abd16679dc26c1e8b6e6a1f20cabe69d65201c78607a360370e577bdba587"),
+        hex!("0e1bba7a1186bdb5223abde7ada14a23c42a0ca7915af6fe06985e7ed1e4d43b9b3f7055dd4eba6f2bafaaebca731c30"),
+        hex!("19713e47937cd1be0dfd0b8f1d43fb93cd2fcbcb6caf493fd1183e416389e61031bf3a5cce3fbafce813711ad011c132"),
+        hex!("18b46a908f36f6deb918c143fed2edcc523559b8aaf0c2462e6bfe7f911f643249d9cdf41b44d606ce07c8a4d0074d8e"),
+        hex!("0b182cac101b9399d155096004f53f447aa7b12a3426b08ec02710e807b4633f06c851c1919211f20d4c04f00b971ef8"),
+        hex!("0245a394ad1eca9b72fc00ae7be315dc757b3b080d4c158013e6632d3c40659cc6cf90ad1c232a6442d9d3f5db980133"),
+        hex!("05c129645e44cf1102a159f748c4a3fc5e673d81d7e86568d9ab0f5d396a7ce46ba1049b6579afb7866b1e715475224b"),
+        hex!("15e6be4e990f03ce4ea50b3b42df2eb5cb181d8f84965a3957add4fa95af01b2b665027efec01c7704b456be69c8b604"),
+    ];
+    #[cfg(test)]
+    const ISO_Y_DEN: [[u8; 48]; 16] = [
+        hex!("16112c4c3a9c98b252181140fad0eae9601a6de578980be6eec3232b5be72e7a07f3688ef60c206d01479253b03663c1"),
+        hex!("1962d75c2381201e1a0cbd6c43c348b885c84ff731c4d59ca4a10356f453e01f78a4260763529e3532f6102c2e49a03d"),
+        hex!("058df3306640da276faaae7d6e8eb15778c4855551ae7f310c35a5dd279cd2eca6757cd636f96f891e2538b53dbf67f2"),
+        hex!("16b7d288798e5395f20d23bf89edb4d1d115c5dbddbcd30e123da489e726af41727364f2c28297ada8d26d98445f5416"),
+        hex!("0be0e079545f43e4b00cc912f8228ddcc6d19c9f0f69bbb0542eda0fc9dec916a20b15dc0fd2ededda39142311a5001d"),
+        hex!("08d9e5297186db2d9fb266eaac783182b70152c65550d881c5ecd87b6f0f5a6449f38db9dfa9cce202c6477faaf9b7ac"),
+        hex!("166007c08a99db2fc3ba8734ace9824b5eecfdfa8d0cf8ef5dd365bc400a0051d5fa9c01a58b1fb93d1a1399126a775c"),
+        hex!("16a3ef08be3ea7ea03bcddfabba6ff6ee5a4375efa1f4fd7feb34fd206357132b920f5b00801dee460ee415a15812ed9"),
+        hex!("1866c8ed336c61231a1be54fd1d74cc4f9fb0ce4c6af5920abc5750c4bf39b4852cfe2f7bb9248836b233d9d55535d4a"),
+        hex!("167a55cda70a6e1cea820597d94a84903216f763e13d87bb5308592e7ea7d4fbc7385ea3d529b35e346ef48bb8913f55"),
+        hex!("04d2f259eea405bd48f010a01ad2911d9c6dd039bb61a6290e591b36e636a5c871a5c29f4f83060400f8b49cba8f6aa8"),
+        hex!("0accbb67481d033ff5852c1e48c50c477f94ff8aefce42d28c0f9a88cea7913516f968986f7ebbea9684b529e2561092"),
+        hex!("0ad6b9514c767fe3c3613144b45f1496543346d98adf02267d5ceef9a00d9b8693000763e3b90ac11e99b138573345cc"),
+        hex!("02660400eb2e4f3b628bdd0d53cd76f2bf565b94e72927c1cb748df27942480e420517bd8714cc80d1fadc1326ed06f7"),
+        hex!("0e0fa1d816ddc03e6b24255e0d7819c171c40f65e273b853324efcd6356caa205ca2f570f13497804415473a1d634b8f"),
+        hex!("000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000001"),
+    ];
+
+    /// Maps an element of the quadratic extension to a point of G2, as
+    /// specified in <https://www.rfc-editor.org/rfc/rfc9380#section-6.6.3>:
+    /// the simplified SWU map onto a 3-isogenous curve, followed by the
+    /// isogeny map and the clearing of the cofactor.
+    #[cfg(test)]
+    pub(crate) fn map_fp2_to_g2(u: Fp2<BLS381>) -> CurveAff<Fp2<BLS381>> {
+        let a = Fp2 {
+            re: BLS381::ZERO,
+            im: BLS381::new(SSWU_G2_A),
+        };
+        let b = Fp2 {
+            re: BLS381::new(SSWU_G2_B),
+            im: BLS381::new(SSWU_G2_B),
+        };
+        let z = -Fp2 {
+            re: BLS381::new(2),
+            im: BLS381::UNIT,
+        };
+
+        let u_sq = u * u;
+        let tv1 = z * z * u_sq * u_sq + z * u_sq;
+        let x1 = if tv1 == Fp2::<BLS381>::ZERO {
+            b / (z * a)
+        } else {
+            (-b / a) * (Fp2::<BLS381>::UNIT + tv1.inv())
+        };
+        let gx1 = x1 * x1 * x1 + a * x1 + b;
+        let (x, mut y) = if let Some(y1) = fp2_sqrt(gx1) {
+            (x1, y1)
+        } else {
+            let x2 = z * u_sq * x1;
+            let gx2 = x2 * x2 * x2 + a * x2 + b;
+            // When gx1 isn't a square, gx2 = Z^3 * u^6 * gx1 is.
+            let y2 = fp2_sqrt(gx2).expect("gx2 should be a quadratic residue");
+            (x2, y2)
+        };
+        if fp2_sgn0(u) != fp2_sgn0(y) {
+            y = -y;
+        }
+
+        let eval = |coeffs: &[[[u8; 48]; 2]]| {
+            coeffs
+                .iter()
+                .rev()
+                .fold(Fp2::<BLS381>::ZERO, |acc, [re, im]| {
+                    acc * x
+                        + Fp2 {
+                            re: fp_from_bytes(re),
+                            im: fp_from_bytes(im),
+                        }
+                })
+        };
+        let p = CurveAff::<Fp2<BLS381>> {
+            x: eval(&ISO_G2_X_NUM) / eval(&ISO_G2_X_DEN),
+            y: y * eval(&ISO_G2_Y_NUM) / eval(&ISO_G2_Y_DEN),
+        };
+
+        p.mul_be_bytes(&H_EFF_G2)
+    }
+
+    /// Returns a square root of the given element, or `None` if it isn't a
+    /// quadratic residue.
+    pub(crate) fn fp2_sqrt(a: Fp2<BLS381>) -> Option<Fp2<BLS381>> {
+        if a.im == BLS381::ZERO {
+            return match a.re.sqrt() {
+                Ok(re) => Some(Fp2 {
+                    re,
+                    im: BLS381::ZERO,
+                }),
+                // Since -1 isn't a square, -a.re is.
+                Err(_) => (-a.re).sqrt().ok().map(|im| Fp2 {
+                    re: BLS381::ZERO,
+                    im,
+                }),
+            };
+        }
+
+        // With a = (x + iy)^2, we have x^2 - y^2 = a.re and x^2 + y^2 = norm,
+        // where norm^2 = a.re^2 + a.im^2.
+        let norm = (a.re * a.re + a.im * a.im).sqrt().ok()?;
+        let half = BLS381::new(2).inv();
+        let re = ((a.re + norm) * half)
+            .sqrt()
+            .or_else(|_| ((a.re - norm) * half).sqrt())
+            .ok()?;
+        let im = a.im / (BLS381::new(2) * re);
+        Some(Fp2 { re, im })
+    }
+
+    /// The sign of an element of the quadratic extension, as defined in
+    /// <https://www.rfc-editor.org/rfc/rfc9380#section-4.1>.
+    #[cfg(test)]
+    fn fp2_sgn0(a: Fp2<BLS381>) -> bool {
+        a.re.val.bit(0) || (a.re == BLS381::ZERO && a.im.val.bit(0))
+    }
+
+    /// Effective cofactor used to clear the cofactor of G2, see section 8.8.2
+    /// of RFC 9380.
+    #[cfg(test)]
+    const H_EFF_G2: [u8; 80] = hex!("0bc69f08f2ee75b3584c6a0ea91b352888e2a8e9145ad7689986ff031508ffe1329c2f178731db956d82bf015d1212b02ec0ec69d7477c1ae954cbc06689f6a359894c0adebbf6b4e8020005aaa95551");
+
+    /// Constants of the 3-isogenous curve y^2 = x^3 + A' * x + B' used by the
+    /// simplified SWU map, with A' = 240 * i and B' = 1012 * (1 + i), see
+    /// section 8.8.2 of RFC 9380.
+    #[cfg(test)]
+    const SSWU_G2_A: usize = 240;
+    #[cfg(test)]
+    const SSWU_G2_B: usize = 1012;
+
+    // Coefficients of the 3-isogeny map, as pairs (re, im), in increasing
+    // degree order, as listed in <https://www.rfc-editor.org/rfc/rfc9380#appendix-E.3>.
+    #[cfg(test)]
+    const ISO_G2_X_NUM: [[[u8; 48]; 2]; 4] = [
+        [
+            hex!("05c759507e8e333ebb5b7a9a47d7ed8532c52d39fd3a042a88b58423c50ae15d5c2638e343d9c71c6238aaaaaaaa97d6"),
+            hex!("05c759507e8e333ebb5b7a9a47d7ed8532c52d39fd3a042a88b58423c50ae15d5c2638e343d9c71c6238aaaaaaaa97d6"),
+        ],
+        [
+            hex!("000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000"),
+            hex!("11560bf17baa99bc32126fced787c88f984f87adf7ae0c7f9a208c6b4f20a4181472aaa9cb8d555526a9ffffffffc71a"),
+        ],
+        [
+            hex!("11560bf17baa99bc32126fced787c88f984f87adf7ae0c7f9a208c6b4f20a4181472aaa9cb8d555526a9ffffffffc71e"),
+            hex!("08ab05f8bdd54cde190937e76bc3e447cc27c3d6fbd7063fcd104635a790520c0a395554e5c6aaaa9354ffffffffe38d"),
+        ],
+        [
+            hex!("171d6541fa38ccfaed6dea691f5fb614cb14b4e7f4e810aa22d6108f142b85757098e38d0f671c7188e2aaaaaaaa5ed1"),
+            hex!("000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000"),
+        ],
+    ];
+    #[cfg(test)]
+    const ISO_G2_X_DEN: [[[u8; 48]; 2]; 3] = [
+        [
+            hex!("000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000"),
+            hex!("1a0111ea397fe69a4b1ba7b6434bacd764774b84f38512bf6730d2a0f6b0f6241eabfffeb153ffffb9feffffffffaa63"),
+        ],
+        [
+            hex!("00000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000c"),
+            hex!("1a0111ea397fe69a4b1ba7b6434bacd764774b84f38512bf6730d2a0f6b0f6241eabfffeb153ffffb9feffffffffaa9f"),
+        ],
+        [
+            hex!("000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000001"),
+            hex!("000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000"),
+        ],
+    ];
+    #[cfg(test)]
+    const ISO_G2_Y_NUM: [[[u8; 48]; 2]; 4] = [
+        [
+            hex!("1530477c7ab4113b59a4c18b076d11930f7da5d4a07f649bf54439d87d27e500fc8c25ebf8c92f6812cfc71c71c6d706"),
+            hex!("1530477c7ab4113b59a4c18b076d11930f7da5d4a07f649bf54439d87d27e500fc8c25ebf8c92f6812cfc71c71c6d706"),
+        ],
+        [
+            hex!("000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000"),
+            hex!("05c759507e8e333ebb5b7a9a47d7ed8532c52d39fd3a042a88b58423c50ae15d5c2638e343d9c71c6238aaaaaaaa97be"),
+        ],
+        [
+            hex!("11560bf17baa99bc32126fced787c88f984f87adf7ae0c7f9a208c6b4f20a4181472aaa9cb8d555526a9ffffffffc71c"),
+            hex!("08ab05f8bdd54cde190937e76bc3e447cc27c3d6fbd7063fcd104635a790520c0a395554e5c6aaaa9354ffffffffe38f"),
+        ],
+        [
+            hex!("124c9ad43b6cf79bfbf7043de3811ad0761b0f37a1e26286b0e977c69aa274524e79097a56dc4bd9e1b371c71c718b10"),
+            hex!("000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000"),
+        ],
+    ];
+    #[cfg(test)]
+    const ISO_G2_Y_DEN: [[[u8; 48]; 2]; 4] = [
+        [
+            hex!("1a0111ea397fe69a4b1ba7b6434bacd764774b84f38512bf6730d2a0f6b0f6241eabfffeb153ffffb9feffffffffa8fb"),
+            hex!("1a0111ea397fe69a4b1ba7b6434bacd764774b84f38512bf6730d2a0f6b0f6241eabfffeb153ffffb9feffffffffa8fb"),
+        ],
+        [
+            hex!("000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000"),
+            hex!("1a0111ea397fe69a4b1ba7b6434bacd764774b84f38512bf6730d2a0f6b0f6241eabfffeb153ffffb9feffffffffa9d3"),
+        ],
+        [
+            hex!("000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000012"),
+            hex!("1a0111ea397fe69a4b1ba7b6434bacd764774b84f38512bf6730d2a0f6b0f6241eabfffeb153ffffb9feffffffffaa99"),
+        ],
+        [
+            hex!("000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000001"),
+            hex!("000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000"),
+        ],
+    ];
+
     // The optimal Ate pairing takes a point each from the curve and its twist and
     // outputs an Fp12 element.
     pub(crate) fn ate_optim(p: CurveAff<BLS381>, q: CurveAff<Fp2<BLS381>>) -> Fp12<BLS381> {
@@ -1018,6 +1498,16 @@ mod tests {
         assert_eq!(running_product, Fp12::<BLS381>::UNIT);
     }
 
+    #[test]
+    fn test_bls_maps_to_subgroups() {
+        let mut rng = rand::thread_rng();
+
+        let p = bls381::map_fp_to_g1(rng.gen());
+        assert!(bls381::g1_is_on_curve(p) && bls381::is_in_subgroup(p));
+        let q = bls381::map_fp2_to_g2(rng.gen());
+        assert!(bls381::g2_is_on_curve(q) && bls381::is_in_subgroup(q));
+    }
+
     #[test]
     fn test_bn_pairing() {
         let mut rng = rand::thread_rng();
@@ -940,6 +940,19 @@ where
         self.block.circuit.verifier_data()
     }
 
+    /// Returns the verifier data of the block wrapper circuit, to verify
+    /// proofs generated by [`AllRecursiveCircuits::prove_block_wrapper`].
+    pub fn block_wrapper_verifier_data(&self) -> VerifierCircuitData<F, C, D> {
+        self.block_wrapper.circuit.verifier_data()
+    }
+
+    /// Returns the verifier data of the two-to-one block aggregation circuit,
+    /// to verify proofs generated by
+    /// [`AllRecursiveCircuits::prove_two_to_one_block`].
+    pub fn two_to_one_block_verifier_data(&self) -> VerifierCircuitData<F, C, D> {
+        self.two_to_one_block.circuit.verifier_data()
+    }
+
     fn create_segment_circuit(
         by_table: &[RecursiveCircuitsForTable<F, C, D>; NUM_TABLES],
         stark_config: &StarkConfig,
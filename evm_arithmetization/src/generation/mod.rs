@@ -36,6 +36,7 @@ use crate::witness::state::RegistersState;
 
 pub(crate) mod linked_list;
 pub mod mpt;
+pub mod profiler;
 pub(crate) mod prover_input;
 pub(crate) mod rlp;
 pub(crate) mod segments;
@@ -101,6 +102,15 @@ pub struct GenerationInputs<F: RichField> {
     /// Withdrawal pairs `(addr, amount)`. At the end of the txs, `amount` is
     /// added to `addr`'s balance. See EIP-4895.
     pub withdrawals: Vec<(Address, U256)>,
+    /// Whether to process the execution layer requests of the block, which
+    /// must only be set along with the final batch of a block. The deposit
+    /// requests are then parsed from the receipts of all the transactions of
+    /// the block, the withdrawal and consolidation requests system calls are
+    /// performed, and the block's `requests_hash` is checked. See EIP-7685.
+    ///
+    /// Note: this is only used when feature `eth_mainnet` is activated.
+    #[serde(default)]
+    pub process_requests: bool,
 
     pub tries: TrieInputs,
     /// Expected trie roots after the transactions are executed.
@@ -311,6 +321,13 @@ fn apply_metadata_and_tries_memops<F: RichField + Extendable<D>, const D: usize>
             GlobalMetadata::ParentBeaconBlockRoot,
             h2u(metadata.parent_beacon_block_root),
         ),
+        #[cfg(feature = "eth_mainnet")]
+        (GlobalMetadata::RequestsHash, h2u(metadata.requests_hash)),
+        #[cfg(feature = "eth_mainnet")]
+        (
+            GlobalMetadata::DepositContractAddress,
+            U256::from_big_endian(&metadata.deposit_contract_address.0),
+        ),
         (GlobalMetadata::BlockGasUsedBefore, inputs.gas_used_before),
         (GlobalMetadata::BlockGasUsedAfter, inputs.gas_used_after),
         (GlobalMetadata::TxnNumberBefore, inputs.txn_number_before),
@@ -525,12 +542,26 @@ pub fn generate_traces<F: RichField + Extendable<D>, const D: usize>(
     let registers_after: RegistersData = RegistersData::from(*registers_after);
     apply_metadata_and_tries_memops(&mut state, inputs, &registers_before, &registers_after);
 
+    let profile_observer = profiler::profile_observer();
+    if profile_observer.is_some() {
+        state.profile = Some(Box::default());
+    }
+
     timed!(
         timing,
         "simulate CPU",
         simulate_cpu(&mut state, *max_cpu_len_log)
     )?;
 
+    if let (Some(observer), Some(profile)) = (profile_observer, state.profile.take()) {
+        let segment_id = profiler::SegmentId {
+            block_number: inputs.block_metadata.block_number.low_u64(),
+            txn_number_before: inputs.txn_number_before.low_u64(),
+            segment_index: segment_data.segment_index,
+        };
+        observer(segment_id, &profile);
+    }
+
     let trace_lengths = state.traces.get_lengths();
 
     let read_metadata = |field| state.memory.read_global_metadata(field);
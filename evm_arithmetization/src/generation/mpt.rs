@@ -83,6 +83,7 @@ pub fn decode_receipt(rlp: &[u8]) -> Result<(PayloadInfo, usize, LegacyReceiptRl
         1 => 1,
         2 => 2,
         3 => 3,
+        4 => 4,
         _ => 0,
     };
 
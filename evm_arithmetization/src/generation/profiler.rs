@@ -0,0 +1,405 @@
+//! Profiling of the CPU execution, attributing CPU cycles and STARK table rows
+//! to opcodes and to kernel functions.
+//!
+//! Kernel functions are delimited by the global labels of the kernel. As the
+//! kernel has no call instruction, calls are approximated by jumps to the
+//! start of a function, and returns by jumps back into a function of the
+//! current call stack.
+
+use std::collections::{BTreeMap, HashMap};
+use std::io::{self, Write};
+use std::ops::AddAssign;
+
+use once_cell::sync::{Lazy, OnceCell};
+
+use crate::cpu::kernel::aggregator::KERNEL;
+use crate::witness::operation::Operation;
+use crate::witness::state::RegistersState;
+
+/// Maximum depth of the kernel call stack. Deeper calls replace the innermost
+/// function instead, so that jumps which never return can't grow the stack
+/// forever.
+const MAX_CALL_DEPTH: usize = 64;
+
+const KERNEL_ROOT: usize = 0;
+const USER_ROOT: usize = 1;
+
+/// The global labels of the kernel, sorted by offset.
+static FUNCTIONS: Lazy<Vec<(usize, &'static str)>> = Lazy::new(|| {
+    let mut functions: Vec<_> = KERNEL
+        .global_labels
+        .iter()
+        .map(|(label, &offset)| (offset, label.as_str()))
+        .collect();
+    functions.sort_unstable();
+    functions
+});
+
+/// Callback invoked with the profile of each segment whose traces are
+/// generated.
+static PROFILE_OBSERVER: OnceCell<fn(SegmentId, &CycleProfile)> = OnceCell::new();
+
+/// Registers a callback invoked with the profile of each segment whose traces
+/// are generated. Profiling is disabled until a callback is registered, as it
+/// slows down trace generation.
+///
+/// Returns `false` if a callback was already registered.
+pub fn set_profile_observer(observer: fn(SegmentId, &CycleProfile)) -> bool {
+    PROFILE_OBSERVER.set(observer).is_ok()
+}
+
+pub(crate) fn profile_observer() -> Option<fn(SegmentId, &CycleProfile)> {
+    PROFILE_OBSERVER.get().copied()
+}
+
+/// Identifies a segment of a batch of transactions.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct SegmentId {
+    pub block_number: u64,
+    /// The index of the first transaction of the batch within its block.
+    pub txn_number_before: u64,
+    pub segment_index: usize,
+}
+
+/// Rows of each STARK table, except the memory continuation ones.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct TableRows {
+    pub arithmetic: usize,
+    pub byte_packing: usize,
+    pub cpu: usize,
+    pub keccak: usize,
+    pub keccak_sponge: usize,
+    pub logic: usize,
+    /// A lower bound, as the memory table also fills gaps between addresses.
+    pub memory: usize,
+    #[cfg(feature = "cdk_erigon")]
+    pub poseidon: usize,
+}
+
+impl AddAssign for TableRows {
+    fn add_assign(&mut self, rhs: Self) {
+        self.arithmetic += rhs.arithmetic;
+        self.byte_packing += rhs.byte_packing;
+        self.cpu += rhs.cpu;
+        self.keccak += rhs.keccak;
+        self.keccak_sponge += rhs.keccak_sponge;
+        self.logic += rhs.logic;
+        self.memory += rhs.memory;
+        #[cfg(feature = "cdk_erigon")]
+        {
+            self.poseidon += rhs.poseidon;
+        }
+    }
+}
+
+/// The costs attributed to an opcode or a function.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Costs {
+    /// Number of executed instructions.
+    pub instructions: usize,
+    pub cycles: usize,
+    /// Rows added to each table. These are only tracked when generating
+    /// traces, and are all zero, except memory ones, when simulating the
+    /// execution.
+    pub rows: TableRows,
+}
+
+impl AddAssign for Costs {
+    fn add_assign(&mut self, rhs: Self) {
+        self.instructions += rhs.instructions;
+        self.cycles += rhs.cycles;
+        self.rows += rhs.rows;
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+struct Frame {
+    offset: usize,
+    node: usize,
+}
+
+#[derive(Clone, Debug)]
+struct Node {
+    parent: Option<usize>,
+    name: &'static str,
+}
+
+/// The costs of an execution, by call stack and opcode.
+#[derive(Clone, Debug)]
+pub struct CycleProfile {
+    /// Call stacks, each node being a function called by its parent.
+    nodes: Vec<Node>,
+    node_ids: HashMap<(usize, &'static str), usize>,
+    /// The current kernel call stack, empty in user mode.
+    frames: Vec<Frame>,
+    /// Costs by call stack node and opcode, `None` standing for exceptions.
+    costs: HashMap<(usize, Option<Operation>), Costs>,
+}
+
+impl Default for CycleProfile {
+    fn default() -> Self {
+        Self {
+            nodes: vec![
+                Node {
+                    parent: None,
+                    name: "kernel",
+                },
+                Node {
+                    parent: None,
+                    name: "user",
+                },
+            ],
+            node_ids: HashMap::new(),
+            frames: vec![],
+            costs: HashMap::new(),
+        }
+    }
+}
+
+impl CycleProfile {
+    /// Records an instruction, `op` being `None` for exceptions, given the
+    /// registers before and after its execution.
+    pub(crate) fn record(
+        &mut self,
+        before: RegistersState,
+        after: RegistersState,
+        op: Option<Operation>,
+        cycles: usize,
+        rows: TableRows,
+    ) {
+        let node = match before.is_kernel {
+            true => {
+                if self.frames.is_empty() {
+                    self.enter_kernel(before.program_counter);
+                }
+                self.frames.last().expect("in kernel mode").node
+            }
+            false => USER_ROOT,
+        };
+        *self.costs.entry((node, op)).or_default() += Costs {
+            instructions: 1,
+            cycles,
+            rows,
+        };
+
+        match (before.is_kernel, after.is_kernel) {
+            (_, false) => self.frames.clear(),
+            (false, true) => self.enter_kernel(after.program_counter),
+            (true, true) => match op {
+                Some(Operation::Jump | Operation::Jumpi) => self.jump(after.program_counter, true),
+                Some(Operation::ExitKernel | Operation::Syscall(..)) | None => {
+                    self.jump(after.program_counter, false)
+                }
+                _ => {}
+            },
+        }
+    }
+
+    /// Returns the total costs of the execution.
+    pub fn total(&self) -> Costs {
+        let mut total = Costs::default();
+        for &costs in self.costs.values() {
+            total += costs;
+        }
+        total
+    }
+
+    /// Returns the costs of each opcode.
+    pub fn by_opcode(&self) -> BTreeMap<String, Costs> {
+        let mut by_opcode = BTreeMap::<_, Costs>::new();
+        for (&(_, op), &costs) in &self.costs {
+            *by_opcode.entry(op_name(op)).or_default() += costs;
+        }
+        by_opcode
+    }
+
+    /// Returns the costs of the instructions of each kernel function, not
+    /// including the functions it calls. User code is reported as `user`.
+    pub fn by_function(&self) -> BTreeMap<&'static str, Costs> {
+        let mut by_function = BTreeMap::<_, Costs>::new();
+        for (&(node, _), &costs) in &self.costs {
+            *by_function.entry(self.nodes[node].name).or_default() += costs;
+        }
+        by_function
+    }
+
+    /// Writes the profile in the folded stack format read by flamegraph
+    /// tools, with one `stack;opcode weight` line per call stack and opcode.
+    pub fn write_folded<W: Write>(
+        &self,
+        mut writer: W,
+        weight: impl Fn(&Costs) -> usize,
+    ) -> io::Result<()> {
+        // Parents are created before their children.
+        let mut paths: Vec<String> = Vec::with_capacity(self.nodes.len());
+        for node in &self.nodes {
+            let path = match node.parent {
+                Some(parent) => format!("{};{}", paths[parent], node.name),
+                None => node.name.to_string(),
+            };
+            paths.push(path);
+        }
+
+        let mut lines: Vec<_> = self
+            .costs
+            .iter()
+            .map(|(&(node, op), costs)| (format!("{};{}", paths[node], op_name(op)), weight(costs)))
+            .filter(|(_, weight)| *weight > 0)
+            .collect();
+        lines.sort_unstable();
+        for (stack, weight) in lines {
+            writeln!(writer, "{stack} {weight}")?;
+        }
+        Ok(())
+    }
+
+    fn enter_kernel(&mut self, pc: usize) {
+        self.frames.clear();
+        let (offset, name) = function_at(pc);
+        let node = self.node(KERNEL_ROOT, name);
+        self.frames.push(Frame { offset, node });
+    }
+
+    /// Updates the call stack after a jump to `pc` in the kernel.
+    fn jump(&mut self, pc: usize, is_jump: bool) {
+        let (offset, name) = function_at(pc);
+        let Some(top) = self.frames.last() else {
+            return self.enter_kernel(pc);
+        };
+        if top.offset == offset {
+            return;
+        }
+
+        // Jumping back into a function of the call stack is a return.
+        if let Some(i) = self.frames.iter().rposition(|frame| frame.offset == offset) {
+            self.frames.truncate(i + 1);
+            return;
+        }
+
+        // Jumping to the start of a function is a call, and any other jump
+        // leaves the current function.
+        if !(is_jump && pc == offset && self.frames.len() < MAX_CALL_DEPTH) {
+            self.frames.pop();
+        }
+        let parent = self.frames.last().map_or(KERNEL_ROOT, |frame| frame.node);
+        let node = self.node(parent, name);
+        self.frames.push(Frame { offset, node });
+    }
+
+    fn node(&mut self, parent: usize, name: &'static str) -> usize {
+        *self.node_ids.entry((parent, name)).or_insert_with(|| {
+            self.nodes.push(Node {
+                parent: Some(parent),
+                name,
+            });
+            self.nodes.len() - 1
+        })
+    }
+}
+
+/// Returns the offset and the label of the kernel function containing `pc`.
+fn function_at(pc: usize) -> (usize, &'static str) {
+    match FUNCTIONS.partition_point(|&(offset, _)| offset <= pc) {
+        0 => (0, "unknown"),
+        i => FUNCTIONS[i - 1],
+    }
+}
+
+fn op_name(op: Option<Operation>) -> String {
+    match op {
+        Some(Operation::Syscall(opcode, ..)) => format!("Syscall({opcode:#04x})"),
+        Some(op) => format!("{op:?}"),
+        None => "exception".to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::arithmetic::BinaryOperator;
+
+    fn kernel(program_counter: usize) -> RegistersState {
+        RegistersState {
+            program_counter,
+            is_kernel: true,
+            ..Default::default()
+        }
+    }
+
+    fn user(program_counter: usize) -> RegistersState {
+        RegistersState {
+            program_counter,
+            is_kernel: false,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn attributes_costs_to_call_stacks() {
+        let (caller, caller_name) = function_at(KERNEL.global_labels["main"]);
+        let (callee, callee_name) = function_at(KERNEL.global_labels["init_access_lists"]);
+        let rows = TableRows {
+            memory: 2,
+            ..Default::default()
+        };
+
+        let mut profile = CycleProfile::default();
+        // Call, return, then exit the kernel.
+        profile.record(
+            kernel(caller),
+            kernel(callee),
+            Some(Operation::Jump),
+            1,
+            rows,
+        );
+        profile.record(
+            kernel(callee),
+            kernel(callee + 2),
+            Some(Operation::Push(1)),
+            1,
+            rows,
+        );
+        profile.record(
+            kernel(callee + 2),
+            kernel(caller + 5),
+            Some(Operation::Jump),
+            1,
+            rows,
+        );
+        profile.record(
+            kernel(caller + 5),
+            user(0),
+            Some(Operation::ExitKernel),
+            1,
+            rows,
+        );
+        let add = Some(Operation::BinaryArithmetic(BinaryOperator::Add));
+        profile.record(user(0), user(1), add, 1, rows);
+        profile.record(user(1), user(2), add, 1, rows);
+
+        let mut folded = vec![];
+        profile
+            .write_folded(&mut folded, |costs| costs.cycles)
+            .unwrap();
+        let expected = [
+            format!("kernel;{caller_name};ExitKernel 1"),
+            format!("kernel;{caller_name};Jump 1"),
+            format!("kernel;{caller_name};{callee_name};Jump 1"),
+            format!("kernel;{caller_name};{callee_name};Push(1) 1"),
+            "user;BinaryArithmetic(Add) 2".to_string(),
+        ];
+        assert_eq!(
+            String::from_utf8(folded)
+                .unwrap()
+                .lines()
+                .collect::<Vec<_>>(),
+            expected
+        );
+
+        assert_eq!(profile.total().instructions, 6);
+        assert_eq!(profile.total().rows.memory, 12);
+        assert_eq!(profile.by_opcode()["Jump"].cycles, 2);
+        assert_eq!(profile.by_function()[callee_name].instructions, 2);
+        assert_eq!(profile.by_function()["user"].rows.memory, 4);
+    }
+}
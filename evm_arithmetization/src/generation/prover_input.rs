@@ -22,9 +22,14 @@ use crate::cpu::kernel::constants::cancun_constants::{
     POINT_EVALUATION_PRECOMPILE_RETURN_VALUE,
 };
 use crate::cpu::kernel::constants::context_metadata::ContextMetadata;
+use crate::cpu::kernel::constants::eip7702_constants::{
+    DELEGATION_DESIGNATOR_LEN, DELEGATION_DESIGNATOR_PREFIX,
+};
 use crate::cpu::kernel::interpreter::simulate_cpu_and_get_user_jumps;
 use crate::curve_pairings::{bls381, CurveAff, CyclicGroup};
-use crate::extension_tower::{FieldExt, Fp12, Fp2, BLS381, BLS_BASE, BLS_SCALAR, BN254, BN_BASE};
+use crate::extension_tower::{
+    FieldExt, Fp12, Fp2, Fp6, BLS381, BLS_BASE, BLS_SCALAR, BN254, BN_BASE,
+};
 use crate::generation::prover_input::EvmField::{
     Bls381Base, Bls381Scalar, Bn254Base, Bn254Scalar, Secp256k1Base, Secp256k1Scalar,
 };
@@ -33,7 +38,9 @@ use crate::generation::state::GenerationState;
 use crate::generation::GlobalMetadata;
 use crate::memory::segments::Segment;
 use crate::memory::segments::Segment::BnPairing;
-use crate::util::{biguint_to_mem_vec, mem_vec_to_biguint, sha2, u256_to_u8, u256_to_usize};
+use crate::util::{
+    biguint_to_mem_vec, biguint_to_u256, mem_vec_to_biguint, sha2, u256_to_u8, u256_to_usize,
+};
 use crate::witness::errors::ProverInputError::*;
 use crate::witness::errors::{ProgramError, ProverInputError};
 use crate::witness::memory::MemoryAddress;
@@ -64,8 +71,10 @@ impl<F: RichField> GenerationState<F> {
             "blobbasefee" => self.run_blobbasefee(),
             "current_hash" => self.run_current_hash(),
             "account_code" => self.run_account_code(),
+            "delegation" => self.run_delegation(),
             "bignum_modmul" => self.run_bignum_modmul(),
             "withdrawal" => self.run_withdrawal(),
+            "requests" => self.run_requests(),
             "num_bits" => self.run_num_bits(),
             "jumpdest_table" => self.run_jumpdest_table(input_fn),
             "access_lists" => self.run_access_lists(input_fn),
@@ -143,28 +152,39 @@ impl<F: RichField> GenerationState<F> {
     }
 
     /// Special finite field operations.
+    ///
+    /// `sf::<field>::<op>_<k>` returns the `k`-th word of the output of `op`.
+    /// The kernel requests the words from the last one down to the first one,
+    /// so that the inputs of `op` lie right below the words already provided.
     fn run_sf(&self, input_fn: &ProverInputFn) -> Result<U256, ProgramError> {
         let field = EvmField::from_str(input_fn.0[1].as_str())
             .map_err(|_| ProgramError::ProverInputError(InvalidFunction))?;
-        let inputs: [U256; 4] = match field {
-            Bls381Base => (0..4)
-                .map(|i| stack_peek(self, i))
-                .collect::<Result<Vec<U256>, _>>()?
-                .try_into()
-                .unwrap(),
-            _ => todo!(),
-        };
-        let res = match input_fn.0[2].as_str() {
-            "add_lo" => field.add_lo(inputs),
-            "add_hi" => field.add_hi(inputs),
-            "mul_lo" => field.mul_lo(inputs),
-            "mul_hi" => field.mul_hi(inputs),
-            "sub_lo" => field.sub_lo(inputs),
-            "sub_hi" => field.sub_hi(inputs),
+        let (op, k) = input_fn.0[2]
+            .rsplit_once('_')
+            .and_then(|(op, k)| Some((op, k.parse::<usize>().ok()?)))
+            .ok_or(ProgramError::ProverInputError(InvalidFunction))?;
+        let (num_words, num_inputs) = match op {
+            "mul" => (4, 4),
+            "fp2_div" => (4, 8),
+            "sqrt" => (2, 2),
+            "fp2_sqrt" => (4, 4),
             _ => return Err(ProgramError::ProverInputError(InvalidFunction)),
         };
+        if k >= num_words {
+            return Err(ProgramError::ProverInputError(InvalidFunction));
+        }
+        let inputs = (0..num_inputs)
+            .map(|i| stack_peek(self, num_words - 1 - k + i))
+            .collect::<Result<Vec<U256>, _>>()?;
+        let output = match op {
+            "mul" => field.mul_with_quotient(&inputs)?,
+            "fp2_div" => field.fp2_div(&inputs)?,
+            "sqrt" => field.sqrt_or_sqrt_of_non_residue(&inputs)?,
+            "fp2_sqrt" => field.fp2_sqrt_or_sqrt_of_non_residue(&inputs)?,
+            _ => unreachable!(),
+        };
 
-        Ok(res)
+        Ok(output[k])
     }
 
     /// Finite field extension operations.
@@ -178,13 +198,27 @@ impl<F: RichField> GenerationState<F> {
             .unwrap()
             .parse::<usize>()
             .unwrap();
-        let ptr = stack_peek(self, 11 - n).map(u256_to_usize)??;
 
-        let f: [U256; 12] = match field {
-            Bn254Base => std::array::from_fn(|i| current_context_peek(self, BnPairing, ptr + i)),
+        match field {
+            Bn254Base => {
+                let ptr = stack_peek(self, 11 - n).map(u256_to_usize)??;
+                let f: [U256; 12] =
+                    std::array::from_fn(|i| current_context_peek(self, BnPairing, ptr + i));
+                Ok(field.field_extension_inverse(n, f))
+            }
+            // The kernel keeps the full address of the input on top of the stack.
+            Bls381Base => {
+                let ptr = stack_peek(self, 0)?;
+                let f = (0..24)
+                    .map(|i| {
+                        MemoryAddress::new_bundle(ptr + i)
+                            .map(|addr| self.memory.get_with_init(addr))
+                    })
+                    .collect::<Result<Vec<U256>, _>>()?;
+                field.bls381_fp12_inverse(n, &f)
+            }
             _ => todo!(),
-        };
-        Ok(field.field_extension_inverse(n, f))
+        }
     }
 
     /// RLP data.
@@ -229,6 +263,25 @@ impl<F: RichField> GenerationState<F> {
         Ok(code.len().into())
     }
 
+    /// Returns the address an account delegates its code execution to, given
+    /// its codehash, or 0 if its code is not a delegation designator. See
+    /// EIP-7702.
+    fn run_delegation(&mut self) -> Result<U256, ProgramError> {
+        // stack: codehash, ...
+        let codehash = stack_peek(self, 0)?;
+        let code = self
+            .inputs
+            .contract_code
+            .get(&H256::from_uint(&codehash))
+            .ok_or(ProgramError::ProverInputError(CodeHashNotFound))?;
+        match code.strip_prefix(&DELEGATION_DESIGNATOR_PREFIX) {
+            Some(delegate) if code.len() == DELEGATION_DESIGNATOR_LEN => {
+                Ok(U256::from_big_endian(delegate))
+            }
+            _ => Ok(U256::zero()),
+        }
+    }
+
     // Bignum modular multiplication.
     // On the first call, calculates the remainder and quotient of the given inputs.
     // These are stored, as limbs, in self.bignum_modmul_result_limbs.
@@ -295,6 +348,13 @@ impl<F: RichField> GenerationState<F> {
             .ok_or(ProgramError::ProverInputError(OutOfWithdrawalData))
     }
 
+    /// Execution layer requests data.
+    fn run_requests(&mut self) -> Result<U256, ProgramError> {
+        self.requests_prover_inputs
+            .pop()
+            .ok_or(ProgramError::ProverInputError(OutOfRequestsData))
+    }
+
     /// Return the number of bits of the top of the stack or an error if
     /// the top of the stack is zero or empty.
     fn run_num_bits(&mut self) -> Result<U256, ProgramError> {
@@ -1036,52 +1096,121 @@ impl EvmField {
         modexp(x, q, n)
     }
 
-    fn add_lo(&self, inputs: [U256; 4]) -> U256 {
-        let [y1, x0, x1, y0] = inputs;
-        let x = U512::from(x0) + (U512::from(x1) << 256);
-        let y = U512::from(y0) + (U512::from(y1) << 256);
-        let z = BLS381 { val: x } + BLS381 { val: y };
-        z.lo()
+    /// Returns the words of `z = x * y mod p` followed by those of the quotient
+    /// `q = (x * y - z) / p`, as checked by `mul_fp381`.
+    fn mul_with_quotient(&self, inputs: &[U256]) -> Result<Vec<U256>, ProgramError> {
+        let to_biguint = |val: U512| {
+            let mut bytes = [0u8; 64];
+            val.to_big_endian(&mut bytes);
+            BigUint::from_bytes_be(&bytes)
+        };
+        let x = self.fp_from_words(&inputs[..2])?;
+        let y = self.fp_from_words(&inputs[2..])?;
+        let p = to_biguint(self.fp_order()?);
+        let prod = to_biguint(x.val) * to_biguint(y.val);
+        let mask = (BigUint::from(1u8) << 256) - 1u8;
+        Ok([&prod % &p, &prod / &p]
+            .into_iter()
+            .flat_map(|z| [biguint_to_u256(&z & &mask), biguint_to_u256(z >> 256)])
+            .collect())
     }
 
-    fn add_hi(&self, inputs: [U256; 4]) -> U256 {
-        let [x0, x1, y0, y1] = inputs;
-        let x = U512::from(x0) + (U512::from(x1) << 256);
-        let y = U512::from(y0) + (U512::from(y1) << 256);
-        let z = BLS381 { val: x } + BLS381 { val: y };
-        z.hi()
+    /// Returns the words of `x / y` for elements `x`, `y` of Fp2.
+    fn fp2_div(&self, inputs: &[U256]) -> Result<Vec<U256>, ProgramError> {
+        let x = self.fp2_from_words(&inputs[..4])?;
+        let y = self.fp2_from_words(&inputs[4..])?;
+        if y == Fp2::<BLS381>::ZERO {
+            return Err(ProgramError::ProverInputError(InvalidInput));
+        }
+        Ok(fp2_to_words(x / y))
     }
 
-    fn mul_lo(&self, inputs: [U256; 4]) -> U256 {
-        let [y1, x0, x1, y0] = inputs;
-        let x = U512::from(x0) + (U512::from(x1) << 256);
-        let y = U512::from(y0) + (U512::from(y1) << 256);
-        let z = BLS381 { val: x } * BLS381 { val: y };
-        z.lo()
+    /// Returns the words of a square root of `x`, or of `11 * x` if `x` isn't a
+    /// square, 11 being a non-residue.
+    fn sqrt_or_sqrt_of_non_residue(&self, inputs: &[U256]) -> Result<Vec<U256>, ProgramError> {
+        let x = self.fp_from_words(inputs)?;
+        let s = x
+            .sqrt()
+            .or_else(|_| (x * BLS381::new(11)).sqrt())
+            .map_err(|_| ProgramError::ProverInputError(InvalidInput))?;
+        Ok(vec![s.lo(), s.hi()])
     }
 
-    fn mul_hi(&self, inputs: [U256; 4]) -> U256 {
-        let [x0, x1, y0, y1] = inputs;
-        let x = U512::from(x0) + (U512::from(x1) << 256);
-        let y = U512::from(y0) + (U512::from(y1) << 256);
-        let z = BLS381 { val: x } * BLS381 { val: y };
-        z.hi()
+    /// Returns the words of a square root of `x` in Fp2, or of `-(2 + i) * x`
+    /// if `x` isn't a square, `-(2 + i)` being a non-residue.
+    fn fp2_sqrt_or_sqrt_of_non_residue(&self, inputs: &[U256]) -> Result<Vec<U256>, ProgramError> {
+        let x = self.fp2_from_words(inputs)?;
+        let z = -Fp2 {
+            re: BLS381::new(2),
+            im: BLS381::UNIT,
+        };
+        let s = bls381::fp2_sqrt(x)
+            .or_else(|| bls381::fp2_sqrt(z * x))
+            .ok_or(ProgramError::ProverInputError(InvalidInput))?;
+        Ok(fp2_to_words(s))
     }
 
-    fn sub_lo(&self, inputs: [U256; 4]) -> U256 {
-        let [y1, x0, x1, y0] = inputs;
-        let x = U512::from(x0) + (U512::from(x1) << 256);
-        let y = U512::from(y0) + (U512::from(y1) << 256);
-        let z = BLS381 { val: x } - BLS381 { val: y };
-        z.lo()
+    /// Returns the `n`-th word of the inverse of the element of Fp12 given by
+    /// its 24 words, as laid out by the kernel.
+    fn bls381_fp12_inverse(&self, n: usize, f: &[U256]) -> Result<U256, ProgramError> {
+        let coeffs = f
+            .chunks(4)
+            .map(|words| self.fp2_from_words(words))
+            .collect::<Result<Vec<_>, _>>()?;
+        let f = Fp12 {
+            z0: Fp6 {
+                t0: coeffs[0],
+                t1: coeffs[1],
+                t2: coeffs[2],
+            },
+            z1: Fp6 {
+                t0: coeffs[3],
+                t1: coeffs[4],
+                t2: coeffs[5],
+            },
+        };
+        if f == Fp12::<BLS381>::ZERO {
+            return Err(ProgramError::ProverInputError(InvalidInput));
+        }
+        let f_inv = f.inv();
+        let words = [
+            f_inv.z0.t0,
+            f_inv.z0.t1,
+            f_inv.z0.t2,
+            f_inv.z1.t0,
+            f_inv.z1.t1,
+            f_inv.z1.t2,
+        ]
+        .into_iter()
+        .flat_map(fp2_to_words)
+        .collect::<Vec<_>>();
+        Ok(words[n])
     }
 
-    fn sub_hi(&self, inputs: [U256; 4]) -> U256 {
-        let [x0, x1, y0, y1] = inputs;
-        let x = U512::from(x0) + (U512::from(x1) << 256);
-        let y = U512::from(y0) + (U512::from(y1) << 256);
-        let z = BLS381 { val: x } - BLS381 { val: y };
-        z.hi()
+    /// Returns the order of the base field of BLS12-381, the only field whose
+    /// elements don't fit in a word.
+    fn fp_order(&self) -> Result<U512, ProgramError> {
+        match self {
+            Bls381Base => Ok(self.order()),
+            _ => Err(ProgramError::ProverInputError(Unimplemented)),
+        }
+    }
+
+    /// Decodes a canonical element of Fp from its words `(lo, hi)`.
+    fn fp_from_words(&self, words: &[U256]) -> Result<BLS381, ProgramError> {
+        let val = U512::from(words[0]) + (U512::from(words[1]) << 256);
+        if val >= self.fp_order()? {
+            return Err(ProgramError::ProverInputError(InvalidInput));
+        }
+        Ok(BLS381 { val })
+    }
+
+    /// Decodes a canonical element of Fp2 from its words `(re, im)`.
+    fn fp2_from_words(&self, words: &[U256]) -> Result<Fp2<BLS381>, ProgramError> {
+        Ok(Fp2 {
+            re: self.fp_from_words(&words[..2])?,
+            im: self.fp_from_words(&words[2..])?,
+        })
     }
 
     fn field_extension_inverse(&self, n: usize, f: [U256; 12]) -> U256 {
@@ -1091,6 +1220,10 @@ impl EvmField {
     }
 }
 
+fn fp2_to_words(x: Fp2<BLS381>) -> Vec<U256> {
+    vec![x.re.lo(), x.re.hi(), x.im.lo(), x.im.hi()]
+}
+
 fn modexp(x: U256, e: U256, n: U256) -> Result<U256, ProgramError> {
     let mut current = x;
     let mut product = U256::one();
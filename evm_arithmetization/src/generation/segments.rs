@@ -79,6 +79,7 @@ fn build_segment_data<F: RichField>(
                 .generation_state
                 .withdrawal_prover_inputs
                 .clone(),
+            requests_prover_inputs: interpreter.generation_state.requests_prover_inputs.clone(),
             ger_prover_inputs: interpreter.generation_state.ger_prover_inputs.clone(),
             trie_root_ptrs: interpreter.generation_state.trie_root_ptrs.clone(),
             jumpdest_table: interpreter.generation_state.jumpdest_table.clone(),
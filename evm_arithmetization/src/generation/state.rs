@@ -10,11 +10,13 @@ use plonky2::hash::hash_types::RichField;
 
 use super::linked_list::LinkedListsPtrs;
 use super::mpt::TrieRootPtrs;
+use super::profiler::CycleProfile;
 use super::segments::GenerationSegmentData;
 use super::{TrieInputs, TrimmedGenerationInputs, NUM_EXTRA_CYCLES_AFTER};
 use crate::byte_packing::byte_packing_stark::BytePackingOp;
 use crate::cpu::kernel::aggregator::KERNEL;
 use crate::cpu::kernel::constants::context_metadata::ContextMetadata;
+use crate::cpu::kernel::constants::eip7702_constants::DELEGATION_DESIGNATOR_PREFIX;
 use crate::cpu::stack::MAX_USER_STACK_SIZE;
 use crate::generation::mpt::load_linked_lists_and_txn_and_receipt_mpts;
 use crate::generation::rlp::all_rlp_prover_inputs_reversed;
@@ -290,6 +292,7 @@ pub(crate) trait State<F: RichField> {
                     self.get_mut_registers().check_overflow = true;
                 }
 
+                self.record_profile(checkpoint, Some(op));
                 Ok(())
             }
             Err(e) => {
@@ -307,11 +310,37 @@ pub(crate) trait State<F: RichField> {
                     );
                 }
                 self.rollback(checkpoint);
-                self.handle_error(e)
+                self.handle_error(e)?;
+                self.record_profile(checkpoint, None);
+                Ok(())
             }
         }
     }
 
+    /// Attributes the costs of the instruction executed since `checkpoint` to
+    /// the profile of the execution, if it is being profiled. `op` is `None`
+    /// for exceptions.
+    fn record_profile(&mut self, checkpoint: GenerationStateCheckpoint, op: Option<Operation>) {
+        if self.get_generation_state().profile.is_none() {
+            return;
+        }
+
+        let cycles = self.get_clock().saturating_sub(checkpoint.clock);
+        let registers = self.get_registers();
+        let GenerationState {
+            profile, traces, ..
+        } = self.get_mut_generation_state();
+        if let Some(profile) = profile {
+            profile.record(
+                checkpoint.registers,
+                registers,
+                op,
+                cycles,
+                traces.rows_since(checkpoint.traces),
+            );
+        }
+    }
+
     fn try_perform_instruction(&mut self) -> Result<Operation, ProgramError>;
 
     /// Row that has the correct values for system registers and the code
@@ -365,6 +394,8 @@ pub struct GenerationState<F: RichField> {
 
     pub(crate) withdrawal_prover_inputs: Vec<U256>,
 
+    pub(crate) requests_prover_inputs: Vec<U256>,
+
     pub(crate) ger_prover_inputs: Vec<U256>,
 
     /// The state trie only stores state keys, which are hashes of addresses,
@@ -395,6 +426,9 @@ pub struct GenerationState<F: RichField> {
     /// Provides quick access to pointers that reference the memory location of
     /// either and account or a slot in the respective access list.
     pub(crate) state_ptrs: LinkedListsPtrs,
+
+    /// The profile of the execution, when it is being profiled.
+    pub(crate) profile: Option<Box<CycleProfile>>,
 }
 
 impl<F: RichField> GenerationState<F> {
@@ -437,6 +471,7 @@ impl<F: RichField> GenerationState<F> {
     ) -> Result<Self, ProgramError> {
         let rlp_prover_inputs = all_rlp_prover_inputs_reversed(&inputs.signed_txns);
         let withdrawal_prover_inputs = all_withdrawals_prover_inputs_reversed(&inputs.withdrawals);
+        let requests_prover_inputs = all_requests_prover_inputs(inputs.process_requests);
         let ger_prover_inputs = all_ger_prover_inputs(inputs.ger_data);
         let bignum_modmul_result_limbs = Vec::new();
 
@@ -449,6 +484,7 @@ impl<F: RichField> GenerationState<F> {
             stale_contexts: Vec::new(),
             rlp_prover_inputs,
             withdrawal_prover_inputs,
+            requests_prover_inputs,
             state_key_to_address: HashMap::new(),
             bignum_modmul_result_limbs,
             trie_root_ptrs: TrieRootPtrs {
@@ -460,6 +496,7 @@ impl<F: RichField> GenerationState<F> {
             access_lists_ptrs: LinkedListsPtrs::default(),
             state_ptrs: LinkedListsPtrs::default(),
             ger_prover_inputs,
+            profile: None,
         };
         let trie_root_ptrs =
             state.preinitialize_linked_lists_and_txn_and_receipt_mpts(&inputs.tries);
@@ -503,6 +540,13 @@ impl<F: RichField> GenerationState<F> {
             let tip_u256 = stack_peek(self, 0)?;
             let tip_h256 = H256::from_uint(&tip_u256);
             self.observe_contract(tip_h256)?;
+        } else if cfg!(feature = "eth_mainnet")
+            && dst == KERNEL.global_labels["observe_new_delegation"]
+        {
+            let tip_u256 = stack_peek(self, 0)?;
+            let tip_h256 = H256::from_uint(&tip_u256);
+            let tip_h160 = H160::from(tip_h256);
+            self.observe_delegation(tip_h160);
         }
 
         Ok(())
@@ -515,6 +559,14 @@ impl<F: RichField> GenerationState<F> {
         self.state_key_to_address.insert(state_key, address);
     }
 
+    /// Observe the given delegate, and store the associated delegation
+    /// designator so that the code of delegating accounts can be retrieved
+    /// from their codehash. See EIP-7702.
+    pub(crate) fn observe_delegation(&mut self, delegate: Address) {
+        let code = [&DELEGATION_DESIGNATOR_PREFIX[..], delegate.as_bytes()].concat();
+        self.inputs.contract_code.insert(keccak(&code), code);
+    }
+
     /// Observe the given code hash and store the associated code.
     /// When called, the code corresponding to `codehash` should be stored in
     /// the return data.
@@ -566,6 +618,7 @@ impl<F: RichField> GenerationState<F> {
             state_key_to_address: self.state_key_to_address.clone(),
             bignum_modmul_result_limbs: self.bignum_modmul_result_limbs.clone(),
             withdrawal_prover_inputs: self.withdrawal_prover_inputs.clone(),
+            requests_prover_inputs: self.requests_prover_inputs.clone(),
             ger_prover_inputs: self.ger_prover_inputs.clone(),
             trie_root_ptrs: TrieRootPtrs {
                 state_root_ptr: Some(0),
@@ -575,6 +628,7 @@ impl<F: RichField> GenerationState<F> {
             jumpdest_table: None,
             access_lists_ptrs: self.access_lists_ptrs.clone(),
             state_ptrs: self.state_ptrs.clone(),
+            profile: None,
         }
     }
 
@@ -585,6 +639,8 @@ impl<F: RichField> GenerationState<F> {
             .clone_from(&segment_data.extra_data.rlp_prover_inputs);
         self.withdrawal_prover_inputs
             .clone_from(&segment_data.extra_data.withdrawal_prover_inputs);
+        self.requests_prover_inputs
+            .clone_from(&segment_data.extra_data.requests_prover_inputs);
         self.ger_prover_inputs
             .clone_from(&segment_data.extra_data.ger_prover_inputs);
         self.trie_root_ptrs
@@ -770,6 +826,7 @@ impl<F: RichField> Transition<F> for GenerationState<F> {
     }
 }
 
+#[derive(Clone, Copy)]
 pub(crate) struct GenerationStateCheckpoint {
     pub(crate) registers: RegistersState,
     pub(crate) traces: TraceCheckpoint,
@@ -789,6 +846,12 @@ pub(crate) fn all_withdrawals_prover_inputs_reversed(withdrawals: &[(Address, U2
     withdrawal_prover_inputs
 }
 
+/// Requests prover input array is `[1]` if the requests of the block should be
+/// processed, `[0]` otherwise.
+pub(crate) fn all_requests_prover_inputs(process_requests: bool) -> Vec<U256> {
+    vec![U256::from(process_requests as u8)]
+}
+
 /// Global exit root prover input tuple containing the global exit root and its
 /// associated l1blockhash.
 pub(crate) fn all_ger_prover_inputs(ger_data: Option<(H256, H256)>) -> Vec<U256> {
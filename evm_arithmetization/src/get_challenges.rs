@@ -72,6 +72,12 @@ fn observe_block_metadata<
         challenger.observe_element(excess_blob_gas.0);
         challenger.observe_element(excess_blob_gas.1);
         challenger.observe_elements(&h256_limbs::<F>(block_metadata.parent_beacon_block_root));
+        challenger.observe_elements(&h256_limbs::<F>(block_metadata.requests_hash));
+        challenger.observe_elements(
+            &u256_limbs::<F>(U256::from_big_endian(
+                &block_metadata.deposit_contract_address.0,
+            ))[..5],
+        );
     }
     for i in 0..8 {
         challenger.observe_elements(&u256_limbs(block_metadata.block_bloom[i]));
@@ -104,6 +110,8 @@ fn observe_block_metadata_target<
         challenger.observe_elements(&block_metadata.block_blob_gas_used);
         challenger.observe_elements(&block_metadata.block_excess_blob_gas);
         challenger.observe_elements(&block_metadata.parent_beacon_block_root);
+        challenger.observe_elements(&block_metadata.requests_hash);
+        challenger.observe_elements(&block_metadata.deposit_contract_address);
     }
     challenger.observe_elements(&block_metadata.block_bloom);
 }
@@ -91,6 +91,15 @@ pub struct PublicValues<F: RichField> {
 }
 
 impl<F: RichField> PublicValues<F> {
+    /// The number of public inputs read by [`Self::from_public_inputs`].
+    pub const NUM_PUBLIC_INPUTS: usize = TrieRootsTarget::SIZE * 2
+        + BurnAddrTarget::get_size()
+        + BlockMetadataTarget::SIZE
+        + BlockHashesTarget::SIZE
+        + ExtraBlockDataTarget::SIZE
+        + RegistersDataTarget::SIZE * 2
+        + MemCapTarget::SIZE * 2;
+
     /// Extracts public values from the given public inputs of a proof.
     /// Public values are always the first public inputs added to the circuit,
     /// so we can start extracting at index 0.
@@ -168,6 +177,9 @@ pub struct FinalPublicValues<F: RichField, H: Hasher<F>> {
 }
 
 impl<F: RichField, H: Hasher<F>> FinalPublicValues<F, H> {
+    /// The number of public inputs read by [`Self::from_public_inputs`].
+    pub const NUM_PUBLIC_INPUTS: usize = FinalPublicValuesTarget::SIZE;
+
     /// Extracts final public values from the given public inputs of a proof.
     /// Public values are always the first public inputs added to the circuit,
     /// so we can start extracting at index 0.
@@ -419,6 +431,15 @@ pub struct BlockMetadata {
     pub block_excess_blob_gas: U256,
     /// The hash tree root of the parent beacon block.
     pub parent_beacon_block_root: H256,
+    /// The commitment to the execution layer requests of this block, as
+    /// defined in <https://eips.ethereum.org/EIPS/eip-7685>.
+    #[serde(default)]
+    pub requests_hash: H256,
+    /// The address of the deposit contract of the chain, whose logs are
+    /// parsed into deposit requests, as defined in
+    /// <https://eips.ethereum.org/EIPS/eip-6110>.
+    #[serde(default)]
+    pub deposit_contract_address: Address,
     /// The block bloom of this block, represented as the consecutive
     /// 32-byte chunks of a block's final bloom filter string.
     pub block_bloom: [U256; 8],
@@ -443,8 +464,10 @@ impl BlockMetadata {
         let block_excess_blob_gas =
             (pis[23].to_canonical_u64() + (pis[24].to_canonical_u64() << 32)).into();
         let parent_beacon_block_root = get_h256(&pis[25..33]);
+        let requests_hash = get_h256(&pis[33..41]);
+        let deposit_contract_address = get_h160(&pis[41..46]);
         let block_bloom =
-            core::array::from_fn(|i| h2u(get_h256(&pis[33 + 8 * i..33 + 8 * (i + 1)])));
+            core::array::from_fn(|i| h2u(get_h256(&pis[46 + 8 * i..46 + 8 * (i + 1)])));
 
         Self {
             block_beneficiary,
@@ -459,6 +482,8 @@ impl BlockMetadata {
             block_blob_gas_used,
             block_excess_blob_gas,
             parent_beacon_block_root,
+            requests_hash,
+            deposit_contract_address,
             block_bloom,
         }
     }
@@ -689,6 +714,8 @@ impl PublicValuesTarget {
             block_blob_gas_used,
             block_excess_blob_gas,
             parent_beacon_block_root,
+            requests_hash,
+            deposit_contract_address,
             block_bloom,
         } = self.block_metadata;
 
@@ -704,6 +731,8 @@ impl PublicValuesTarget {
         buffer.write_target_array(&block_blob_gas_used)?;
         buffer.write_target_array(&block_excess_blob_gas)?;
         buffer.write_target_array(&parent_beacon_block_root)?;
+        buffer.write_target_array(&requests_hash)?;
+        buffer.write_target_array(&deposit_contract_address)?;
         buffer.write_target_array(&block_bloom)?;
 
         let BlockHashesTarget {
@@ -794,6 +823,8 @@ impl PublicValuesTarget {
             block_blob_gas_used: buffer.read_target_array()?,
             block_excess_blob_gas: buffer.read_target_array()?,
             parent_beacon_block_root: buffer.read_target_array()?,
+            requests_hash: buffer.read_target_array()?,
+            deposit_contract_address: buffer.read_target_array()?,
             block_bloom: buffer.read_target_array()?,
         };
 
@@ -1182,15 +1213,19 @@ pub struct BlockMetadataTarget {
     pub(crate) block_blob_gas_used: [Target; 2],
     /// `Target`s for the excess blob gas of this block.
     pub(crate) block_excess_blob_gas: [Target; 2],
-    /// `Target`s for the block bloom of this block.
     /// `Target`s for the parent beacon block root.
     pub(crate) parent_beacon_block_root: [Target; 8],
+    /// `Target`s for the execution layer requests hash.
+    pub(crate) requests_hash: [Target; 8],
+    /// `Target`s for the address of the deposit contract.
+    pub(crate) deposit_contract_address: [Target; 5],
+    /// `Target`s for the block bloom of this block.
     pub(crate) block_bloom: [Target; 64],
 }
 
 impl BlockMetadataTarget {
     /// Number of `Target`s required for the block metadata.
-    pub(crate) const SIZE: usize = 97;
+    pub(crate) const SIZE: usize = 110;
 
     /// Extracts block metadata `Target`s from the provided public input
     /// `Target`s. The provided `pis` should start with the block metadata.
@@ -1207,7 +1242,9 @@ impl BlockMetadataTarget {
         let block_blob_gas_used = pis[21..23].try_into().unwrap();
         let block_excess_blob_gas = pis[23..25].try_into().unwrap();
         let parent_beacon_block_root = pis[25..33].try_into().unwrap();
-        let block_bloom = pis[33..97].try_into().unwrap();
+        let requests_hash = pis[33..41].try_into().unwrap();
+        let deposit_contract_address = pis[41..46].try_into().unwrap();
+        let block_bloom = pis[46..110].try_into().unwrap();
 
         Self {
             block_beneficiary,
@@ -1222,6 +1259,8 @@ impl BlockMetadataTarget {
             block_blob_gas_used,
             block_excess_blob_gas,
             parent_beacon_block_root,
+            requests_hash,
+            deposit_contract_address,
             block_bloom,
         }
     }
@@ -1275,6 +1314,16 @@ impl BlockMetadataTarget {
                     bm1.parent_beacon_block_root[i],
                 )
             }),
+            requests_hash: core::array::from_fn(|i| {
+                builder.select(condition, bm0.requests_hash[i], bm1.requests_hash[i])
+            }),
+            deposit_contract_address: core::array::from_fn(|i| {
+                builder.select(
+                    condition,
+                    bm0.deposit_contract_address[i],
+                    bm1.deposit_contract_address[i],
+                )
+            }),
             block_bloom: core::array::from_fn(|i| {
                 builder.select(condition, bm0.block_bloom[i], bm1.block_bloom[i])
             }),
@@ -1314,6 +1363,15 @@ impl BlockMetadataTarget {
                 bm1.parent_beacon_block_root[i],
             )
         }
+        for i in 0..8 {
+            builder.connect(bm0.requests_hash[i], bm1.requests_hash[i])
+        }
+        for i in 0..5 {
+            builder.connect(
+                bm0.deposit_contract_address[i],
+                bm1.deposit_contract_address[i],
+            )
+        }
         for i in 0..64 {
             builder.connect(bm0.block_bloom[i], bm1.block_bloom[i])
         }
@@ -1,10 +1,11 @@
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
+use std::time::{Duration, Instant};
 
 use anyhow::{anyhow, Result};
 use ethereum_types::U256;
 use itertools::Itertools;
-use once_cell::sync::Lazy;
+use once_cell::sync::OnceCell;
 use plonky2::field::extension::Extendable;
 use plonky2::field::polynomial::PolynomialValues;
 use plonky2::fri::oracle::PolynomialBatch;
@@ -27,6 +28,17 @@ use crate::get_challenges::observe_public_values;
 use crate::proof::{AllProof, MemCap, MultiProof, PublicValues, DEFAULT_CAP_LEN};
 use crate::GenerationSegmentData;
 
+/// Callback invoked with the time taken to prove each STARK table.
+static TABLE_PROOF_OBSERVER: OnceCell<fn(Table, Duration)> = OnceCell::new();
+
+/// Registers a callback invoked with the time taken to prove each STARK table,
+/// e.g. to export proving metrics.
+///
+/// Returns `false` if a callback was already registered.
+pub fn set_table_proof_observer(observer: fn(Table, Duration)) -> bool {
+    TABLE_PROOF_OBSERVER.set(observer).is_ok()
+}
+
 /// Generate traces, then create all STARK proofs.
 pub fn prove<F, C, const D: usize>(
     all_stark: &AllStark<F, D>,
@@ -45,7 +57,7 @@ where
     // Sanity check on the provided config
     assert_eq!(DEFAULT_CAP_LEN, 1 << config.fri_config.cap_height);
 
-    timed!(timing, "build kernel", Lazy::force(&KERNEL));
+    timed!(timing, "build kernel", &*KERNEL);
 
     let mut tables_with_pvs = timed!(
         timing,
@@ -227,7 +239,8 @@ where
     macro_rules! prove_table {
         ($stark:ident, $table:expr) => {
             if table_in_use[*$table] {
-                Some(timed!(
+                let start = Instant::now();
+                let proof = timed!(
                     timing,
                     &format!("prove {} STARK", stringify!($stark)),
                     prove_single_table(
@@ -241,7 +254,11 @@ where
                         timing,
                         abort_signal.clone(),
                     )?
-                ))
+                );
+                if let Some(observer) = TABLE_PROOF_OBSERVER.get() {
+                    observer($table, start.elapsed());
+                }
+                Some(proof)
             } else {
                 None
             }
@@ -358,6 +375,8 @@ pub fn check_abort_signal(abort_signal: Option<Arc<AtomicBool>>) -> Result<()> {
 pub(crate) fn features_check<F: RichField>(inputs: &TrimmedGenerationInputs<F>) {
     if !cfg!(feature = "eth_mainnet") {
         assert!(inputs.block_metadata.parent_beacon_block_root.is_zero());
+        assert!(inputs.block_metadata.requests_hash.is_zero());
+        assert!(inputs.block_metadata.deposit_contract_address.is_zero());
         assert!(inputs.block_metadata.block_blob_gas_used.is_zero());
         assert!(inputs.block_metadata.block_excess_blob_gas.is_zero());
     }
@@ -370,6 +389,7 @@ pub(crate) fn features_check<F: RichField>(inputs: &TrimmedGenerationInputs<F>)
 /// A utility module designed to test witness generation externally.
 pub mod testing {
     use super::*;
+    use crate::generation::profiler::CycleProfile;
     use crate::generation::ErrorWithTries;
     use crate::{
         cpu::kernel::interpreter::Interpreter,
@@ -389,6 +409,27 @@ pub mod testing {
         Ok(())
     }
 
+    /// Simulates the zkEVM CPU execution like [`simulate_execution`], and
+    /// returns the profile of the execution. As no trace is generated, only
+    /// CPU cycles and memory operations are reported.
+    pub fn simulate_execution_with_profile<F: RichField>(
+        inputs: GenerationInputs<F>,
+    ) -> Result<CycleProfile> {
+        features_check(&inputs.clone().trim());
+
+        let initial_stack = vec![];
+        let initial_offset = KERNEL.global_labels["init"];
+        let mut interpreter: Interpreter<F> =
+            Interpreter::new_with_generation_inputs(initial_offset, initial_stack, &inputs, None);
+        interpreter.generation_state.profile = Some(Box::default());
+        interpreter.run()?;
+        Ok(*interpreter
+            .generation_state
+            .profile
+            .take()
+            .expect("the execution is profiled"))
+    }
+
     pub fn prove_all_segments<F, C, const D: usize>(
         all_stark: &AllStark<F, D>,
         config: &StarkConfig,
@@ -47,6 +47,10 @@ pub type ProofWithPublicValues = crate::fixed_recursive_verifier::ProofWithPubli
 /// proofs.
 pub type PublicValues = crate::proof::PublicValues<Field>;
 
+/// A type alias for the public values of wrapped block proofs, which are
+/// enough to connect them with other chains' proofs.
+pub type FinalPublicValues = crate::proof::FinalPublicValues<Field, Hasher>;
+
 pub type AllData = Result<
     (TrimmedGenerationInputs, GenerationSegmentData),
     crate::generation::ErrorWithTries<SegmentError>,
@@ -402,9 +402,9 @@ pub(crate) fn get_memory_extra_looking_sum_circuit<F: RichField + Extendable<D>,
 
     // This contains the `block_beneficiary`, `block_random`, `block_base_fee`, and
     // `cur_hash`, as well as the additional `block_blob_gas_used`,
-    // `block_excess_blob_gas`, `parent_beacon_block_root` when compiling with
-    // `eth_mainnet` feature flag.
-    const LENGTH: usize = if cfg!(feature = "eth_mainnet") { 7 } else { 4 };
+    // `block_excess_blob_gas`, `parent_beacon_block_root`, `requests_hash` and
+    // `deposit_contract_address` when compiling with `eth_mainnet` feature flag.
+    const LENGTH: usize = if cfg!(feature = "eth_mainnet") { 9 } else { 4 };
     let block_fields_arrays: [(GlobalMetadata, &[Target]); LENGTH] = [
         (
             GlobalMetadata::BlockBeneficiary,
@@ -433,6 +433,16 @@ pub(crate) fn get_memory_extra_looking_sum_circuit<F: RichField + Extendable<D>,
             GlobalMetadata::ParentBeaconBlockRoot,
             &public_values.block_metadata.parent_beacon_block_root,
         ),
+        #[cfg(feature = "eth_mainnet")]
+        (
+            GlobalMetadata::RequestsHash,
+            &public_values.block_metadata.requests_hash,
+        ),
+        #[cfg(feature = "eth_mainnet")]
+        (
+            GlobalMetadata::DepositContractAddress,
+            &public_values.block_metadata.deposit_contract_address,
+        ),
         (
             GlobalMetadata::BlockCurrentHash,
             &public_values.block_hashes.cur_hash,
@@ -751,6 +761,8 @@ pub(crate) fn add_virtual_block_metadata_public_input<
     let block_blob_gas_used = builder.add_virtual_public_input_arr();
     let block_excess_blob_gas = builder.add_virtual_public_input_arr();
     let parent_beacon_block_root = builder.add_virtual_public_input_arr();
+    let requests_hash = builder.add_virtual_public_input_arr();
+    let deposit_contract_address = builder.add_virtual_public_input_arr();
     let block_bloom = builder.add_virtual_public_input_arr();
 
     BlockMetadataTarget {
@@ -766,6 +778,8 @@ pub(crate) fn add_virtual_block_metadata_public_input<
         block_blob_gas_used,
         block_excess_blob_gas,
         parent_beacon_block_root,
+        requests_hash,
+        deposit_contract_address,
         block_bloom,
     }
 }
@@ -1163,6 +1177,25 @@ where
                 &h256_limbs(block_metadata.parent_beacon_block_root),
             )
             .map_err(ProgramError::from)?;
+
+        witness
+            .set_target_arr(
+                &block_metadata_target.requests_hash,
+                &h256_limbs(block_metadata.requests_hash),
+            )
+            .map_err(ProgramError::from)?;
+
+        let deposit_contract_limbs: [F; 5] = u256_limbs::<F>(U256::from_big_endian(
+            &block_metadata.deposit_contract_address.0,
+        ))[..5]
+            .try_into()
+            .unwrap();
+        witness
+            .set_target_arr(
+                &block_metadata_target.deposit_contract_address,
+                &deposit_contract_limbs,
+            )
+            .map_err(ProgramError::from)?;
     }
 
     let mut block_bloom_limbs = [F::ZERO; 64];
@@ -18,6 +18,7 @@ use starky::config::StarkConfig;
 
 pub use crate::cpu::kernel::cancun_constants::*;
 pub use crate::cpu::kernel::constants::global_exit_root::*;
+pub use crate::cpu::kernel::prague_constants::*;
 use crate::generation::{TrieInputs, TrimmedGenerationInputs};
 use crate::proof::TrieRoots;
 #[cfg(test)]
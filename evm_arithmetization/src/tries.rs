@@ -371,6 +371,13 @@ impl StorageTrie {
     pub fn get(&mut self, key: &MptKey) -> Option<&[u8]> {
         self.untyped.get(key.into_nibbles())
     }
+    /// Whether the trie is known not to have `key`, i.e. whether looking it up
+    /// doesn't end on a hash node.
+    pub fn is_unset(&self, key: MptKey) -> bool {
+        self.untyped.get(key.into_nibbles()).is_none()
+            && get_trie_trace(&self.untyped, key).0.last()
+                != Some(&mpt_trie::utils::TrieSegment::Hash)
+    }
     pub fn insert(&mut self, key: MptKey, value: Vec<u8>) -> anyhow::Result<()> {
         self.untyped.insert(key.into_nibbles(), value)?;
         Ok(())
@@ -407,6 +414,22 @@ impl From<StorageTrie> for HashedPartialTrie {
     }
 }
 
+#[test]
+fn storage_trie_is_unset() {
+    let set = MptKey::from_hash(H256::repeat_byte(0x11));
+    let hashed_out = MptKey::from_hash(H256::repeat_byte(0x22));
+    let unset = MptKey::from_hash(H256::repeat_byte(0x33));
+
+    let mut trie = StorageTrie::new(OnOrphanedHashNode::Reject);
+    trie.insert(set, vec![1; 33]).unwrap();
+    trie.insert(hashed_out, vec![2; 33]).unwrap();
+    trie.mask([set]).unwrap();
+
+    assert!(!trie.is_unset(set));
+    assert!(!trie.is_unset(hashed_out));
+    assert!(trie.is_unset(unset));
+}
+
 /// If a branch collapse occurred after a delete, then we must ensure that
 /// the other single child that remains also is not hashed when passed into
 /// plonky2. Returns the key to the remaining child if a collapse occurred.
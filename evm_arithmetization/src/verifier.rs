@@ -371,6 +371,16 @@ pub mod testing {
                 GlobalMetadata::ParentBeaconBlockRoot,
                 h2u(public_values.block_metadata.parent_beacon_block_root),
             ),
+            #[cfg(feature = "eth_mainnet")]
+            (
+                GlobalMetadata::RequestsHash,
+                h2u(public_values.block_metadata.requests_hash),
+            ),
+            #[cfg(feature = "eth_mainnet")]
+            (
+                GlobalMetadata::DepositContractAddress,
+                U256::from_big_endian(&public_values.block_metadata.deposit_contract_address.0),
+            ),
             (
                 GlobalMetadata::BlockCurrentHash,
                 h2u(public_values.block_hashes.cur_hash),
@@ -614,6 +624,16 @@ pub(crate) mod debug_utils {
                 GlobalMetadata::ParentBeaconBlockRoot,
                 h2u(public_values.block_metadata.parent_beacon_block_root),
             ),
+            #[cfg(feature = "eth_mainnet")]
+            (
+                GlobalMetadata::RequestsHash,
+                h2u(public_values.block_metadata.requests_hash),
+            ),
+            #[cfg(feature = "eth_mainnet")]
+            (
+                GlobalMetadata::DepositContractAddress,
+                U256::from_big_endian(&public_values.block_metadata.deposit_contract_address.0),
+            ),
             (
                 GlobalMetadata::TxnNumberBefore,
                 public_values.extra_block_data.txn_number_before,
@@ -38,6 +38,7 @@ pub enum ProverInputError {
     OutOfMptData,
     OutOfRlpData,
     OutOfWithdrawalData,
+    OutOfRequestsData,
     OutOfGerData,
     CodeHashNotFound,
     InvalidMptInput,
@@ -11,6 +11,7 @@ use crate::all_stark::{AllStark, NUM_TABLES};
 use crate::arithmetic::{BinaryOperator, Operation};
 use crate::byte_packing::byte_packing_stark::BytePackingOp;
 use crate::cpu::columns::CpuColumnsView;
+use crate::generation::profiler::TableRows;
 use crate::generation::MemBeforeValues;
 use crate::keccak_sponge::keccak_sponge_stark::KeccakSpongeOp;
 use crate::memory_continuation::memory_continuation_stark::mem_before_values_to_rows;
@@ -64,31 +65,11 @@ impl<T: Copy + Field> Traces<T> {
     //  Uses a `TraceCheckPoint` as return object for convenience.
     pub(crate) fn get_lengths(&self) -> TraceCheckpoint {
         TraceCheckpoint {
-            arithmetic_len: self
-                .arithmetic_ops
-                .iter()
-                .map(|op| match op {
-                    Operation::TernaryOperation { .. } => 2,
-                    Operation::BinaryOperation { operator, .. } => match operator {
-                        BinaryOperator::Div
-                        | BinaryOperator::Mod
-                        | BinaryOperator::AddFp254
-                        | BinaryOperator::SubFp254
-                        | BinaryOperator::MulFp254
-                        | BinaryOperator::Shr => 2,
-                        _ => 1,
-                    },
-                    Operation::RangeCheckOperation { .. } => 1,
-                })
-                .sum(),
+            arithmetic_len: arithmetic_rows(&self.arithmetic_ops),
             byte_packing_len: self.byte_packing_ops.len(),
             cpu_len: self.cpu.len(),
             keccak_len: self.keccak_inputs.len() * keccak::keccak_stark::NUM_ROUNDS,
-            keccak_sponge_len: self
-                .keccak_sponge_ops
-                .iter()
-                .map(|op| op.input.len() / keccak_sponge::columns::KECCAK_RATE_BYTES + 1)
-                .sum(),
+            keccak_sponge_len: keccak_sponge_rows(&self.keccak_sponge_ops),
             logic_len: self.logic_ops.len(),
             // This is technically a lower-bound, as we may fill gaps,
             // but this gives a relatively good estimate.
@@ -98,6 +79,32 @@ impl<T: Copy + Field> Traces<T> {
         }
     }
 
+    /// Returns the number of rows added to each STARK module since
+    /// `checkpoint`, estimated like in [`Traces::get_lengths`].
+    pub(crate) fn rows_since(&self, checkpoint: TraceCheckpoint) -> TableRows {
+        let since = |len: usize, checkpoint_len: usize| len.saturating_sub(checkpoint_len);
+        TableRows {
+            arithmetic: arithmetic_rows(
+                self.arithmetic_ops
+                    .get(checkpoint.arithmetic_len..)
+                    .unwrap_or_default(),
+            ),
+            byte_packing: since(self.byte_packing_ops.len(), checkpoint.byte_packing_len),
+            cpu: since(self.cpu.len(), checkpoint.cpu_len),
+            keccak: since(self.keccak_inputs.len(), checkpoint.keccak_len)
+                * keccak::keccak_stark::NUM_ROUNDS,
+            keccak_sponge: keccak_sponge_rows(
+                self.keccak_sponge_ops
+                    .get(checkpoint.keccak_sponge_len..)
+                    .unwrap_or_default(),
+            ),
+            logic: since(self.logic_ops.len(), checkpoint.logic_len),
+            memory: since(self.memory_ops.len(), checkpoint.memory_len),
+            #[cfg(feature = "cdk_erigon")]
+            poseidon: since(self.poseidon_ops.len(), checkpoint.poseidon_len),
+        }
+    }
+
     /// Returns the number of operations for each STARK module.
     pub(crate) fn checkpoint(&self) -> TraceCheckpoint {
         TraceCheckpoint {
@@ -259,6 +266,30 @@ impl<T: Copy + Field> Traces<T> {
     }
 }
 
+fn arithmetic_rows(ops: &[Operation]) -> usize {
+    ops.iter()
+        .map(|op| match op {
+            Operation::TernaryOperation { .. } => 2,
+            Operation::BinaryOperation { operator, .. } => match operator {
+                BinaryOperator::Div
+                | BinaryOperator::Mod
+                | BinaryOperator::AddFp254
+                | BinaryOperator::SubFp254
+                | BinaryOperator::MulFp254
+                | BinaryOperator::Shr => 2,
+                _ => 1,
+            },
+            Operation::RangeCheckOperation { .. } => 1,
+        })
+        .sum()
+}
+
+fn keccak_sponge_rows(ops: &[KeccakSpongeOp]) -> usize {
+    ops.iter()
+        .map(|op| op.input.len() / keccak_sponge::columns::KECCAK_RATE_BYTES + 1)
+        .sum()
+}
+
 impl<T: Copy + Field> Default for Traces<T> {
     fn default() -> Self {
         Self::new()
@@ -68,6 +68,9 @@ pub trait World {
     /// Creates a new account at `address` if it does not exist.
     fn set_code(&mut self, address: Address, code: Either<&[u8], H256>) -> anyhow::Result<()>;
 
+    /// The code hash of the account at the given address, if it exists.
+    fn code_hash(&mut self, address: Address) -> anyhow::Result<Option<H256>>;
+
     /// The `core` module of the `trace_decoder` crate tracks required subtries
     /// for proving.
     ///
@@ -94,6 +97,10 @@ pub trait World {
     /// Load an integer from the given account at the given `slot`.
     fn load_int(&mut self, address: Address, slot: U256) -> anyhow::Result<U256>;
 
+    /// Whether the given account's storage is known not to have the given
+    /// `slot`, as opposed to having it or having it hashed out.
+    fn is_unset(&mut self, address: Address, slot: U256) -> anyhow::Result<bool>;
+
     /// Delete the given slot from the given account's storage.
     ///
     /// In case of a delete, it may be that certain parts of the subtrie
@@ -199,6 +206,12 @@ impl World for Type1World {
         acct.code_hash = code.right_or_else(Self::CodeHasher::hash);
         self.state.insert(key, acct)
     }
+    fn code_hash(&mut self, address: Address) -> anyhow::Result<Option<H256>> {
+        Ok(self
+            .state
+            .get(keccak_hash::keccak(address))
+            .map(|it| it.code_hash))
+    }
     fn reporting_destroy(&mut self, address: Address) -> anyhow::Result<Option<Self::SubtriePath>> {
         self.state.reporting_remove(address)
     }
@@ -247,6 +260,12 @@ impl World for Type1World {
         Ok(rlp::decode(bytes)?)
     }
 
+    fn is_unset(&mut self, address: Address, slot: U256) -> anyhow::Result<bool> {
+        Ok(self
+            .get_storage_mut(address)?
+            .is_unset(MptKey::from_slot_position(slot)))
+    }
+
     fn reporting_destroy_slot(
         &mut self,
         address: Address,
@@ -308,6 +327,12 @@ impl World for Type2World {
         };
         Ok(())
     }
+    fn code_hash(&mut self, address: Address) -> anyhow::Result<Option<H256>> {
+        Ok(self.accounts.get(&address).map(|it| match it.code_hash {
+            Some(hash) => H256::from_uint(&hash),
+            None => Self::CodeHasher::hash(&[]),
+        }))
+    }
     fn reporting_destroy(&mut self, address: Address) -> anyhow::Result<Option<Self::SubtriePath>> {
         self.accounts.remove(&address);
         Ok(None)
@@ -348,6 +373,14 @@ impl World for Type2World {
             .copied()
             .unwrap_or_default())
     }
+    fn is_unset(&mut self, address: Address, slot: U256) -> anyhow::Result<bool> {
+        Ok(!self
+            .accounts
+            .get(&address)
+            .context("no account")?
+            .storage
+            .contains_key(&slot))
+    }
     fn reporting_destroy_slot(
         &mut self,
         address: Address,
@@ -453,4 +486,12 @@ impl Type2World {
             hashed_out,
         }
     }
+
+    pub fn accounts(&self) -> &BTreeMap<Address, Type2Entry> {
+        &self.accounts
+    }
+
+    pub fn hashed_out(&self) -> &BTreeMap<SmtKey, H256> {
+        &self.hashed_out
+    }
 }
@@ -184,6 +184,7 @@ fn get_generation_inputs() -> GenerationInputs {
         signed_txns: vec![txn.to_vec()],
         burn_addr: None,
         withdrawals: vec![],
+        process_requests: false,
         ger_data: None,
         tries: tries_before,
         trie_roots_after,
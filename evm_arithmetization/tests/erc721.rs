@@ -189,6 +189,7 @@ fn test_erc721() -> anyhow::Result<()> {
         signed_txns: vec![txn.to_vec()],
         burn_addr: None,
         withdrawals: vec![],
+        process_requests: false,
         ger_data: None,
         tries: tries_before,
         trie_roots_after,
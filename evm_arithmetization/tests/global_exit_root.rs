@@ -95,6 +95,7 @@ fn test_global_exit_root() -> anyhow::Result<()> {
         signed_txns: vec![],
         burn_addr: None,
         withdrawals: vec![],
+        process_requests: false,
         ger_data,
         tries: TrieInputs {
             state_trie: state_trie_before,
@@ -251,6 +251,7 @@ fn test_log_opcodes() -> anyhow::Result<()> {
         signed_txns: vec![txn.to_vec()],
         burn_addr,
         withdrawals: vec![],
+        process_requests: false,
         ger_data: None,
         tries: tries_before,
         trie_roots_after,
@@ -148,6 +148,7 @@ fn test_simple_transfer() -> anyhow::Result<()> {
         signed_txns: vec![txn.to_vec()],
         burn_addr: None,
         withdrawals: vec![],
+        process_requests: false,
         ger_data: None,
         tries: tries_before,
         trie_roots_after,
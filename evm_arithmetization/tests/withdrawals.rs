@@ -86,6 +86,7 @@ fn test_withdrawals() -> anyhow::Result<()> {
         signed_txns: vec![],
         burn_addr: None,
         withdrawals,
+        process_requests: false,
         ger_data: None,
         tries: TrieInputs {
             state_trie: state_trie_before,
@@ -71,6 +71,15 @@ pub trait PartialTrie:
     where
         K: Into<Nibbles>;
 
+    /// Returns the RLP encoded nodes along the path of a key, starting from
+    /// the root, in the same format as `eth_getProof`.
+    ///
+    /// The proof also proves the absence of a key, and can be checked with
+    /// [`verify_proof`](crate::trie_ops::verify_proof).
+    fn get_proof<K>(&self, k: K) -> TrieOpResult<Vec<Vec<u8>>>
+    where
+        K: Into<Nibbles>;
+
     /// Deletes a `Leaf` node or `Branch` value field if it exists.
     ///
     /// To agree with Ethereum specs, deleting nodes does not result in the trie
@@ -244,6 +253,13 @@ impl PartialTrie for StandardTrie {
         self.0.trie_get(k)
     }
 
+    fn get_proof<K>(&self, k: K) -> TrieOpResult<Vec<Vec<u8>>>
+    where
+        K: Into<Nibbles>,
+    {
+        self.0.trie_get_proof(k)
+    }
+
     fn delete<K>(&mut self, k: K) -> TrieOpResult<Option<Vec<u8>>>
     where
         K: Into<Nibbles>,
@@ -394,6 +410,13 @@ impl PartialTrie for HashedPartialTrie {
         self.node.trie_get(k)
     }
 
+    fn get_proof<K>(&self, k: K) -> TrieOpResult<Vec<Vec<u8>>>
+    where
+        K: Into<crate::nibbles::Nibbles>,
+    {
+        self.node.trie_get_proof(k)
+    }
+
     fn delete<K>(&mut self, k: K) -> TrieOpResult<Option<Vec<u8>>>
     where
         K: Into<crate::nibbles::Nibbles>,
@@ -36,9 +36,21 @@ pub(crate) fn hash_trie<N: PartialTrie + TrieNodeIntern>(node: &Node<N>) -> H256
 pub(crate) fn rlp_encode_and_hash_node<N: PartialTrie + TrieNodeIntern>(
     node: &Node<N>,
 ) -> EncodedNode {
-    let res = match node {
+    match node {
         Node::Empty => EncodedNode::Raw(Bytes::from_static(&rlp::NULL_RLP)),
         Node::Hash(h) => EncodedNode::Hashed(h.0),
+        Node::Branch { .. } | Node::Extension { .. } | Node::Leaf { .. } => {
+            hash_bytes_if_large_enough(rlp_encode_node(node).expect("not a `Hash` node"))
+        }
+    }
+}
+
+/// RLP encodes a node, regardless of its size.
+/// Returns `None` for `Hash` nodes, whose encoding is unknown.
+pub(crate) fn rlp_encode_node<N: PartialTrie + TrieNodeIntern>(node: &Node<N>) -> Option<Bytes> {
+    let res = match node {
+        Node::Empty => Bytes::from_static(&rlp::NULL_RLP),
+        Node::Hash(_) => return None,
         Node::Branch { children, value } => {
             let mut stream = RlpStream::new_list(17);
 
@@ -51,7 +63,7 @@ pub(crate) fn rlp_encode_and_hash_node<N: PartialTrie + TrieNodeIntern>(
                 true => stream.append_empty_data(),
             };
 
-            hash_bytes_if_large_enough(stream.out().into())
+            stream.out().into()
         }
         Node::Extension { nibbles, child } => {
             let mut stream = RlpStream::new_list(2);
@@ -59,7 +71,7 @@ pub(crate) fn rlp_encode_and_hash_node<N: PartialTrie + TrieNodeIntern>(
             stream.append(&nibbles.to_hex_prefix_encoding(false));
             append_to_stream(&mut stream, child.hash_intern());
 
-            hash_bytes_if_large_enough(stream.out().into())
+            stream.out().into()
         }
         Node::Leaf { nibbles, value } => {
             let hex_prefix_k = nibbles.to_hex_prefix_encoding(true);
@@ -68,11 +80,11 @@ pub(crate) fn rlp_encode_and_hash_node<N: PartialTrie + TrieNodeIntern>(
             stream.append(&hex_prefix_k);
             stream.append(value);
 
-            hash_bytes_if_large_enough(stream.out().into())
+            stream.out().into()
         }
     };
 
-    res
+    Some(res)
 }
 
 fn hash_bytes_if_large_enough(bytes: Bytes) -> EncodedNode {
@@ -456,4 +468,32 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn proofs_agree_with_eth_trie() -> Result<(), Box<dyn std::error::Error>> {
+        common_setup();
+
+        let entries: Vec<_> = generate_n_random_fixed_even_nibble_padded_trie_value_entries(
+            NUM_INSERTS_FOR_ETH_TRIE_CRATE_MASSIVE_TEST,
+            14,
+        )
+        .collect();
+
+        let our_trie = HashedPartialTrie::try_from_iter(entries.iter().cloned())?;
+        let mut truth_trie = create_truth_trie();
+
+        for (k, v) in entries.iter() {
+            truth_trie.insert(&k.bytes_be(), v)?;
+        }
+        truth_trie.root_hash()?;
+
+        for (k, _) in entries.iter() {
+            assert_eq!(
+                our_trie.get_proof(*k)?,
+                truth_trie.get_proof(&k.bytes_be())?
+            );
+        }
+
+        Ok(())
+    }
 }
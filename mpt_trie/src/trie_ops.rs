@@ -1,16 +1,20 @@
 //! Defines various operations for
 //! [`PartialTrie`].
 
-use std::{fmt::Display, mem::size_of};
+use std::{collections::HashMap, fmt::Display, mem::size_of};
 
 use enum_as_inner::EnumAsInner;
 use ethereum_types::{H256, U128, U256, U512};
+use keccak_hash::keccak;
 use log::trace;
+use rlp::{Prototype, Rlp};
 use thiserror::Error;
+use zk_evm_common::EMPTY_TRIE_HASH;
 
 use crate::{
     nibbles::{Nibble, Nibbles},
     partial_trie::{Node, OnOrphanedHashNode, PartialTrie, WrappedNode},
+    trie_hashing::rlp_encode_node,
     utils::TrieNodeType,
 };
 
@@ -52,6 +56,16 @@ pub enum TrieOpError {
     /// Failed to insert a hash node into the trie.
     #[error("Attempted to place a hash node on an existing node! (hash: {0})")]
     ExistingHashNodeError(H256),
+
+    /// An error that occurs when a hash node is found while generating a
+    /// proof, as its preimage can not be included in the proof.
+    #[error("Attempted to generate a proof through a hash node! (hash: {0})")]
+    HashNodeProofError(H256),
+
+    /// An error that occurs when a proof does not match the root it is
+    /// verified against.
+    #[error("Invalid Merkle proof: {0}")]
+    InvalidProofError(String),
 }
 
 /// A entry to be inserted into a `PartialTrie`.
@@ -373,6 +387,65 @@ impl<T: PartialTrie> Node<T> {
         }
     }
 
+    /// Returns the nodes along the path of a key, RLP encoded, as in the
+    /// `accountProof` and `storageProof` fields of `eth_getProof`.
+    ///
+    /// The proof starts at the root and ends at the node where the lookup of
+    /// the key terminates, so it also proves the absence of a key. Nodes
+    /// smaller than 32 bytes are inlined in their parents, and are therefore
+    /// not part of the proof, except for the root.
+    pub(crate) fn trie_get_proof<K>(&self, k: K) -> TrieOpResult<Vec<Vec<u8>>>
+    where
+        K: Into<Nibbles>,
+    {
+        let mut proof = Vec::new();
+        if !matches!(self, Node::Empty) {
+            self.trie_get_proof_intern(&mut k.into(), &mut proof, true)?;
+        }
+
+        Ok(proof)
+    }
+
+    fn trie_get_proof_intern(
+        &self,
+        curr_nibbles: &mut Nibbles,
+        proof: &mut Vec<Vec<u8>>,
+        is_root: bool,
+    ) -> TrieOpResult<()> {
+        if let Node::Hash(h) = self {
+            return Err(TrieOpError::HashNodeProofError(*h));
+        }
+
+        let encoded = rlp_encode_node(self).expect("not a `Hash` node");
+        if is_root || encoded.len() >= 32 {
+            proof.push(encoded.to_vec());
+        }
+
+        match self {
+            Node::Empty | Node::Hash(_) | Node::Leaf { .. } => Ok(()),
+            Node::Branch { children, .. } => {
+                if curr_nibbles.is_empty() {
+                    return Ok(());
+                }
+
+                let nib = curr_nibbles.pop_next_nibble_front();
+                trace!("Get proof traversed Branch (nibble: {:x})", nib);
+                children[nib as usize].trie_get_proof_intern(curr_nibbles, proof, false)
+            }
+            Node::Extension { nibbles, child } => {
+                trace!("Get proof traversed Extension (nibbles: {:?})", nibbles);
+                if curr_nibbles.count < nibbles.count
+                    || curr_nibbles.get_next_nibbles(nibbles.count) != *nibbles
+                {
+                    return Ok(());
+                }
+
+                curr_nibbles.pop_nibbles_front(nibbles.count);
+                child.trie_get_proof_intern(curr_nibbles, proof, false)
+            }
+        }
+    }
+
     /// Deletes a key if it exists in the trie.
     ///
     /// If the key exists, then the existing node value that was deleted is
@@ -887,10 +960,114 @@ fn create_node_if_ins_val_not_hash<N, F: FnOnce(Vec<u8>) -> WrappedNode<N>>(
     }
 }
 
+/// Verifies a proof, as returned by [`PartialTrie::get_proof`] or by the
+/// `eth_getProof` RPC method, of the value of a key in the trie with the given
+/// root hash.
+///
+/// Returns the value of the key if it is in the trie, and `None` if the proof
+/// shows that it is not.
+pub fn verify_proof<K>(root: H256, k: K, proof: &[Vec<u8>]) -> TrieOpResult<Option<Vec<u8>>>
+where
+    K: Into<Nibbles>,
+{
+    let nodes: HashMap<H256, &[u8]> = proof
+        .iter()
+        .map(|node| (keccak(node), node.as_slice()))
+        .collect();
+    let get_node = |hash: H256| {
+        nodes.get(&hash).copied().ok_or_else(|| {
+            TrieOpError::InvalidProofError(format!("missing node with hash {hash:x}"))
+        })
+    };
+    let invalid = |e: rlp::DecoderError| TrieOpError::InvalidProofError(e.to_string());
+
+    if root == EMPTY_TRIE_HASH {
+        return Ok(None);
+    }
+
+    let mut curr_nibbles = k.into();
+    let mut node = Rlp::new(get_node(root)?);
+    loop {
+        let child = match node.item_count().map_err(invalid)? {
+            17 => {
+                if curr_nibbles.is_empty() {
+                    let value: Vec<u8> = node.val_at(16).map_err(invalid)?;
+                    return Ok((!value.is_empty()).then_some(value));
+                }
+
+                node.at(curr_nibbles.pop_next_nibble_front() as usize)
+                    .map_err(invalid)?
+            }
+            2 => {
+                let path = node.at(0).map_err(invalid)?;
+                let (nibbles, is_leaf) = decode_hex_prefix_path(path.data().map_err(invalid)?)?;
+                if curr_nibbles.count < nibbles.count
+                    || curr_nibbles.get_next_nibbles(nibbles.count) != nibbles
+                {
+                    return Ok(None);
+                }
+
+                curr_nibbles.pop_nibbles_front(nibbles.count);
+                match is_leaf {
+                    false => node.at(1).map_err(invalid)?,
+                    true => {
+                        return Ok(curr_nibbles
+                            .is_empty()
+                            .then(|| node.val_at(1))
+                            .transpose()
+                            .map_err(invalid)?)
+                    }
+                }
+            }
+            n => {
+                return Err(TrieOpError::InvalidProofError(format!(
+                    "node with {n} items"
+                )))
+            }
+        };
+
+        node = match child.prototype().map_err(invalid)? {
+            // Nodes smaller than 32 bytes are inlined in their parent.
+            Prototype::List(_) => child,
+            Prototype::Data(0) => return Ok(None),
+            Prototype::Data(32) => {
+                Rlp::new(get_node(H256::from_slice(child.data().map_err(invalid)?))?)
+            }
+            _ => {
+                return Err(TrieOpError::InvalidProofError(format!(
+                    "invalid child node {:x?}",
+                    child.as_raw()
+                )))
+            }
+        };
+    }
+}
+
+/// Decodes the hex prefix encoded path of a leaf or an extension node,
+/// returning the path and whether the node is a leaf.
+fn decode_hex_prefix_path(bytes: &[u8]) -> TrieOpResult<(Nibbles, bool)> {
+    let mut nibbles =
+        Nibbles::from_bytes_be(bytes).map_err(|e| TrieOpError::InvalidProofError(e.to_string()))?;
+
+    let flags = nibbles.pop_next_nibble_front();
+    if flags > 3 {
+        return Err(TrieOpError::InvalidProofError(format!(
+            "invalid hex prefix flags {flags:#x}"
+        )));
+    }
+    // Even paths are padded with a zero nibble.
+    if flags & 1 == 0 {
+        nibbles.pop_next_nibble_front();
+    }
+
+    Ok((nibbles, flags & 2 != 0))
+}
+
 #[cfg(test)]
 mod tests {
     use std::{collections::HashSet, iter::once};
 
+    use ethereum_types::H256;
     use log::debug;
 
     use super::ValOrHash;
@@ -904,7 +1081,7 @@ mod tests {
             generate_n_random_variable_trie_value_entries, get_non_hash_values_in_trie,
             unwrap_iter_item_to_val, TestInsertValEntry,
         },
-        trie_ops::TrieOpResult,
+        trie_ops::{verify_proof, TrieOpError, TrieOpResult},
         utils::{create_mask_of_1s, TryFromIterator},
     };
 
@@ -1247,4 +1424,78 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn proofs_of_present_and_absent_keys_verify() -> TrieOpResult<()> {
+        common_setup();
+
+        let entries: Vec<_> = generate_n_random_fixed_trie_value_entries(1000, 11).collect();
+        let absent_entries = generate_n_random_fixed_trie_value_entries(100, 12);
+        let trie = HashedPartialTrie::try_from_iter(entries.iter().cloned())?;
+        let root = trie.hash();
+
+        for (k, v) in entries.iter() {
+            let proof = trie.get_proof(*k)?;
+            assert_eq!(verify_proof(root, *k, &proof)?, Some(v.clone()));
+        }
+
+        for (k, _) in absent_entries {
+            let proof = trie.get_proof(k)?;
+            assert_eq!(
+                verify_proof(root, k, &proof)?,
+                trie.get(k).map(<[u8]>::to_vec)
+            );
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn tampered_proofs_do_not_verify() -> TrieOpResult<()> {
+        common_setup();
+
+        let entries: Vec<_> = generate_n_random_fixed_trie_value_entries(100, 13).collect();
+        let trie = HashedPartialTrie::try_from_iter(entries.iter().cloned())?;
+        let root = trie.hash();
+        let (k, v) = &entries[0];
+
+        let mut proof = trie.get_proof(*k)?;
+        let leaf = proof.last_mut().unwrap();
+        *leaf.last_mut().unwrap() ^= 1;
+        assert!(verify_proof(root, *k, &proof) != Ok(Some(v.clone())));
+
+        let mut proof = trie.get_proof(*k)?;
+        proof.remove(0);
+        assert!(matches!(
+            verify_proof(root, *k, &proof),
+            Err(TrieOpError::InvalidProofError(_))
+        ));
+
+        Ok(())
+    }
+
+    #[test]
+    fn proof_of_empty_trie_is_empty() -> TrieOpResult<()> {
+        let trie = HashedPartialTrie::default();
+        let proof = trie.get_proof(0x1234)?;
+
+        assert!(proof.is_empty());
+        assert_eq!(verify_proof(trie.hash(), 0x1234, &proof)?, None);
+
+        Ok(())
+    }
+
+    #[test]
+    fn proof_through_hash_node_fails() -> TrieOpResult<()> {
+        let h = H256::from_low_u64_be(1);
+        let mut trie = HashedPartialTrie::default();
+        trie.insert(0x1234, h)?;
+
+        assert_eq!(
+            trie.get_proof(0x1234),
+            Err(TrieOpError::HashNodeProofError(h))
+        );
+
+        Ok(())
+    }
 }
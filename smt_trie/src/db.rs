@@ -1,8 +1,14 @@
+use std::collections::hash_map::Entry;
 use std::collections::HashMap;
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, BufReader, BufWriter, Read, Seek, SeekFrom, Write};
+use std::path::{Path, PathBuf};
 
-use crate::smt::{Key, Node};
+use plonky2::field::types::{Field, Field64, PrimeField64};
 
-pub trait Db: Default {
+use crate::smt::{Key, Node, F};
+
+pub trait Db {
     fn get_node(&self, key: &Key) -> Option<&Node>;
     fn set_node(&mut self, key: Key, value: Node);
 }
@@ -12,6 +18,17 @@ pub struct MemoryDb {
     pub db: HashMap<Key, Node>,
 }
 
+impl MemoryDb {
+    /// Removes all the nodes which aren't reachable from `roots`.
+    /// Returns the number of removed nodes.
+    pub fn prune(&mut self, roots: &[Key]) -> usize {
+        let live = ref_counts(self, roots);
+        let before = self.db.len();
+        self.db.retain(|key, _| live.contains_key(key));
+        before - self.db.len()
+    }
+}
+
 impl Db for MemoryDb {
     fn get_node(&self, key: &Key) -> Option<&Node> {
         self.db.get(key)
@@ -21,3 +38,240 @@ impl Db for MemoryDb {
         self.db.insert(key, value);
     }
 }
+
+/// The magic bytes at the start of a [`FileDb`] file.
+pub const FILE_DB_MAGIC: [u8; 8] = *b"SMTFILDB";
+/// The version of the [`FileDb`] file format.
+pub const FILE_DB_VERSION: u32 = 1;
+
+/// Size of the header of a [`FileDb`] file, i.e. [`FILE_DB_MAGIC`] followed by
+/// [`FILE_DB_VERSION`] as a little-endian `u32`.
+const HEADER_LEN: usize = FILE_DB_MAGIC.len() + 4;
+
+/// Size of a record in a [`FileDb`], i.e. a key followed by its node, as
+/// little-endian `u64`s.
+const RECORD_LEN: usize = (4 + 12) * 8;
+
+/// Persistent [`Db`], backed by an append-only file of `(key, node)` records,
+/// following a header with [`FILE_DB_MAGIC`] and [`FILE_DB_VERSION`].
+///
+/// All the nodes are cached in memory when the file is opened, so that reads
+/// never touch the disk, and IO errors surface in [`FileDb::open`],
+/// [`FileDb::flush`] and [`FileDb::prune`] only.
+/// Writes are buffered in memory until [`FileDb::flush`], which appends them
+/// in a single batch. Unflushed writes are lost on drop.
+///
+/// Nodes are content-addressed, so the file only ever grows: use
+/// [`FileDb::prune`] to drop the nodes of stale tries.
+#[derive(Debug)]
+pub struct FileDb {
+    path: PathBuf,
+    file: File,
+    /// All the nodes, flushed or not.
+    nodes: HashMap<Key, Node>,
+    /// Keys of the nodes written since the last flush.
+    pending: Vec<Key>,
+}
+
+impl FileDb {
+    /// Opens the database at `path`, creating it if needed.
+    ///
+    /// A trailing partial record, e.g. from a crash in the middle of a flush,
+    /// is discarded.
+    ///
+    /// Fails with [`io::ErrorKind::InvalidData`] if the file isn't a database
+    /// of the supported version, or holds a non-canonical field element.
+    pub fn open(path: impl AsRef<Path>) -> io::Result<Self> {
+        let path = path.as_ref().to_path_buf();
+        let mut file = OpenOptions::new()
+            .read(true)
+            .write(true)
+            .create(true)
+            .truncate(false)
+            .open(&path)?;
+
+        let file_len = file.metadata()?.len();
+        if file_len == 0 {
+            write_header(&mut file)?;
+        } else {
+            read_header(&mut file)?;
+        }
+
+        let num_records = file_len.saturating_sub(HEADER_LEN as u64) / RECORD_LEN as u64;
+        let len = HEADER_LEN as u64 + num_records * RECORD_LEN as u64;
+        if file_len > len {
+            file.set_len(len)?;
+        }
+
+        let mut reader = BufReader::new(&file);
+        reader.seek(SeekFrom::Start(HEADER_LEN as u64))?;
+        let mut nodes = HashMap::with_capacity(num_records as usize);
+        let mut record = [0; RECORD_LEN];
+        for _ in 0..num_records {
+            reader.read_exact(&mut record)?;
+            let (key, node) = decode_record(&record)?;
+            nodes.insert(key, node);
+        }
+        drop(reader);
+
+        Ok(Self {
+            path,
+            file,
+            nodes,
+            pending: Vec::new(),
+        })
+    }
+
+    /// Number of nodes in the database, including unflushed ones.
+    pub fn len(&self) -> usize {
+        self.nodes.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Appends all the pending writes to the file.
+    pub fn flush(&mut self) -> io::Result<()> {
+        if self.pending.is_empty() {
+            return Ok(());
+        }
+
+        self.file.seek(SeekFrom::End(0))?;
+        let mut writer = BufWriter::new(&self.file);
+        for key in &self.pending {
+            writer.write_all(&encode_record(key, &self.nodes[key]))?;
+        }
+        writer.flush()?;
+        drop(writer);
+        self.file.sync_data()?;
+        self.pending.clear();
+        Ok(())
+    }
+
+    /// Removes all the nodes which aren't reachable from `roots`, by rewriting
+    /// the file with the live nodes only.
+    /// Returns the number of removed nodes.
+    pub fn prune(&mut self, roots: &[Key]) -> io::Result<usize> {
+        self.flush()?;
+        let live = ref_counts(self, roots);
+
+        let tmp_path = self.path.with_extension("prune");
+        let mut writer = BufWriter::new(File::create(&tmp_path)?);
+        write_header(&mut writer)?;
+        let mut nodes = HashMap::with_capacity(live.len());
+        for key in live.keys() {
+            // Hashed-out nodes are referenced but not stored.
+            if let Some(node) = self.nodes.get(key) {
+                writer.write_all(&encode_record(key, node))?;
+                nodes.insert(*key, *node);
+            }
+        }
+        writer
+            .into_inner()
+            .map_err(io::IntoInnerError::into_error)?
+            .sync_all()?;
+        fs::rename(&tmp_path, &self.path)?;
+
+        let removed = self.nodes.len() - nodes.len();
+        self.file = OpenOptions::new().read(true).write(true).open(&self.path)?;
+        self.nodes = nodes;
+        Ok(removed)
+    }
+}
+
+impl Db for FileDb {
+    fn get_node(&self, key: &Key) -> Option<&Node> {
+        self.nodes.get(key)
+    }
+
+    fn set_node(&mut self, key: Key, value: Node) {
+        if let Entry::Vacant(entry) = self.nodes.entry(key) {
+            entry.insert(value);
+            self.pending.push(key);
+        }
+    }
+}
+
+/// Counts the references to each node reachable from `roots`.
+/// Unreachable nodes are absent from the result.
+pub fn ref_counts<D: Db>(db: &D, roots: &[Key]) -> HashMap<Key, usize> {
+    let mut counts = HashMap::new();
+    // `(key, is_value)`, as value nodes have no children.
+    let mut stack = roots.iter().map(|root| (*root, false)).collect::<Vec<_>>();
+    while let Some((key, is_value)) = stack.pop() {
+        if key.0.iter().all(F::is_zero) {
+            continue;
+        }
+        let count = counts.entry(key).or_insert(0);
+        *count += 1;
+        if *count > 1 || is_value {
+            continue;
+        }
+        let Some(node) = db.get_node(&key) else {
+            continue;
+        };
+        if node.is_one_siblings() {
+            stack.push((Key(node.0[4..8].try_into().unwrap()), true));
+        } else {
+            stack.push((Key(node.0[0..4].try_into().unwrap()), false));
+            stack.push((Key(node.0[4..8].try_into().unwrap()), false));
+        }
+    }
+    counts
+}
+
+fn write_header(writer: &mut impl Write) -> io::Result<()> {
+    writer.write_all(&FILE_DB_MAGIC)?;
+    writer.write_all(&FILE_DB_VERSION.to_le_bytes())
+}
+
+fn read_header(reader: &mut impl Read) -> io::Result<()> {
+    let mut header = [0; HEADER_LEN];
+    reader.read_exact(&mut header).map_err(|e| match e.kind() {
+        io::ErrorKind::UnexpectedEof => {
+            io::Error::new(io::ErrorKind::InvalidData, "truncated SMT database header")
+        }
+        _ => e,
+    })?;
+    let (magic, version) = header.split_at(FILE_DB_MAGIC.len());
+    if magic != FILE_DB_MAGIC {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            "not an SMT database",
+        ));
+    }
+    let version = u32::from_le_bytes(version.try_into().unwrap());
+    if version != FILE_DB_VERSION {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("unsupported SMT database version {version}"),
+        ));
+    }
+    Ok(())
+}
+
+fn encode_record(key: &Key, node: &Node) -> [u8; RECORD_LEN] {
+    let mut record = [0; RECORD_LEN];
+    for (chunk, f) in record.chunks_exact_mut(8).zip(key.0.iter().chain(&node.0)) {
+        chunk.copy_from_slice(&f.to_canonical_u64().to_le_bytes());
+    }
+    record
+}
+
+fn decode_record(record: &[u8]) -> io::Result<(Key, Node)> {
+    let mut limbs = [F::ZERO; RECORD_LEN / 8];
+    for (limb, chunk) in limbs.iter_mut().zip(record.chunks_exact(8)) {
+        let n = u64::from_le_bytes(chunk.try_into().unwrap());
+        if n >= F::ORDER {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "non-canonical field element in SMT database",
+            ));
+        }
+        *limb = F::from_canonical_u64(n);
+    }
+    let key = Key(limbs[..4].try_into().unwrap());
+    let node = Node(limbs[4..].try_into().unwrap());
+    Ok((key, node))
+}
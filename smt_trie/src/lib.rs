@@ -2,6 +2,7 @@ pub mod bits;
 pub mod code;
 pub mod db;
 pub mod keys;
+pub mod proof;
 pub mod smt;
 #[cfg(test)]
 mod smt_test;
@@ -0,0 +1,111 @@
+use alloy::primitives::U256;
+use plonky2::field::types::Field;
+use plonky2::hash::poseidon::Poseidon;
+
+use crate::bits::Bits;
+use crate::db::Db;
+use crate::smt::{HashOut, Key, Node, Smt, F};
+use crate::utils::{f2limbs, hash0, hash_key_hash};
+
+/// Proof of the value associated with a key in an [`Smt`].
+///
+/// It consists of the hashes of the siblings along the path of the key, from
+/// the root down, and of the node this path ends at.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SmtProof {
+    /// The sibling hash at each level of the path.
+    pub siblings: Vec<[F; 4]>,
+    pub terminal: SmtProofTerminal,
+}
+
+/// The node an [`SmtProof`] path ends at.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SmtProofTerminal {
+    /// An empty subtree, so the key isn't in the SMT.
+    Empty,
+    /// A leaf, either for the key itself, or for another key with the same
+    /// path, in which case the key isn't in the SMT.
+    Leaf {
+        /// The remaining bits of the leaf's key, below the path.
+        rem_key: Key,
+        /// The hash of the leaf's value.
+        value_hash: [F; 4],
+    },
+}
+
+impl<D: Db> Smt<D> {
+    /// Returns a proof of the value associated with `key`, which is 0 if the
+    /// key isn't in the SMT.
+    ///
+    /// Returns `None` if the path of `key` goes through a hashed-out subtree.
+    pub fn prove(&self, key: Key) -> Option<SmtProof> {
+        let keys = key.split();
+        let mut siblings = vec![];
+        let mut r = Key(self.root.elements);
+
+        let terminal = loop {
+            if r.0.iter().all(F::is_zero) {
+                break SmtProofTerminal::Empty;
+            }
+            let node = self.db.get_node(&r)?;
+            if node.is_one_siblings() {
+                break SmtProofTerminal::Leaf {
+                    rem_key: Key(node.0[0..4].try_into().unwrap()),
+                    value_hash: node.0[4..8].try_into().unwrap(),
+                };
+            }
+            let b = keys.get_bit(siblings.len()) as usize;
+            siblings.push(node.0[(1 - b) * 4..(2 - b) * 4].try_into().unwrap());
+            r = Key(node.0[b * 4..(b + 1) * 4].try_into().unwrap());
+        };
+
+        Some(SmtProof { siblings, terminal })
+    }
+}
+
+/// Checks that `proof` shows that `key` is associated with `value` in the SMT
+/// with the given `root`. A `value` of 0 checks that `key` isn't in the SMT.
+pub fn verify(root: HashOut, key: Key, value: U256, proof: &SmtProof) -> bool {
+    let keys = key.split();
+    let depth = proof.siblings.len();
+    if depth > keys.count {
+        return false;
+    }
+
+    let mut h = match proof.terminal {
+        SmtProofTerminal::Empty => {
+            if !value.is_zero() {
+                return false;
+            }
+            [F::ZERO; 4]
+        }
+        SmtProofTerminal::Leaf {
+            rem_key,
+            value_hash,
+        } => {
+            let mut acc_key = Bits::empty();
+            for level in 0..depth {
+                acc_key.push_bit(keys.get_bit(level));
+            }
+            let is_member = Key::join(acc_key, rem_key) == key;
+            let is_valid = match is_member {
+                true => !value.is_zero() && hash0(f2limbs(value)) == value_hash,
+                false => value.is_zero(),
+            };
+            if !is_valid {
+                return false;
+            }
+            hash_key_hash(rem_key, value_hash)
+        }
+    };
+
+    for (level, sibling) in proof.siblings.iter().enumerate().rev() {
+        let mut node = Node([F::ZERO; 12]);
+        let b = keys.get_bit(level) as usize;
+        node.0[b * 4..(b + 1) * 4].copy_from_slice(&h);
+        node.0[(1 - b) * 4..(2 - b) * 4].copy_from_slice(sibling);
+        h = F::poseidon(node.0)[0..4].try_into().unwrap();
+    }
+
+    h == root.elements
+}
@@ -90,6 +90,38 @@ pub struct Smt<D: Db> {
 }
 
 impl<D: Db> Smt<D> {
+    /// Returns the SMT with the given root, whose nodes are already stored in
+    /// `db`, e.g. a [`FileDb`](crate::db::FileDb) persisted by a previous run.
+    /// `kv_store` is rebuilt from the leaves of the SMT, skipping hashed-out
+    /// subtrees.
+    pub fn with_root(db: D, root: HashOut) -> Self {
+        let mut kv_store = HashMap::new();
+        let mut stack = vec![(Key(root.elements), Bits::empty())];
+        while let Some((key, bits)) = stack.pop() {
+            if key.0.iter().all(F::is_zero) {
+                continue;
+            }
+            let Some(node) = db.get_node(&key) else {
+                continue;
+            };
+            if node.is_one_siblings() {
+                let val_a = db
+                    .get_node(&Key(node.0[4..8].try_into().unwrap()))
+                    .unwrap()
+                    .0[0..8]
+                    .try_into()
+                    .unwrap();
+                let rem_key = Key(node.0[0..4].try_into().unwrap());
+                kv_store.insert(Key::join(bits, rem_key), limbs2f(val_a));
+            } else {
+                stack.push((Key(node.0[0..4].try_into().unwrap()), bits.add_bit(false)));
+                stack.push((Key(node.0[4..8].try_into().unwrap()), bits.add_bit(true)));
+            }
+        }
+
+        Self { db, kv_store, root }
+    }
+
     /// Returns `Poseidon(x, [0,0,0,0])` and save it in DB.
     pub fn hash0(&mut self, x: [F; 8]) -> [F; 4] {
         let h = hash0(x);
@@ -6,10 +6,11 @@ use rand::{random, thread_rng, Rng};
 
 use crate::bits::Bits;
 use crate::db::Db;
+use crate::proof::{verify, SmtProofTerminal};
 use crate::smt::HASH_TYPE;
 use crate::utils::hashout2u;
 use crate::{
-    db::MemoryDb,
+    db::{FileDb, MemoryDb, FILE_DB_MAGIC},
     smt::{hash_serialize, Key, Smt, F},
 };
 
@@ -411,3 +412,140 @@ fn test_serialize_and_prune() {
     );
     assert_eq!(hash_serialize(&trivial_ser), smt.root);
 }
+
+#[test]
+fn test_file_db() {
+    let path = std::env::temp_dir().join(format!("smt_file_db_{}.bin", random::<u64>()));
+    let mut smt = Smt::with_root(FileDb::open(&path).unwrap(), HashOut::ZERO);
+    let mut mem_smt = Smt::<MemoryDb>::default();
+
+    let kvs = (0..128)
+        .map(|_| (Key(F::rand_array()), U256::from(random::<u64>())))
+        .collect::<Vec<_>>();
+    for &(k, v) in &kvs {
+        smt.set(k, v);
+        mem_smt.set(k, v);
+    }
+    assert_eq!(smt.root, mem_smt.root);
+    assert_eq!(smt.serialize(), mem_smt.serialize());
+    smt.db.flush().unwrap();
+
+    // Reopen from disk.
+    let root = smt.root;
+    drop(smt);
+    let mut smt = Smt::with_root(FileDb::open(&path).unwrap(), root);
+    for &(k, v) in &kvs {
+        assert_eq!(smt.get(k), v);
+    }
+    assert_eq!(smt.kv_store, mem_smt.kv_store);
+    assert_eq!(smt.serialize(), mem_smt.serialize());
+
+    // Overwrite half of the values, then prune the stale nodes.
+    for &(k, _) in &kvs[..64] {
+        let v = U256::from(random::<u64>());
+        smt.set(k, v);
+        mem_smt.set(k, v);
+    }
+    assert_eq!(smt.root, mem_smt.root);
+    let len = smt.db.len();
+    let removed = smt.db.prune(&[Key(smt.root.elements)]).unwrap();
+    let mem_removed = mem_smt.db.prune(&[Key(mem_smt.root.elements)]);
+    assert!(removed > 0);
+    assert_eq!(smt.db.len(), len - removed);
+    assert_eq!(smt.db.len(), mem_smt.db.db.len());
+    assert!(mem_removed > 0);
+
+    let smt = Smt::with_root(FileDb::open(&path).unwrap(), mem_smt.root);
+    for &(k, _) in &kvs {
+        assert_eq!(smt.get(k), mem_smt.get(k));
+    }
+
+    std::fs::remove_file(path).unwrap();
+}
+
+#[test]
+fn test_file_db_header() {
+    let path = std::env::temp_dir().join(format!("smt_file_db_{}.bin", random::<u64>()));
+    let mut smt = Smt::with_root(FileDb::open(&path).unwrap(), HashOut::ZERO);
+    let k = Key(F::rand_array());
+    smt.set(k, U256::from(1));
+    smt.db.flush().unwrap();
+    let root = smt.root;
+    drop(smt);
+
+    // A trailing partial record is discarded.
+    let bytes = std::fs::read(&path).unwrap();
+    assert!(bytes.starts_with(&FILE_DB_MAGIC));
+    std::fs::write(&path, [&bytes[..], &[0; 5]].concat()).unwrap();
+    let smt = Smt::with_root(FileDb::open(&path).unwrap(), root);
+    assert_eq!(smt.get(k), U256::from(1));
+    drop(smt);
+    assert_eq!(std::fs::read(&path).unwrap(), bytes);
+
+    // Files of another version or format are rejected.
+    let mut wrong_version = bytes.clone();
+    wrong_version[FILE_DB_MAGIC.len()] += 1;
+    std::fs::write(&path, wrong_version).unwrap();
+    let err = FileDb::open(&path).unwrap_err();
+    assert_eq!(err.kind(), std::io::ErrorKind::InvalidData);
+
+    // So are records with non-canonical field elements.
+    let mut non_canonical = bytes.clone();
+    let record = FILE_DB_MAGIC.len() + 4;
+    non_canonical[record..record + 8].copy_from_slice(&u64::MAX.to_le_bytes());
+    std::fs::write(&path, non_canonical).unwrap();
+    let err = FileDb::open(&path).unwrap_err();
+    assert_eq!(err.kind(), std::io::ErrorKind::InvalidData);
+
+    for not_a_db in [&b"not an SMT database"[..], &FILE_DB_MAGIC[..4]] {
+        std::fs::write(&path, not_a_db).unwrap();
+        let err = FileDb::open(&path).unwrap_err();
+        assert_eq!(err.kind(), std::io::ErrorKind::InvalidData);
+    }
+
+    std::fs::remove_file(path).unwrap();
+}
+
+#[test]
+fn test_prove_and_verify() {
+    let mut smt = Smt::<MemoryDb>::default();
+
+    // Empty SMT.
+    let k = Key(F::rand_array());
+    let proof = smt.prove(k).unwrap();
+    assert_eq!(proof.terminal, SmtProofTerminal::Empty);
+    assert!(verify(smt.root, k, U256::ZERO, &proof));
+
+    let kvs = (0..128)
+        .map(|_| {
+            (
+                Key(F::rand_array()),
+                U256::from(random::<u64>()) + U256::from(1),
+            )
+        })
+        .collect::<Vec<_>>();
+    for &(k, v) in &kvs {
+        smt.set(k, v);
+    }
+
+    for &(k, v) in &kvs {
+        let proof = smt.prove(k).unwrap();
+        assert!(verify(smt.root, k, v, &proof));
+        assert!(!verify(smt.root, k, v + U256::from(1), &proof));
+        assert!(!verify(smt.root, k, U256::ZERO, &proof));
+    }
+
+    // Non-membership, ending at either an empty subtree or another leaf.
+    for _ in 0..128 {
+        let k = Key(F::rand_array());
+        let proof = smt.prove(k).unwrap();
+        assert!(verify(smt.root, k, U256::ZERO, &proof));
+        assert!(!verify(smt.root, k, U256::from(1), &proof));
+    }
+
+    // Tampered proof.
+    let (k, v) = kvs[0];
+    let mut proof = smt.prove(k).unwrap();
+    proof.siblings[0][0] += F::ONE;
+    assert!(!verify(smt.root, k, v, &proof));
+}
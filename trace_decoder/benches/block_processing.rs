@@ -7,7 +7,7 @@
 
 use criterion::{criterion_group, criterion_main, BatchSize, Criterion};
 use trace_decoder::observer::DummyObserver;
-use trace_decoder::{BlockTrace, OtherBlockData};
+use trace_decoder::{BatchingStrategy, BlockTrace, OtherBlockData};
 use zero::prover::WIRE_DISPOSITION;
 
 #[derive(Clone, Debug, serde::Deserialize)]
@@ -38,7 +38,7 @@ fn criterion_benchmark(c: &mut Criterion) {
                     trace_decoder::entrypoint(
                         block_trace,
                         other_data,
-                        batch_size,
+                        BatchingStrategy::Count(batch_size),
                         &mut DummyObserver::new(),
                         WIRE_DISPOSITION,
                     )
@@ -9,8 +9,19 @@ use anyhow::{anyhow, bail, ensure, Context as _};
 use either::Either;
 use ethereum_types::{Address, BigEndianHash as _, U256};
 use evm_arithmetization::{
+    cpu::kernel::{
+        eip7702_constants::{DELEGATION_DESIGNATOR_LEN, DELEGATION_DESIGNATOR_PREFIX},
+        prague_constants::{
+            CONSOLIDATION_REQUEST_PREDEPLOY_ADDRESS, CONSOLIDATION_REQUEST_SLOTS,
+            EXCESS_REQUESTS_SLOT, MAX_CONSOLIDATION_REQUESTS_PER_BLOCK,
+            MAX_WITHDRAWAL_REQUESTS_PER_BLOCK, REQUESTS_COUNT_SLOT, REQUESTS_QUEUE_HEAD_SLOT,
+            REQUESTS_QUEUE_OFFSET, REQUESTS_QUEUE_TAIL_SLOT,
+            TARGET_CONSOLIDATION_REQUESTS_PER_BLOCK, TARGET_WITHDRAWAL_REQUESTS_PER_BLOCK,
+            WITHDRAWAL_REQUEST_PREDEPLOY_ADDRESS, WITHDRAWAL_REQUEST_SLOTS,
+        },
+    },
     generation::TrieInputs,
-    proof::{BlockMetadata, TrieRoots},
+    proof::{BlockHashes, BlockMetadata, TrieRoots},
     tries::{MptKey, ReceiptTrie, StateMpt, StorageTrie, TransactionTrie},
     world::{Hasher, KeccakHash, PoseidonHash, Type1World, Type2World, World},
     GenerationInputs,
@@ -31,7 +42,7 @@ use crate::{
 /// Expected trie type when parsing from binary in a [`BlockTrace`].
 ///
 /// See [`crate::wire`] and [`CombinedPreImages`] for more.
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy)]
 pub enum WireDisposition {
     /// MPT
     Type1,
@@ -39,15 +50,60 @@ pub enum WireDisposition {
     Type2,
 }
 
+/// How [`entrypoint`] groups the transactions of a block into batches, each
+/// of which is proven from one [`GenerationInputs`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BatchingStrategy {
+    /// Batches of this many transactions.
+    Count(usize),
+    /// Batches of transactions whose estimated costs add up to at most
+    /// `budget`. A transaction costing more than `budget` is batched alone.
+    Cost {
+        estimate: TxnCostEstimate,
+        budget: u64,
+    },
+}
+
+/// How the proving cost of a transaction is estimated from its trace, for
+/// [`BatchingStrategy::Cost`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum TxnCostEstimate {
+    /// [`TxnMeta::gas_used`].
+    #[default]
+    GasUsed,
+    /// The number of accounts and storage slots read or written, as each of
+    /// them costs hashing and trie updates in the kernel.
+    StateAccesses,
+}
+
+impl TxnCostEstimate {
+    fn of(self, txn: &TxnInfo) -> u64 {
+        match self {
+            TxnCostEstimate::GasUsed => txn.meta.gas_used,
+            TxnCostEstimate::StateAccesses => txn
+                .traces
+                .values()
+                .map(|trace| 1 + trace.storage_read.len() + trace.storage_written.len())
+                .sum::<usize>() as u64,
+        }
+    }
+}
+
 /// TODO(0xaatif): document this after <https://github.com/0xPolygonZero/zk_evm/issues/275>
 pub fn entrypoint(
     trace: BlockTrace,
     other: OtherBlockData,
-    batch_size_hint: usize,
+    batching: BatchingStrategy,
     observer: &mut impl Observer<Type1World>,
     wire_disposition: WireDisposition,
 ) -> anyhow::Result<Vec<GenerationInputs>> {
-    ensure!(batch_size_hint != 0);
+    ensure!(
+        !matches!(
+            batching,
+            BatchingStrategy::Count(0) | BatchingStrategy::Cost { budget: 0, .. }
+        ),
+        "batches must have a nonzero size"
+    );
 
     let BlockTrace {
         trie_pre_images,
@@ -84,9 +140,10 @@ pub fn entrypoint(
             Either::Left(
                 middle(
                     type1world,
-                    batch(txn_info, batch_size_hint),
+                    batch(txn_info, batching),
                     &mut code,
                     &b_meta,
+                    &b_hashes,
                     ger_data,
                     withdrawals,
                     fatal_missing_code,
@@ -101,9 +158,10 @@ pub fn entrypoint(
             Either::Right(
                 middle(
                     type2world,
-                    batch(txn_info, batch_size_hint),
+                    batch(txn_info, batching),
                     &mut code,
                     &b_meta,
+                    &b_hashes,
                     ger_data,
                     withdrawals,
                     fatal_missing_code,
@@ -132,6 +190,7 @@ pub fn entrypoint(
                      },
                  after,
                  withdrawals,
+                 process_requests,
              }| {
                 let (state, storage) = world
                     .clone()
@@ -146,6 +205,7 @@ pub fn entrypoint(
                     },
                     signed_txns: byte_code.into_iter().map(Into::into).collect(),
                     withdrawals,
+                    process_requests,
                     ger_data,
                     tries: TrieInputs {
                         state_trie: state.into(),
@@ -192,49 +252,8 @@ fn start(
     Ok(match pre_images {
         // TODO(0xaatif): https://github.com/0xPolygonZero/zk_evm/issues/401
         //                refactor our convoluted input types
-        BlockTraceTriePreImages::Separate(SeparateTriePreImages {
-            state: SeparateTriePreImage::Direct(state),
-            storage: SeparateStorageTriesPreImage::MultipleTries(storage),
-        }) => {
-            let state =
-                state
-                    .items()
-                    .try_fold(StateMpt::new(), |mut acc, (nibbles, hash_or_val)| {
-                        let path = MptKey::from_nibbles(nibbles);
-                        match hash_or_val {
-                            mpt_trie::trie_ops::ValOrHash::Val(bytes) => {
-                                acc.insert(
-                                    path.into_hash()
-                                        .context("invalid path length in direct state trie")?,
-                                    rlp::decode(&bytes)
-                                        .context("invalid AccountRlp in direct state trie")?,
-                                )?;
-                            }
-                            mpt_trie::trie_ops::ValOrHash::Hash(h) => {
-                                acc.insert_hash(path, h)?;
-                            }
-                        };
-                        anyhow::Ok(acc)
-                    })?;
-            let storage = storage
-                .into_iter()
-                .map(|(k, SeparateTriePreImage::Direct(v))| {
-                    v.items()
-                        .try_fold(StorageTrie::default(), |mut acc, (nibbles, hash_or_val)| {
-                            let path = MptKey::from_nibbles(nibbles);
-                            match hash_or_val {
-                                mpt_trie::trie_ops::ValOrHash::Val(value) => {
-                                    acc.insert(path, value)?;
-                                }
-                                mpt_trie::trie_ops::ValOrHash::Hash(h) => {
-                                    acc.insert_hash(path, h)?;
-                                }
-                            };
-                            anyhow::Ok(acc)
-                        })
-                        .map(|v| (k, v))
-                })
-                .collect::<Result<_, _>>()?;
+        BlockTraceTriePreImages::Separate(separate) => {
+            let (state, storage) = separate2tries(separate)?;
             Either::Left((Type1World::new(state, storage)?, Hash2Code::new()))
         }
         BlockTraceTriePreImages::Combined(CombinedPreImages { compact }) => {
@@ -267,23 +286,325 @@ fn start(
     })
 }
 
-/// Break `txns` into batches of length `batch_size_hint`, prioritising creating
+/// Converts [`SeparateTriePreImages`] into the equivalent
+/// [`CombinedPreImages`] for [`WireDisposition::Type1`].
+///
+/// The combined format carries contract code inline, so `code` should contain
+/// the code of the accounts in the state trie, like [`BlockTrace::code_db`].
+pub fn combine_pre_images(
+    separate: SeparateTriePreImages,
+    code: impl IntoIterator<Item = Vec<u8>>,
+) -> anyhow::Result<CombinedPreImages> {
+    encode_pre_images(
+        BlockTraceTriePreImages::Separate(separate),
+        code,
+        WireDisposition::Type1,
+    )
+}
+
+/// Encodes `pre_images` in the [`wire`](crate::wire) format of
+/// `wire_disposition`, the inverse of parsing [`CombinedPreImages`].
+///
+/// `code` is added to the code already in `pre_images`, and should contain the
+/// code of their accounts, like [`BlockTrace::code_db`]. Code which no account
+/// refers to is dropped for [`WireDisposition::Type1`], and the accounts whose
+/// code is missing are encoded with its hash, and a code length of 0.
+///
+/// [`SeparateTriePreImages`] are MPTs, so they can't be encoded for
+/// [`WireDisposition::Type2`]: see [`encode_type2_pre_images`] instead.
+pub fn encode_pre_images(
+    pre_images: BlockTraceTriePreImages,
+    code: impl IntoIterator<Item = Vec<u8>>,
+    wire_disposition: WireDisposition,
+) -> anyhow::Result<CombinedPreImages> {
+    let code = code
+        .into_iter()
+        .filter_map(|it| NonEmpty::<Vec<_>>::new(it).ok());
+    let instructions = match (pre_images, wire_disposition) {
+        (BlockTraceTriePreImages::Separate(separate), WireDisposition::Type1) => {
+            let (state, storage) = separate2tries(separate)?;
+            crate::type1::encode(&crate::type1::Frontend {
+                state,
+                storage,
+                code: code.collect(),
+            })?
+        }
+        (BlockTraceTriePreImages::Separate(_), WireDisposition::Type2) => {
+            bail!("separate pre-images can't be encoded as type 2")
+        }
+        (BlockTraceTriePreImages::Combined(CombinedPreImages { compact }), wire_disposition) => {
+            let instructions = crate::wire::parse(&compact)
+                .context("couldn't parse instructions from binary format")?;
+            match wire_disposition {
+                WireDisposition::Type1 => {
+                    let mut frontend = crate::type1::frontend(instructions)?;
+                    frontend.code.extend(code);
+                    crate::type1::encode(&frontend)?
+                }
+                WireDisposition::Type2 => {
+                    let mut frontend = crate::type2::frontend(instructions)?;
+                    frontend.code.extend(code);
+                    crate::type2::encode(&frontend)?
+                }
+            }
+        }
+    };
+    Ok(CombinedPreImages {
+        compact: crate::wire::encode(instructions),
+    })
+}
+
+/// Encodes the SMT of `world` in the [`wire`](crate::wire) format of
+/// [`WireDisposition::Type2`], along with `code`, which should contain the code
+/// of its accounts.
+pub fn encode_type2_pre_images(
+    world: Type2World,
+    code: impl IntoIterator<Item = Vec<u8>>,
+) -> anyhow::Result<CombinedPreImages> {
+    let code = code
+        .into_iter()
+        .filter_map(|it| NonEmpty::<Vec<_>>::new(it).ok())
+        .collect();
+    let instructions = crate::type2::encode(&crate::type2::Frontend { world, code })?;
+    Ok(CombinedPreImages {
+        compact: crate::wire::encode(instructions),
+    })
+}
+
+#[test]
+fn test_encode_type2_pre_images() {
+    for case in
+        serde_json::from_str::<Vec<crate::Case>>(include_str!("cases/hermez_cdk_erigon.json"))
+            .unwrap()
+    {
+        let crate::type2::Frontend { world, code } =
+            crate::type2::frontend(crate::wire::parse(&case.bytes).unwrap()).unwrap();
+        let pre_images =
+            encode_type2_pre_images(world, code.into_iter().map(NonEmpty::into_vec)).unwrap();
+        let mut reencoded =
+            crate::type2::frontend(crate::wire::parse(&pre_images.compact).unwrap()).unwrap();
+        assert_eq!(case.expected_state_root, reencoded.world.root());
+    }
+}
+
+impl BlockTraceTriePreImages {
+    /// The hashed addresses of the accounts in the state trie, except those
+    /// which are hashed out.
+    pub fn accounts(&self, wire_disposition: WireDisposition) -> anyhow::Result<BTreeSet<H256>> {
+        let crate::type1::Frontend { state, .. } = self.type1_frontend(wire_disposition)?;
+        Ok(state
+            .iter()
+            .map(|(hashed_address, _)| hashed_address)
+            .collect())
+    }
+
+    /// Hashes out the accounts of the state trie which are not in `keep`, and
+    /// drops their storage tries.
+    ///
+    /// The storage tries of the accounts in `keep` are hashed out except for
+    /// the given hashed slots, or kept whole for [`None`].
+    ///
+    /// Only type 1 pre-images are supported.
+    pub fn mask(
+        &mut self,
+        wire_disposition: WireDisposition,
+        keep: &BTreeMap<H256, Option<BTreeSet<H256>>>,
+    ) -> anyhow::Result<()> {
+        let crate::type1::Frontend {
+            mut state,
+            mut storage,
+            code,
+        } = self.type1_frontend(wire_disposition)?;
+        state.mask(keep.keys().copied().map(MptKey::from_hash))?;
+        storage.retain(|hashed_address, _| keep.contains_key(hashed_address));
+        for (hashed_address, slots) in keep {
+            if let (Some(slots), Some(trie)) = (slots, storage.get_mut(hashed_address)) {
+                trie.mask(slots.iter().copied().map(MptKey::from_hash))?;
+            }
+        }
+
+        *self = match self {
+            BlockTraceTriePreImages::Separate(_) => {
+                BlockTraceTriePreImages::Separate(SeparateTriePreImages {
+                    state: SeparateTriePreImage::Direct(state.into()),
+                    storage: SeparateStorageTriesPreImage::MultipleTries(
+                        storage
+                            .into_iter()
+                            .map(|(k, v)| (k, SeparateTriePreImage::Direct(v.into())))
+                            .collect(),
+                    ),
+                })
+            }
+            BlockTraceTriePreImages::Combined(_) => {
+                let frontend = crate::type1::Frontend {
+                    state,
+                    storage,
+                    code,
+                };
+                BlockTraceTriePreImages::Combined(CombinedPreImages {
+                    compact: crate::wire::encode(crate::type1::encode(&frontend)?),
+                })
+            }
+        };
+        Ok(())
+    }
+
+    fn type1_frontend(
+        &self,
+        wire_disposition: WireDisposition,
+    ) -> anyhow::Result<crate::type1::Frontend> {
+        match self {
+            BlockTraceTriePreImages::Separate(separate) => {
+                let (state, storage) = separate2tries(separate.clone())?;
+                Ok(crate::type1::Frontend {
+                    state,
+                    storage,
+                    code: BTreeSet::new(),
+                })
+            }
+            BlockTraceTriePreImages::Combined(CombinedPreImages { compact }) => {
+                match wire_disposition {
+                    WireDisposition::Type1 => crate::type1::frontend(
+                        crate::wire::parse(compact)
+                            .context("couldn't parse instructions from binary format")?,
+                    ),
+                    WireDisposition::Type2 => bail!("only type 1 pre-images are supported"),
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn test_mask() {
+    /// The hashed slots of the leaves of a storage trie.
+    fn slots(trie: &StorageTrie) -> BTreeSet<H256> {
+        trie.as_hashed_partial_trie()
+            .items()
+            .filter(|(_, v)| matches!(v, mpt_trie::trie_ops::ValOrHash::Val(_)))
+            .map(|(k, _)| MptKey::from_nibbles(k).into_hash().unwrap())
+            .collect()
+    }
+
+    for (ix, case) in
+        serde_json::from_str::<Vec<crate::Case>>(include_str!("cases/zero_jerigon.json"))
+            .unwrap()
+            .into_iter()
+            .enumerate()
+    {
+        println!("case {}", ix);
+        let mut pre_images = BlockTraceTriePreImages::Combined(CombinedPreImages {
+            compact: case.bytes,
+        });
+        let before = pre_images.type1_frontend(WireDisposition::Type1).unwrap();
+
+        // Keep every other account, with only the first slot of its storage.
+        let keep = pre_images
+            .accounts(WireDisposition::Type1)
+            .unwrap()
+            .into_iter()
+            .step_by(2)
+            .map(|account| {
+                let slots = before
+                    .storage
+                    .get(&account)
+                    .map(|trie| slots(trie).into_iter().take(1).collect());
+                (account, slots)
+            })
+            .collect::<BTreeMap<_, _>>();
+        pre_images.mask(WireDisposition::Type1, &keep).unwrap();
+
+        let after = pre_images.type1_frontend(WireDisposition::Type1).unwrap();
+        assert_eq!(case.expected_state_root, after.state.root());
+        assert_eq!(
+            pre_images.accounts(WireDisposition::Type1).unwrap(),
+            keep.keys().copied().collect()
+        );
+        itertools::assert_equal(
+            after.storage.keys(),
+            keep.keys().filter(|it| before.storage.contains_key(it)),
+        );
+        for (account, trie) in &after.storage {
+            assert_eq!(before.storage[account].root(), trie.root());
+            assert_eq!(Some(&slots(trie)), keep[account].as_ref());
+        }
+    }
+}
+
+fn separate2tries(
+    SeparateTriePreImages {
+        state: SeparateTriePreImage::Direct(state),
+        storage: SeparateStorageTriesPreImage::MultipleTries(storage),
+    }: SeparateTriePreImages,
+) -> anyhow::Result<(StateMpt, BTreeMap<H256, StorageTrie>)> {
+    let state = state
+        .items()
+        .try_fold(StateMpt::new(), |mut acc, (nibbles, hash_or_val)| {
+            let path = MptKey::from_nibbles(nibbles);
+            match hash_or_val {
+                mpt_trie::trie_ops::ValOrHash::Val(bytes) => {
+                    acc.insert(
+                        path.into_hash()
+                            .context("invalid path length in direct state trie")?,
+                        rlp::decode(&bytes).context("invalid AccountRlp in direct state trie")?,
+                    )?;
+                }
+                mpt_trie::trie_ops::ValOrHash::Hash(h) => {
+                    acc.insert_hash(path, h)?;
+                }
+            };
+            anyhow::Ok(acc)
+        })?;
+    let storage = storage
+        .into_iter()
+        .map(|(k, SeparateTriePreImage::Direct(v))| {
+            v.items()
+                .try_fold(StorageTrie::default(), |mut acc, (nibbles, hash_or_val)| {
+                    let path = MptKey::from_nibbles(nibbles);
+                    match hash_or_val {
+                        mpt_trie::trie_ops::ValOrHash::Val(value) => {
+                            acc.insert(path, value)?;
+                        }
+                        mpt_trie::trie_ops::ValOrHash::Hash(h) => {
+                            acc.insert_hash(path, h)?;
+                        }
+                    };
+                    anyhow::Ok(acc)
+                })
+                .map(|v| (k, v))
+        })
+        .collect::<Result<_, _>>()?;
+    Ok((state, storage))
+}
+
+/// Break `txns` into batches according to `batching`, prioritising creating
 /// at least two batches.
 ///
 /// [`None`] represents a dummy transaction that should not increment the
 /// transaction index.
-fn batch(txns: Vec<TxnInfo>, batch_size_hint: usize) -> Vec<Vec<Option<TxnInfo>>> {
-    let hint = cmp::max(batch_size_hint, 1);
+fn batch(txns: Vec<TxnInfo>, batching: BatchingStrategy) -> Vec<Vec<Option<TxnInfo>>> {
+    let lens = match batching {
+        BatchingStrategy::Count(hint) => {
+            let hint = cmp::max(hint, 1);
+            let mut lens = vec![hint; txns.len() / hint];
+            if txns.len() % hint != 0 {
+                lens.push(txns.len() % hint)
+            }
+            lens
+        }
+        BatchingStrategy::Cost { estimate, budget } => {
+            cost_batch_lens(txns.iter().map(|txn| estimate.of(txn)), budget)
+        }
+    };
     let mut txns = txns.into_iter().map(Some).collect::<Vec<_>>();
-    let n_batches = txns.iter().chunks(hint).into_iter().count();
-    match (txns.len(), n_batches) {
+    match (txns.len(), lens.len()) {
         // enough
-        (_, 2..) => txns
-            .into_iter()
-            .chunks(hint)
-            .into_iter()
-            .map(FromIterator::from_iter)
-            .collect(),
+        (_, 2..) => {
+            let mut txns = txns.into_iter();
+            lens.into_iter()
+                .map(|len| txns.by_ref().take(len).collect())
+                .collect()
+        }
         // not enough batches at `hint`, but enough real transactions,
         // so just split them in half
         (2.., ..2) => {
@@ -299,13 +620,32 @@ fn batch(txns: Vec<TxnInfo>, batch_size_hint: usize) -> Vec<Vec<Option<TxnInfo>>
     }
 }
 
+/// The lengths of the batches of consecutive transactions with the given
+/// `costs`, greedily filled up to `budget`.
+fn cost_batch_lens(costs: impl IntoIterator<Item = u64>, budget: u64) -> Vec<usize> {
+    let mut lens = vec![];
+    let (mut len, mut total) = (0, 0u64);
+    for cost in costs {
+        if len != 0 && total.saturating_add(cost) > budget {
+            lens.push(len);
+            (len, total) = (0, 0);
+        }
+        len += 1;
+        total = total.saturating_add(cost);
+    }
+    if len != 0 {
+        lens.push(len)
+    }
+    lens
+}
+
 #[test]
 fn test_batch() {
     #[track_caller]
     fn do_test(n: usize, hint: usize, exp: impl IntoIterator<Item = usize>) {
         itertools::assert_equal(
             exp,
-            batch(vec![TxnInfo::default(); n], hint)
+            batch(vec![TxnInfo::default(); n], BatchingStrategy::Count(hint))
                 .iter()
                 .map(Vec::len),
         )
@@ -320,6 +660,34 @@ fn test_batch() {
     do_test(3, 3, [1, 2]); // big hint
 }
 
+#[test]
+fn test_cost_batch() {
+    #[track_caller]
+    fn do_test(gas_used: &[u64], budget: u64, exp: impl IntoIterator<Item = usize>) {
+        let txns = gas_used
+            .iter()
+            .map(|&gas_used| TxnInfo {
+                meta: TxnMeta {
+                    gas_used,
+                    ..Default::default()
+                },
+                ..Default::default()
+            })
+            .collect();
+        let batching = BatchingStrategy::Cost {
+            estimate: TxnCostEstimate::GasUsed,
+            budget,
+        };
+        itertools::assert_equal(exp, batch(txns, batching).iter().map(Vec::len))
+    }
+
+    do_test(&[], 10, [1, 1]); // pad2
+    do_test(&[1, 2, 3, 4], 10, [2, 2]); // fits in one batch, so split in half
+    do_test(&[5, 5, 5, 5, 5], 10, [2, 2, 1]);
+    do_test(&[1, 100, 1, 1], 10, [1, 1, 2]); // expensive transactions go alone
+    do_test(&[100, 100], 10, [1, 1]);
+}
+
 #[derive(Debug)]
 struct Batch<StateTrieT> {
     pub first_txn_ix: usize,
@@ -334,6 +702,9 @@ struct Batch<StateTrieT> {
 
     /// Empty for all but the final batch
     pub withdrawals: Vec<(Address, U256)>,
+    /// False for all but the final batch, and for blocks without execution
+    /// layer requests.
+    pub process_requests: bool,
 }
 
 impl<T> Batch<T> {
@@ -346,6 +717,7 @@ impl<T> Batch<T> {
             before,
             after,
             withdrawals,
+            process_requests,
         } = self;
         Batch {
             first_txn_ix,
@@ -355,6 +727,7 @@ impl<T> Batch<T> {
             before: before.map(f),
             after,
             withdrawals,
+            process_requests,
         }
     }
 }
@@ -400,6 +773,7 @@ fn middle<WorldT: World + Clone>(
     batches: Vec<Vec<Option<TxnInfo>>>,
     code: &mut Hash2Code<WorldT::CodeHasher>,
     block: &BlockMetadata,
+    block_hashes: &BlockHashes,
     ger_data: Option<(H256, H256)>,
     // added to final batch
     mut withdrawals: Vec<(Address, U256)>,
@@ -440,6 +814,7 @@ where
         if txn_ix == 0 {
             do_pre_execution(
                 block,
+                block_hashes,
                 ger_data,
                 &mut storage_masks,
                 &mut state_mask,
@@ -501,12 +876,22 @@ where
                     world.create_storage(addr)?
                 }
 
-                let do_writes = !just_access
-                    && match born {
-                        // if txn failed, don't commit changes to trie
-                        true => receipt.status,
-                        false => true,
-                    };
+                let is_delegation = is_delegation(&code_usage);
+
+                // The kernel checks whether any called account delegates its
+                // execution, which requires its code even if the call fails
+                // before running it, so include it whenever it is known.
+                if code_usage.is_none() {
+                    if let Some(byte_code) = world.code_hash(addr)?.and_then(|hash| code.get(hash))
+                    {
+                        if let Some(delegate) = delegate_of(&byte_code) {
+                            state_mask.insert(<WorldT::SubtriePath>::from(delegate));
+                        }
+                        batch_contract_code.insert(byte_code);
+                    }
+                }
+
+                let commit = commit(just_access, born, receipt.status, is_delegation);
 
                 let storage_mask = storage_masks.entry(addr).or_default();
 
@@ -517,8 +902,8 @@ where
                         .map(|it| MptKey::from_hash(keccak_hash::keccak(it))),
                 );
 
-                if do_writes {
-                    if let Some(new) = balance {
+                if commit != Commit::Nothing {
+                    if let Some(new) = balance.filter(|_| commit == Commit::All) {
                         world.update_balance(addr, |it| *it = new)?
                     }
                     if let Some(new) = nonce {
@@ -538,6 +923,12 @@ where
                                         bail!("no code for hash {hash:x}")
                                     }
                                     (_, Some(byte_code)) => {
+                                        // The kernel also needs the delegate's account
+                                        // to follow a delegation designator.
+                                        if let Some(delegate) = delegate_of(&byte_code) {
+                                            state_mask
+                                                .insert(<WorldT::SubtriePath>::from(delegate));
+                                        }
                                         world.set_code(addr, Either::Left(&byte_code))?;
                                         batch_contract_code.insert(byte_code);
                                     }
@@ -545,6 +936,9 @@ where
                                 }
                             }
                             ContractCodeUsage::Write(bytes) => {
+                                if let Some(delegate) = delegate_of(&bytes) {
+                                    state_mask.insert(<WorldT::SubtriePath>::from(delegate));
+                                }
                                 code.insert(bytes.clone());
                                 world.set_code(addr, Either::Left(&bytes))?;
                                 batch_contract_code.insert(bytes);
@@ -552,7 +946,7 @@ where
                         };
                     }
 
-                    if !storage_written.is_empty() {
+                    if commit == Commit::All && !storage_written.is_empty() {
                         for (k, v) in storage_written {
                             match v.is_zero() {
                                 // this is actually a delete
@@ -596,9 +990,29 @@ where
                 }
                 false => vec![],
             },
+            // Must come after the withdrawals.
+            process_requests: match loop_ix == loop_len {
+                true => do_post_execution(
+                    block,
+                    code,
+                    &mut batch_contract_code,
+                    &mut storage_masks,
+                    &mut state_mask,
+                    &mut world,
+                )?,
+                false => false,
+            },
             before: {
                 before.world.mask(state_mask)?;
-                before.receipt.mask(batch_first_txn_ix..txn_ix)?;
+                // The kernel parses the deposit requests from the receipts of
+                // the whole block.
+                match loop_ix == loop_len
+                    && cfg!(feature = "eth_mainnet")
+                    && !block.requests_hash.is_zero()
+                {
+                    true => before.receipt.mask(0..txn_ix)?,
+                    false => before.receipt.mask(batch_first_txn_ix..txn_ix)?,
+                }
                 before.transaction.mask(batch_first_txn_ix..txn_ix)?;
                 before.world.mask_storage(storage_masks)?;
                 before
@@ -625,6 +1039,7 @@ where
 /// Performs all the pre-txn execution rules of the targeted network.
 fn do_pre_execution<WorldT: World + Clone>(
     block: &BlockMetadata,
+    block_hashes: &BlockHashes,
     ger_data: Option<(H256, H256)>,
     trim_storage: &mut BTreeMap<ethereum_types::H160, BTreeSet<MptKey>>,
     trim_state: &mut BTreeSet<WorldT::SubtriePath>,
@@ -633,14 +1048,21 @@ fn do_pre_execution<WorldT: World + Clone>(
 where
     WorldT::SubtriePath: From<Address> + Ord,
 {
-    // Ethereum mainnet: EIP-4788
+    // Ethereum mainnet: EIP-4788 and EIP-2935
     if cfg!(feature = "eth_mainnet") {
-        return do_beacon_hook(
+        do_beacon_hook(
             block.block_timestamp,
             trim_storage,
             block.parent_beacon_block_root,
             trim_state,
             world,
+        )?;
+        return do_history_storage_hook(
+            block.block_number,
+            block_hashes.prev_hashes.last().copied().unwrap_or_default(),
+            trim_storage,
+            trim_state,
+            world,
         );
     }
 
@@ -784,6 +1206,355 @@ where
     Ok(())
 }
 
+/// Stores the parent block hash in the history storage contract,
+/// according to <https://eips.ethereum.org/EIPS/eip-2935>
+///
+/// This is Prague-specific, and runs at the start of the block,
+/// before any transactions (as per the EIP). It is a no-op for the genesis
+/// block, or if the contract hasn't been deployed yet.
+fn do_history_storage_hook<WorldT: World + Clone>(
+    block_number: U256,
+    parent_block_hash: H256,
+    trim_storage: &mut BTreeMap<ethereum_types::H160, BTreeSet<MptKey>>,
+    trim_state: &mut BTreeSet<WorldT::SubtriePath>,
+    world: &mut WorldT,
+) -> anyhow::Result<()>
+where
+    WorldT::SubtriePath: From<Address> + Ord,
+{
+    use evm_arithmetization::testing_utils::{HISTORY_SERVE_WINDOW, HISTORY_STORAGE_ADDRESS};
+
+    // The kernel needs to witness the (non-)existence of the contract either way.
+    trim_state.insert(<WorldT::SubtriePath>::from(HISTORY_STORAGE_ADDRESS));
+
+    if block_number.is_zero() || !world.contains(HISTORY_STORAGE_ADDRESS)? {
+        return Ok(());
+    }
+
+    let ix = (block_number - 1) % HISTORY_SERVE_WINDOW.value;
+    let slot = MptKey::from_slot_position(ix);
+    let history_trim = trim_storage.entry(HISTORY_STORAGE_ADDRESS).or_default();
+    history_trim.insert(slot);
+
+    match parent_block_hash.is_zero() {
+        true => history_trim.extend(world.reporting_destroy_slot(HISTORY_STORAGE_ADDRESS, ix)?),
+        false => world.store_int(
+            HISTORY_STORAGE_ADDRESS,
+            ix,
+            U256::from_big_endian(parent_block_hash.as_bytes()),
+        )?,
+    }
+    Ok(())
+}
+
+#[test]
+fn test_history_storage_hook() {
+    use evm_arithmetization::testing_utils::{
+        HISTORY_SERVE_WINDOW, HISTORY_STORAGE_ACCOUNT, HISTORY_STORAGE_ADDRESS,
+    };
+
+    let mut state = StateMpt::new();
+    state
+        .insert(
+            keccak_hash::keccak(HISTORY_STORAGE_ADDRESS),
+            HISTORY_STORAGE_ACCOUNT,
+        )
+        .unwrap();
+    let storage = BTreeMap::from([(
+        keccak_hash::keccak(HISTORY_STORAGE_ADDRESS),
+        StorageTrie::default(),
+    )]);
+    let world = Type1World::new(state, storage).unwrap();
+    let parent_block_hash = H256::repeat_byte(0x11);
+
+    // The slot of the parent of block `HISTORY_SERVE_WINDOW + 5` wraps around the
+    // ring buffer.
+    let mut trim_storage = BTreeMap::new();
+    let mut trim_state = BTreeSet::new();
+    let mut block_world = world.clone();
+    do_history_storage_hook(
+        HISTORY_SERVE_WINDOW.value + 5,
+        parent_block_hash,
+        &mut trim_storage,
+        &mut trim_state,
+        &mut block_world,
+    )
+    .unwrap();
+    assert_eq!(
+        block_world
+            .load_int(HISTORY_STORAGE_ADDRESS, U256::from(4))
+            .unwrap(),
+        parent_block_hash.into_uint()
+    );
+    assert_eq!(
+        trim_storage,
+        BTreeMap::from([(
+            HISTORY_STORAGE_ADDRESS,
+            BTreeSet::from([MptKey::from_slot_position(U256::from(4))])
+        )])
+    );
+    assert_eq!(
+        trim_state,
+        BTreeSet::from([MptKey::from(HISTORY_STORAGE_ADDRESS)])
+    );
+
+    // Nothing is stored for the genesis block, but the contract is still
+    // witnessed.
+    let mut trim_storage = BTreeMap::new();
+    let mut trim_state = BTreeSet::new();
+    let mut genesis_world = world.clone();
+    do_history_storage_hook(
+        U256::zero(),
+        parent_block_hash,
+        &mut trim_storage,
+        &mut trim_state,
+        &mut genesis_world,
+    )
+    .unwrap();
+    assert_eq!(genesis_world.root(), world.clone().root());
+    assert!(trim_storage.is_empty());
+    assert_eq!(
+        trim_state,
+        BTreeSet::from([MptKey::from(HISTORY_STORAGE_ADDRESS)])
+    );
+}
+
+/// Performs all the post-txn execution rules of the targeted network.
+///
+/// Returns whether the kernel should process the execution layer requests of
+/// the block.
+fn do_post_execution<WorldT: World + Clone>(
+    block: &BlockMetadata,
+    code: &mut Hash2Code<WorldT::CodeHasher>,
+    contract_code: &mut BTreeSet<Vec<u8>>,
+    trim_storage: &mut BTreeMap<ethereum_types::H160, BTreeSet<MptKey>>,
+    trim_state: &mut BTreeSet<WorldT::SubtriePath>,
+    world: &mut WorldT,
+) -> anyhow::Result<bool>
+where
+    WorldT::SubtriePath: From<Address> + Ord,
+{
+    // Ethereum mainnet: EIP-7685
+    if cfg!(feature = "eth_mainnet") && !block.requests_hash.is_zero() {
+        for queue in [WITHDRAWAL_REQUESTS_QUEUE, CONSOLIDATION_REQUESTS_QUEUE] {
+            do_dequeue_requests_hook(&queue, code, contract_code, trim_storage, trim_state, world)?;
+        }
+        return Ok(true);
+    }
+
+    Ok(false)
+}
+
+/// Parameters of a request queue predeploy contract, as per
+/// <https://eips.ethereum.org/EIPS/eip-7002> and
+/// <https://eips.ethereum.org/EIPS/eip-7251>.
+struct RequestsQueue {
+    address: Address,
+    /// Number of storage slots taken by each queued request.
+    slots_per_request: u64,
+    max_per_block: u64,
+    target_per_block: u64,
+}
+
+const WITHDRAWAL_REQUESTS_QUEUE: RequestsQueue = RequestsQueue {
+    address: WITHDRAWAL_REQUEST_PREDEPLOY_ADDRESS,
+    slots_per_request: WITHDRAWAL_REQUEST_SLOTS,
+    max_per_block: MAX_WITHDRAWAL_REQUESTS_PER_BLOCK,
+    target_per_block: TARGET_WITHDRAWAL_REQUESTS_PER_BLOCK,
+};
+
+const CONSOLIDATION_REQUESTS_QUEUE: RequestsQueue = RequestsQueue {
+    address: CONSOLIDATION_REQUEST_PREDEPLOY_ADDRESS,
+    slots_per_request: CONSOLIDATION_REQUEST_SLOTS,
+    max_per_block: MAX_CONSOLIDATION_REQUESTS_PER_BLOCK,
+    target_per_block: TARGET_CONSOLIDATION_REQUESTS_PER_BLOCK,
+};
+
+/// Mirrors the system call dequeuing the pending requests of the given
+/// predeploy contract at the end of the block, i.e. advances the queue head
+/// and updates the excess and count slots.
+///
+/// This is Prague-specific.
+fn do_dequeue_requests_hook<WorldT: World + Clone>(
+    queue: &RequestsQueue,
+    code: &mut Hash2Code<WorldT::CodeHasher>,
+    contract_code: &mut BTreeSet<Vec<u8>>,
+    trim_storage: &mut BTreeMap<ethereum_types::H160, BTreeSet<MptKey>>,
+    trim_state: &mut BTreeSet<WorldT::SubtriePath>,
+    world: &mut WorldT,
+) -> anyhow::Result<()>
+where
+    WorldT::SubtriePath: From<Address> + Ord,
+{
+    const EXCESS_INHIBITOR: U256 = U256::MAX;
+
+    let RequestsQueue {
+        address,
+        slots_per_request,
+        max_per_block,
+        target_per_block,
+    } = *queue;
+
+    trim_state.insert(<WorldT::SubtriePath>::from(address));
+
+    // The kernel executes the contract, so it needs its code.
+    let code_hash = world
+        .code_hash(address)?
+        .context(format!("missing request predeploy {address:x}"))?;
+    contract_code.insert(
+        code.get(code_hash)
+            .context(format!("no code for request predeploy {address:x}"))?,
+    );
+
+    // Slots the predeploy never wrote are absent from its storage trie.
+    let mut load = |slot: u64| -> anyhow::Result<U256> {
+        if world.is_unset(address, slot.into())? {
+            return Ok(U256::zero());
+        }
+        world.load_int(address, slot.into()).context(format!(
+            "couldn't load slot {slot} of request predeploy {address:x}"
+        ))
+    };
+    let head = load(REQUESTS_QUEUE_HEAD_SLOT)?;
+    let tail = load(REQUESTS_QUEUE_TAIL_SLOT)?;
+    let count = load(REQUESTS_COUNT_SLOT)?;
+    let stored_excess = load(EXCESS_REQUESTS_SLOT)?;
+    let excess = match stored_excess == EXCESS_INHIBITOR {
+        true => U256::zero(),
+        false => stored_excess,
+    };
+    let num_dequeued = cmp::min(tail.saturating_sub(head), max_per_block.into()).as_u64();
+
+    // The kernel reads the dequeued requests.
+    let trim = trim_storage.entry(address).or_default();
+    trim.extend(
+        (0..num_dequeued)
+            .flat_map(|ix| {
+                let first_slot =
+                    U256::from(REQUESTS_QUEUE_OFFSET) + (head + ix) * slots_per_request;
+                (0..slots_per_request).map(move |offset| first_slot + offset)
+            })
+            .chain(
+                [
+                    EXCESS_REQUESTS_SLOT,
+                    REQUESTS_COUNT_SLOT,
+                    REQUESTS_QUEUE_HEAD_SLOT,
+                    REQUESTS_QUEUE_TAIL_SLOT,
+                ]
+                .map(U256::from),
+            )
+            .map(MptKey::from_slot_position),
+    );
+
+    let new_head = head + num_dequeued;
+    let new_excess = excess
+        .saturating_add(count)
+        .saturating_sub(target_per_block.into());
+    let writes = match new_head == tail {
+        true => vec![
+            (REQUESTS_QUEUE_HEAD_SLOT, head, U256::zero()),
+            (REQUESTS_QUEUE_TAIL_SLOT, tail, U256::zero()),
+        ],
+        false => vec![(REQUESTS_QUEUE_HEAD_SLOT, head, new_head)],
+    };
+    for (slot, old, new) in writes.into_iter().chain([
+        (EXCESS_REQUESTS_SLOT, stored_excess, new_excess),
+        (REQUESTS_COUNT_SLOT, count, U256::zero()),
+    ]) {
+        match (old.is_zero(), new.is_zero()) {
+            (true, true) => {}
+            // this is actually a delete
+            (false, true) => trim.extend(world.reporting_destroy_slot(address, slot.into())?),
+            (_, false) => world.store_int(address, slot.into(), new)?,
+        }
+    }
+    Ok(())
+}
+
+/// Returns the delegate of the given code, if it is a delegation designator
+/// as per <https://eips.ethereum.org/EIPS/eip-7702>.
+fn delegate_of(code: &[u8]) -> Option<Address> {
+    match code.strip_prefix(&DELEGATION_DESIGNATOR_PREFIX) {
+        Some(delegate) if code.len() == DELEGATION_DESIGNATOR_LEN => {
+            Some(Address::from_slice(delegate))
+        }
+        _ => None,
+    }
+}
+
+/// Returns whether the given code usage sets a delegation designator, as per
+/// <https://eips.ethereum.org/EIPS/eip-7702>.
+fn is_delegation(code_usage: &Option<ContractCodeUsage>) -> bool {
+    matches!(code_usage, Some(ContractCodeUsage::Write(bytes)) if delegate_of(bytes).is_some())
+}
+
+/// Which changes of an account's trace are committed to the state trie.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Commit {
+    /// The account was only accessed.
+    Nothing,
+    /// Only the nonce and delegation designator set by an
+    /// [EIP-7702](https://eips.ethereum.org/EIPS/eip-7702) authorization.
+    Delegation,
+    /// All the changes.
+    All,
+}
+
+/// Returns which changes of an account's trace are committed.
+///
+/// If the txn failed, the changes to accounts it created are reverted.
+/// EIP-7702 authorizations are applied before execution, so a delegation
+/// persists, but not the other changes of the reverted execution.
+fn commit(just_access: bool, born: bool, status: bool, is_delegation: bool) -> Commit {
+    match (just_access, born, status, is_delegation) {
+        (true, ..) => Commit::Nothing,
+        (false, false, _, _) | (false, true, true, _) => Commit::All,
+        (false, true, false, true) => Commit::Delegation,
+        (false, true, false, false) => Commit::Nothing,
+    }
+}
+
+#[test]
+fn test_commit() {
+    // A successful txn commits all the changes.
+    assert_eq!(commit(false, true, true, false), Commit::All);
+    assert_eq!(commit(false, true, true, true), Commit::All);
+    // Changes to existing accounts are committed even if the txn failed.
+    assert_eq!(commit(false, false, false, false), Commit::All);
+    // A reverted txn doesn't create accounts...
+    assert_eq!(commit(false, true, false, false), Commit::Nothing);
+    // ...except for the authorities of a 7702 txn, which only keep their
+    // delegation.
+    assert_eq!(commit(false, true, false, true), Commit::Delegation);
+    assert_eq!(commit(true, true, true, false), Commit::Nothing);
+}
+
+#[test]
+fn test_delegation() {
+    let delegate = Address::from_low_u64_be(0xff);
+    let designator = [&DELEGATION_DESIGNATOR_PREFIX[..], delegate.as_bytes()].concat();
+    assert_eq!(delegate_of(&designator), Some(delegate));
+    assert!(is_delegation(&Some(ContractCodeUsage::Write(
+        designator.clone()
+    ))));
+
+    // Only the exact designator delegates, not code starting with it.
+    let longer = [&designator[..], &[0x00]].concat();
+    assert_eq!(delegate_of(&longer), None);
+    assert_eq!(
+        delegate_of(&designator[..DELEGATION_DESIGNATOR_LEN - 1]),
+        None
+    );
+    assert_eq!(delegate_of(&[]), None);
+    assert!(!is_delegation(&Some(ContractCodeUsage::Write(longer))));
+
+    // Reading a designator doesn't set one.
+    assert!(!is_delegation(&Some(ContractCodeUsage::Read(
+        keccak_hash::keccak(&designator)
+    ))));
+    assert!(!is_delegation(&None));
+}
+
 fn map_receipt_bytes(bytes: Vec<u8>) -> anyhow::Result<Vec<u8>> {
     match rlp::decode::<evm_arithmetization::generation::mpt::LegacyReceiptRlp>(&bytes) {
         Ok(_) => Ok(bytes),
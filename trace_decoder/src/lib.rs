@@ -68,7 +68,10 @@ mod type1;
 mod type2;
 mod wire;
 
-pub use core::{entrypoint, WireDisposition};
+pub use core::{
+    combine_pre_images, encode_pre_images, encode_type2_pre_images, entrypoint, BatchingStrategy,
+    TxnCostEstimate, WireDisposition,
+};
 
 mod core;
 
@@ -2,7 +2,7 @@
 //! Ethereum node (a.k.a "jerigon").
 
 use std::array;
-use std::collections::{BTreeMap, BTreeSet};
+use std::collections::{BTreeMap, BTreeSet, HashMap};
 
 use anyhow::{bail, ensure, Context as _};
 use either::Either;
@@ -10,7 +10,8 @@ use evm_arithmetization::generation::mpt::AccountRlp;
 use evm_arithmetization::tries::{MptKey, StateMpt, StorageTrie};
 use evm_arithmetization::world::{Hasher as _, Type1World, World};
 use keccak_hash::H256;
-use mpt_trie::partial_trie::OnOrphanedHashNode;
+use mpt_trie::nibbles::Nibbles;
+use mpt_trie::partial_trie::{HashedPartialTrie, Node as MptNode, OnOrphanedHashNode};
 use nunny::NonEmpty;
 use u4::U4;
 
@@ -46,6 +47,152 @@ pub fn frontend(instructions: impl IntoIterator<Item = Instruction>) -> anyhow::
     Ok(frontend)
 }
 
+/// The inverse of [`frontend`].
+///
+/// Code which no account leaf refers to is not encoded.
+pub fn encode(frontend: &Frontend) -> anyhow::Result<Vec<Instruction>> {
+    let code = frontend
+        .code
+        .iter()
+        .map(|code| (<Type1World as World>::CodeHasher::hash(code), code))
+        .collect::<HashMap<_, _>>();
+    let state = frontend.state.as_hashed_partial_trie();
+    let mut instructions = vec![];
+    match &**state {
+        MptNode::Empty => instructions.push(Instruction::EmptyRoot),
+        _ => encode_mpt(
+            state,
+            &mut vec![],
+            &mut instructions,
+            &mut |path, key, value, instructions| {
+                let address = MptKey::new(path.iter().copied())?
+                    .into_hash()
+                    .context("invalid depth for leaf of state trie")?;
+                let account = rlp::decode::<AccountRlp>(value)?;
+                let has_code = account.code_hash != <Type1World as World>::CodeHasher::hash(&[]);
+                // The length of code which isn't included is unknown, see
+                // `Instruction::AccountLeaf`.
+                let code_length = match (has_code, code.get(&account.code_hash)) {
+                    (true, Some(&code)) => {
+                        instructions.push(Instruction::Code {
+                            raw_code: code.clone(),
+                        });
+                        Some(code.len() as u64)
+                    }
+                    (true, None) => {
+                        instructions.push(Instruction::Hash {
+                            raw_hash: account.code_hash.0,
+                        });
+                        Some(0)
+                    }
+                    (false, _) => None,
+                };
+                let storage = frontend
+                    .storage
+                    .get(&address)
+                    .map(StorageTrie::as_hashed_partial_trie)
+                    .filter(|storage| !matches!(***storage, MptNode::Empty));
+                let has_storage = match storage {
+                    Some(storage) => {
+                        encode_mpt(
+                            storage,
+                            &mut vec![],
+                            instructions,
+                            &mut |_path, key, value, instructions| {
+                                instructions.push(Instruction::Leaf {
+                                    key,
+                                    value: NonEmpty::<Vec<_>>::new(rlp::decode(value)?)
+                                        .ok()
+                                        .context("empty value in storage trie")?,
+                                });
+                                Ok(())
+                            },
+                        )?;
+                        true
+                    }
+                    // The storage trie was not included.
+                    None if account.storage_root != StorageTrie::default().root() => {
+                        instructions.push(Instruction::Hash {
+                            raw_hash: account.storage_root.0,
+                        });
+                        true
+                    }
+                    None => false,
+                };
+                ensure!(
+                    account.nonce <= u64::MAX.into(),
+                    "nonce of account {address:x} doesn't fit in a u64"
+                );
+                instructions.push(Instruction::AccountLeaf {
+                    key,
+                    nonce: Some(account.nonce.as_u64()),
+                    balance: Some(account.balance),
+                    code_length,
+                    has_storage,
+                });
+                Ok(())
+            },
+        )?,
+    }
+    Ok(instructions)
+}
+
+/// Encodes `node` for [`execute`], which builds tries from their leaves up,
+/// calling `leaf` with the full path, key and value of each leaf.
+fn encode_mpt(
+    node: &HashedPartialTrie,
+    path: &mut Vec<U4>,
+    instructions: &mut Vec<Instruction>,
+    leaf: &mut dyn FnMut(
+        &[U4],
+        NonEmpty<Vec<U4>>,
+        &[u8],
+        &mut Vec<Instruction>,
+    ) -> anyhow::Result<()>,
+) -> anyhow::Result<()> {
+    match &**node {
+        MptNode::Empty => instructions.push(Instruction::EmptyRoot),
+        MptNode::Hash(hash) => instructions.push(Instruction::Hash { raw_hash: hash.0 }),
+        MptNode::Branch { children, value } => {
+            ensure!(value.is_empty(), "unsupported value in branch node");
+            let mut mask = 0;
+            for (ix, child) in children.iter().enumerate() {
+                if !matches!(****child, MptNode::Empty) {
+                    mask |= 1 << ix;
+                    path.push(U4::new(ix as u8).expect("ix is in range 0..16"));
+                    encode_mpt(child, path, instructions, leaf)?;
+                    path.pop();
+                }
+            }
+            instructions.push(Instruction::Branch { mask })
+        }
+        MptNode::Extension { nibbles, child } => {
+            let key = nibbles2key(nibbles)?;
+            path.extend(key.iter());
+            encode_mpt(child, path, instructions, leaf)?;
+            path.truncate(path.len() - key.len());
+            instructions.push(Instruction::Extension { key })
+        }
+        MptNode::Leaf { nibbles, value } => {
+            let key = nibbles2key(nibbles)?;
+            let full_path = path.iter().chain(key.iter()).copied().collect::<Vec<_>>();
+            leaf(&full_path, key, value, instructions)?
+        }
+    }
+    Ok(())
+}
+
+fn nibbles2key(nibbles: &Nibbles) -> anyhow::Result<NonEmpty<Vec<U4>>> {
+    let mut nibbles = *nibbles;
+    let mut key = vec![];
+    while !nibbles.is_empty() {
+        key.push(U4::new(nibbles.pop_next_nibble_front()).context("invalid nibble")?)
+    }
+    NonEmpty::<Vec<_>>::new(key)
+        .ok()
+        .context("keys must not be empty in the wire format")
+}
+
 fn visit(
     frontend: &mut Frontend,
     path: &stackstack::Stack<'_, U4>,
@@ -256,7 +403,7 @@ fn execute(
                 key,
                 nonce,
                 balance,
-                has_code,
+                code_length,
                 has_storage,
             } => {
                 // BUG: the spec sometimes writes Node::Account with 5 fields..
@@ -264,7 +411,7 @@ fn execute(
                 //                should these fields even be optional?
                 let nonce = nonce.unwrap_or_default();
                 let balance = balance.unwrap_or_default();
-                let account = match (has_code, has_storage) {
+                let account = match (code_length.is_some(), has_storage) {
                     (true, true) => {
                         let right = stack.pop();
                         let left = stack.pop();
@@ -385,3 +532,98 @@ fn test_tries() {
         }
     }
 }
+
+#[test]
+fn test_encode() {
+    for (ix, case) in
+        serde_json::from_str::<Vec<super::Case>>(include_str!("cases/zero_jerigon.json"))
+            .unwrap()
+            .into_iter()
+            .enumerate()
+    {
+        println!("case {}", ix);
+        let frontend = frontend(crate::wire::parse(&case.bytes).unwrap()).unwrap();
+        let bytes = crate::wire::encode(encode(&frontend).unwrap());
+        let reencoded = self::frontend(crate::wire::parse(&bytes).unwrap()).unwrap();
+        assert_eq!(case.expected_state_root, reencoded.state.root());
+        assert_eq!(frontend.code, reencoded.code);
+        itertools::assert_equal(
+            frontend.storage.iter().map(|(k, v)| (k, v.root())),
+            reencoded.storage.iter().map(|(k, v)| (k, v.root())),
+        );
+    }
+}
+
+#[test]
+fn test_encode_generated() {
+    use ethereum_types::U256;
+    use keccak_hash::keccak;
+
+    for seed in 0..8u64 {
+        let random = |ix: u64| keccak([seed.to_be_bytes(), ix.to_be_bytes()].concat());
+
+        let mut frontend = Frontend::default();
+        let mut kept = BTreeSet::new();
+        let mut kept_code = BTreeSet::new();
+        for ix in 0..1 + 5 * seed {
+            let hashed_address = random(ix);
+            let code = NonEmpty::<Vec<_>>::new(random(ix + 1000).0[..ix as usize % 33].to_vec());
+            let mut storage = StorageTrie::new(OnOrphanedHashNode::Reject);
+            let slots = (0..ix % 4)
+                .map(|slot| random(ix * 4 + slot + 2000))
+                .collect::<Vec<_>>();
+            for (slot, value) in slots.iter().zip(1u64..) {
+                storage
+                    .insert(
+                        MptKey::from_hash(*slot),
+                        rlp::encode(&U256::from(value)).to_vec(),
+                    )
+                    .unwrap();
+            }
+            // Hash out every other account, and the first slot of the others,
+            // which also shortens the keys of their leaves to odd lengths.
+            if ix % 2 == 0 {
+                kept.insert(hashed_address);
+                storage
+                    .mask(slots.iter().skip(1).copied().map(MptKey::from_hash))
+                    .unwrap();
+                if let Ok(code) = &code {
+                    kept_code.insert(code.clone());
+                }
+            }
+            let account = AccountRlp {
+                nonce: ix.into(),
+                balance: U256::from_big_endian(&random(ix + 3000).0[..ix as usize % 33]),
+                storage_root: storage.root(),
+                code_hash: match &code {
+                    Ok(code) => <Type1World as World>::CodeHasher::hash(code),
+                    Err(_) => <Type1World as World>::CodeHasher::hash(&[]),
+                },
+            };
+            frontend.state.insert(hashed_address, account).unwrap();
+            frontend.storage.insert(hashed_address, storage);
+            if let Ok(code) = code {
+                frontend.code.insert(code);
+            }
+        }
+        let root = frontend.state.root();
+        frontend
+            .state
+            .mask(kept.iter().copied().map(MptKey::from_hash))
+            .unwrap();
+        frontend
+            .storage
+            .retain(|hashed_address, _| kept.contains(hashed_address));
+        assert_eq!(frontend.state.root(), root);
+
+        let bytes = crate::wire::encode(encode(&frontend).unwrap());
+        let reencoded = self::frontend(crate::wire::parse(&bytes).unwrap()).unwrap();
+        assert_eq!(reencoded.state.root(), root);
+        assert_eq!(reencoded.code, kept_code);
+        itertools::assert_equal(
+            frontend.storage.iter().map(|(k, v)| (k, v.root())),
+            reencoded.storage.iter().map(|(k, v)| (k, v.root())),
+        );
+        assert_eq!(crate::wire::encode(encode(&reencoded).unwrap()), bytes);
+    }
+}
@@ -3,18 +3,19 @@
 
 use std::collections::{BTreeMap, HashSet};
 
+use alloy_compat::Compat as _;
 use anyhow::{bail, ensure, Context as _};
 use ethereum_types::{Address, U256};
 use evm_arithmetization::{
     tries::SmtKey,
     world::{Type2Entry, Type2World},
 };
-use itertools::EitherOrBoth;
+use itertools::{EitherOrBoth, Itertools as _};
 use keccak_hash::H256;
 use nunny::NonEmpty;
 use stackstack::Stack;
 
-use crate::wire::{Instruction, SmtLeaf, SmtLeafType};
+use crate::wire::{trimmed_be_bytes, Instruction, SmtLeaf, SmtLeafType};
 
 pub struct Frontend {
     pub world: Type2World,
@@ -31,6 +32,117 @@ pub fn frontend(instructions: impl IntoIterator<Item = Instruction>) -> anyhow::
     Ok(Frontend { world, code })
 }
 
+/// The inverse of [`frontend`].
+pub fn encode(frontend: &Frontend) -> anyhow::Result<Vec<Instruction>> {
+    use smt_trie::keys::{key_balance, key_code, key_code_length, key_nonce, key_storage};
+
+    let mut nodes = vec![];
+    for (path, hash) in frontend.world.hashed_out() {
+        let bits = path.into_smt_bits();
+        nodes.push((
+            (0..bits.count).map(|ix| bits.get_bit(ix)).collect(),
+            Instruction::Hash { raw_hash: hash.0 },
+        ));
+    }
+    for (&address, entry) in frontend.world.accounts() {
+        let Type2Entry {
+            balance,
+            nonce,
+            code_hash,
+            code_length,
+            storage,
+        } = entry;
+        let addr = address.compat();
+        let mut leaf = |node_type, key: smt_trie::smt::Key, value: U256| {
+            let bits = key.split();
+            nodes.push((
+                (0..bits.count).map(|ix| bits.get_bit(ix)).collect(),
+                Instruction::SmtLeaf(SmtLeaf {
+                    node_type,
+                    address: NonEmpty::<Vec<_>>::new(address.as_bytes().to_vec())
+                        .expect("addresses are not empty"),
+                    value: nonempty_be_bytes(value),
+                }),
+            ))
+        };
+        for (value, node_type, key) in [
+            (balance, SmtLeafType::Balance, key_balance(addr)),
+            (nonce, SmtLeafType::Nonce, key_nonce(addr)),
+            (code_hash, SmtLeafType::Code, key_code(addr)),
+            (code_length, SmtLeafType::CodeLength, key_code_length(addr)),
+        ] {
+            if let Some(value) = value {
+                leaf(node_type, key, *value)
+            }
+        }
+        for (&slot, &value) in storage {
+            leaf(
+                SmtLeafType::Storage(nonempty_be_bytes(slot)),
+                key_storage(addr, slot.compat()),
+                value,
+            )
+        }
+    }
+    ensure!(!nodes.is_empty(), "cannot encode an empty world");
+
+    // `fold` is lenient WRT leading Code instructions
+    let mut instructions = frontend
+        .code
+        .iter()
+        .sorted()
+        .map(|code| Instruction::Code {
+            raw_code: code.clone(),
+        })
+        .collect();
+    encode_smt(nodes, 0, &mut instructions)?;
+    Ok(instructions)
+}
+
+fn nonempty_be_bytes(value: U256) -> NonEmpty<Vec<u8>> {
+    NonEmpty::<Vec<_>>::new(trimmed_be_bytes(value)).expect("at least one byte is kept")
+}
+
+/// Lay out `nodes` at their paths like the SMT does, with each leaf as close
+/// to the root as the other nodes allow, emitting them in the pre-order
+/// expected by [`fold1`].
+fn encode_smt(
+    nodes: Vec<(Vec<bool>, Instruction)>,
+    depth: usize,
+    instructions: &mut Vec<Instruction>,
+) -> anyhow::Result<()> {
+    let is_terminal = match nodes.as_slice() {
+        [(path, Instruction::Hash { .. })] => path.len() == depth,
+        [(_, Instruction::SmtLeaf(_))] => true,
+        _ => false,
+    };
+    if is_terminal {
+        instructions.extend(nodes.into_iter().map(|(_, it)| it));
+        return Ok(());
+    }
+
+    ensure!(
+        nodes.iter().all(|(path, _)| path.len() > depth),
+        "conflicting nodes at depth {depth} of the SMT"
+    );
+    let (left, right) = nodes
+        .into_iter()
+        .partition::<Vec<_>, _>(|(path, _)| !path[depth]);
+    // note that the single-child bits are reversed...
+    instructions.push(Instruction::Branch {
+        mask: match (left.is_empty(), right.is_empty()) {
+            (false, true) => 0b_01,
+            (true, false) => 0b_10,
+            _ => 0b_11,
+        },
+    });
+    for child in [left, right] {
+        if !child.is_empty() {
+            encode_smt(child, depth + 1, instructions)?
+        }
+    }
+    Ok(())
+}
+
 /// Node in a binary (SMT) tree.
 ///
 /// This is an intermediary type on the way to [`Type2World`].
@@ -187,3 +299,110 @@ fn test_tries() {
         assert_eq!(case.expected_state_root, frontend.world.root());
     }
 }
+
+#[test]
+fn test_encode() {
+    use evm_arithmetization::world::World as _;
+    for (ix, case) in
+        serde_json::from_str::<Vec<super::Case>>(include_str!("cases/hermez_cdk_erigon.json"))
+            .unwrap()
+            .into_iter()
+            .enumerate()
+    {
+        println!("case {}", ix);
+        let frontend = frontend(crate::wire::parse(&case.bytes).unwrap()).unwrap();
+        let bytes = crate::wire::encode(encode(&frontend).unwrap());
+        let mut reencoded = self::frontend(crate::wire::parse(&bytes).unwrap()).unwrap();
+        assert_eq!(case.expected_state_root, reencoded.world.root());
+        assert_eq!(frontend.code, reencoded.code);
+    }
+}
+
+#[test]
+fn test_encode_generated() {
+    use evm_arithmetization::world::World as _;
+    use keccak_hash::keccak;
+    use smt_trie::keys::{key_balance, key_code, key_code_length, key_nonce, key_storage};
+
+    let key_bits = |key: smt_trie::smt::Key| {
+        let bits = key.split();
+        (0..bits.count)
+            .map(|ix| bits.get_bit(ix))
+            .collect::<Vec<_>>()
+    };
+
+    for seed in 0..8u64 {
+        let random = |ix: u64| keccak([seed.to_be_bytes(), ix.to_be_bytes()].concat());
+        // Leaves with a zero value are deleted from the SMT.
+        let value = |ix: u64| U256::from_big_endian(&random(ix).0[..1 + ix as usize % 31]) + 1;
+
+        let mut accounts = BTreeMap::new();
+        let mut leaf_keys = vec![];
+        for ix in 0..1 + 3 * seed {
+            let address = Address::from_slice(&random(ix).0[..20]);
+            let addr = address.compat();
+            let storage = (0..ix % 4)
+                .map(|slot| (value(ix * 4 + slot + 1000), value(ix * 4 + slot + 2000)))
+                .collect::<BTreeMap<_, _>>();
+            let entry = Type2Entry {
+                balance: Some(value(ix + 3000)),
+                nonce: (ix % 2 == 0).then(|| value(ix + 4000)),
+                code_hash: (ix % 3 == 0).then(|| value(ix + 5000)),
+                code_length: (ix % 3 == 0).then(|| value(ix + 6000)),
+                storage,
+            };
+            leaf_keys.push(key_bits(key_balance(addr)));
+            if entry.nonce.is_some() {
+                leaf_keys.push(key_bits(key_nonce(addr)));
+            }
+            if entry.code_hash.is_some() {
+                leaf_keys.push(key_bits(key_code(addr)));
+                leaf_keys.push(key_bits(key_code_length(addr)));
+            }
+            for &slot in entry.storage.keys() {
+                leaf_keys.push(key_bits(key_storage(addr, slot.compat())));
+            }
+            accounts.insert(address, entry);
+        }
+
+        // Hash out subtries wherever they don't overlap with the leaves or with
+        // each other.
+        let mut hashed_paths = Vec::<Vec<bool>>::new();
+        let mut hashed_out = BTreeMap::new();
+        for ix in 0..2 * seed {
+            let bytes = random(ix + 7000).0;
+            let depth = 4 + ix as usize % 6;
+            let path = (0..depth)
+                .map(|bit| bytes[bit / 8] & (0x80 >> (bit % 8)) != 0)
+                .collect::<Vec<_>>();
+            let overlaps =
+                |other: &Vec<bool>| other.starts_with(&path) || path.starts_with(other.as_slice());
+            if leaf_keys.iter().any(overlaps) || hashed_paths.iter().any(overlaps) {
+                continue;
+            }
+            hashed_out.insert(
+                SmtKey::new(path.iter().copied()).unwrap(),
+                random(ix + 8000),
+            );
+            hashed_paths.push(path);
+        }
+
+        let code = (0..seed % 3)
+            .map(|ix| NonEmpty::<Vec<_>>::new(random(ix + 9000).0.to_vec()).unwrap())
+            .collect();
+        let mut frontend = Frontend {
+            world: Type2World::new_unchecked(accounts, hashed_out),
+            code,
+        };
+        let bytes = crate::wire::encode(encode(&frontend).unwrap());
+        let mut reencoded = self::frontend(crate::wire::parse(&bytes).unwrap()).unwrap();
+        assert_eq!(frontend.world.root(), reencoded.world.root());
+        assert_eq!(frontend.world.hashed_out(), reencoded.world.hashed_out());
+        assert_eq!(
+            format!("{:?}", frontend.world.accounts()),
+            format!("{:?}", reencoded.world.accounts())
+        );
+        assert_eq!(frontend.code, reencoded.code);
+        assert_eq!(crate::wire::encode(encode(&reencoded).unwrap()), bytes);
+    }
+}
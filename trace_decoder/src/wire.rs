@@ -15,7 +15,7 @@ use either::Either;
 use ethereum_types::U256;
 use nunny::NonEmpty;
 use serde::de::DeserializeOwned;
-use u4::{U4x2, U4};
+use u4::{AsNibbles, U4x2, U4};
 use winnow::{
     combinator::{empty, eof, fail, preceded, repeat_till, trace},
     error::{ErrorKind, FromExternalError, StrContext},
@@ -38,6 +38,132 @@ pub fn parse(input: &[u8]) -> anyhow::Result<NonEmpty<Vec<Instruction>>> {
     }
 }
 
+/// The inverse of [`parse`].
+///
+/// Instructions parsed from a witness of the nodes encode back to the same
+/// bytes.
+pub fn encode(instructions: impl IntoIterator<Item = Instruction>) -> Vec<u8> {
+    let mut out = vec![1]; // header
+    for instruction in instructions {
+        encode_instruction(instruction, &mut out)
+    }
+    out
+}
+
+fn encode_instruction(instruction: Instruction, out: &mut Vec<u8>) {
+    match instruction {
+        Instruction::Leaf { key, value } => {
+            out.push(0x00);
+            encode_key(&key, true, out);
+            encode_bytes(&value, out);
+        }
+        Instruction::Extension { key } => {
+            out.push(0x01);
+            encode_key(&key, false, out);
+        }
+        Instruction::Branch { mask } => {
+            out.push(0x02);
+            encode_cbor(mask, out);
+        }
+        Instruction::Hash { raw_hash } => {
+            out.push(0x03);
+            out.extend(raw_hash);
+        }
+        Instruction::Code { raw_code } => {
+            out.push(0x04);
+            encode_bytes(&raw_code, out);
+        }
+        Instruction::AccountLeaf {
+            key,
+            nonce,
+            balance,
+            code_length,
+            has_storage,
+        } => {
+            out.push(0x05);
+            encode_key(&key, true, out);
+            // see `AccountLeafFlags` in [`account_leaf`]
+            out.push(
+                u8::from(code_length.is_some())
+                    | u8::from(has_storage) << 1
+                    | u8::from(nonce.is_some()) << 2
+                    | u8::from(balance.is_some()) << 3,
+            );
+            if let Some(nonce) = nonce {
+                encode_cbor(nonce, out)
+            }
+            if let Some(balance) = balance {
+                encode_bytes(&trimmed_be_bytes(balance), out)
+            }
+            if let Some(code_length) = code_length {
+                encode_cbor(code_length, out)
+            }
+        }
+        Instruction::EmptyRoot => out.push(0x06),
+        Instruction::SmtLeaf(SmtLeaf {
+            node_type,
+            address,
+            value,
+        }) => {
+            out.push(0x07);
+            out.push(match node_type {
+                SmtLeafType::Balance => 0,
+                SmtLeafType::Nonce => 1,
+                SmtLeafType::Code => 2,
+                SmtLeafType::Storage(_) => 3,
+                SmtLeafType::CodeLength => 4,
+            });
+            encode_bytes(&address, out);
+            if let SmtLeafType::Storage(slot) = node_type {
+                encode_bytes(&slot, out)
+            }
+            encode_bytes(&value, out);
+        }
+        Instruction::NewTrie => out.push(0xBB),
+    }
+}
+
+/// The inverse of [`decode_key`].
+///
+/// Like Erigon, the keys of leaves are flagged as `terminated`, which
+/// [`decode_key`] ignores.
+fn encode_key(key: &NonEmpty<[U4]>, terminated: bool, out: &mut Vec<u8>) {
+    let bytes = match key.split_first() {
+        (only, &[]) => vec![*only as u8],
+        _ => {
+            let (pairs, last) = key.split_at(key.len() / 2 * 2);
+            let mut bytes = vec![0; 1 + pairs.len() / 2];
+            AsNibbles(&mut bytes[1..]).pack_from_slice(pairs);
+            if terminated {
+                bytes[0] |= 0b0000_0010; // TERMINATED
+            }
+            if let [last] = last {
+                bytes[0] |= 0b0000_0001; // ODD
+                bytes.push((*last as u8) << 4);
+            }
+            bytes
+        }
+    };
+    encode_bytes(&bytes, out)
+}
+
+/// Encodes `bytes` as a CBOR byte string.
+fn encode_bytes(bytes: &[u8], out: &mut Vec<u8>) {
+    encode_cbor(ciborium::Value::Bytes(bytes.to_vec()), out)
+}
+
+fn encode_cbor(value: impl serde::Serialize, out: &mut Vec<u8>) {
+    ciborium::into_writer(&value, out).expect("writing to a Vec is infallible")
+}
+
+/// The big-endian bytes of `value`, without leading zeros, but at least one.
+pub(crate) fn trimmed_be_bytes(value: U256) -> Vec<u8> {
+    let mut bytes = [0; 32];
+    value.to_big_endian(&mut bytes);
+    let leading_zeros = bytes.iter().take(31).take_while(|it| **it == 0).count();
+    bytes[leading_zeros..].to_vec()
+}
+
 /// Names are taken from the spec.
 /// Spec also requires sequences to be non-empty.
 ///
@@ -70,7 +196,12 @@ pub enum Instruction {
         nonce: Option<u64>,
         /// BUG(spec): see parse site [`account_leaf`].
         balance: Option<U256>,
-        has_code: bool,
+        /// [`Some`] if the account has code, with the length of its code.
+        ///
+        /// Parsing ignores the length. When the code isn't included, but only
+        /// its hash, its length is unknown to the encoder, which writes 0, so
+        /// such witnesses don't re-encode to the same bytes.
+        code_length: Option<u64>,
         has_storage: bool,
     },
     SmtLeaf(SmtLeaf),
@@ -171,15 +302,12 @@ fn account_leaf(input: &mut &[u8]) -> PResult<Instruction> {
             false => None,
         },
         has_storage: flags.contains(AccountLeafFlags::HAS_STORAGE),
-        has_code: {
-            let has_code = flags.contains(AccountLeafFlags::HAS_CODE);
-            if has_code {
-                // BUG(spec): this field is undocumented, but the previous
-                //            version of this code had it, and our tests fail
-                //            without it
-                trace("code_length", cbor::<u64>).parse_next(input)?;
-            }
-            has_code
+        code_length: match flags.contains(AccountLeafFlags::HAS_CODE) {
+            // BUG(spec): this field is undocumented, but the previous
+            //            version of this code had it, and our tests fail
+            //            without it
+            true => Some(trace("code_length", cbor::<u64>).parse_next(input)?),
+            false => None,
         },
     })
 }
@@ -315,3 +443,39 @@ fn cbor_test_cases() {
     do_test(b"\x0a", 10, cbor);
     do_test(b"\x17", 23, cbor);
 }
+
+#[test]
+fn round_trip() {
+    for cases in [
+        include_str!("cases/zero_jerigon.json"),
+        include_str!("cases/hermez_cdk_erigon.json"),
+    ] {
+        for (ix, case) in serde_json::from_str::<Vec<super::Case>>(cases)
+            .unwrap()
+            .into_iter()
+            .enumerate()
+        {
+            println!("case {}", ix);
+            let instructions = parse(&case.bytes).unwrap();
+            let bytes = encode(instructions.clone());
+            assert_eq!(parse(&bytes).unwrap(), instructions);
+            // The nodes encode minimally, like `ciborium`, so the format is
+            // canonical.
+            assert_eq!(bytes, case.bytes);
+        }
+    }
+}
+
+#[test]
+fn key_round_trip() {
+    for len in 1..=64 {
+        let key =
+            NonEmpty::<Vec<_>>::new((0..len).map(|ix| U4::new(ix as u8 % 16).unwrap()).collect())
+                .unwrap();
+        for terminated in [false, true] {
+            let mut bytes = vec![];
+            encode_key(&key, terminated, &mut bytes);
+            do_test(&bytes, key.clone(), self::key);
+        }
+    }
+}
@@ -10,7 +10,7 @@ use common::{cases, Case};
 use itertools::Itertools;
 use libtest_mimic::{Arguments, Trial};
 use mpt_trie::partial_trie::PartialTrie as _;
-use trace_decoder::observer::DummyObserver;
+use trace_decoder::{observer::DummyObserver, BatchingStrategy};
 use zero::prover::WIRE_DISPOSITION;
 
 fn main() -> anyhow::Result<()> {
@@ -28,7 +28,7 @@ fn main() -> anyhow::Result<()> {
                 let gen_inputs = trace_decoder::entrypoint(
                     trace,
                     other.clone(),
-                    batch_size,
+                    BatchingStrategy::Count(batch_size),
                     &mut DummyObserver::new(),
                     WIRE_DISPOSITION,
                 )
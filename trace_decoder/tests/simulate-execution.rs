@@ -8,7 +8,7 @@ use anyhow::Context as _;
 use common::{cases, Case};
 use libtest_mimic::{Arguments, Trial};
 use plonky2::field::goldilocks_field::GoldilocksField;
-use trace_decoder::observer::DummyObserver;
+use trace_decoder::{observer::DummyObserver, BatchingStrategy};
 use zero::prover::WIRE_DISPOSITION;
 
 fn main() -> anyhow::Result<()> {
@@ -24,7 +24,7 @@ fn main() -> anyhow::Result<()> {
             let gen_inputs = trace_decoder::entrypoint(
                 trace,
                 other,
-                batch_size,
+                BatchingStrategy::Count(batch_size),
                 &mut DummyObserver::new(),
                 WIRE_DISPOSITION,
             )
@@ -0,0 +1,309 @@
+//! A lightweight verifier of zkEVM block proofs.
+//!
+//! Block proofs are verified against a [`VerifierKey`], i.e. the verifier
+//! data of the final block circuit, exported to a versioned file. This crate
+//! only depends on `plonky2` and `serde`, so that block proofs can be checked
+//! without the prover and its table circuits.
+//!
+//! # Verifier key format
+//!
+//! A verifier key file is laid out as follows, with integers in little-endian
+//! order:
+//!
+//! | Field     | Size    | Description                                         |
+//! |-----------|---------|-----------------------------------------------------|
+//! | magic     | 8       | [`MAGIC`]                                           |
+//! | version   | 4       | [`VERSION`]                                         |
+//! | digest    | 32      | The circuit digest, see [`VerifierKey::digest`]     |
+//! | length    | 8       | The length of the verifier data                     |
+//! | data      | length  | The verifier data, serialized by `plonky2`          |
+
+use std::fmt;
+
+use plonky2::field::goldilocks_field::GoldilocksField;
+use plonky2::field::types::{Field as _, PrimeField64 as _};
+use plonky2::hash::hash_types::HashOut;
+use plonky2::hash::poseidon::PoseidonHash;
+use plonky2::plonk::circuit_data::VerifierCircuitData;
+use plonky2::plonk::config::{Hasher as _, PoseidonGoldilocksConfig};
+use plonky2::recursion::cyclic_recursion::check_cyclic_proof_verifier_data;
+use plonky2::util::serialization::DefaultGateSerializer;
+use serde::{Deserialize, Serialize};
+
+/// The field of the block proofs.
+pub type F = GoldilocksField;
+/// The configuration of the block circuit.
+pub type C = PoseidonGoldilocksConfig;
+/// The extension degree of the field used by the block circuit.
+pub const D: usize = 2;
+
+/// The verifier data of a circuit.
+pub type VerifierData = VerifierCircuitData<F, C, D>;
+/// A proof along with its public inputs.
+pub type ProofWithPublicInputs = plonky2::plonk::proof::ProofWithPublicInputs<F, C, D>;
+
+/// The magic bytes at the start of a verifier key file.
+pub const MAGIC: [u8; 8] = *b"ZKEVM-VK";
+/// The version of the verifier key format.
+pub const VERSION: u32 = 1;
+
+/// The length of a circuit digest.
+pub const DIGEST_LEN: usize = 32;
+const HEADER_LEN: usize = MAGIC.len() + 4 + DIGEST_LEN + 8;
+
+/// An error while reading a verifier key or verifying a proof.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Error {
+    /// The file doesn't start with [`MAGIC`].
+    InvalidMagic,
+    /// The file has a version other than [`VERSION`].
+    UnsupportedVersion(u32),
+    /// The file is shorter than its header says.
+    Truncated,
+    /// The verifier data couldn't be serialized or deserialized.
+    Serialization,
+    /// The digest of the verifier data doesn't match the one in the header,
+    /// or the circuit it was computed from.
+    DigestMismatch,
+    /// The proof doesn't verify against the verifier key.
+    InvalidProof(String),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::InvalidMagic => write!(f, "not a verifier key file"),
+            Error::UnsupportedVersion(version) => {
+                write!(f, "unsupported verifier key version {version}")
+            }
+            Error::Truncated => write!(f, "truncated verifier key file"),
+            Error::Serialization => write!(f, "invalid verifier data"),
+            Error::DigestMismatch => write!(f, "verifier data doesn't match its digest"),
+            Error::InvalidProof(e) => write!(f, "invalid proof: {e}"),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+/// Returns the digest of the circuit with the given verifier data, i.e. the
+/// little-endian bytes of its `circuit_digest`, which identifies it.
+pub fn circuit_digest(data: &VerifierData) -> [u8; DIGEST_LEN] {
+    let mut digest = [0; DIGEST_LEN];
+    for (chunk, f) in digest
+        .chunks_exact_mut(8)
+        .zip(data.verifier_only.circuit_digest.elements)
+    {
+        chunk.copy_from_slice(&f.to_canonical_u64().to_le_bytes());
+    }
+    digest
+}
+
+/// Recomputes the `circuit_digest` of a circuit from the rest of its verifier
+/// data, as `CircuitBuilder::build` does, assuming that the circuit has no
+/// domain separator, like the block circuit.
+fn recompute_circuit_digest(data: &VerifierData) -> HashOut<F> {
+    let mut parts = data.verifier_only.constants_sigmas_cap.flatten();
+    parts.extend(PoseidonHash::hash_pad(&[]).elements);
+    parts.push(F::from_canonical_usize(data.common.degree_bits()));
+    PoseidonHash::hash_no_pad(&parts)
+}
+
+/// A block proof, serialized in the same format as the ones output by the
+/// `leader`.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct BlockProof {
+    /// Associated block height.
+    pub b_height: u64,
+    /// Underlying plonky2 proof.
+    pub intern: ProofWithPublicInputs,
+}
+
+/// The verifier data of the final block circuit.
+#[derive(Debug)]
+pub struct VerifierKey {
+    data: VerifierData,
+}
+
+impl VerifierKey {
+    pub const fn new(data: VerifierData) -> Self {
+        Self { data }
+    }
+
+    pub const fn data(&self) -> &VerifierData {
+        &self.data
+    }
+
+    /// Returns the digest of the circuit, which identifies it.
+    pub fn digest(&self) -> [u8; DIGEST_LEN] {
+        circuit_digest(&self.data)
+    }
+
+    /// Serializes the verifier key in the verifier key file format.
+    pub fn to_bytes(&self) -> Result<Vec<u8>, Error> {
+        let data = self
+            .data
+            .to_bytes(&DefaultGateSerializer)
+            .map_err(|_| Error::Serialization)?;
+
+        let mut bytes = Vec::with_capacity(HEADER_LEN + data.len());
+        bytes.extend_from_slice(&MAGIC);
+        bytes.extend_from_slice(&VERSION.to_le_bytes());
+        bytes.extend_from_slice(&self.digest());
+        bytes.extend_from_slice(&(data.len() as u64).to_le_bytes());
+        bytes.extend_from_slice(&data);
+        Ok(bytes)
+    }
+
+    /// Deserializes a verifier key file, checking that the verifier data
+    /// matches the digest in its header, and that this digest is the one of
+    /// the circuit described by the verifier data.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, Error> {
+        let (magic, rest) = bytes
+            .split_at_checked(MAGIC.len())
+            .ok_or(Error::Truncated)?;
+        if magic != MAGIC {
+            return Err(Error::InvalidMagic);
+        }
+        let (version, rest) = rest.split_first_chunk::<4>().ok_or(Error::Truncated)?;
+        let version = u32::from_le_bytes(*version);
+        if version != VERSION {
+            return Err(Error::UnsupportedVersion(version));
+        }
+        let (digest, rest) = rest
+            .split_first_chunk::<DIGEST_LEN>()
+            .ok_or(Error::Truncated)?;
+        let (len, rest) = rest.split_first_chunk::<8>().ok_or(Error::Truncated)?;
+        let len = usize::try_from(u64::from_le_bytes(*len)).map_err(|_| Error::Truncated)?;
+        let data = rest.get(..len).ok_or(Error::Truncated)?;
+
+        let key = Self::new(
+            VerifierData::from_bytes(data.to_vec(), &DefaultGateSerializer)
+                .map_err(|_| Error::Serialization)?,
+        );
+        if key.digest() != *digest
+            || key.data.verifier_only.circuit_digest != recompute_circuit_digest(&key.data)
+        {
+            return Err(Error::DigestMismatch);
+        }
+        Ok(key)
+    }
+
+    /// Verifies a block proof, along with the verifier data it carries in its
+    /// public inputs.
+    pub fn verify(&self, proof: &ProofWithPublicInputs) -> Result<(), Error> {
+        self.data
+            .verify(proof.clone())
+            .and_then(|()| {
+                check_cyclic_proof_verifier_data(proof, &self.data.verifier_only, &self.data.common)
+            })
+            .map_err(|e| Error::InvalidProof(e.to_string()))
+    }
+
+    /// Verifies a [`BlockProof`].
+    pub fn verify_block_proof(&self, block_proof: &BlockProof) -> Result<(), Error> {
+        self.verify(&block_proof.intern)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use plonky2::field::types::Field as _;
+    use plonky2::iop::witness::{PartialWitness, WitnessWrite as _};
+    use plonky2::plonk::circuit_builder::CircuitBuilder;
+    use plonky2::plonk::circuit_data::CircuitConfig;
+
+    use super::*;
+
+    /// Proves a small circuit which, like the block circuit, exposes its
+    /// verifier data in its public inputs.
+    fn key_and_proof() -> (VerifierKey, ProofWithPublicInputs) {
+        let mut builder = CircuitBuilder::<F, D>::new(CircuitConfig::standard_recursion_config());
+        let x = builder.add_virtual_public_input();
+        let x_squared = builder.square(x);
+        builder.register_public_input(x_squared);
+        let verifier_data_target = builder.add_verifier_data_public_inputs();
+        let circuit = builder.build::<C>();
+
+        let mut witness = PartialWitness::new();
+        witness.set_target(x, F::from_canonical_u64(3)).unwrap();
+        witness
+            .set_verifier_data_target(&verifier_data_target, &circuit.verifier_only)
+            .unwrap();
+        let proof = circuit.prove(witness).unwrap();
+
+        (VerifierKey::new(circuit.verifier_data()), proof)
+    }
+
+    #[test]
+    fn exported_key_verifies_proofs() {
+        let (key, proof) = key_and_proof();
+        let bytes = key.to_bytes().unwrap();
+
+        let key = VerifierKey::from_bytes(&bytes).unwrap();
+        assert_eq!(
+            &bytes[MAGIC.len() + 4..MAGIC.len() + 4 + DIGEST_LEN],
+            key.digest()
+        );
+        key.verify(&proof).unwrap();
+        key.verify_block_proof(&BlockProof {
+            b_height: 1,
+            intern: proof,
+        })
+        .unwrap();
+    }
+
+    #[test]
+    fn invalid_proofs_do_not_verify() {
+        let (key, mut proof) = key_and_proof();
+
+        proof.public_inputs[1] = F::from_canonical_u64(10);
+        assert!(matches!(key.verify(&proof), Err(Error::InvalidProof(_))));
+    }
+
+    #[test]
+    fn invalid_keys_are_rejected() {
+        let (key, _) = key_and_proof();
+        let bytes = key.to_bytes().unwrap();
+
+        let mut invalid_magic = bytes.clone();
+        invalid_magic[0] = 0;
+        assert_eq!(
+            VerifierKey::from_bytes(&invalid_magic).unwrap_err(),
+            Error::InvalidMagic
+        );
+
+        let mut invalid_version = bytes.clone();
+        invalid_version[MAGIC.len()] = 2;
+        assert_eq!(
+            VerifierKey::from_bytes(&invalid_version).unwrap_err(),
+            Error::UnsupportedVersion(2)
+        );
+
+        let mut invalid_digest = bytes.clone();
+        invalid_digest[MAGIC.len() + 4] ^= 1;
+        assert_eq!(
+            VerifierKey::from_bytes(&invalid_digest).unwrap_err(),
+            Error::DigestMismatch
+        );
+
+        assert_eq!(
+            VerifierKey::from_bytes(&bytes[..bytes.len() - 1]).unwrap_err(),
+            Error::Truncated
+        );
+    }
+
+    #[test]
+    fn keys_with_forged_digests_are_rejected() {
+        let (key, _) = key_and_proof();
+        let mut data = key.data;
+        data.verifier_only.circuit_digest.elements[0] += F::ONE;
+        // The header digest matches the forged one.
+        let bytes = VerifierKey::new(data).to_bytes().unwrap();
+
+        assert_eq!(
+            VerifierKey::from_bytes(&bytes).unwrap_err(),
+            Error::DigestMismatch
+        );
+    }
+}
@@ -22,6 +22,7 @@ mod leader {
     pub mod cli;
     pub mod client;
     pub mod http;
+    pub mod jobs;
     pub mod stdio;
 }
 
@@ -41,6 +42,9 @@ async fn main() -> Result<()> {
         return zero::prover_state::persistence::delete_all();
     }
 
+    args.metrics_config.start().await?;
+    args.profiling_config.start()?;
+
     let mut light_proof_routing_key = TASK_IPC_ROUTING_KEY.to_string();
     let mut heavy_proof_routing_key = TASK_IPC_ROUTING_KEY.to_string();
     if args.worker_run_mode == cli::WorkerRunMode::Affinity {
@@ -110,10 +114,17 @@ async fn main() -> Result<()> {
             end_block,
             backoff,
             max_retries,
+            rpc_cache,
+            two_to_one,
         } => {
             // Construct the provider.
             let previous_proof = get_previous_proof(previous_proof)?;
-            let retry_provider = build_http_retry_provider(rpc_url.clone(), backoff, max_retries)?;
+            let retry_provider = build_http_retry_provider(
+                rpc_url.clone(),
+                backoff,
+                max_retries,
+                rpc_cache.open()?,
+            )?;
             let cached_provider = Arc::new(zero::provider::CachedProvider::new(
                 retry_provider,
                 rpc_type,
@@ -138,6 +149,7 @@ async fn main() -> Result<()> {
                     checkpoint_block_number,
                     previous_proof,
                     prover_config,
+                    two_to_one,
                 },
             )
             .await?;
@@ -3,9 +3,11 @@ use std::path::PathBuf;
 use alloy::eips::BlockId;
 use alloy::transports::http::reqwest::Url;
 use clap::{Parser, Subcommand, ValueEnum, ValueHint};
+use zero::metrics::CliMetricsConfig;
+use zero::profiling::CliProfilingConfig;
 use zero::prover::cli::CliProverConfig;
 use zero::prover_state::cli::CliProverStateConfig;
-use zero::rpc::RpcType;
+use zero::rpc::{cache::CliResponseCacheConfig, RpcType};
 
 const WORKER_HELP_HEADING: &str = "Worker Config options";
 
@@ -27,6 +29,14 @@ pub(crate) struct Cli {
     #[clap(flatten)]
     pub(crate) prover_state_config: CliProverStateConfig,
 
+    #[clap(flatten)]
+    pub(crate) metrics_config: CliMetricsConfig,
+
+    // Note this is only relevant for the leader when running in in-memory
+    // mode.
+    #[clap(flatten)]
+    pub(crate) profiling_config: CliProfilingConfig,
+
     // Mode to use for worker for setup (affinity or default)
     #[arg(long = "worker-run-mode", help_heading = WORKER_HELP_HEADING, value_enum, default_value = "default")]
     pub(crate) worker_run_mode: WorkerRunMode,
@@ -94,13 +104,27 @@ pub(crate) enum Command {
         /// The maximum number of retries
         #[arg(long, env = "ZERO_BIN_MAX_RETRIES", default_value_t = 0)]
         max_retries: u32,
+        #[clap(flatten)]
+        rpc_cache: CliResponseCacheConfig,
+        /// Prove the blocks independently of each other, and aggregate them
+        /// into a single two-to-one block aggregation proof, instead of
+        /// chaining their proofs. Requires an end block.
+        #[arg(
+            long,
+            env = "ZERO_BIN_TWO_TO_ONE",
+            requires = "end_block",
+            conflicts_with_all = ["checkpoint_block", "previous_proof"]
+        )]
+        two_to_one: bool,
     },
-    /// Reads input from HTTP and writes output to a directory.
+    /// Reads input from HTTP and writes output to a directory, either
+    /// directly or through a queue of proving jobs.
     Http {
         /// The port on which to listen.
         #[arg(short, long, env = "ZERO_BIN_PORT", default_value_t = 8080)]
         port: u16,
-        /// The directory to which output should be written.
+        /// The directory to which output should be written. Proving jobs are
+        /// persisted in its `jobs` subdirectory.
         #[arg(short, long, env="ZERO_BIN_OUTPUT_DIR", value_hint = ValueHint::DirPath)]
         output_dir: PathBuf,
     },
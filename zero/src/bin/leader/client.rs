@@ -7,6 +7,7 @@ use anyhow::{anyhow, Result};
 use tokio::sync::mpsc;
 use tracing::info;
 use zero::block_interval::{BlockInterval, BlockIntervalStream};
+use zero::journal::{JournalInterval, ProofJournal};
 use zero::pre_checks::check_previous_proof_and_checkpoint;
 use zero::proof_types::GeneratedBlockProof;
 use zero::prover::{self, BlockProverInput, ProverConfig};
@@ -20,6 +21,9 @@ pub struct LeaderConfig {
     pub checkpoint_block_number: u64,
     pub previous_proof: Option<GeneratedBlockProof>,
     pub prover_config: ProverConfig,
+    /// Whether to prove the blocks independently and aggregate them with
+    /// two-to-one block aggregation, instead of chaining their proofs.
+    pub two_to_one: bool,
 }
 
 /// The main function for the client.
@@ -27,7 +31,7 @@ pub(crate) async fn client_main<ProviderT, TransportT>(
     proof_runtime: Arc<ProofRuntime>,
     cached_provider: Arc<CachedProvider<ProviderT, TransportT>>,
     block_time: u64,
-    block_interval: BlockInterval,
+    mut block_interval: BlockInterval,
     mut leader_config: LeaderConfig,
 ) -> Result<()>
 where
@@ -37,11 +41,61 @@ where
     use futures::StreamExt;
 
     let test_only = leader_config.prover_config.test_only;
+    let two_to_one = leader_config.two_to_one;
 
-    if !test_only {
-        // For actual proof runs, perform a sanity check on the provided inputs.
-        check_previous_proof_and_checkpoint(
+    if two_to_one && matches!(block_interval, BlockInterval::FollowFrom { .. }) {
+        anyhow::bail!("two-to-one block aggregation requires an end block");
+    }
+
+    // For actual proof runs, keep a journal of the generated proofs, and resume
+    // from it if a previous run of the same interval was interrupted. Blocks
+    // proven independently are only aggregated at the end, so there is nothing
+    // to resume from in two-to-one mode.
+    let journal = match test_only || two_to_one {
+        true => None,
+        false => {
+            let previous_block_hash = match &leader_config.previous_proof {
+                Some(proof) => Some(proof.public_values()?.block_hashes.cur_hash),
+                None => None,
+            };
+            let journal = ProofJournal::open(
+                &leader_config.prover_config.proof_output_dir,
+                JournalInterval {
+                    checkpoint_block_number: leader_config.checkpoint_block_number,
+                    start_block: block_interval.get_start_block()?,
+                    end_block: block_interval.get_end_block(),
+                },
+                previous_block_hash,
+            )?;
+            if let Some(proof) = journal.latest_proof()? {
+                match block_interval.skip_through(proof.b_height) {
+                    Some(remaining) => {
+                        info!("Resuming interval from block {}", proof.b_height + 1);
+                        block_interval = remaining;
+                    }
+                    None => {
+                        info!("All the blocks of the interval have already been proven");
+                        proof_runtime.light_proof.close().await?;
+                        proof_runtime.heavy_proof.close().await?;
+                        return Ok(());
+                    }
+                }
+                leader_config.previous_proof = Some(proof);
+            }
+            Some(Arc::new(journal))
+        }
+    };
+
+    if !test_only && !two_to_one {
+        // For actual proof runs, perform a sanity check on the provided inputs,
+        // including the proof resumed from the journal.
+        let checkpoint = rpc::fetch_checkpoint(
+            cached_provider.clone(),
             leader_config.checkpoint_block_number,
+        )
+        .await?;
+        check_previous_proof_and_checkpoint(
+            &checkpoint,
             &leader_config.previous_proof,
             block_interval.get_start_block()?,
         )?;
@@ -54,12 +108,20 @@ where
 
     // Run proving task
     let proof_runtime_ = proof_runtime.clone();
-    let proving_task = tokio::spawn(prover::prove(
-        block_rx,
-        proof_runtime_,
-        leader_config.previous_proof.take(),
-        Arc::new(leader_config.prover_config),
-    ));
+    let proving_task = match two_to_one {
+        true => tokio::spawn(prover::prove_two_to_one(
+            block_rx,
+            proof_runtime_,
+            Arc::new(leader_config.prover_config),
+        )),
+        false => tokio::spawn(prover::prove(
+            block_rx,
+            proof_runtime_,
+            leader_config.previous_proof.take(),
+            Arc::new(leader_config.prover_config),
+            journal,
+        )),
+    };
 
     // Create block interval stream. Could be bounded or unbounded.
     let mut block_interval_stream: BlockIntervalStream = match block_interval {
@@ -76,13 +138,16 @@ where
     while let Some(block_interval_elem) = block_interval_stream.next().await {
         let (block_num, is_last_block) = block_interval_elem?;
         let block_id = BlockId::Number(BlockNumberOrTag::Number(block_num));
+        // Blocks proven independently are each checked against their parent
+        // block's state.
+        let checkpoint_block_number = match two_to_one {
+            true => block_num.saturating_sub(1),
+            false => leader_config.checkpoint_block_number,
+        };
         // Get prover input for particular block.
-        let block_prover_input = rpc::block_prover_input(
-            cached_provider.clone(),
-            block_id,
-            leader_config.checkpoint_block_number,
-        )
-        .await?;
+        let block_prover_input =
+            rpc::block_prover_input(cached_provider.clone(), block_id, checkpoint_block_number)
+                .await?;
         block_tx
             .send((block_prover_input, is_last_block))
             .await
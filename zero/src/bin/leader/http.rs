@@ -1,17 +1,41 @@
+use std::io::Write as _;
 use std::{net::SocketAddr, path::PathBuf, sync::Arc};
 
 use alloy::primitives::U256;
 use anyhow::{bail, Result};
-use axum::{http::StatusCode, routing::post, Json, Router};
+use axum::{
+    body::Body,
+    extract::Path,
+    http::{header, StatusCode},
+    response::{IntoResponse, Response},
+    routing::{get, post},
+    Json, Router,
+};
+use futures::StreamExt as _;
 use serde::{Deserialize, Serialize};
-use serde_json::to_writer;
+use tokio::io::AsyncReadExt as _;
 use tracing::{debug, error, info};
-use zero::proof_types::GeneratedBlockProof;
+use zero::proof_format::{self, serialize_proofs, ProofFormat};
+use zero::proof_types::{GeneratedBlockProof, GeneratedProof};
 use zero::prover::{BlockProverInput, ProverConfig};
+use zero::prover_state::p_verifier;
 
+use crate::jobs::{self, JobId, JobStatus, JobStore};
 use crate::ProofRuntime;
 
+/// Size of the chunks in which proofs are streamed.
+const PROOF_CHUNK_SIZE: usize = 64 * 1024;
+
 /// The main function for the HTTP mode.
+///
+/// Besides the blocking `POST /prove` endpoint, which writes proofs to
+/// `output_dir`, blocks can be proven asynchronously through:
+/// - `POST /jobs`, which queues a proving job and returns it, with its id;
+/// - `GET /jobs/{id}`, which returns the status of a job;
+/// - `GET /jobs/{id}/proof`, which returns the proof of a job once done, in
+///   the `--proof-format` of the leader.
+///
+/// Jobs are persisted under `output_dir/jobs`, and resumed on restart.
 pub(crate) async fn http_main(
     proof_runtime: Arc<ProofRuntime>,
     port: u16,
@@ -21,10 +45,24 @@ pub(crate) async fn http_main(
     let addr = SocketAddr::from(([0, 0, 0, 0], port));
     debug!("listening on {}", addr);
 
-    let app = Router::new().route(
-        "/prove",
-        post(move |body| prove(body, proof_runtime, output_dir.clone(), prover_config)),
-    );
+    let (job_store, job_queue) = JobStore::open(output_dir.join("jobs"))?;
+    tokio::spawn(jobs::run_jobs(
+        job_store.clone(),
+        job_queue,
+        proof_runtime.clone(),
+        prover_config.clone(),
+    ));
+
+    let submit_store = job_store.clone();
+    let status_store = job_store.clone();
+    let app = Router::new()
+        .route(
+            "/prove",
+            post(move |body| prove(body, proof_runtime, output_dir.clone(), prover_config)),
+        )
+        .route("/jobs", post(move |body| submit_job(body, submit_store)))
+        .route("/jobs/:id", get(move |id| job_status(id, status_store)))
+        .route("/jobs/:id/proof", get(move |id| job_proof(id, job_store)));
     let listener = tokio::net::TcpListener::bind(&addr).await?;
     Ok(axum::serve(listener, app).await?)
 }
@@ -36,14 +74,19 @@ fn write_to_file(
     output_dir: PathBuf,
     block_number: U256,
     generated_block_proof: &GeneratedBlockProof,
+    proof_format: ProofFormat,
 ) -> Result<PathBuf> {
-    let file_name = format!("proof-{}.json", block_number);
+    let extension = match proof_format {
+        ProofFormat::Json => "json",
+        ProofFormat::Binary => "zkproof",
+    };
+    let file_name = format!("proof-{}.{}", block_number, extension);
     let fully_qualified_file_name = output_dir.join(file_name);
     let file = std::fs::File::create(fully_qualified_file_name.clone());
 
     match file {
-        Ok(file) => {
-            to_writer(file, &generated_block_proof)?;
+        Ok(mut file) => {
+            file.write_all(&serialize_block_proof(generated_block_proof, proof_format)?)?;
             Ok(fully_qualified_file_name)
         }
         Err(e) => {
@@ -52,44 +95,62 @@ fn write_to_file(
     }
 }
 
+/// Serializes a block proof in the given format, as a single JSON proof or a
+/// binary container.
+pub(crate) fn serialize_block_proof(
+    generated_block_proof: &GeneratedBlockProof,
+    proof_format: ProofFormat,
+) -> Result<Vec<u8>> {
+    match proof_format {
+        ProofFormat::Json => Ok(serde_json::to_vec(generated_block_proof)?),
+        ProofFormat::Binary => serialize_proofs(
+            &[GeneratedProof::Block(generated_block_proof.clone())],
+            proof_format,
+            p_verifier(),
+        ),
+    }
+}
+
 #[derive(Serialize, Deserialize, Debug)]
-struct HttpProverInput {
-    prover_input: BlockProverInput,
-    previous: Option<GeneratedBlockProof>,
+pub(crate) struct HttpProverInput {
+    pub(crate) prover_input: BlockProverInput,
+    pub(crate) previous: Option<GeneratedBlockProof>,
 }
 
-async fn prove(
-    Json(payload): Json<HttpProverInput>,
+/// Proves a single block, using the previous proof if provided.
+pub(crate) async fn prove_block(
+    payload: HttpProverInput,
     proof_runtime: Arc<ProofRuntime>,
-    output_dir: PathBuf,
     prover_config: Arc<ProverConfig>,
-) -> StatusCode {
-    debug!("Received payload: {:#?}", payload);
-
-    let block_number = payload.prover_input.get_block_number();
-
-    let proof_res = if prover_config.test_only {
+) -> Result<GeneratedBlockProof> {
+    let previous = payload.previous.map(futures::future::ok);
+    if prover_config.test_only {
         payload
             .prover_input
-            .prove_test(
-                proof_runtime,
-                payload.previous.map(futures::future::ok),
-                prover_config,
-            )
+            .prove_test(proof_runtime, previous, prover_config)
             .await
     } else {
         payload
             .prover_input
-            .prove(
-                proof_runtime,
-                payload.previous.map(futures::future::ok),
-                prover_config,
-            )
+            .prove(proof_runtime, previous, prover_config)
             .await
-    };
+    }
+}
+
+async fn prove(
+    Json(payload): Json<HttpProverInput>,
+    proof_runtime: Arc<ProofRuntime>,
+    output_dir: PathBuf,
+    prover_config: Arc<ProverConfig>,
+) -> StatusCode {
+    debug!("Received payload: {:#?}", payload);
 
+    let block_number = payload.prover_input.get_block_number();
+    let proof_format = prover_config.proof_format;
+
+    let proof_res = prove_block(payload, proof_runtime, prover_config).await;
     match proof_res {
-        Ok(b_proof) => match write_to_file(output_dir, block_number, &b_proof) {
+        Ok(b_proof) => match write_to_file(output_dir, block_number, &b_proof, proof_format) {
             Ok(file) => {
                 info!("Successfully wrote proof to {}", file.display());
                 StatusCode::OK
@@ -105,3 +166,70 @@ async fn prove(
         }
     }
 }
+
+async fn submit_job(Json(payload): Json<HttpProverInput>, store: Arc<JobStore>) -> Response {
+    debug!("Received job payload: {:#?}", payload);
+
+    match store.submit(payload).await {
+        Ok(job) => {
+            info!("Queued job {} for block {}", job.id, job.block_number);
+            (StatusCode::ACCEPTED, Json(job)).into_response()
+        }
+        Err(e) => {
+            error!("Error while queuing job: {e:#}");
+            StatusCode::INTERNAL_SERVER_ERROR.into_response()
+        }
+    }
+}
+
+async fn job_status(Path(id): Path<JobId>, store: Arc<JobStore>) -> Response {
+    match store.get(id) {
+        Some(job) => Json(job).into_response(),
+        None => StatusCode::NOT_FOUND.into_response(),
+    }
+}
+
+async fn job_proof(Path(id): Path<JobId>, store: Arc<JobStore>) -> Response {
+    match store.get(id).map(|job| job.status) {
+        Some(JobStatus::Done) => {}
+        Some(_) => return (StatusCode::CONFLICT, "proof is not available").into_response(),
+        None => return StatusCode::NOT_FOUND.into_response(),
+    }
+
+    let mut file = match tokio::fs::File::open(store.proof_path(id)).await {
+        Ok(file) => file,
+        Err(e) => {
+            error!("Error while opening the proof of job {id}: {e}");
+            return StatusCode::INTERNAL_SERVER_ERROR.into_response();
+        }
+    };
+
+    // The proof is in the format the leader was running with when the job was
+    // done, which may not be the current one.
+    let mut first_chunk = Vec::with_capacity(PROOF_CHUNK_SIZE);
+    if let Err(e) = file.read_buf(&mut first_chunk).await {
+        error!("Error while reading the proof of job {id}: {e}");
+        return StatusCode::INTERNAL_SERVER_ERROR.into_response();
+    }
+    let content_type = if first_chunk.starts_with(&proof_format::MAGIC) {
+        "application/octet-stream"
+    } else {
+        "application/json"
+    };
+
+    let rest = futures::stream::try_unfold(file, |mut file| async move {
+        let mut chunk = Vec::with_capacity(PROOF_CHUNK_SIZE);
+        match file.read_buf(&mut chunk).await? {
+            0 => Ok::<_, std::io::Error>(None),
+            _ => Ok(Some((chunk, file))),
+        }
+    });
+
+    let stream = futures::stream::once(async { Ok::<_, std::io::Error>(first_chunk) }).chain(rest);
+
+    (
+        [(header::CONTENT_TYPE, content_type)],
+        Body::from_stream(stream),
+    )
+        .into_response()
+}
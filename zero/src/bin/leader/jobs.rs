@@ -0,0 +1,356 @@
+use std::collections::BTreeMap;
+use std::io::Write as _;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+
+use alloy::primitives::U256;
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use tokio::sync::mpsc;
+use tracing::{error, info, warn};
+use zero::proof_types::GeneratedBlockProof;
+use zero::prover::ProverConfig;
+
+use crate::http::{prove_block, serialize_block_proof, HttpProverInput};
+use crate::ProofRuntime;
+
+const JOB_FILE_NAME: &str = "job.json";
+const INPUT_FILE_NAME: &str = "input.json";
+/// The proof is written in the `--proof-format` of the leader, so the file has
+/// no extension.
+const PROOF_FILE_NAME: &str = "proof";
+
+pub(crate) type JobId = u64;
+
+/// The state of a proving job.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "status", rename_all = "snake_case")]
+pub(crate) enum JobStatus {
+    /// Waiting for the jobs submitted before it to be proven.
+    Queued,
+    /// Being proven.
+    Running,
+    /// Proving failed, with the given error.
+    Failed { error: String },
+    /// The proof is available.
+    Done,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub(crate) struct Job {
+    pub(crate) id: JobId,
+    pub(crate) block_number: U256,
+    #[serde(flatten)]
+    pub(crate) status: JobStatus,
+}
+
+/// Persistent queue of proving jobs.
+///
+/// Each job is stored in its own `<jobs_dir>/<id>` directory, which holds its
+/// state, its input until it is proven, and its proof once done. On startup,
+/// jobs which were queued or running when the leader stopped are queued again.
+#[derive(Debug)]
+pub(crate) struct JobStore {
+    jobs_dir: PathBuf,
+    jobs: Mutex<BTreeMap<JobId, Job>>,
+    /// The id of the next submitted job.
+    next_id: AtomicU64,
+    queue: mpsc::UnboundedSender<JobId>,
+}
+
+impl JobStore {
+    /// Loads the jobs in `jobs_dir`, creating it if needed.
+    ///
+    /// Returns the store and the receiving end of its queue, to be passed to
+    /// [`run_jobs`].
+    pub(crate) fn open(jobs_dir: PathBuf) -> Result<(Arc<Self>, mpsc::UnboundedReceiver<JobId>)> {
+        std::fs::create_dir_all(&jobs_dir)?;
+
+        let mut jobs = BTreeMap::new();
+        for entry in std::fs::read_dir(&jobs_dir)? {
+            let path = entry?.path().join(JOB_FILE_NAME);
+            match read_json::<Job>(&path) {
+                Ok(job) => {
+                    jobs.insert(job.id, job);
+                }
+                Err(e) => warn!("Skipping invalid job at {}: {e:#}", path.display()),
+            }
+        }
+
+        let (queue, queue_rx) = mpsc::unbounded_channel();
+        let store = Arc::new(Self {
+            jobs_dir,
+            jobs: Mutex::new(BTreeMap::new()),
+            next_id: AtomicU64::new(jobs.keys().next_back().map_or(0, |id| id + 1)),
+            queue,
+        });
+
+        for (id, mut job) in jobs {
+            if matches!(job.status, JobStatus::Queued | JobStatus::Running) {
+                info!("Resuming job {id} for block {}", job.block_number);
+                job.status = JobStatus::Queued;
+                store.write_job(&job)?;
+                store.queue.send(id)?;
+            }
+            store.lock().insert(id, job);
+        }
+
+        Ok((store, queue_rx))
+    }
+
+    /// Persists and enqueues a new job.
+    ///
+    /// The job is only visible once its files are written, which happens
+    /// outside of the lock, so that the other requests aren't held up.
+    pub(crate) async fn submit(self: &Arc<Self>, input: HttpProverInput) -> Result<Job> {
+        let id = self.next_id.fetch_add(1, Ordering::Relaxed);
+        let job = Job {
+            id,
+            block_number: input.prover_input.get_block_number(),
+            status: JobStatus::Queued,
+        };
+
+        let store = self.clone();
+        let job_ = job.clone();
+        tokio::task::spawn_blocking(move || {
+            std::fs::create_dir_all(store.job_dir(id))?;
+            write_json(&store.input_path(id), &input)?;
+            store.write_job(&job_)
+        })
+        .await??;
+        self.lock().insert(id, job.clone());
+        self.queue.send(id)?;
+
+        Ok(job)
+    }
+
+    pub(crate) fn get(&self, id: JobId) -> Option<Job> {
+        self.lock().get(&id).cloned()
+    }
+
+    /// Returns the path of the proof of a job, which exists once it is done.
+    pub(crate) fn proof_path(&self, id: JobId) -> PathBuf {
+        self.job_dir(id).join(PROOF_FILE_NAME)
+    }
+
+    fn input_path(&self, id: JobId) -> PathBuf {
+        self.job_dir(id).join(INPUT_FILE_NAME)
+    }
+
+    /// Updates the status of a job, and persists it outside of the lock.
+    ///
+    /// Only the job runner updates the status of a job, so the writes of
+    /// successive updates can't be reordered.
+    async fn set_status(self: &Arc<Self>, id: JobId, status: JobStatus) -> Result<()> {
+        let job = {
+            let mut jobs = self.lock();
+            let job = jobs.get_mut(&id).context("unknown job")?;
+            job.status = status;
+            job.clone()
+        };
+
+        let store = self.clone();
+        tokio::task::spawn_blocking(move || store.write_job(&job)).await?
+    }
+
+    fn job_dir(&self, id: JobId) -> PathBuf {
+        self.jobs_dir.join(id.to_string())
+    }
+
+    fn write_job(&self, job: &Job) -> Result<()> {
+        write_json(&self.job_dir(job.id).join(JOB_FILE_NAME), job)
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, BTreeMap<JobId, Job>> {
+        self.jobs.lock().expect("poisoned jobs lock")
+    }
+}
+
+/// Proves the queued jobs, one at a time, in submission order.
+pub(crate) async fn run_jobs(
+    store: Arc<JobStore>,
+    mut queue: mpsc::UnboundedReceiver<JobId>,
+    proof_runtime: Arc<ProofRuntime>,
+    prover_config: Arc<ProverConfig>,
+) {
+    while let Some(id) = queue.recv().await {
+        let status = match run_job(&store, id, proof_runtime.clone(), prover_config.clone()).await {
+            Ok(()) => {
+                info!("Job {id} done");
+                JobStatus::Done
+            }
+            Err(e) => {
+                error!("Job {id} failed: {e:#}");
+                JobStatus::Failed {
+                    error: format!("{e:#}"),
+                }
+            }
+        };
+
+        let is_done = status == JobStatus::Done;
+        if let Err(e) = store.set_status(id, status).await {
+            error!("Failed to update the status of job {id}: {e:#}");
+            continue;
+        }
+
+        // The input is only needed to resume the job, and can be large.
+        if is_done {
+            if let Err(e) = tokio::fs::remove_file(store.input_path(id)).await {
+                warn!("Failed to remove the input of job {id}: {e}");
+            }
+        }
+    }
+}
+
+async fn run_job(
+    store: &Arc<JobStore>,
+    id: JobId,
+    proof_runtime: Arc<ProofRuntime>,
+    prover_config: Arc<ProverConfig>,
+) -> Result<()> {
+    // The leader may have stopped after the proof was written, but before the
+    // job was marked as done.
+    if tokio::fs::try_exists(store.proof_path(id)).await? {
+        return Ok(());
+    }
+
+    let input_path = store.input_path(id);
+    let input: HttpProverInput =
+        tokio::task::spawn_blocking(move || read_json(&input_path)).await??;
+    store.set_status(id, JobStatus::Running).await?;
+    let proof_format = prover_config.proof_format;
+    let proof: GeneratedBlockProof = prove_block(input, proof_runtime, prover_config).await?;
+
+    let proof_path = store.proof_path(id);
+    tokio::task::spawn_blocking(move || {
+        write_atomically(&proof_path, &serialize_block_proof(&proof, proof_format)?)
+    })
+    .await?
+}
+
+fn read_json<T: for<'de> Deserialize<'de>>(path: &Path) -> Result<T> {
+    let file = std::fs::File::open(path)?;
+    let des = &mut serde_json::Deserializer::from_reader(std::io::BufReader::new(file));
+    Ok(serde_path_to_error::deserialize(des)?)
+}
+
+fn write_json<T: Serialize>(path: &Path, value: &T) -> Result<()> {
+    write_atomically(path, &serde_json::to_vec(value)?)
+}
+
+/// Writes `bytes` to a temporary file first, so that a crash never leaves a
+/// partially written file at `path`.
+fn write_atomically(path: &Path, bytes: &[u8]) -> Result<()> {
+    let mut tmp_path = path.as_os_str().to_owned();
+    tmp_path.push(".tmp");
+    let mut file = std::fs::File::create(&tmp_path)?;
+    file.write_all(bytes)?;
+    file.sync_all()?;
+    std::fs::rename(&tmp_path, path)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use zero::prover::BlockProverInput;
+
+    use super::*;
+
+    /// Returns a new temporary directory.
+    fn temp_dir() -> PathBuf {
+        static COUNTER: AtomicU64 = AtomicU64::new(0);
+        std::env::temp_dir().join(format!(
+            "leader_jobs_{}_{}",
+            std::process::id(),
+            COUNTER.fetch_add(1, Ordering::Relaxed)
+        ))
+    }
+
+    /// Returns inputs for blocks 3 to 6.
+    fn inputs() -> Vec<HttpProverInput> {
+        serde_json::from_str::<Vec<BlockProverInput>>(include_str!(
+            "../../../../artifacts/witness_b3_b6.json"
+        ))
+        .unwrap()
+        .into_iter()
+        .map(|prover_input| HttpProverInput {
+            prover_input,
+            previous: None,
+        })
+        .collect()
+    }
+
+    #[tokio::test]
+    async fn submit() {
+        let (store, mut queue) = JobStore::open(temp_dir()).unwrap();
+
+        for (id, input) in (0..).zip(inputs()) {
+            let block_number = input.prover_input.get_block_number();
+            let job = store.submit(input).await.unwrap();
+            assert_eq!(job.id, id);
+            assert_eq!(job.block_number, block_number);
+            assert_eq!(job.status, JobStatus::Queued);
+            assert_eq!(queue.recv().await, Some(id));
+
+            let stored = store.get(id).unwrap();
+            assert_eq!(stored.block_number, block_number);
+            assert_eq!(stored.status, JobStatus::Queued);
+            let input: HttpProverInput = read_json(&store.input_path(id)).unwrap();
+            assert_eq!(input.prover_input.get_block_number(), block_number);
+        }
+        assert!(store.get(4).is_none());
+    }
+
+    #[tokio::test]
+    async fn set_status() {
+        let (store, _queue) = JobStore::open(temp_dir()).unwrap();
+        let job = store.submit(inputs().remove(0)).await.unwrap();
+
+        let status = JobStatus::Failed {
+            error: String::from("error"),
+        };
+        store.set_status(job.id, status.clone()).await.unwrap();
+        assert_eq!(store.get(job.id).unwrap().status, status);
+        let stored: Job = read_json(&store.job_dir(job.id).join(JOB_FILE_NAME)).unwrap();
+        assert_eq!(stored.status, status);
+
+        assert!(store.set_status(job.id + 1, JobStatus::Done).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn open_resumes_unfinished_jobs() {
+        let dir = temp_dir();
+        let (store, _queue) = JobStore::open(dir.clone()).unwrap();
+        for input in inputs() {
+            store.submit(input).await.unwrap();
+        }
+        store.set_status(1, JobStatus::Running).await.unwrap();
+        store.set_status(2, JobStatus::Done).await.unwrap();
+        store
+            .set_status(
+                3,
+                JobStatus::Failed {
+                    error: String::from("error"),
+                },
+            )
+            .await
+            .unwrap();
+        drop(store);
+
+        let (store, mut queue) = JobStore::open(dir).unwrap();
+        assert_eq!(queue.recv().await, Some(0));
+        assert_eq!(queue.recv().await, Some(1));
+        assert!(queue.try_recv().is_err());
+        assert_eq!(store.get(1).unwrap().status, JobStatus::Queued);
+        assert_eq!(store.get(2).unwrap().status, JobStatus::Done);
+        assert!(matches!(
+            store.get(3).unwrap().status,
+            JobStatus::Failed { .. }
+        ));
+
+        // Ids keep increasing past the resumed jobs.
+        let job = store.submit(inputs().remove(0)).await.unwrap();
+        assert_eq!(job.id, 4);
+    }
+}
@@ -32,6 +32,7 @@ pub(crate) async fn stdio_main(
         proof_runtime_,
         previous,
         prover_config_,
+        None,
     ));
 
     let interval_len = block_prover_inputs.len();
@@ -0,0 +1,299 @@
+//! This binary is a debugging tool used to shrink the witness of a block which
+//! fails to be decoded or simulated, so that the failure can be reproduced and
+//! investigated on a much smaller input.
+//!
+//! As input, it uses a standard witness JSON file (same as `leader` in stdio
+//! mode). It runs the trace decoder and a simulation of the kernel on the
+//! chosen block, then repeatedly drops transactions and hashes out accounts
+//! and storage slots, keeping each reduction only if the block still fails
+//! with the same error. The minimized witness is written in the same format.
+//!
+//! Accounts and storage slots can only be hashed out of type 1 (MPT)
+//! witnesses: the witnesses of type 2 (SMT) chains, i.e. with the
+//! `cdk_erigon` feature, only get their transactions dropped.
+//!
+//! Example usage:
+//! ```
+//! RUST_LOG=info cargo run --release --bin minimize_witness -- \
+//!     ./artifacts/witness_b19807080.json -o ./artifacts/minimized_b19807080.json
+//! ```
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::panic::{catch_unwind, AssertUnwindSafe};
+use std::path::PathBuf;
+
+use anyhow::{bail, Context as _, Result};
+use clap::{Parser, ValueHint};
+use evm_arithmetization::prover::testing::simulate_execution;
+use keccak_hash::{keccak, H256};
+use trace_decoder::observer::DummyObserver;
+use trace_decoder::{BatchingStrategy, WireDisposition};
+use tracing::{info, warn};
+use zero::prover::{BlockProverInput, WIRE_DISPOSITION};
+
+/// This binary is a debugging tool used to shrink the witness of a failing
+/// block.
+///
+/// Usage:
+///
+/// `minimize_witness <OPTIONS> <INPUT> --output <OUTPUT>`
+///
+/// Transactions are dropped, and accounts and storage slots are hashed out of
+/// type 1 (MPT) witnesses. Type 2 (SMT) witnesses only get their transactions
+/// dropped.
+#[derive(Parser)]
+#[command(version = zero::version(), propagate_version = true)]
+pub(crate) struct Cli {
+    /// The witness JSON file containing the failing block.
+    #[arg(value_hint = ValueHint::FilePath)]
+    input: PathBuf,
+
+    /// Where to write the minimized witness JSON file.
+    #[arg(short, long, value_hint = ValueHint::FilePath)]
+    output: PathBuf,
+
+    /// The block to minimize. Defaults to the first failing block of the
+    /// input.
+    #[arg(short, long)]
+    block: Option<u64>,
+
+    /// Keep a reduction if the error contains this pattern. Defaults to
+    /// requiring exactly the error of the original block.
+    #[arg(short, long)]
+    error_pattern: Option<String>,
+
+    /// The number of transactions per batch.
+    #[arg(long, default_value_t = 1)]
+    batch_size: usize,
+}
+
+fn main() -> Result<()> {
+    zero::tracing::init();
+
+    let args = Cli::parse();
+
+    let inputs: Vec<BlockProverInput> = serde_json::from_slice(
+        &std::fs::read(&args.input)
+            .with_context(|| format!("couldn't read {}", args.input.display()))?,
+    )
+    .context("invalid witness")?;
+
+    let (input, error) = match args.block {
+        Some(block) => {
+            let Some(input) = inputs
+                .into_iter()
+                .find(|input| input.get_block_number() == block.into())
+            else {
+                bail!("block {block} is not in the input");
+            };
+            let Some(error) = failure(&input, args.batch_size) else {
+                bail!("block {block} doesn't fail");
+            };
+            (input, error)
+        }
+        None => {
+            let Some(found) = inputs.into_iter().find_map(|input| {
+                info!("running block {}", input.get_block_number());
+                failure(&input, args.batch_size).map(|error| (input, error))
+            }) else {
+                bail!("no block of the input fails");
+            };
+            found
+        }
+    };
+    info!("block {} fails with: {error}", input.get_block_number());
+
+    let still_fails = |input: &BlockProverInput| match failure(input, args.batch_size) {
+        Some(e) => match &args.error_pattern {
+            Some(pattern) => e.contains(pattern.as_str()),
+            None => e == error,
+        },
+        None => false,
+    };
+    if !still_fails(&input) {
+        bail!(
+            "the error of block {} doesn't match the pattern",
+            input.get_block_number()
+        );
+    }
+
+    let input = minimize_txns(input, &still_fails);
+    let input = match WIRE_DISPOSITION {
+        WireDisposition::Type1 => match minimize_accounts(input.clone(), &still_fails) {
+            Ok(minimized) => minimized,
+            Err(e) => {
+                warn!("couldn't hash out accounts: {e:#}");
+                input
+            }
+        },
+        WireDisposition::Type2 => {
+            info!("accounts can't be hashed out of type 2 witnesses, keeping them all");
+            input
+        }
+    };
+
+    std::fs::write(&args.output, serde_json::to_vec_pretty(&vec![input])?)
+        .with_context(|| format!("couldn't write {}", args.output.display()))?;
+    info!("wrote the minimized witness to {}", args.output.display());
+
+    Ok(())
+}
+
+/// Decodes and simulates the block, returning the first error, if any.
+fn failure(input: &BlockProverInput, batch_size: usize) -> Option<String> {
+    let result = catch_unwind(AssertUnwindSafe(|| {
+        let generation_inputs = trace_decoder::entrypoint(
+            input.block_trace.clone(),
+            input.other_data.clone(),
+            BatchingStrategy::Count(batch_size),
+            &mut DummyObserver::new(),
+            WIRE_DISPOSITION,
+        )?;
+        for generation_inputs in generation_inputs {
+            simulate_execution::<evm_arithmetization::Field>(generation_inputs)?;
+        }
+        anyhow::Ok(())
+    }));
+    match result {
+        Ok(Ok(())) => None,
+        Ok(Err(e)) => Some(format!("{e:#}")),
+        Err(panic) => Some(match panic.downcast_ref::<&str>() {
+            Some(message) => format!("panicked: {message}"),
+            None => match panic.downcast_ref::<String>() {
+                Some(message) => format!("panicked: {message}"),
+                None => String::from("panicked"),
+            },
+        }),
+    }
+}
+
+/// Drops as many transactions of the block as possible.
+fn minimize_txns(
+    input: BlockProverInput,
+    still_fails: &impl Fn(&BlockProverInput) -> bool,
+) -> BlockProverInput {
+    let txns = input.block_trace.txn_info.clone();
+    let with_txns = |txns: &[_]| {
+        let mut input = input.clone();
+        input.block_trace.txn_info = txns.to_vec();
+        input
+    };
+    let txns = reduce(txns, |txns| still_fails(&with_txns(txns)));
+    info!("kept {} transaction(s)", txns.len());
+    with_txns(&txns)
+}
+
+/// Hashes out as many accounts and storage slots of the block as possible.
+///
+/// The accounts and slots accessed by the transactions, and the withdrawal
+/// recipients, are always kept.
+fn minimize_accounts(
+    input: BlockProverInput,
+    still_fails: &impl Fn(&BlockProverInput) -> bool,
+) -> Result<BlockProverInput> {
+    let mut touched = BTreeMap::<H256, BTreeSet<H256>>::new();
+    for txn in &input.block_trace.txn_info {
+        for (address, trace) in &txn.traces {
+            touched.entry(keccak(address)).or_default().extend(
+                trace
+                    .storage_read
+                    .iter()
+                    .chain(trace.storage_written.keys())
+                    .map(keccak),
+            );
+        }
+    }
+    for (address, _) in &input.other_data.b_data.withdrawals {
+        touched.entry(keccak(address)).or_default();
+    }
+
+    let masked = |keep: &BTreeMap<H256, Option<BTreeSet<H256>>>| {
+        let mut input = input.clone();
+        input
+            .block_trace
+            .trie_pre_images
+            .mask(WIRE_DISPOSITION, keep)?;
+        anyhow::Ok(input)
+    };
+
+    // Try the smallest witness first: only the touched slots of the touched
+    // accounts.
+    let keep = touched
+        .iter()
+        .map(|(account, slots)| (*account, Some(slots.clone())))
+        .collect();
+    let minimized = masked(&keep)?;
+    if still_fails(&minimized) {
+        info!("kept only the {} touched account(s)", keep.len());
+        return Ok(minimized);
+    }
+
+    let keep = touched.keys().map(|account| (*account, None)).collect();
+    let minimized = masked(&keep)?;
+    if still_fails(&minimized) {
+        info!("kept only the {} touched account(s)", keep.len());
+        return Ok(minimized);
+    }
+
+    let untouched = input
+        .block_trace
+        .trie_pre_images
+        .accounts(WIRE_DISPOSITION)?
+        .into_iter()
+        .filter(|account| !touched.contains_key(account))
+        .collect::<Vec<_>>();
+    let with_untouched = |untouched: &[H256]| {
+        let keep = touched
+            .keys()
+            .chain(untouched)
+            .map(|account| (*account, None))
+            .collect();
+        masked(&keep)
+    };
+    let untouched = reduce(untouched, |untouched| {
+        with_untouched(untouched).is_ok_and(|input| still_fails(&input))
+    });
+    info!(
+        "kept {} touched and {} untouched account(s)",
+        touched.len(),
+        untouched.len()
+    );
+    with_untouched(&untouched)
+}
+
+/// Removes as many `items` as possible while `still_fails` holds, trying to
+/// remove chunks of halving sizes.
+fn reduce<T: Clone>(mut items: Vec<T>, still_fails: impl Fn(&[T]) -> bool) -> Vec<T> {
+    let mut chunk_len = items.len().div_ceil(2);
+    while chunk_len > 0 {
+        let mut start = 0;
+        while start < items.len() {
+            let end = usize::min(start + chunk_len, items.len());
+            let candidate = [&items[..start], &items[end..]].concat();
+            if still_fails(&candidate) {
+                info!("removed {} item(s), {} left", end - start, candidate.len());
+                items = candidate;
+            } else {
+                start = end;
+            }
+        }
+        chunk_len /= 2;
+    }
+    items
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reduce_to_required_items() {
+        let required = |items: &[u32]| items.contains(&3) && items.contains(&7);
+        assert_eq!(reduce((0..10).collect(), required), vec![3, 7]);
+        assert_eq!(reduce((0..10).collect(), |_| true), Vec::<u32>::new());
+        assert_eq!(
+            reduce((0..10).collect(), |_| false),
+            (0..10).collect::<Vec<_>>()
+        );
+    }
+}
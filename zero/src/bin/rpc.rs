@@ -8,7 +8,7 @@ use alloy::transports::Transport;
 use anyhow::anyhow;
 use clap::{Args, Parser, Subcommand, ValueHint};
 use futures::StreamExt;
-use trace_decoder::observer::DummyObserver;
+use trace_decoder::{observer::DummyObserver, BatchingStrategy};
 use tracing_subscriber::{prelude::*, EnvFilter};
 use url::Url;
 use zero::block_interval::BlockInterval;
@@ -18,7 +18,7 @@ use zero::prover::WIRE_DISPOSITION;
 use zero::provider::CachedProvider;
 use zero::rpc;
 
-use self::rpc::{retry::build_http_retry_provider, RpcType};
+use self::rpc::{cache::CliResponseCacheConfig, retry::build_http_retry_provider, RpcType};
 
 #[derive(Clone, Debug, Copy)]
 struct FetchParams {
@@ -46,6 +46,8 @@ struct RpcToolConfig {
     /// The maximum number of retries.
     #[arg(long, env = "ZERO_BIN_MAX_RETRIES", default_value_t = 0)]
     max_retries: u32,
+    #[clap(flatten)]
+    rpc_cache: CliResponseCacheConfig,
 }
 
 #[derive(Subcommand)]
@@ -118,6 +120,7 @@ impl Cli {
             self.config.rpc_url.clone(),
             self.config.backoff,
             self.config.max_retries,
+            self.config.rpc_cache.open()?,
         )?;
         let cached_provider = Arc::new(CachedProvider::new(retry_provider, self.config.rpc_type));
 
@@ -170,7 +173,7 @@ impl Cli {
                         let generation_inputs = trace_decoder::entrypoint(
                             block_prover_input.block_trace,
                             block_prover_input.other_data,
-                            batch_size,
+                            BatchingStrategy::Count(batch_size),
                             &mut DummyObserver::new(),
                             WIRE_DISPOSITION,
                         )?;
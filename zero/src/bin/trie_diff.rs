@@ -96,7 +96,7 @@ async fn main() -> Result<()> {
         let block_generation_inputs = trace_decoder::entrypoint(
             block_prover_input.block_trace.clone(),
             block_prover_input.other_data.clone(),
-            prover_config.batch_size,
+            prover_config.batching,
             &mut observer,
             WIRE_DISPOSITION,
         )?;
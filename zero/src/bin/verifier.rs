@@ -1,14 +1,13 @@
 zk_evm_common::check_chain_features!();
 
-use std::fs::File;
-
-use anyhow::Result;
+use anyhow::{Context as _, Result};
 use clap::Parser;
 use dotenvy::dotenv;
-use serde_json::Deserializer;
 use tracing::info;
-use zero::proof_types::GeneratedBlockProof;
+use zero::proof_format::{deserialize_proofs_with_digests, read_proofs, DIGEST_LEN};
+use zero::proof_types::GeneratedProof;
 use zero::prover_state::persistence::set_circuit_cache_dir_env_if_not_set;
+use zk_evm_verifier::VerifierKey;
 
 use self::verifier::*;
 mod verifier {
@@ -23,18 +22,71 @@ fn main() -> Result<()> {
 
     let args = cli::Cli::parse();
 
-    let file = File::open(args.file_path)?;
-    let des = &mut Deserializer::from_reader(&file);
-    let input_proofs: Vec<GeneratedBlockProof> = serde_path_to_error::deserialize(des)?;
+    if let Some(cli::Command::Inspect { file_path }) = args.command {
+        let public_values = read_proofs(&file_path)?
+            .iter()
+            .map(GeneratedProof::public_values)
+            .collect::<Result<Vec<_>>>()?;
+        serde_json::to_writer_pretty(std::io::stdout(), &public_values)?;
+        println!();
+        return Ok(());
+    }
+
+    if let Some(key_path) = args.export_verifier_key {
+        let verifier = args
+            .prover_state_config
+            .into_prover_state_manager()
+            .verifier()?;
+        let key = VerifierKey::new(verifier.state);
+        std::fs::write(&key_path, key.to_bytes()?)?;
+        info!(
+            "Wrote verifier key with digest 0x{} to {}",
+            hex::encode(key.digest()),
+            key_path.display()
+        );
+        return Ok(());
+    }
+
+    let file_path = args
+        .file_path
+        .expect("a proof file is required unless exporting a verifier key");
+    let input_proofs = deserialize_proofs_with_digests(&std::fs::read(&file_path)?)
+        .with_context(|| format!("failed to read proofs from {}", file_path.display()))?;
 
-    let verifier = args
-        .prover_state_config
-        .into_prover_state_manager()
-        .verifier()?;
+    // Proofs whose circuit digest is known are first checked to come from the
+    // circuit they are verified against.
+    let verify: Box<dyn Fn(&GeneratedProof, Option<[u8; DIGEST_LEN]>) -> Result<()>> =
+        match args.verifier_key {
+            // A verifier key only holds the verifier data of the block circuit.
+            Some(key_path) => {
+                let key = VerifierKey::from_bytes(&std::fs::read(key_path)?)?;
+                info!(
+                    "Loaded verifier key with digest 0x{}",
+                    hex::encode(key.digest())
+                );
+                Box::new(move |proof, digest| match proof {
+                    GeneratedProof::Block(proof) => {
+                        check_circuit_digest(digest, key.digest())?;
+                        Ok(key.verify(&proof.intern)?)
+                    }
+                    _ => anyhow::bail!("only block proofs can be verified with a verifier key"),
+                })
+            }
+            None => {
+                let verifier = args
+                    .prover_state_config
+                    .into_prover_state_manager()
+                    .verifier()?;
+                Box::new(move |proof, digest| {
+                    check_circuit_digest(digest, verifier.circuit_digest(proof))?;
+                    verifier.verify_generated_proof(proof)
+                })
+            }
+        };
 
-    if input_proofs.into_iter().all(|block_proof| {
-        verifier
-            .verify(&block_proof.intern)
+    if input_proofs.into_iter().all(|(proof, digest)| {
+        info!("Verifying {} proof", proof.kind());
+        verify(&proof, digest)
             .map_err(|e| {
                 info!("Proof verification failed with error: {:?}", e);
             })
@@ -45,3 +97,21 @@ fn main() -> Result<()> {
 
     Ok(())
 }
+
+/// Checks that a proof comes from the circuit with the `expected` digest, if
+/// the `digest` of its circuit is known. It is unknown for the proofs of a
+/// leader whose workers hold the circuits, which are then only checked by
+/// their verification.
+fn check_circuit_digest(
+    digest: Option<[u8; DIGEST_LEN]>,
+    expected: [u8; DIGEST_LEN],
+) -> Result<()> {
+    match digest {
+        Some(digest) if digest != expected => anyhow::bail!(
+            "the proof comes from the circuit with digest 0x{}, not 0x{}",
+            hex::encode(digest),
+            hex::encode(expected)
+        ),
+        _ => Ok(()),
+    }
+}
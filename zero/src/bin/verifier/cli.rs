@@ -1,16 +1,42 @@
 use std::path::PathBuf;
 
-use clap::{Parser, ValueHint};
+use clap::{Parser, Subcommand, ValueHint};
 use zero::prover_state::cli::CliProverStateConfig;
 
 #[derive(Parser)]
-#[command(version = zero::version(), propagate_version = true)]
+#[command(
+    version = zero::version(),
+    propagate_version = true,
+    args_conflicts_with_subcommands = true,
+    subcommand_negates_reqs = true
+)]
 pub(crate) struct Cli {
+    #[command(subcommand)]
+    pub(crate) command: Option<Command>,
     /// The file containing the proof to verify
-    #[arg(short, long, value_hint = ValueHint::FilePath)]
-    pub(crate) file_path: PathBuf,
+    #[arg(short, long, value_hint = ValueHint::FilePath, required_unless_present = "export_verifier_key")]
+    pub(crate) file_path: Option<PathBuf>,
+    /// Write the verifier key of the block circuit to this file, instead of
+    /// verifying a proof.
+    #[arg(long, value_hint = ValueHint::FilePath, conflicts_with = "file_path")]
+    pub(crate) export_verifier_key: Option<PathBuf>,
+    /// Verify block proofs against this verifier key file, instead of loading
+    /// the verifier state from the prover configuration.
+    #[arg(long, value_hint = ValueHint::FilePath, conflicts_with = "export_verifier_key")]
+    pub(crate) verifier_key: Option<PathBuf>,
     /// The prover configuration used to generate the preprocessed circuits
     /// and the verifier state.
     #[clap(flatten)]
     pub(crate) prover_state_config: CliProverStateConfig,
 }
+
+#[derive(Subcommand)]
+pub(crate) enum Command {
+    /// Decodes the public values of the proofs in a file, of any kind, and
+    /// writes them to stdout as JSON.
+    Inspect {
+        /// The file containing the proofs to inspect
+        #[arg(short, long, value_hint = ValueHint::FilePath)]
+        file_path: PathBuf,
+    },
+}
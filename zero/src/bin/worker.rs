@@ -2,6 +2,8 @@ use anyhow::Result;
 use clap::Parser;
 use dotenvy::dotenv;
 use paladin::runtime::WorkerRuntime;
+use zero::metrics::CliMetricsConfig;
+use zero::profiling::CliProfilingConfig;
 use zero::prover_state::{
     cli::CliProverStateConfig, persistence::set_circuit_cache_dir_env_if_not_set,
 };
@@ -20,6 +22,10 @@ struct Cli {
     paladin: paladin::config::Config,
     #[clap(flatten)]
     prover_state_config: CliProverStateConfig,
+    #[clap(flatten)]
+    metrics_config: CliMetricsConfig,
+    #[clap(flatten)]
+    profiling_config: CliProfilingConfig,
 }
 
 #[tokio::main]
@@ -28,6 +34,8 @@ async fn main() -> Result<()> {
     tracing::init();
     set_circuit_cache_dir_env_if_not_set()?;
     let args = Cli::parse();
+    args.metrics_config.start().await?;
+    args.profiling_config.start()?;
 
     args.prover_state_config
         .into_prover_state_manager()
@@ -137,6 +137,31 @@ impl BlockInterval {
         }
     }
 
+    /// Returns the (inclusive) end block number of the interval, or `None` if
+    /// it is unbounded.
+    pub fn get_end_block(&self) -> Option<u64> {
+        match self {
+            BlockInterval::SingleBlockId(num) => Some(*num),
+            BlockInterval::Range(range) => Some(range.end - 1),
+            BlockInterval::FollowFrom { .. } => None,
+        }
+    }
+
+    /// Returns the remainder of the interval after `block_number`, or `None`
+    /// if `block_number` is the last block of the interval, or past it.
+    pub fn skip_through(self, block_number: u64) -> Option<BlockInterval> {
+        match self {
+            BlockInterval::SingleBlockId(num) => (block_number < num).then_some(self),
+            BlockInterval::Range(range) => {
+                let start = range.start.max(block_number + 1);
+                (start < range.end).then_some(BlockInterval::Range(start..range.end))
+            }
+            BlockInterval::FollowFrom { start_block } => Some(BlockInterval::FollowFrom {
+                start_block: start_block.max(block_number + 1),
+            }),
+        }
+    }
+
     /// Convert the block interval into an unbounded async stream of block
     /// numbers. Query the blockchain node for the latest block number.
     pub async fn into_unbounded_stream<T>(
@@ -370,6 +395,28 @@ mod test {
             });
     }
 
+    #[test]
+    fn can_skip_through_block_interval() {
+        assert_eq!(
+            BlockInterval::Range(1..10).skip_through(4),
+            Some(BlockInterval::Range(5..10))
+        );
+        assert_eq!(
+            BlockInterval::Range(5..10).skip_through(2),
+            Some(BlockInterval::Range(5..10))
+        );
+        assert_eq!(BlockInterval::Range(1..10).skip_through(9), None);
+        assert_eq!(BlockInterval::SingleBlockId(3).skip_through(3), None);
+        assert_eq!(
+            BlockInterval::SingleBlockId(3).skip_through(2),
+            Some(BlockInterval::SingleBlockId(3))
+        );
+        assert_eq!(
+            BlockInterval::FollowFrom { start_block: 1 }.skip_through(7),
+            Some(BlockInterval::FollowFrom { start_block: 8 })
+        );
+    }
+
     #[tokio::test]
     async fn can_into_bounded_stream() {
         use futures::StreamExt;
@@ -1,9 +1,9 @@
-use std::fs::File;
 use std::path::PathBuf;
 
 use anyhow::anyhow;
 
-use crate::proof_types::GeneratedBlockProof;
+use crate::proof_format::read_proofs;
+use crate::proof_types::{GeneratedBlockProof, GeneratedProof};
 
 pub fn generate_block_proof_file_name(directory: &Option<&str>, block_height: u64) -> PathBuf {
     let mut path = PathBuf::from(directory.unwrap_or(""));
@@ -11,19 +11,39 @@ pub fn generate_block_proof_file_name(directory: &Option<&str>, block_height: u6
     path
 }
 
+/// Returns the path of the wrapped proof of the block at `block_height`.
+pub fn generate_wrapped_block_proof_file_name(
+    directory: &Option<&str>,
+    block_height: u64,
+) -> PathBuf {
+    let mut path = PathBuf::from(directory.unwrap_or(""));
+    path.push(format!("b{}_wrapped.zkproof", block_height));
+    path
+}
+
+/// Returns the path of the two-to-one aggregation proof of the blocks from
+/// `first_block` to `last_block`.
+pub fn generate_agg_block_proof_file_name(
+    directory: &Option<&str>,
+    first_block: u64,
+    last_block: u64,
+) -> PathBuf {
+    let mut path = PathBuf::from(directory.unwrap_or(""));
+    path.push(format!("b{}_{}_agg.zkproof", first_block, last_block));
+    path
+}
+
 pub fn get_previous_proof(path: Option<PathBuf>) -> anyhow::Result<Option<GeneratedBlockProof>> {
     if path.is_none() {
         return Ok(None);
     }
 
     let path = path.unwrap();
-    let file = File::open(path)?;
-    let des = &mut serde_json::Deserializer::from_reader(&file);
-    let proof: Vec<GeneratedBlockProof> = serde_path_to_error::deserialize(des)?;
     // Individual proofs are serialized as vector to match other output formats.
-    if proof.len() != 1 {
-        return Err(anyhow!("Invalid proof format, expected vector of generated block proofs with a single element."));
+    match <[GeneratedProof; 1]>::try_from(read_proofs(&path)?) {
+        Ok([GeneratedProof::Block(proof)]) => Ok(Some(proof)),
+        _ => Err(anyhow!(
+            "Invalid proof format, expected a single generated block proof."
+        )),
     }
-
-    Ok(Some(proof[0].to_owned()))
 }
@@ -0,0 +1,267 @@
+//! On-disk journal of the progress of a block interval, so that proving can
+//! resume where it stopped after the leader restarts.
+
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+use anyhow::{Context as _, Result};
+use keccak_hash::H256;
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use tracing::{info, warn};
+
+use crate::proof_types::GeneratedBlockProof;
+
+const JOURNAL_FILE_NAME: &str = "journal.json";
+const LATEST_PROOF_FILE_NAME: &str = "journal_latest_proof.json";
+
+/// Identifies the block interval a journal is kept for.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct JournalInterval {
+    pub checkpoint_block_number: u64,
+    pub start_block: u64,
+    /// Inclusive, `None` when following the head of the chain.
+    pub end_block: Option<u64>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct JournalState {
+    interval: JournalInterval,
+    /// The hash of the block of the previous proof the interval was started
+    /// from, if any.
+    previous_block_hash: Option<H256>,
+    /// The block height of the latest proof, which aggregates all the blocks
+    /// since the checkpoint.
+    latest_block: Option<u64>,
+}
+
+/// Journal of the proofs generated for a block interval, kept in the proof
+/// output directory.
+///
+/// It stores the latest block proof. As block proofs are chained, this proof
+/// is all that's needed to resume proving the interval from the next block.
+#[derive(Debug)]
+pub struct ProofJournal {
+    dir: PathBuf,
+    state: Mutex<JournalState>,
+}
+
+impl ProofJournal {
+    /// Opens the journal in `dir` for `interval`, started from the proof of
+    /// the block with hash `previous_block_hash`, if any.
+    ///
+    /// A journal left by a different interval is discarded, but one left by
+    /// the same interval started from another previous proof is an error, as
+    /// its proofs can't be chained with that proof.
+    pub fn open(
+        dir: &Path,
+        interval: JournalInterval,
+        previous_block_hash: Option<H256>,
+    ) -> Result<Self> {
+        std::fs::create_dir_all(dir)?;
+
+        let path = dir.join(JOURNAL_FILE_NAME);
+        let state = match path.exists() {
+            true => Some(
+                read_json::<JournalState>(&path)
+                    .with_context(|| format!("invalid journal at {}", path.display()))?,
+            ),
+            false => None,
+        };
+
+        let state = match state {
+            Some(state) if state.interval == interval => {
+                anyhow::ensure!(
+                    state.previous_block_hash == previous_block_hash,
+                    "the journal at {} was started from the previous proof of block hash {:?}, \
+                     which conflicts with the previous proof of block hash {:?}",
+                    path.display(),
+                    state.previous_block_hash,
+                    previous_block_hash
+                );
+                if let Some(latest_block) = state.latest_block {
+                    info!("Found journal for {interval:?}, proven up to block {latest_block}");
+                }
+                state
+            }
+            state => {
+                if let Some(state) = state {
+                    warn!(
+                        "Discarding journal for interval {:?}, as it doesn't match {interval:?}",
+                        state.interval
+                    );
+                }
+                let state = JournalState {
+                    interval,
+                    previous_block_hash,
+                    latest_block: None,
+                };
+                write_json(&path, &state)?;
+                state
+            }
+        };
+
+        Ok(Self {
+            dir: dir.to_path_buf(),
+            state: Mutex::new(state),
+        })
+    }
+
+    /// Returns the interval of the journal.
+    pub fn interval(&self) -> JournalInterval {
+        self.lock().interval.clone()
+    }
+
+    /// Returns the block height of the latest block proof recorded in the
+    /// journal, if any.
+    pub fn latest_block(&self) -> Option<u64> {
+        self.lock().latest_block
+    }
+
+    /// Returns the latest block proof recorded in the journal, if any.
+    pub fn latest_proof(&self) -> Result<Option<GeneratedBlockProof>> {
+        let state = self.lock();
+        let Some(latest_block) = state.latest_block else {
+            return Ok(None);
+        };
+
+        let proof: GeneratedBlockProof = read_json(&self.dir.join(LATEST_PROOF_FILE_NAME))?;
+        if proof.b_height != latest_block {
+            anyhow::bail!(
+                "journal proof is for block {}, expected block {latest_block}",
+                proof.b_height
+            );
+        }
+
+        Ok(Some(proof))
+    }
+
+    /// Records a block proof.
+    pub fn record(&self, proof: &GeneratedBlockProof) -> Result<()> {
+        let mut state = self.lock();
+        // Proofs are chained, so the latest proof aggregates all the
+        // previous ones.
+        if state.latest_block >= Some(proof.b_height) {
+            return Ok(());
+        }
+
+        write_json(&self.dir.join(LATEST_PROOF_FILE_NAME), proof)?;
+        state.latest_block = Some(proof.b_height);
+        write_json(&self.dir.join(JOURNAL_FILE_NAME), &*state)
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, JournalState> {
+        self.state.lock().expect("poisoned journal lock")
+    }
+}
+
+fn read_json<T: DeserializeOwned>(path: &Path) -> Result<T> {
+    let file = std::fs::File::open(path)?;
+    let des = &mut serde_json::Deserializer::from_reader(std::io::BufReader::new(file));
+    Ok(serde_path_to_error::deserialize(des)?)
+}
+
+/// Writes `value` to a temporary file first, so that a crash never leaves a
+/// partially written journal.
+fn write_json<T: Serialize>(path: &Path, value: &T) -> Result<()> {
+    let tmp_path = path.with_extension("json.tmp");
+    let mut writer = std::io::BufWriter::new(std::fs::File::create(&tmp_path)?);
+    serde_json::to_writer(&mut writer, value)?;
+    writer.into_inner()?.sync_all()?;
+    std::fs::rename(&tmp_path, path)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicU64, Ordering};
+
+    use evm_arithmetization::Field;
+    use plonky2::field::types::Field as _;
+
+    use super::*;
+    use crate::proof_types::dummy_block_proof;
+
+    const INTERVAL: JournalInterval = JournalInterval {
+        checkpoint_block_number: 9,
+        start_block: 10,
+        end_block: Some(20),
+    };
+
+    /// Returns a new temporary directory.
+    fn temp_dir() -> PathBuf {
+        static COUNTER: AtomicU64 = AtomicU64::new(0);
+        std::env::temp_dir().join(format!(
+            "proof_journal_{}_{}",
+            std::process::id(),
+            COUNTER.fetch_add(1, Ordering::Relaxed)
+        ))
+    }
+
+    fn block_proof(b_height: u64) -> GeneratedBlockProof {
+        dummy_block_proof(b_height, vec![Field::from_canonical_u64(b_height)])
+    }
+
+    #[test]
+    fn record_and_resume() {
+        let dir = temp_dir();
+        let journal = ProofJournal::open(&dir, INTERVAL, None).unwrap();
+        assert_eq!(journal.latest_block(), None);
+        assert!(journal.latest_proof().unwrap().is_none());
+
+        // Proofs may be recorded out of order, only the latest one is kept.
+        journal.record(&block_proof(11)).unwrap();
+        journal.record(&block_proof(10)).unwrap();
+        assert_eq!(journal.latest_block(), Some(11));
+        drop(journal);
+
+        let journal = ProofJournal::open(&dir, INTERVAL, None).unwrap();
+        assert_eq!(journal.interval(), INTERVAL);
+        let proof = journal.latest_proof().unwrap().unwrap();
+        assert_eq!(proof.b_height, 11);
+        assert_eq!(proof.intern.public_inputs, [Field::from_canonical_u64(11)]);
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn other_interval_is_discarded() {
+        let dir = temp_dir();
+        let journal = ProofJournal::open(&dir, INTERVAL, None).unwrap();
+        journal.record(&block_proof(10)).unwrap();
+        drop(journal);
+
+        let interval = JournalInterval {
+            end_block: None,
+            ..INTERVAL
+        };
+        let journal = ProofJournal::open(&dir, interval.clone(), None).unwrap();
+        assert_eq!(journal.interval(), interval);
+        assert_eq!(journal.latest_block(), None);
+        assert!(journal.latest_proof().unwrap().is_none());
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn conflicting_previous_proof_is_rejected() {
+        let dir = temp_dir();
+        let previous_block_hash = Some(H256::repeat_byte(1));
+        let journal = ProofJournal::open(&dir, INTERVAL, previous_block_hash).unwrap();
+        journal.record(&block_proof(10)).unwrap();
+        drop(journal);
+
+        assert!(ProofJournal::open(&dir, INTERVAL, None).is_err());
+        assert!(ProofJournal::open(&dir, INTERVAL, Some(H256::repeat_byte(2))).is_err());
+        let journal = ProofJournal::open(&dir, INTERVAL, previous_block_hash).unwrap();
+        assert_eq!(journal.latest_block(), Some(10));
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn mismatching_latest_proof_is_rejected() {
+        let dir = temp_dir();
+        let journal = ProofJournal::open(&dir, INTERVAL, None).unwrap();
+        journal.record(&block_proof(10)).unwrap();
+        write_json(&dir.join(LATEST_PROOF_FILE_NAME), &block_proof(12)).unwrap();
+        assert!(journal.latest_proof().is_err());
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -4,10 +4,14 @@ pub mod block_interval;
 pub mod debug_utils;
 pub mod env;
 pub mod fs;
+pub mod journal;
+pub mod metrics;
 pub mod ops;
 pub mod parsing;
 pub mod pre_checks;
+pub mod proof_format;
 pub mod proof_types;
+pub mod profiling;
 pub mod prover;
 pub mod prover_state;
 pub mod provider;
@@ -0,0 +1,221 @@
+//! Prometheus metrics of the leader and the worker, optionally served over
+//! HTTP at `/metrics`.
+
+use std::net::SocketAddr;
+use std::time::Duration;
+
+use axum::{http::StatusCode, routing::get, Router};
+use clap::Args;
+use evm_arithmetization::all_stark::Table;
+use once_cell::sync::Lazy;
+use prometheus::{
+    exponential_buckets, register_histogram, register_histogram_vec, register_int_counter,
+    register_int_counter_vec, register_int_gauge, Encoder as _, Histogram, HistogramVec,
+    IntCounter, IntCounterVec, IntGauge, TextEncoder,
+};
+use tracing::{error, info};
+
+const HELP_HEADING: &str = "Metrics options";
+
+/// Buckets for proving durations, from 100ms to ~14min.
+fn proving_buckets() -> Vec<f64> {
+    exponential_buckets(0.1, 2.0, 14).expect("valid buckets")
+}
+
+/// Time taken to prove each STARK table of a segment.
+pub static STARK_TABLE_PROOF_SECONDS: Lazy<HistogramVec> = Lazy::new(|| {
+    register_histogram_vec!(
+        "zero_stark_table_proof_seconds",
+        "Time taken to prove a STARK table of a segment.",
+        &["table"],
+        proving_buckets()
+    )
+    .expect("valid metric")
+});
+
+/// Time taken to prove a segment, including the recursive table proofs.
+pub static SEGMENT_PROOF_SECONDS: Lazy<Histogram> = Lazy::new(|| {
+    register_histogram!(
+        "zero_segment_proof_seconds",
+        "Time taken to prove a segment.",
+        proving_buckets()
+    )
+    .expect("valid metric")
+});
+
+/// Time taken by aggregation proofs, labelled by `kind`: `segment`, `batch`,
+/// `block`, `block_wrapper` or `two_to_one_block`.
+pub static AGGREGATION_PROOF_SECONDS: Lazy<HistogramVec> = Lazy::new(|| {
+    register_histogram_vec!(
+        "zero_aggregation_proof_seconds",
+        "Time taken by a segment, batch, block, block wrapper or two-to-one block aggregation proof.",
+        &["kind"],
+        proving_buckets()
+    )
+    .expect("valid metric")
+});
+
+/// Number of blocks waiting for a permit of the block proving pool.
+pub static BLOCK_PROVING_QUEUE_DEPTH: Lazy<IntGauge> = Lazy::new(|| {
+    register_int_gauge!(
+        "zero_block_proving_queue_depth",
+        "Number of blocks waiting for a block proving permit."
+    )
+    .expect("valid metric")
+});
+
+/// Number of blocks being proven.
+pub static BLOCK_PROVING_IN_FLIGHT: Lazy<IntGauge> = Lazy::new(|| {
+    register_int_gauge!(
+        "zero_block_proving_in_flight",
+        "Number of blocks holding a block proving permit."
+    )
+    .expect("valid metric")
+});
+
+/// Increments a gauge for as long as it's alive, e.g. for the duration of a
+/// task, including when the task fails or is cancelled.
+#[must_use = "the gauge is decremented when the guard is dropped"]
+pub struct GaugeGuard<'a>(&'a IntGauge);
+
+impl<'a> GaugeGuard<'a> {
+    pub fn new(gauge: &'a IntGauge) -> Self {
+        gauge.inc();
+        Self(gauge)
+    }
+}
+
+impl Drop for GaugeGuard<'_> {
+    fn drop(&mut self) {
+        self.0.dec();
+    }
+}
+
+/// Latency of RPC requests, retries included.
+pub static RPC_REQUEST_SECONDS: Lazy<Histogram> = Lazy::new(|| {
+    register_histogram!(
+        "zero_rpc_request_seconds",
+        "Latency of RPC requests, including retries."
+    )
+    .expect("valid metric")
+});
+
+/// Number of retried RPC requests.
+pub static RPC_RETRIES_TOTAL: Lazy<IntCounter> = Lazy::new(|| {
+    register_int_counter!("zero_rpc_retries_total", "Number of RPC request retries.")
+        .expect("valid metric")
+});
+
+/// Number of RPC requests which failed after all their retries.
+pub static RPC_FAILURES_TOTAL: Lazy<IntCounter> = Lazy::new(|| {
+    register_int_counter!(
+        "zero_rpc_failures_total",
+        "Number of RPC requests which failed after all their retries."
+    )
+    .expect("valid metric")
+});
+
+/// Circuit cache lookups which found the circuits on disk, by `resource`.
+pub static CIRCUIT_CACHE_HITS_TOTAL: Lazy<IntCounterVec> = Lazy::new(|| {
+    register_int_counter_vec!(
+        "zero_circuit_cache_hits_total",
+        "Number of circuits loaded from the on-disk cache.",
+        &["resource"]
+    )
+    .expect("valid metric")
+});
+
+/// Circuit cache lookups which didn't find the circuits on disk, by
+/// `resource`.
+pub static CIRCUIT_CACHE_MISSES_TOTAL: Lazy<IntCounterVec> = Lazy::new(|| {
+    register_int_counter_vec!(
+        "zero_circuit_cache_misses_total",
+        "Number of circuits missing from the on-disk cache.",
+        &["resource"]
+    )
+    .expect("valid metric")
+});
+
+fn observe_table_proof(table: Table, duration: Duration) {
+    STARK_TABLE_PROOF_SECONDS
+        .with_label_values(&[&format!("{table:?}")])
+        .observe(duration.as_secs_f64());
+}
+
+/// Metrics configuration.
+#[derive(Args, Clone, Debug, Default)]
+pub struct CliMetricsConfig {
+    /// If provided, serve Prometheus metrics at `/metrics` on this address.
+    #[arg(long, env = "ZERO_BIN_METRICS_ADDR", help_heading = HELP_HEADING)]
+    pub metrics_addr: Option<SocketAddr>,
+}
+
+impl CliMetricsConfig {
+    /// Starts serving the metrics in the background, if enabled.
+    pub async fn start(&self) -> anyhow::Result<()> {
+        let Some(addr) = self.metrics_addr else {
+            return Ok(());
+        };
+
+        // Register all the metrics upfront, so that they are exported even
+        // before being updated.
+        Lazy::force(&STARK_TABLE_PROOF_SECONDS);
+        Lazy::force(&SEGMENT_PROOF_SECONDS);
+        Lazy::force(&AGGREGATION_PROOF_SECONDS);
+        Lazy::force(&BLOCK_PROVING_QUEUE_DEPTH);
+        Lazy::force(&BLOCK_PROVING_IN_FLIGHT);
+        Lazy::force(&RPC_REQUEST_SECONDS);
+        Lazy::force(&RPC_RETRIES_TOTAL);
+        Lazy::force(&RPC_FAILURES_TOTAL);
+        Lazy::force(&CIRCUIT_CACHE_HITS_TOTAL);
+        Lazy::force(&CIRCUIT_CACHE_MISSES_TOTAL);
+        evm_arithmetization::prover::set_table_proof_observer(observe_table_proof);
+
+        let app = Router::new().route("/metrics", get(metrics));
+        let listener = tokio::net::TcpListener::bind(&addr).await?;
+        info!("serving metrics on {addr}");
+        tokio::spawn(async move {
+            if let Err(e) = axum::serve(listener, app).await {
+                error!("metrics server failed: {e}");
+            }
+        });
+
+        Ok(())
+    }
+}
+
+async fn metrics() -> Result<String, StatusCode> {
+    let mut buffer = vec![];
+    TextEncoder::new()
+        .encode(&prometheus::gather(), &mut buffer)
+        .map_err(|e| {
+            error!("failed to encode metrics: {e}");
+            StatusCode::INTERNAL_SERVER_ERROR
+        })?;
+
+    String::from_utf8(buffer).map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn gauge_guard() {
+        let gauge = IntGauge::new("test_gauge", "A test gauge.").unwrap();
+        let guard = GaugeGuard::new(&gauge);
+        {
+            let _guard = GaugeGuard::new(&gauge);
+            assert_eq!(gauge.get(), 2);
+        }
+        assert_eq!(gauge.get(), 1);
+
+        // The guard is released when its task panics.
+        let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(move || {
+            let _guard = guard;
+            panic!("task failed");
+        }));
+        assert!(result.is_err());
+        assert_eq!(gauge.get(), 0);
+    }
+}
@@ -15,7 +15,11 @@ use tracing::error;
 use tracing::{event, info_span, Level};
 
 use crate::debug_utils::save_tries_to_disk;
-use crate::proof_types::{BatchAggregatableProof, GeneratedBlockProof, SegmentAggregatableProof};
+use crate::metrics::{AGGREGATION_PROOF_SECONDS, SEGMENT_PROOF_SECONDS};
+use crate::proof_types::{
+    AggregatableBlockProof, BatchAggregatableProof, GeneratedAggBlockProof, GeneratedBlockProof,
+    GeneratedWrappedBlockProof, SegmentAggregatableProof,
+};
 use crate::prover_state::ProverState;
 use crate::{debug_utils::save_inputs_to_disk, prover_state::p_state};
 
@@ -198,6 +202,7 @@ impl SegmentProofSpan {
 
 impl Drop for SegmentProofSpan {
     fn drop(&mut self) {
+        SEGMENT_PROOF_SECONDS.observe(self.start.elapsed().as_secs_f64());
         event!(
             Level::INFO,
             "segment proof ({}) took {:?}",
@@ -260,6 +265,9 @@ impl Monoid for SegmentAggProof {
     type Elem = SegmentAggregatableProof;
 
     fn combine(&self, a: Self::Elem, b: Self::Elem, _abort: AbortSignal) -> Result<Self::Elem> {
+        let _timer = AGGREGATION_PROOF_SECONDS
+            .with_label_values(&["segment"])
+            .start_timer();
         let proof = generate_segment_agg_proof(p_state(), &a, &b, false).map_err(|e| {
             if self.save_inputs_on_error {
                 let pv = vec![
@@ -305,6 +313,9 @@ impl Monoid for BatchAggProof {
     type Elem = BatchAggregatableProof;
 
     fn combine(&self, a: Self::Elem, b: Self::Elem, _abort: AbortSignal) -> Result<Self::Elem> {
+        let _timer = AGGREGATION_PROOF_SECONDS
+            .with_label_values(&["batch"])
+            .start_timer();
         let lhs = match a {
             BatchAggregatableProof::Segment(segment) => BatchAggregatableProof::SegmentAgg(
                 generate_segment_agg_proof(
@@ -379,6 +390,9 @@ impl Operation for BlockProof {
     type Output = GeneratedBlockProof;
 
     fn execute(&self, input: Self::Input, _abort: AbortSignal) -> Result<Self::Output> {
+        let _timer = AGGREGATION_PROOF_SECONDS
+            .with_label_values(&["block"])
+            .start_timer();
         let b_height = input.public_values.block_metadata.block_number.low_u64();
         let parent_intern = self.prev.as_ref().map(|p| &p.intern);
 
@@ -407,3 +421,126 @@ impl Operation for BlockProof {
         })
     }
 }
+
+/// Wraps a block proof, attaching its final public values.
+#[derive(Deserialize, Serialize, RemoteExecute, Clone)]
+pub struct BlockWrapperProof {
+    pub save_inputs_on_error: bool,
+}
+
+impl Operation for BlockWrapperProof {
+    type Input = GeneratedBlockProof;
+    type Output = GeneratedWrappedBlockProof;
+
+    fn execute(&self, input: Self::Input, _abort: AbortSignal) -> Result<Self::Output> {
+        let _timer = AGGREGATION_PROOF_SECONDS
+            .with_label_values(&["block_wrapper"])
+            .start_timer();
+        let block_proof = ProofWithPublicValues {
+            public_values: input
+                .public_values()
+                .map_err(|e| FatalError::from_anyhow(e, FatalStrategy::Terminate))?,
+            intern: input.intern,
+        };
+
+        let (intern, public_values) =
+            p_state()
+                .state
+                .prove_block_wrapper(&block_proof)
+                .map_err(|e| {
+                    if self.save_inputs_on_error {
+                        if let Err(write_err) = save_inputs_to_disk(
+                            format!("b{}_block_wrapper_input.json", input.b_height),
+                            block_proof.public_values,
+                        ) {
+                            error!(
+                                "Failed to save block wrapper proof input to disk: {:?}",
+                                write_err
+                            );
+                        }
+                    }
+
+                    FatalError::from_str(&e.to_string(), FatalStrategy::Terminate)
+                })?;
+
+        Ok(GeneratedWrappedBlockProof {
+            b_height: input.b_height,
+            intern,
+            public_values,
+        })
+    }
+}
+
+/// Proves a block without a parent block proof, and wraps it so that it can be
+/// aggregated with other blocks by [`TwoToOneBlockAggProof`].
+#[derive(Deserialize, Serialize, RemoteExecute, Clone)]
+pub struct WrappedBlockProof {
+    pub save_inputs_on_error: bool,
+}
+
+impl Operation for WrappedBlockProof {
+    type Input = ProofWithPublicValues;
+    type Output = AggregatableBlockProof;
+
+    fn execute(&self, input: Self::Input, _abort: AbortSignal) -> Result<Self::Output> {
+        let _timer = AGGREGATION_PROOF_SECONDS
+            .with_label_values(&["block"])
+            .start_timer();
+        let b_height = input.public_values.block_metadata.block_number.low_u64();
+
+        let wrapped_proof = p_state()
+            .state
+            .prove_block(None, &input)
+            .and_then(|block_proof| p_state().state.prove_block_wrapper(&block_proof))
+            .map_err(|e| {
+                if self.save_inputs_on_error {
+                    if let Err(write_err) = save_inputs_to_disk(
+                        format!(
+                            "b{}_block_input.json",
+                            input.public_values.block_metadata.block_number
+                        ),
+                        input.public_values,
+                    ) {
+                        error!("Failed to save block proof input to disk: {:?}", write_err);
+                    }
+                }
+
+                FatalError::from_str(&e.to_string(), FatalStrategy::Terminate)
+            })?;
+
+        Ok(AggregatableBlockProof::Block(GeneratedBlockProof {
+            b_height,
+            intern: wrapped_proof.0,
+        }))
+    }
+}
+
+/// Aggregates wrapped block proofs, or aggregations of them, two by two.
+///
+/// The aggregated blocks don't need to be contiguous.
+#[derive(Deserialize, Serialize, RemoteExecute, Clone)]
+pub struct TwoToOneBlockAggProof;
+
+impl Monoid for TwoToOneBlockAggProof {
+    type Elem = AggregatableBlockProof;
+
+    fn combine(&self, a: Self::Elem, b: Self::Elem, _abort: AbortSignal) -> Result<Self::Elem> {
+        let _timer = AGGREGATION_PROOF_SECONDS
+            .with_label_values(&["two_to_one_block"])
+            .start_timer();
+
+        let proof = p_state()
+            .state
+            .prove_two_to_one_block(a.intern(), a.is_agg(), b.intern(), b.is_agg())
+            .map_err(|e| FatalError::from_str(&e.to_string(), FatalStrategy::Terminate))?;
+
+        Ok(AggregatableBlockProof::Agg(GeneratedAggBlockProof {
+            intern: proof,
+        }))
+    }
+
+    fn empty(&self) -> Self::Elem {
+        // Expect that there is at least one block to aggregate.
+        unimplemented!("empty two-to-one block agg proof")
+    }
+}
@@ -1,10 +1,27 @@
+use evm_arithmetization::ConsolidatedHash;
+use keccak_hash::H256;
+
 use crate::proof_types::GeneratedBlockProof;
 
+/// The state of the chain at the checkpoint block of an interval, which the
+/// block proofs of the interval are chained from.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Checkpoint {
+    pub block_number: u64,
+    pub state_trie_root: H256,
+    /// The consolidated hashes of the 256 blocks up to the checkpoint block.
+    pub consolidated_hash: ConsolidatedHash,
+}
+
+/// Checks that the previous proof, if any, can be chained with the proofs of
+/// the blocks from `start`, i.e. that it is the proof of the block before
+/// `start`, generated against the same checkpoint.
 pub fn check_previous_proof_and_checkpoint(
-    checkpoint_block_number: u64,
+    checkpoint: &Checkpoint,
     previous_proof: &Option<GeneratedBlockProof>,
     start: u64,
 ) -> anyhow::Result<()> {
+    let checkpoint_block_number = checkpoint.block_number;
     if let Some(proof) = previous_proof {
         if proof.b_height + 1 != start {
             return Err(anyhow::Error::msg(format!(
@@ -18,6 +35,24 @@ pub fn check_previous_proof_and_checkpoint(
                 checkpoint_block_number, start
             )));
         }
+
+        let extra_block_data = proof.public_values()?.extra_block_data;
+        if extra_block_data.checkpoint_state_trie_root != checkpoint.state_trie_root {
+            return Err(anyhow::Error::msg(format!(
+                "Previous proof checkpoint state trie root {:?} does not match the state trie root {:?} of checkpoint block {}",
+                extra_block_data.checkpoint_state_trie_root,
+                checkpoint.state_trie_root,
+                checkpoint_block_number
+            )));
+        }
+        if extra_block_data.checkpoint_consolidated_hash != checkpoint.consolidated_hash {
+            return Err(anyhow::Error::msg(format!(
+                "Previous proof checkpoint consolidated hash {:?} does not match the consolidated hash {:?} of checkpoint block {}",
+                extra_block_data.checkpoint_consolidated_hash,
+                checkpoint.consolidated_hash,
+                checkpoint_block_number
+            )));
+        }
     } else if checkpoint_block_number != start - 1 {
         return Err(anyhow::Error::msg(format!(
             "Previous proof not found. Found checkpoint block number {} whereas range start is {}",
@@ -27,3 +62,56 @@ pub fn check_previous_proof_and_checkpoint(
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use evm_arithmetization::{Field, PublicValues};
+    use plonky2::field::types::Field as _;
+
+    use super::*;
+    use crate::proof_types::dummy_block_proof;
+
+    /// The checkpoint decoded from a proof with zero public inputs.
+    fn zero_checkpoint(block_number: u64) -> Checkpoint {
+        Checkpoint {
+            block_number,
+            state_trie_root: H256::zero(),
+            consolidated_hash: [Field::ZERO; 4],
+        }
+    }
+
+    fn zero_block_proof(b_height: u64) -> GeneratedBlockProof {
+        dummy_block_proof(b_height, vec![Field::ZERO; PublicValues::NUM_PUBLIC_INPUTS])
+    }
+
+    #[test]
+    fn no_previous_proof() {
+        assert!(check_previous_proof_and_checkpoint(&zero_checkpoint(9), &None, 10).is_ok());
+        assert!(check_previous_proof_and_checkpoint(&zero_checkpoint(8), &None, 10).is_err());
+    }
+
+    #[test]
+    fn previous_proof_heights() {
+        let proof = Some(zero_block_proof(9));
+        assert!(check_previous_proof_and_checkpoint(&zero_checkpoint(5), &proof, 10).is_ok());
+        assert!(check_previous_proof_and_checkpoint(&zero_checkpoint(5), &proof, 11).is_err());
+        assert!(check_previous_proof_and_checkpoint(&zero_checkpoint(10), &proof, 10).is_err());
+    }
+
+    #[test]
+    fn previous_proof_public_values() {
+        let proof = Some(zero_block_proof(9));
+
+        let mut checkpoint = zero_checkpoint(5);
+        checkpoint.state_trie_root = H256::repeat_byte(1);
+        assert!(check_previous_proof_and_checkpoint(&checkpoint, &proof, 10).is_err());
+
+        let mut checkpoint = zero_checkpoint(5);
+        checkpoint.consolidated_hash[0] = Field::ONE;
+        assert!(check_previous_proof_and_checkpoint(&checkpoint, &proof, 10).is_err());
+
+        // Proofs whose public values can't be decoded are rejected.
+        let proof = Some(dummy_block_proof(9, vec![Field::ZERO; 8]));
+        assert!(check_previous_proof_and_checkpoint(&zero_checkpoint(5), &proof, 10).is_err());
+    }
+}
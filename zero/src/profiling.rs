@@ -0,0 +1,110 @@
+//! Optional profiling of the witness generation of the segments proven by the
+//! leader and the worker, written as flamegraph-compatible folded stacks.
+
+use std::fs::File;
+use std::io::{BufWriter, Write as _};
+use std::path::PathBuf;
+
+use clap::{Args, ValueEnum, ValueHint};
+use evm_arithmetization::generation::profiler::{
+    set_profile_observer, Costs, CycleProfile, SegmentId,
+};
+use once_cell::sync::OnceCell;
+use tracing::{error, info};
+
+const HELP_HEADING: &str = "Profiling options";
+
+/// The directory the profiles are written to, and what they are weighted by.
+static PROFILE_OUTPUT: OnceCell<(PathBuf, ProfileWeight)> = OnceCell::new();
+
+/// What the folded stacks of a profile are weighted by.
+#[derive(ValueEnum, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ProfileWeight {
+    /// CPU cycles.
+    #[default]
+    Cycles,
+    /// Rows of the arithmetic table.
+    Arithmetic,
+    /// Rows of the byte packing table.
+    BytePacking,
+    /// Rows of the Keccak table.
+    Keccak,
+    /// Rows of the Keccak sponge table.
+    KeccakSponge,
+    /// Rows of the logic table.
+    Logic,
+    /// Rows of the memory table.
+    Memory,
+}
+
+impl ProfileWeight {
+    fn of(self, costs: &Costs) -> usize {
+        match self {
+            ProfileWeight::Cycles => costs.cycles,
+            ProfileWeight::Arithmetic => costs.rows.arithmetic,
+            ProfileWeight::BytePacking => costs.rows.byte_packing,
+            ProfileWeight::Keccak => costs.rows.keccak,
+            ProfileWeight::KeccakSponge => costs.rows.keccak_sponge,
+            ProfileWeight::Logic => costs.rows.logic,
+            ProfileWeight::Memory => costs.rows.memory,
+        }
+    }
+}
+
+/// Profiling configuration.
+#[derive(Args, Clone, Debug, Default)]
+pub struct CliProfilingConfig {
+    /// If provided, profile the witness generation of each proven segment, and
+    /// write its folded stacks to `b<block>_t<first txn>_s<segment>.folded` in
+    /// this directory. Profiling slows down witness generation.
+    #[arg(long, env = "ZERO_BIN_PROFILE_DIR", value_hint = ValueHint::DirPath, help_heading = HELP_HEADING)]
+    pub profile_dir: Option<PathBuf>,
+    /// What the folded stacks are weighted by.
+    #[arg(long, env = "ZERO_BIN_PROFILE_WEIGHT", value_enum, default_value_t, help_heading = HELP_HEADING)]
+    pub profile_weight: ProfileWeight,
+}
+
+impl CliProfilingConfig {
+    /// Enables profiling, if a profile directory is provided.
+    pub fn start(&self) -> anyhow::Result<()> {
+        let Some(dir) = &self.profile_dir else {
+            return Ok(());
+        };
+
+        std::fs::create_dir_all(dir)?;
+        if PROFILE_OUTPUT
+            .set((dir.clone(), self.profile_weight))
+            .is_err()
+        {
+            anyhow::bail!("profiling was already enabled");
+        }
+        set_profile_observer(write_profile);
+        info!(
+            "writing segment profiles weighted by {:?} to {}",
+            self.profile_weight,
+            dir.display()
+        );
+
+        Ok(())
+    }
+}
+
+fn write_profile(segment: SegmentId, profile: &CycleProfile) {
+    let Some((dir, weight)) = PROFILE_OUTPUT.get() else {
+        return;
+    };
+
+    let path = dir.join(format!(
+        "b{}_t{}_s{}.folded",
+        segment.block_number, segment.txn_number_before, segment.segment_index
+    ));
+    let result = File::create(&path).and_then(|file| {
+        let mut writer = BufWriter::new(file);
+        profile.write_folded(&mut writer, |costs| weight.of(costs))?;
+        writer.flush()
+    });
+    match result {
+        Ok(()) => info!("wrote profile of {segment:?} to {}", path.display()),
+        Err(e) => error!("failed to write profile to {}: {e}", path.display()),
+    }
+}
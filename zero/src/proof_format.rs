@@ -0,0 +1,326 @@
+//! Serialization of proof files, either as JSON or in a compact, versioned
+//! binary container. The format of a proof file is detected when reading it.
+//!
+//! A binary proof file is a sequence of containers, one per proof, laid out as
+//! follows, with integers in little-endian order:
+//!
+//! | Field          | Size   | Description                                      |
+//! |----------------|--------|--------------------------------------------------|
+//! | magic          | 8      | [`MAGIC`]                                        |
+//! | version        | 4      | [`VERSION`]                                      |
+//! | kind           | 1      | 0 for block, 1 for wrapped block and 2 for two-to-one block aggregation proofs |
+//! | block height   | 8      | The block height of the proof, 0 for aggregations |
+//! | circuit digest | 32     | See [`circuit_digest`]                           |
+//! | length         | 8      | The length of the payload                        |
+//! | payload        | length | The CBOR-encoded plonky2 proof, deflated         |
+//!
+//! The circuit digest is the one of the verifier data of the circuit which
+//! generated the proof. It lets a verifier reject proofs of another circuit
+//! before verifying them. It is zero, meaning unknown, when the writer doesn't
+//! hold the circuits, which is the usual case of a leader whose remote workers
+//! do the proving: only the proofs of a leader running its workers in process
+//! carry their digest. Readers report a zero digest as unknown, and can then
+//! only find out about a proof of another circuit by verifying it.
+//!
+//! The payload is the CBOR encoding of the serde form of the proof, rather
+//! than plonky2's own `ProofWithPublicInputs::to_bytes` or compressed proofs:
+//! both of those can only be read back with the `CommonCircuitData` of the
+//! circuit, which readers of proof files, such as `verifier inspect` or a
+//! leader loading its previous proof, don't have.
+
+use std::path::Path;
+
+use anyhow::{anyhow, ensure, Context as _, Result};
+use clap::ValueEnum;
+use evm_arithmetization::{FinalPublicValues, ProofWithPublicInputs};
+pub use zk_evm_verifier::{circuit_digest, DIGEST_LEN};
+
+use crate::proof_types::{
+    GeneratedAggBlockProof, GeneratedBlockProof, GeneratedProof, GeneratedWrappedBlockProof,
+};
+use crate::prover_state::VerifierState;
+
+/// The magic bytes at the start of a binary proof container.
+pub const MAGIC: [u8; 8] = *b"ZKEVMPRF";
+/// The version of the binary proof container.
+pub const VERSION: u32 = 1;
+
+const BLOCK_KIND: u8 = 0;
+const WRAPPED_BLOCK_KIND: u8 = 1;
+const AGG_BLOCK_KIND: u8 = 2;
+
+/// Deflate compression level of the payloads.
+const COMPRESSION_LEVEL: u8 = 6;
+
+/// The largest decompressed payload accepted, far above the size of a proof.
+const MAX_PAYLOAD_LEN: usize = 1 << 28;
+
+/// The format of the proof files written by the leader.
+#[derive(ValueEnum, Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord)]
+pub enum ProofFormat {
+    /// serde JSON.
+    #[default]
+    Json,
+    /// Compressed binary containers.
+    Binary,
+}
+
+/// Serializes `proofs` in the given format.
+///
+/// Binary containers are stamped with the digest of the circuit of each proof
+/// in `verifier`, or with a zero digest, meaning unknown, without one.
+pub fn serialize_proofs(
+    proofs: &[GeneratedProof],
+    format: ProofFormat,
+    verifier: Option<&VerifierState>,
+) -> Result<Vec<u8>> {
+    match format {
+        ProofFormat::Json => Ok(serde_json::to_vec(proofs)?),
+        ProofFormat::Binary => {
+            let mut bytes = vec![];
+            for proof in proofs {
+                let digest = verifier
+                    .map(|verifier| verifier.circuit_digest(proof))
+                    .unwrap_or_default();
+                write_container(&mut bytes, proof, digest)?;
+            }
+            Ok(bytes)
+        }
+    }
+}
+
+/// Deserializes proofs, detecting whether they are binary containers, a JSON
+/// array, or a single JSON proof.
+pub fn deserialize_proofs(bytes: &[u8]) -> Result<Vec<GeneratedProof>> {
+    Ok(deserialize_proofs_with_digests(bytes)?
+        .into_iter()
+        .map(|(proof, _)| proof)
+        .collect())
+}
+
+/// Deserializes proofs like [`deserialize_proofs`], along with the digest of
+/// the circuit of each proof, if it is known, i.e. if the proof is a binary
+/// container with a non-zero digest.
+pub fn deserialize_proofs_with_digests(
+    bytes: &[u8],
+) -> Result<Vec<(GeneratedProof, Option<[u8; DIGEST_LEN]>)>> {
+    if bytes.starts_with(&MAGIC) {
+        let mut rest = bytes;
+        let mut proofs = vec![];
+        while !rest.is_empty() {
+            let (proof, digest, tail) = read_container(rest)
+                .with_context(|| format!("invalid binary proof #{}", proofs.len()))?;
+            proofs.push((proof, (digest != [0; DIGEST_LEN]).then_some(digest)));
+            rest = tail;
+        }
+        return Ok(proofs);
+    }
+
+    let des = &mut serde_json::Deserializer::from_slice(bytes);
+    let proofs: Vec<GeneratedProof> = match bytes.iter().find(|b| !b.is_ascii_whitespace()) {
+        Some(b'{') => vec![serde_path_to_error::deserialize(des)?],
+        _ => serde_path_to_error::deserialize(des)?,
+    };
+    Ok(proofs.into_iter().map(|proof| (proof, None)).collect())
+}
+
+/// Reads the proofs in the file at `path`, in any format.
+pub fn read_proofs(path: &Path) -> Result<Vec<GeneratedProof>> {
+    let bytes = std::fs::read(path)?;
+    deserialize_proofs(&bytes)
+        .with_context(|| format!("failed to read proofs from {}", path.display()))
+}
+
+fn write_container(
+    bytes: &mut Vec<u8>,
+    proof: &GeneratedProof,
+    digest: [u8; DIGEST_LEN],
+) -> Result<()> {
+    let (kind, b_height) = match proof {
+        GeneratedProof::Block(info) => (BLOCK_KIND, info.b_height),
+        GeneratedProof::Wrapped(info) => (WRAPPED_BLOCK_KIND, info.b_height),
+        GeneratedProof::Agg(_) => (AGG_BLOCK_KIND, 0),
+    };
+
+    let mut cbor = vec![];
+    ciborium::into_writer(proof.intern(), &mut cbor)?;
+    let payload = miniz_oxide::deflate::compress_to_vec(&cbor, COMPRESSION_LEVEL);
+
+    bytes.extend_from_slice(&MAGIC);
+    bytes.extend_from_slice(&VERSION.to_le_bytes());
+    bytes.push(kind);
+    bytes.extend_from_slice(&b_height.to_le_bytes());
+    bytes.extend_from_slice(&digest);
+    bytes.extend_from_slice(&(payload.len() as u64).to_le_bytes());
+    bytes.extend_from_slice(&payload);
+    Ok(())
+}
+
+/// Reads the container at the start of `bytes`, returning the proof, the digest
+/// of its circuit, and the bytes following the container.
+fn read_container(bytes: &[u8]) -> Result<(GeneratedProof, [u8; DIGEST_LEN], &[u8])> {
+    let truncated = || anyhow!("truncated binary proof");
+
+    let (magic, rest) = bytes.split_first_chunk::<8>().ok_or_else(truncated)?;
+    ensure!(*magic == MAGIC, "not a binary proof");
+    let (version, rest) = rest.split_first_chunk::<4>().ok_or_else(truncated)?;
+    let version = u32::from_le_bytes(*version);
+    ensure!(
+        version == VERSION,
+        "unsupported binary proof version {version}"
+    );
+    let (kind, rest) = rest.split_first().ok_or_else(truncated)?;
+    let (b_height, rest) = rest.split_first_chunk::<8>().ok_or_else(truncated)?;
+    let b_height = u64::from_le_bytes(*b_height);
+    let (digest, rest) = rest
+        .split_first_chunk::<DIGEST_LEN>()
+        .ok_or_else(truncated)?;
+    let (len, rest) = rest.split_first_chunk::<8>().ok_or_else(truncated)?;
+    let len = usize::try_from(u64::from_le_bytes(*len))?;
+    ensure!(rest.len() >= len, "truncated binary proof");
+    let (payload, rest) = rest.split_at(len);
+
+    let cbor = miniz_oxide::inflate::decompress_to_vec_with_limit(payload, MAX_PAYLOAD_LEN)
+        .map_err(|e| anyhow!("failed to decompress binary proof: {e:?}"))?;
+    let intern: ProofWithPublicInputs = ciborium::from_reader(cbor.as_slice())?;
+
+    let proof = match *kind {
+        BLOCK_KIND => GeneratedProof::Block(GeneratedBlockProof { b_height, intern }),
+        WRAPPED_BLOCK_KIND => {
+            ensure!(
+                intern.public_inputs.len() >= FinalPublicValues::NUM_PUBLIC_INPUTS,
+                "wrapped block proof has {} public inputs, expected at least {}",
+                intern.public_inputs.len(),
+                FinalPublicValues::NUM_PUBLIC_INPUTS
+            );
+            GeneratedProof::Wrapped(GeneratedWrappedBlockProof {
+                b_height,
+                public_values: FinalPublicValues::from_public_inputs(&intern.public_inputs),
+                intern,
+            })
+        }
+        AGG_BLOCK_KIND => GeneratedProof::Agg(GeneratedAggBlockProof { intern }),
+        kind => anyhow::bail!("unknown binary proof kind {kind}"),
+    };
+
+    Ok((proof, *digest, rest))
+}
+
+#[cfg(test)]
+mod tests {
+    use evm_arithmetization::{Field, VerifierData};
+    use plonky2::field::types::Field as _;
+    use plonky2::gates::noop::NoopGate;
+    use plonky2::plonk::circuit_builder::CircuitBuilder;
+    use plonky2::plonk::circuit_data::CircuitConfig;
+
+    use super::*;
+
+    /// Returns the verifier data of a trivial circuit with the given number of
+    /// public inputs.
+    fn dummy_verifier_data(num_public_inputs: usize) -> VerifierData {
+        let mut builder = CircuitBuilder::new(CircuitConfig::default());
+        builder.add_gate(NoopGate, vec![]);
+        for _ in 0..num_public_inputs {
+            builder.add_virtual_public_input();
+        }
+        builder.build::<_>().verifier_data()
+    }
+
+    fn dummy_block_proof(b_height: u64) -> GeneratedBlockProof {
+        crate::proof_types::dummy_block_proof(
+            b_height,
+            (0..8)
+                .map(|i| Field::from_canonical_u64(b_height + i))
+                .collect(),
+        )
+    }
+
+    #[test]
+    fn proofs_roundtrip_in_all_formats() {
+        let proofs = vec![
+            GeneratedProof::Block(dummy_block_proof(1)),
+            GeneratedProof::Agg(GeneratedAggBlockProof {
+                intern: dummy_block_proof(2).intern,
+            }),
+        ];
+
+        for format in [ProofFormat::Json, ProofFormat::Binary] {
+            let bytes = serialize_proofs(&proofs, format, None).unwrap();
+            assert_eq!(bytes.starts_with(&MAGIC), format == ProofFormat::Binary);
+
+            let deserialized = deserialize_proofs(&bytes).unwrap();
+            assert_eq!(deserialized.len(), proofs.len());
+            for (deserialized, proof) in deserialized.iter().zip(&proofs) {
+                assert_eq!(deserialized.kind(), proof.kind());
+                assert_eq!(deserialized.intern(), proof.intern());
+            }
+        }
+    }
+
+    #[test]
+    fn single_json_proofs_are_detected() {
+        let proof = dummy_block_proof(1);
+        let bytes = serde_json::to_vec(&proof).unwrap();
+
+        let deserialized = deserialize_proofs(&bytes).unwrap();
+        assert_eq!(deserialized.len(), 1);
+        assert_eq!(deserialized[0].intern(), &proof.intern);
+    }
+
+    #[test]
+    fn corrupted_binary_proofs_are_rejected() {
+        let proof = GeneratedProof::Block(dummy_block_proof(1));
+        let bytes = serialize_proofs(&[proof], ProofFormat::Binary, None).unwrap();
+
+        let mut wrong_version = bytes.clone();
+        wrong_version[MAGIC.len()] = 2;
+        assert!(deserialize_proofs(&wrong_version).is_err());
+
+        let mut wrong_kind = bytes.clone();
+        wrong_kind[MAGIC.len() + 4] = 3;
+        assert!(deserialize_proofs(&wrong_kind).is_err());
+
+        // A block proof has too few public inputs for a wrapped block proof.
+        let mut wrong_public_inputs = bytes.clone();
+        wrong_public_inputs[MAGIC.len() + 4] = WRAPPED_BLOCK_KIND;
+        assert!(deserialize_proofs(&wrong_public_inputs).is_err());
+
+        assert!(deserialize_proofs(&bytes[..bytes.len() - 1]).is_err());
+    }
+
+    #[test]
+    fn binary_proofs_carry_the_digest_of_their_circuit() {
+        let verifier = VerifierState {
+            state: dummy_verifier_data(8),
+            block_wrapper: dummy_verifier_data(9),
+            two_to_one_block: dummy_verifier_data(10),
+        };
+        let proofs = vec![
+            GeneratedProof::Block(dummy_block_proof(1)),
+            GeneratedProof::Agg(GeneratedAggBlockProof {
+                intern: dummy_block_proof(2).intern,
+            }),
+        ];
+        assert_ne!(
+            verifier.circuit_digest(&proofs[0]),
+            verifier.circuit_digest(&proofs[1])
+        );
+
+        // The digests come from the verifier data, not from the proofs.
+        let bytes = serialize_proofs(&proofs, ProofFormat::Binary, Some(&verifier)).unwrap();
+        let deserialized = deserialize_proofs_with_digests(&bytes).unwrap();
+        assert_eq!(deserialized.len(), proofs.len());
+        for ((deserialized, digest), proof) in deserialized.iter().zip(&proofs) {
+            assert_eq!(deserialized.intern(), proof.intern());
+            assert_eq!(*digest, Some(verifier.circuit_digest(proof)));
+        }
+
+        // Without the circuits, the digests are unknown.
+        for format in [ProofFormat::Json, ProofFormat::Binary] {
+            let bytes = serialize_proofs(&proofs, format, None).unwrap();
+            let deserialized = deserialize_proofs_with_digests(&bytes).unwrap();
+            assert!(deserialized.iter().all(|(_, digest)| digest.is_none()));
+        }
+    }
+}
@@ -3,10 +3,13 @@
 
 use evm_arithmetization::{
     fixed_recursive_verifier::{extract_block_final_public_values, extract_two_to_one_block_hash},
-    BlockHeight, Hash, Hasher, ProofWithPublicInputs, ProofWithPublicValues,
+    BlockHeight, FinalPublicValues, Hash, Hasher, ProofWithPublicInputs, ProofWithPublicValues,
+    PublicValues,
 };
+use plonky2::hash::hash_types::NUM_HASH_OUT_ELTS;
 use plonky2::plonk::config::Hasher as _;
 use serde::{Deserialize, Serialize};
+use serde_json::json;
 
 /// A block proof along with the block height against which this proof ensures
 /// the validity since the last proof checkpoint.
@@ -18,6 +21,32 @@ pub struct GeneratedBlockProof {
     pub intern: ProofWithPublicInputs,
 }
 
+impl GeneratedBlockProof {
+    /// Decodes the public values of the proof from its public inputs.
+    pub fn public_values(&self) -> anyhow::Result<PublicValues> {
+        let public_inputs = &self.intern.public_inputs;
+        anyhow::ensure!(
+            public_inputs.len() >= PublicValues::NUM_PUBLIC_INPUTS,
+            "block proof has {} public inputs, expected at least {}",
+            public_inputs.len(),
+            PublicValues::NUM_PUBLIC_INPUTS
+        );
+        Ok(PublicValues::from_public_inputs(public_inputs))
+    }
+}
+
+/// A wrapped block proof, along with its final public values, which are all
+/// that is needed to connect it with other chains' proofs.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct GeneratedWrappedBlockProof {
+    /// Associated block height.
+    pub b_height: BlockHeight,
+    /// Underlying plonky2 proof.
+    pub intern: ProofWithPublicInputs,
+    /// Public values of the proof.
+    pub public_values: FinalPublicValues,
+}
+
 /// An aggregation block proof along with its hashed public values, for proper
 /// connection with other proofs.
 ///
@@ -119,8 +148,6 @@ impl AggregatableBlockProof {
         }
     }
 
-    // TODO(Robin): https://github.com/0xPolygonZero/zk_evm/issues/387
-    #[allow(unused)]
     pub(crate) const fn is_agg(&self) -> bool {
         match self {
             AggregatableBlockProof::Block(_) => false,
@@ -128,8 +155,6 @@ impl AggregatableBlockProof {
         }
     }
 
-    // TODO(Robin): https://github.com/0xPolygonZero/zk_evm/issues/387
-    #[allow(unused)]
     pub(crate) const fn intern(&self) -> &ProofWithPublicInputs {
         match self {
             AggregatableBlockProof::Block(info) => &info.intern,
@@ -149,3 +174,164 @@ impl From<GeneratedAggBlockProof> for AggregatableBlockProof {
         Self::Agg(v)
     }
 }
+
+/// Any of the proofs output by the leader.
+///
+/// The kind of the proof is detected from its fields when deserializing.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(untagged)]
+pub enum GeneratedProof {
+    /// A wrapped block proof. It comes first, as it has the same fields as a
+    /// block proof, along with its public values.
+    Wrapped(GeneratedWrappedBlockProof),
+    /// A block proof.
+    Block(GeneratedBlockProof),
+    /// A two-to-one block aggregation proof.
+    Agg(GeneratedAggBlockProof),
+}
+
+impl GeneratedProof {
+    pub const fn intern(&self) -> &ProofWithPublicInputs {
+        match self {
+            GeneratedProof::Wrapped(info) => &info.intern,
+            GeneratedProof::Block(info) => &info.intern,
+            GeneratedProof::Agg(info) => &info.intern,
+        }
+    }
+
+    /// Decodes the public values of the proof from its public inputs, into a
+    /// labeled JSON document.
+    ///
+    /// Two-to-one block aggregation proofs only expose the hash of the public
+    /// values of the aggregated blocks.
+    ///
+    /// Fails if the proof has fewer public inputs than its kind requires.
+    pub fn public_values(&self) -> anyhow::Result<serde_json::Value> {
+        let public_inputs = &self.intern().public_inputs;
+        let expected = match self {
+            GeneratedProof::Block(_) => PublicValues::NUM_PUBLIC_INPUTS,
+            GeneratedProof::Wrapped(_) => FinalPublicValues::NUM_PUBLIC_INPUTS,
+            GeneratedProof::Agg(_) => NUM_HASH_OUT_ELTS,
+        };
+        anyhow::ensure!(
+            public_inputs.len() >= expected,
+            "{} proof has {} public inputs, expected at least {}",
+            self.kind(),
+            public_inputs.len(),
+            expected
+        );
+
+        Ok(match self {
+            GeneratedProof::Block(info) => {
+                let pv = PublicValues::from_public_inputs(public_inputs);
+                json!({
+                    "kind": "block",
+                    "b_height": info.b_height,
+                    "trie_roots_before": pv.trie_roots_before,
+                    "trie_roots_after": pv.trie_roots_after,
+                    "burn_addr": pv.burn_addr,
+                    "block_metadata": pv.block_metadata,
+                    "block_hash": pv.block_hashes.cur_hash,
+                    "extra_block_data": pv.extra_block_data,
+                })
+            }
+            GeneratedProof::Wrapped(info) => {
+                let pv = FinalPublicValues::from_public_inputs(public_inputs);
+                json!({
+                    "kind": "wrapped_block",
+                    "b_height": info.b_height,
+                    "chain_id": pv.chain_id,
+                    "checkpoint_state_trie_root": pv.checkpoint_state_trie_root,
+                    "new_state_trie_root": pv.new_state_trie_root,
+                    "checkpoint_consolidated_hash": pv.checkpoint_consolidated_hash,
+                    "new_consolidated_hash": pv.new_consolidated_hash,
+                })
+            }
+            GeneratedProof::Agg(_) => json!({
+                "kind": "two_to_one_block_aggregation",
+                "public_values_hash": extract_two_to_one_block_hash(public_inputs),
+            }),
+        })
+    }
+
+    /// Returns the name of the kind of the proof.
+    pub const fn kind(&self) -> &'static str {
+        match self {
+            GeneratedProof::Wrapped(_) => "wrapped block",
+            GeneratedProof::Block(_) => "block",
+            GeneratedProof::Agg(_) => "two-to-one block aggregation",
+        }
+    }
+}
+
+/// Returns a proof of a trivial circuit with the given public inputs.
+#[cfg(test)]
+pub(crate) fn dummy_block_proof(
+    b_height: BlockHeight,
+    public_inputs: Vec<evm_arithmetization::Field>,
+) -> GeneratedBlockProof {
+    use plonky2::gates::noop::NoopGate;
+    use plonky2::plonk::circuit_builder::CircuitBuilder;
+    use plonky2::plonk::circuit_data::CircuitConfig;
+
+    let mut builder = CircuitBuilder::new(CircuitConfig::default());
+    builder.add_gate(NoopGate, vec![]);
+    for _ in 0..public_inputs.len() {
+        builder.add_virtual_public_input();
+    }
+    let circuit_data = builder.build::<_>();
+
+    GeneratedBlockProof {
+        b_height,
+        intern: plonky2::recursion::dummy_circuit::dummy_proof(
+            &circuit_data,
+            public_inputs.into_iter().enumerate().collect(),
+        )
+        .unwrap(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use evm_arithmetization::Field;
+    use plonky2::field::types::Field as _;
+
+    use super::*;
+
+    #[test]
+    fn public_values() {
+        let block = |num_public_inputs| dummy_block_proof(9, vec![Field::ZERO; num_public_inputs]);
+
+        let proof = GeneratedProof::Block(block(PublicValues::NUM_PUBLIC_INPUTS));
+        let public_values = proof.public_values().unwrap();
+        assert_eq!(public_values["kind"], "block");
+        assert_eq!(public_values["b_height"], 9);
+        let proof = GeneratedProof::Block(block(PublicValues::NUM_PUBLIC_INPUTS - 1));
+        assert!(proof.public_values().is_err());
+
+        let wrapped = |num_public_inputs| {
+            let GeneratedBlockProof { b_height, intern } = block(num_public_inputs);
+            GeneratedProof::Wrapped(GeneratedWrappedBlockProof {
+                b_height,
+                intern,
+                public_values: FinalPublicValues::default(),
+            })
+        };
+        let public_values = wrapped(FinalPublicValues::NUM_PUBLIC_INPUTS)
+            .public_values()
+            .unwrap();
+        assert_eq!(public_values["kind"], "wrapped_block");
+        assert!(wrapped(FinalPublicValues::NUM_PUBLIC_INPUTS - 1)
+            .public_values()
+            .is_err());
+
+        let agg = |num_public_inputs| {
+            GeneratedProof::Agg(GeneratedAggBlockProof {
+                intern: block(num_public_inputs).intern,
+            })
+        };
+        let public_values = agg(NUM_HASH_OUT_ELTS).public_values().unwrap();
+        assert_eq!(public_values["kind"], "two_to_one_block_aggregation");
+        assert!(agg(NUM_HASH_OUT_ELTS - 1).public_values().is_err());
+    }
+}
@@ -9,6 +9,7 @@ use std::sync::Arc;
 use alloy::primitives::U256;
 use anyhow::{Context, Result};
 use evm_arithmetization::Field;
+use evm_arithmetization::ProofWithPublicValues;
 use evm_arithmetization::SegmentDataIterator;
 use futures::{
     future::BoxFuture,
@@ -28,12 +29,18 @@ use tokio::io::AsyncWriteExt;
 use tokio::sync::mpsc::Receiver;
 use tokio::sync::{mpsc, oneshot, Semaphore};
 use trace_decoder::observer::DummyObserver;
-use trace_decoder::{BlockTrace, OtherBlockData, WireDisposition};
+use trace_decoder::{BatchingStrategy, BlockTrace, OtherBlockData, WireDisposition};
 use tracing::{debug, error, info};
 
-use crate::fs::generate_block_proof_file_name;
-use crate::ops;
-use crate::proof_types::GeneratedBlockProof;
+use crate::fs::{
+    generate_agg_block_proof_file_name, generate_block_proof_file_name,
+    generate_wrapped_block_proof_file_name,
+};
+use crate::journal::ProofJournal;
+use crate::proof_format::{serialize_proofs, ProofFormat};
+use crate::proof_types::{AggregatableBlockProof, GeneratedBlockProof, GeneratedProof};
+use crate::prover_state::p_verifier;
+use crate::{metrics, ops};
 
 /// `ProofRuntime` represents the runtime environments used for generating
 /// different types of proofs. It contains separate runtimes for handling:
@@ -72,7 +79,7 @@ pub const WIRE_DISPOSITION: WireDisposition = {
 
 #[derive(Debug, Clone)]
 pub struct ProverConfig {
-    pub batch_size: usize,
+    pub batching: BatchingStrategy,
     pub max_cpu_len_log: usize,
     pub save_inputs_on_error: bool,
     pub test_only: bool,
@@ -81,6 +88,8 @@ pub struct ProverConfig {
     pub block_batch_size: usize,
     pub block_pool_size: usize,
     pub save_tries_on_error: bool,
+    pub wrap: bool,
+    pub proof_format: ProofFormat,
 }
 
 #[derive(Clone, Debug, Deserialize, Serialize)]
@@ -100,11 +109,65 @@ impl BlockProverInput {
         previous: Option<impl Future<Output = Result<GeneratedBlockProof>>>,
         prover_config: Arc<ProverConfig>,
     ) -> Result<GeneratedBlockProof> {
+        let save_inputs_on_error = prover_config.save_inputs_on_error;
+        let (block_number, proof) = self
+            .prove_batches(proof_runtime.clone(), prover_config)
+            .await?;
+
+        let prev = match previous {
+            Some(it) => Some(it.await?),
+            None => None,
+        };
+
+        let block_proof = paladin::directive::Literal(proof)
+            .map(&ops::BlockProof {
+                prev,
+                save_inputs_on_error,
+            })
+            .run(&proof_runtime.light_proof)
+            .await?;
+
+        info!("Successfully proved block {block_number}");
+
+        Ok(block_proof.0)
+    }
+
+    /// Proves the block without a previous block proof, and wraps it for
+    /// two-to-one aggregation with other blocks.
+    pub async fn prove_wrapped(
+        self,
+        proof_runtime: Arc<ProofRuntime>,
+        prover_config: Arc<ProverConfig>,
+    ) -> Result<AggregatableBlockProof> {
+        let save_inputs_on_error = prover_config.save_inputs_on_error;
+        let (block_number, proof) = self
+            .prove_batches(proof_runtime.clone(), prover_config)
+            .await?;
+
+        let block_proof = paladin::directive::Literal(proof)
+            .map(&ops::WrappedBlockProof {
+                save_inputs_on_error,
+            })
+            .run(&proof_runtime.light_proof)
+            .await?;
+
+        info!("Successfully proved and wrapped block {block_number}");
+
+        Ok(block_proof.0)
+    }
+
+    /// Proves all the batches of the block, and aggregates them into a single
+    /// batch aggregation proof.
+    async fn prove_batches(
+        self,
+        proof_runtime: Arc<ProofRuntime>,
+        prover_config: Arc<ProverConfig>,
+    ) -> Result<(u64, ProofWithPublicValues)> {
         use anyhow::Context as _;
 
         let ProverConfig {
             max_cpu_len_log,
-            batch_size,
+            batching,
             save_inputs_on_error,
             ..
         } = *prover_config;
@@ -114,7 +177,7 @@ impl BlockProverInput {
         let block_generation_inputs = trace_decoder::entrypoint(
             self.block_trace,
             self.other_data,
-            batch_size,
+            batching,
             &mut DummyObserver::new(),
             WIRE_DISPOSITION,
         )?;
@@ -311,22 +374,8 @@ impl BlockProverInput {
             let block_number = block_number
                 .to_u64()
                 .context("block number overflows u64")?;
-            let prev = match previous {
-                Some(it) => Some(it.await?),
-                None => None,
-            };
-
-            let block_proof = paladin::directive::Literal(proof)
-                .map(&ops::BlockProof {
-                    prev,
-                    save_inputs_on_error,
-                })
-                .run(&proof_runtime.light_proof)
-                .await?;
-
-            info!("Successfully proved block {block_number}");
 
-            Ok(block_proof.0)
+            Ok((block_number, proof))
         } else {
             anyhow::bail!("AggProof is is not GeneratedAggProof")
         }
@@ -344,7 +393,7 @@ impl BlockProverInput {
 
         let ProverConfig {
             max_cpu_len_log,
-            batch_size,
+            batching,
             save_inputs_on_error,
             save_tries_on_error,
             ..
@@ -356,7 +405,7 @@ impl BlockProverInput {
         let block_generation_inputs = trace_decoder::entrypoint(
             self.block_trace,
             self.other_data,
-            batch_size,
+            batching,
             &mut DummyObserver::new(),
             WIRE_DISPOSITION,
         )?;
@@ -433,11 +482,14 @@ async fn prove_block(
 /// the selected prover configuration. Return the list of block numbers that are
 /// proved and if the proof data is not saved to disk, return the generated
 /// block proofs as well.
+///
+/// If a journal is provided, every block proof is recorded in it.
 pub async fn prove(
     mut block_receiver: Receiver<(BlockProverInput, bool)>,
     proof_runtime: Arc<ProofRuntime>,
     checkpoint_proof: Option<GeneratedBlockProof>,
     prover_config: Arc<ProverConfig>,
+    journal: Option<Arc<ProofJournal>>,
 ) -> Result<()> {
     use tokio::task::JoinSet;
     let mut block_counter: u64 = 0;
@@ -456,9 +508,14 @@ pub async fn prove(
         let prover_config = prover_config.clone();
         let previous_block_proof = prev_proof.take();
         let proof_runtime = proof_runtime.clone();
+        let journal = journal.clone();
         let block_number = block_prover_input.get_block_number();
 
-        let prove_permit = PARALLEL_BLOCK_PROVING_PERMIT_POOL.acquire().await?;
+        let queued = metrics::GaugeGuard::new(&metrics::BLOCK_PROVING_QUEUE_DEPTH);
+        let prove_permit = PARALLEL_BLOCK_PROVING_PERMIT_POOL.acquire().await;
+        drop(queued);
+        let prove_permit = prove_permit?;
+        let in_flight = metrics::GaugeGuard::new(&metrics::BLOCK_PROVING_IN_FLIGHT);
 
         let _abort_handle = task_set.spawn(async move {
             let block_number = block_prover_input.get_block_number();
@@ -466,12 +523,12 @@ pub async fn prove(
             // Prove the block
             let block_proof = prove_block(
                 block_prover_input,
-                proof_runtime,
+                proof_runtime.clone(),
                 previous_block_proof,
                 prover_config.clone(),
             )
             .then(move |proof| async move {
-                drop(prove_permit);
+                drop((prove_permit, in_flight));
                 let proof = proof.inspect_err(|e| {
                     error!("failed to generate proof for block {block_number}, error {e:?}")
                 })?;
@@ -483,20 +540,41 @@ pub async fn prove(
                 // `keep_intermediate_proofs` is set, output all block proofs to disk.
                 let is_block_batch_finished =
                     block_counter % prover_config.block_batch_size as u64 == 0;
-                if !prover_config.test_only
+                let output_proof = !prover_config.test_only
                     && (is_last_block
                         || prover_config.keep_intermediate_proofs
-                        || is_block_batch_finished)
-                {
-                    write_proof_to_dir(&prover_config.proof_output_dir, proof.clone())
-                        .await
-                        .inspect_err(|e| error!("failed to output proof for block {block_number} to directory {e:?}"))?;
+                        || is_block_batch_finished);
+                if output_proof {
+                    write_proof_to_dir(
+                        &prover_config.proof_output_dir,
+                        proof.clone(),
+                        prover_config.proof_format,
+                    )
+                    .await
+                    .inspect_err(|e| {
+                        error!("failed to output proof for block {block_number} to directory {e:?}")
+                    })?;
                 }
 
+                if let Some(journal) = journal {
+                    journal.record(&proof).inspect_err(|e| {
+                        error!(
+                            "failed to record proof for block {block_number} in the journal {e:?}"
+                        )
+                    })?;
+                }
+
+                // The next block only waits for this proof, so it is sent before
+                // the proof is wrapped.
+                let to_wrap = (output_proof && prover_config.wrap).then(|| proof.clone());
                 if tx.send(proof).is_err() {
                     anyhow::bail!("Failed to send proof for block {block_number}");
                 }
 
+                if let Some(proof) = to_wrap {
+                    tokio::spawn(wrap_block_proof(proof, proof_runtime, prover_config)).await??;
+                }
+
                 Ok(block_number)
             })
             .await;
@@ -518,32 +596,175 @@ pub async fn prove(
     Ok(())
 }
 
-/// Write the proof to the `output_dir` directory.
-async fn write_proof_to_dir(output_dir: &Path, proof: GeneratedBlockProof) -> Result<()> {
-    // Check if output directory exists, and create one if it doesn't.
-    if !output_dir.exists() {
-        info!("Created output directory {:?}", output_dir.display());
-        std::fs::create_dir(output_dir)?;
+/// Prove all the blocks in the input independently of each other, without
+/// previous block proofs, and aggregate them into a single two-to-one block
+/// aggregation proof, which is written to the proof output directory.
+///
+/// In test-only mode, only the execution of the blocks is simulated.
+pub async fn prove_two_to_one(
+    mut block_receiver: Receiver<(BlockProverInput, bool)>,
+    proof_runtime: Arc<ProofRuntime>,
+    prover_config: Arc<ProverConfig>,
+) -> Result<()> {
+    use tokio::task::JoinSet;
+    let mut task_set: JoinSet<Result<Option<AggregatableBlockProof>>> = JoinSet::new();
+
+    PARALLEL_BLOCK_PROVING_PERMIT_POOL.add_permits(prover_config.block_pool_size);
+
+    while let Some((block_prover_input, is_last_block)) = block_receiver.recv().await {
+        let prover_config = prover_config.clone();
+        let proof_runtime = proof_runtime.clone();
+
+        let queued = metrics::GaugeGuard::new(&metrics::BLOCK_PROVING_QUEUE_DEPTH);
+        let prove_permit = PARALLEL_BLOCK_PROVING_PERMIT_POOL.acquire().await;
+        drop(queued);
+        let prove_permit = prove_permit?;
+        let in_flight = metrics::GaugeGuard::new(&metrics::BLOCK_PROVING_IN_FLIGHT);
+
+        let _abort_handle = task_set.spawn(async move {
+            let block_number = block_prover_input.get_block_number();
+            info!("Proving block {block_number}");
+            let block_proof = match prover_config.test_only {
+                true => block_prover_input
+                    .prove_test(
+                        proof_runtime,
+                        None::<future::Ready<Result<GeneratedBlockProof>>>,
+                        prover_config,
+                    )
+                    .await
+                    .map(|_| None),
+                false => block_prover_input
+                    .prove_wrapped(proof_runtime, prover_config)
+                    .await
+                    .map(Some),
+            };
+            drop((prove_permit, in_flight));
+            block_proof.inspect_err(|e| {
+                error!("failed to generate proof for block {block_number}, error {e:?}")
+            })
+        });
+        if is_last_block {
+            break;
+        }
+    }
+
+    let mut block_proofs = vec![];
+    while let Some(res) = task_set.join_next().await {
+        block_proofs.extend(res??);
+    }
+    if prover_config.test_only {
+        return Ok(());
     }
 
+    // Keep the aggregated blocks in order, regardless of which one was proven
+    // first.
+    block_proofs.sort_by_key(|proof| match proof {
+        AggregatableBlockProof::Block(block_proof) => block_proof.b_height,
+        AggregatableBlockProof::Agg(_) => unreachable!("blocks are not aggregated yet"),
+    });
+    let (first_block, last_block) = match (block_proofs.first(), block_proofs.last()) {
+        (Some(AggregatableBlockProof::Block(first)), Some(AggregatableBlockProof::Block(last))) => {
+            (first.b_height, last.b_height)
+        }
+        _ => anyhow::bail!("no block to aggregate"),
+    };
+    // An aggregation proof needs two children, so a single block is aggregated
+    // with itself.
+    if block_proofs.len() == 1 {
+        block_proofs.push(block_proofs[0].clone());
+    }
+
+    let agg_proof = Directive::fold(
+        IndexedStream::from(block_proofs),
+        &ops::TwoToOneBlockAggProof,
+    )
+    .run(&proof_runtime.light_proof)
+    .await?;
+    let AggregatableBlockProof::Agg(agg_proof) = agg_proof else {
+        anyhow::bail!("AggregatableBlockProof is not GeneratedAggBlockProof")
+    };
+    info!("Successfully aggregated blocks {first_block} to {last_block}");
+
+    let agg_proof_file_path = generate_agg_block_proof_file_name(
+        &prover_config.proof_output_dir.to_str(),
+        first_block,
+        last_block,
+    );
+    write_proof_file(
+        &agg_proof_file_path,
+        GeneratedProof::Agg(agg_proof),
+        prover_config.proof_format,
+    )
+    .await
+}
+
+/// Write the proof to the `output_dir` directory.
+async fn write_proof_to_dir(
+    output_dir: &Path,
+    proof: GeneratedBlockProof,
+    format: ProofFormat,
+) -> Result<()> {
     let block_proof_file_path =
         generate_block_proof_file_name(&output_dir.to_str(), proof.b_height);
 
-    // Serialize as a single element array to match the expected format.
-    let proof_serialized = serde_json::to_vec(&vec![proof])?;
+    write_proof_file(&block_proof_file_path, GeneratedProof::Block(proof), format).await
+}
+
+/// Wraps the block proof, and writes the wrapped proof to the proof output
+/// directory.
+async fn wrap_block_proof(
+    proof: GeneratedBlockProof,
+    proof_runtime: Arc<ProofRuntime>,
+    prover_config: Arc<ProverConfig>,
+) -> Result<()> {
+    let block_number = proof.b_height;
+    let wrapped_proof = paladin::directive::Literal(proof)
+        .map(&ops::BlockWrapperProof {
+            save_inputs_on_error: prover_config.save_inputs_on_error,
+        })
+        .run(&proof_runtime.light_proof)
+        .await
+        .inspect_err(|e| error!("failed to wrap proof for block {block_number} {e:?}"))?;
+    let wrapped_proof_file_path = generate_wrapped_block_proof_file_name(
+        &prover_config.proof_output_dir.to_str(),
+        block_number,
+    );
+    write_proof_file(
+        &wrapped_proof_file_path,
+        GeneratedProof::Wrapped(wrapped_proof.0),
+        prover_config.proof_format,
+    )
+    .await
+    .inspect_err(|e| {
+        error!("failed to output wrapped proof for block {block_number} to directory {e:?}")
+    })
+}
 
-    if let Some(parent) = block_proof_file_path.parent() {
-        tokio::fs::create_dir_all(parent).await?;
+async fn write_proof_file(
+    proof_file_path: &Path,
+    proof: GeneratedProof,
+    format: ProofFormat,
+) -> Result<()> {
+    // Check if output directory exists, and create one if it doesn't.
+    if let Some(parent) = proof_file_path.parent() {
+        if !parent.exists() {
+            info!("Created output directory {:?}", parent.display());
+            tokio::fs::create_dir_all(parent).await?;
+        }
     }
 
-    let mut f = tokio::fs::File::create(block_proof_file_path.clone()).await?;
+    // JSON proofs are serialized as a single element array to match the
+    // expected format.
+    let proof_serialized = serialize_proofs(&[proof], format, p_verifier())?;
+
+    let mut f = tokio::fs::File::create(proof_file_path).await?;
     f.write_all(&proof_serialized)
         .await
         .context("Failed to write proof to disk")?;
 
     info!(
         "Successfully wrote to disk proof file {}",
-        block_proof_file_path.display()
+        proof_file_path.display()
     );
     Ok(())
 }
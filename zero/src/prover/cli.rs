@@ -1,6 +1,9 @@
 use std::path::PathBuf;
 
-use clap::{Args, ValueHint};
+use clap::{Args, ValueEnum, ValueHint};
+use trace_decoder::{BatchingStrategy, TxnCostEstimate};
+
+use crate::proof_format::ProofFormat;
 
 const HELP_HEADING: &str = "Prover options";
 
@@ -20,6 +23,15 @@ pub struct CliProverConfig {
     /// Number of transactions in a batch to process at once.
     #[arg(short, long, env="ZERO_BIN_BATCH_SIZE", help_heading = HELP_HEADING, default_value_t = 10)]
     batch_size: usize,
+    /// If provided, batch transactions by their estimated cost instead of by
+    /// count, up to this total cost per batch. This balances the number of
+    /// segments per batch when a few transactions are much heavier than the
+    /// others.
+    #[arg(long, env="ZERO_BIN_BATCH_COST", conflicts_with = "batch_size", help_heading = HELP_HEADING)]
+    batch_cost: Option<u64>,
+    /// How the cost of a transaction is estimated, with `--batch-cost`.
+    #[arg(long, env="ZERO_BIN_BATCH_COST_ESTIMATE", value_enum, help_heading = HELP_HEADING, default_value_t)]
+    batch_cost_estimate: BatchCostEstimate,
     /// If true, save the public inputs to disk on error.
     #[arg(short='i', long, env="ZERO_BIN_SAVE_INPUTS_ON_ERROR", help_heading = HELP_HEADING, default_value_t = false)]
     save_inputs_on_error: bool,
@@ -47,12 +59,46 @@ pub struct CliProverConfig {
     /// be greater than zero.
     #[arg(long, env = "ZERO_BIN_BLOCK_POOL_SIZE", default_value_t = 16)]
     block_pool_size: usize,
+    /// If true, also wrap the block proofs written to the proof output
+    /// directory, and write the wrapped proofs along with their final public
+    /// values.
+    #[arg(long, env = "ZERO_BIN_WRAP", help_heading = HELP_HEADING, default_value_t = false)]
+    wrap: bool,
+    /// The format of the proof files written to the proof output directory.
+    /// Proof files are read in either format.
+    #[arg(long, env = "ZERO_BIN_PROOF_FORMAT", value_enum, help_heading = HELP_HEADING, default_value_t = ProofFormat::Json)]
+    proof_format: ProofFormat,
+}
+
+/// How the cost of a transaction is estimated when batching by cost.
+#[derive(ValueEnum, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Debug, Default)]
+pub enum BatchCostEstimate {
+    /// The gas used by the transaction.
+    #[default]
+    GasUsed,
+    /// The number of accounts and storage slots accessed by the transaction.
+    StateAccesses,
+}
+
+impl From<BatchCostEstimate> for TxnCostEstimate {
+    fn from(estimate: BatchCostEstimate) -> Self {
+        match estimate {
+            BatchCostEstimate::GasUsed => TxnCostEstimate::GasUsed,
+            BatchCostEstimate::StateAccesses => TxnCostEstimate::StateAccesses,
+        }
+    }
 }
 
 impl From<CliProverConfig> for super::ProverConfig {
     fn from(cli: CliProverConfig) -> Self {
         Self {
-            batch_size: cli.batch_size,
+            batching: match cli.batch_cost {
+                Some(budget) => BatchingStrategy::Cost {
+                    estimate: cli.batch_cost_estimate.into(),
+                    budget,
+                },
+                None => BatchingStrategy::Count(cli.batch_size),
+            },
             max_cpu_len_log: cli.max_cpu_len_log,
             save_inputs_on_error: cli.save_inputs_on_error,
             test_only: cli.test_only,
@@ -61,6 +107,8 @@ impl From<CliProverConfig> for super::ProverConfig {
             block_batch_size: cli.block_batch_size,
             block_pool_size: cli.block_pool_size,
             save_tries_on_error: false,
+            wrap: cli.wrap,
+            proof_format: cli.proof_format,
         }
     }
 }
@@ -23,12 +23,16 @@ use evm_arithmetization::{
     AllStark, GenerationSegmentData, RecursiveCircuitsForTableSize, StarkConfig,
     TrimmedGenerationInputs,
 };
-use evm_arithmetization::{ProofWithPublicInputs, ProofWithPublicValues, VerifierData};
+use evm_arithmetization::{
+    FinalPublicValues, ProofWithPublicInputs, ProofWithPublicValues, VerifierData,
+};
 use plonky2::recursion::cyclic_recursion::check_cyclic_proof_verifier_data;
 use plonky2::util::timing::TimingTree;
 use tracing::info;
 
 use self::circuit::{CircuitConfig, NUM_TABLES};
+use crate::proof_format::{circuit_digest, DIGEST_LEN};
+use crate::proof_types::GeneratedProof;
 use crate::prover_state::persistence::{
     BaseProverResource, DiskResource, MonolithicProverResource, RecursiveCircuitResource,
     VerifierResource,
@@ -52,18 +56,30 @@ pub struct VerifierState {
     /// The verification circuit data associated to the block proof layer of the
     /// zkEVM prover state.
     pub state: VerifierData,
+    /// The verification circuit data of the block wrapper circuit.
+    pub block_wrapper: VerifierData,
+    /// The verification circuit data of the two-to-one block aggregation
+    /// circuit.
+    pub two_to_one_block: VerifierData,
 }
 
 /// Extracts the verifier state from the entire prover state.
 impl<T: Borrow<ProverState>> From<T> for VerifierState {
     fn from(prover_state: T) -> Self {
-        VerifierState {
-            state: prover_state.borrow().state.final_verifier_data(),
-        }
+        VerifierState::from_circuits(&prover_state.borrow().state)
     }
 }
 
 impl VerifierState {
+    /// Extracts the verifier state from the prover circuits.
+    pub fn from_circuits(circuits: &AllRecursiveCircuits) -> Self {
+        VerifierState {
+            state: circuits.final_verifier_data(),
+            block_wrapper: circuits.block_wrapper_verifier_data(),
+            two_to_one_block: circuits.two_to_one_block_verifier_data(),
+        }
+    }
+
     /// Verifies a `block_proof`.
     pub fn verify(&self, block_proof: &ProofWithPublicInputs) -> anyhow::Result<()> {
         // Proof verification
@@ -72,6 +88,54 @@ impl VerifierState {
         // Verifier data verification
         check_cyclic_proof_verifier_data(block_proof, &self.state.verifier_only, &self.state.common)
     }
+
+    /// Verifies a wrapped block proof.
+    pub fn verify_block_wrapper(
+        &self,
+        wrapped_proof: &ProofWithPublicInputs,
+    ) -> anyhow::Result<()> {
+        self.block_wrapper.verify(wrapped_proof.clone())
+    }
+
+    /// Verifies a two-to-one block aggregation proof.
+    pub fn verify_two_to_one_block(&self, agg_proof: &ProofWithPublicInputs) -> anyhow::Result<()> {
+        // Proof verification
+        self.two_to_one_block.verify(agg_proof.clone())?;
+
+        // Verifier data verification
+        check_cyclic_proof_verifier_data(
+            agg_proof,
+            &self.two_to_one_block.verifier_only,
+            &self.two_to_one_block.common,
+        )
+    }
+
+    /// Verifies a proof of any kind. The public values of a wrapped block proof
+    /// are checked against its public inputs.
+    pub fn verify_generated_proof(&self, proof: &GeneratedProof) -> anyhow::Result<()> {
+        match proof {
+            GeneratedProof::Block(proof) => self.verify(&proof.intern),
+            GeneratedProof::Wrapped(proof) => {
+                self.verify_block_wrapper(&proof.intern)?;
+                if FinalPublicValues::from_public_inputs(&proof.intern.public_inputs)
+                    != proof.public_values
+                {
+                    anyhow::bail!("public values don't match the wrapped block proof");
+                }
+                Ok(())
+            }
+            GeneratedProof::Agg(proof) => self.verify_two_to_one_block(&proof.intern),
+        }
+    }
+
+    /// Returns the digest of the circuit of a proof of the kind of `proof`.
+    pub fn circuit_digest(&self, proof: &GeneratedProof) -> [u8; DIGEST_LEN] {
+        circuit_digest(match proof {
+            GeneratedProof::Block(_) => &self.state,
+            GeneratedProof::Wrapped(_) => &self.block_wrapper,
+            GeneratedProof::Agg(_) => &self.two_to_one_block,
+        })
+    }
 }
 
 /// The global prover state.
@@ -95,10 +159,21 @@ static P_STATE: OnceLock<ProverState> = OnceLock::new();
 /// It's specified as a `OnceLock` for the same reasons as the prover state.
 static MANAGER: OnceLock<ProverStateManager> = OnceLock::new();
 
+/// The verifier state of the global prover state, extracted when first needed.
+static VERIFIER: OnceLock<VerifierState> = OnceLock::new();
+
 pub fn p_state() -> &'static ProverState {
     P_STATE.get().expect("Prover state is not initialized")
 }
 
+/// Returns the verifier state of the global prover state, if this process
+/// holds the circuits.
+pub fn p_verifier() -> Option<&'static VerifierState> {
+    P_STATE
+        .get()
+        .map(|p_state| VERIFIER.get_or_init(|| p_state.into()))
+}
+
 pub fn p_manager() -> &'static ProverStateManager {
     MANAGER
         .get()
@@ -372,9 +447,7 @@ impl ProverStateManager {
             CircuitPersistence::None => {
                 info!("generating circuit...");
                 let prover_state = self.circuit_config.as_all_recursive_circuits();
-                Ok(VerifierState {
-                    state: prover_state.final_verifier_data(),
-                })
+                Ok(VerifierState::from_circuits(&prover_state))
             }
             CircuitPersistence::Disk(_) => {
                 info!("attempting to load preprocessed verifier circuit from disk...");
@@ -383,17 +456,17 @@ impl ProverStateManager {
                 match disk_state {
                     Ok(state) => {
                         info!("successfully loaded preprocessed verifier circuit from disk");
-                        Ok(VerifierState { state })
+                        Ok(state)
                     }
                     Err(_) => {
                         info!("failed to load preprocessed verifier circuit from disk. generating it...");
                         let prover_state = self.circuit_config.as_all_recursive_circuits();
 
                         info!("saving preprocessed verifier circuit to disk");
-                        let state = prover_state.final_verifier_data();
+                        let state = VerifierState::from_circuits(&prover_state);
                         VerifierResource::put(&self.circuit_config, &state)?;
 
-                        Ok(VerifierState { state })
+                        Ok(state)
                     }
                 }
             }
@@ -19,6 +19,8 @@ use plonky2::util::serialization::{
 use thiserror::Error;
 
 use super::circuit::{Circuit, CircuitConfig};
+use super::VerifierState;
+use crate::metrics;
 
 const PROVER_STATE_FILE_PREFIX: &str = "prover_state";
 const VERIFIER_STATE_FILE_PREFIX: &str = "verifier_state";
@@ -74,6 +76,8 @@ pub(crate) trait DiskResource {
     /// The input type / configuration used to generate a unique path to the
     /// resource on disk.
     type PathConstrutor;
+    /// A name for the kind of resource, used in metrics.
+    const NAME: &'static str;
 
     /// Returns the path to the resource on disk.
     fn path(p: &Self::PathConstrutor) -> impl AsRef<Path>;
@@ -86,7 +90,15 @@ pub(crate) trait DiskResource {
 
     /// Reads the resource from disk and deserializes it.
     fn get(p: &Self::PathConstrutor) -> Result<Self::Resource, DiskResourceError<Self::Error>> {
-        Self::deserialize(&fs::read(Self::path(p))?)
+        let res = fs::read(Self::path(p))
+            .map_err(DiskResourceError::from)
+            .and_then(|bytes| Self::deserialize(&bytes));
+        match res {
+            Ok(_) => metrics::CIRCUIT_CACHE_HITS_TOTAL.with_label_values(&[Self::NAME]),
+            Err(_) => metrics::CIRCUIT_CACHE_MISSES_TOTAL.with_label_values(&[Self::NAME]),
+        }
+        .inc();
+        res
     }
 
     /// Writes the resource to disk after serializing it.
@@ -125,6 +137,7 @@ impl DiskResource for BaseProverResource {
     type Resource = AllRecursiveCircuits;
     type Error = IoError;
     type PathConstrutor = CircuitConfig;
+    const NAME: &'static str = "base_prover";
 
     fn path(p: &Self::PathConstrutor) -> impl AsRef<Path> {
         format!(
@@ -161,6 +174,7 @@ impl DiskResource for MonolithicProverResource {
     type Resource = AllRecursiveCircuits;
     type Error = IoError;
     type PathConstrutor = CircuitConfig;
+    const NAME: &'static str = "monolithic_prover";
 
     fn path(p: &Self::PathConstrutor) -> impl AsRef<Path> {
         format!(
@@ -196,6 +210,7 @@ impl DiskResource for RecursiveCircuitResource {
     type Resource = RecursiveCircuitsForTableSize;
     type Error = IoError;
     type PathConstrutor = (Circuit, usize);
+    const NAME: &'static str = "recursive_circuit";
 
     fn path((circuit_type, size): &Self::PathConstrutor) -> impl AsRef<Path> {
         format!(
@@ -232,14 +247,16 @@ impl DiskResource for RecursiveCircuitResource {
     }
 }
 
-/// An individual circuit table with a specific size.
+/// The verifier data of the block, block wrapper and two-to-one block
+/// aggregation circuits.
 #[derive(Debug, Default)]
 pub(crate) struct VerifierResource;
 
 impl DiskResource for VerifierResource {
-    type Resource = VerifierData;
+    type Resource = VerifierState;
     type Error = IoError;
     type PathConstrutor = CircuitConfig;
+    const NAME: &'static str = "verifier";
 
     fn path(p: &Self::PathConstrutor) -> impl AsRef<Path> {
         format!(
@@ -251,16 +268,43 @@ impl DiskResource for VerifierResource {
         )
     }
 
+    /// Each verifier data is serialized with its length as a prefix.
     fn serialize(r: &Self::Resource) -> Result<Vec<u8>, DiskResourceError<Self::Error>> {
         let (gate_serializer, _witness_serializer) = get_serializers();
-        r.to_bytes(&gate_serializer)
-            .map_err(DiskResourceError::Serialization)
+        let mut buf = Vec::new();
+        for verifier_data in [&r.state, &r.block_wrapper, &r.two_to_one_block] {
+            let bytes = verifier_data
+                .to_bytes(&gate_serializer)
+                .map_err(DiskResourceError::Serialization)?;
+            buf.extend_from_slice(&(bytes.len() as u64).to_le_bytes());
+            buf.extend_from_slice(&bytes);
+        }
+        Ok(buf)
     }
 
     fn deserialize(bytes: &[u8]) -> Result<Self::Resource, DiskResourceError<Self::Error>> {
         let (gate_serializer, _) = get_serializers();
-        VerifierData::from_bytes(bytes.to_vec(), &gate_serializer)
-            .map_err(DiskResourceError::Serialization)
+        let mut rest = bytes;
+        let mut next = || {
+            let (len, tail) = rest
+                .split_first_chunk::<8>()
+                .ok_or(DiskResourceError::Serialization(IoError))?;
+            let len = usize::try_from(u64::from_le_bytes(*len))
+                .map_err(|_| DiskResourceError::Serialization(IoError))?;
+            if tail.len() < len {
+                return Err(DiskResourceError::Serialization(IoError));
+            }
+            let (verifier_bytes, tail) = tail.split_at(len);
+            rest = tail;
+            VerifierData::from_bytes(verifier_bytes.to_vec(), &gate_serializer)
+                .map_err(DiskResourceError::Serialization)
+        };
+
+        Ok(VerifierState {
+            state: next()?,
+            block_wrapper: next()?,
+            two_to_one_block: next()?,
+        })
     }
 }
 
@@ -271,7 +315,7 @@ pub fn persist_all_to_disk(
     circuit_config: &CircuitConfig,
 ) -> anyhow::Result<()> {
     prover_to_disk(circuit_config, circuits)?;
-    VerifierResource::put(circuit_config, &circuits.final_verifier_data())?;
+    VerifierResource::put(circuit_config, &VerifierState::from_circuits(circuits))?;
 
     Ok(())
 }
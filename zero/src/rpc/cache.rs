@@ -0,0 +1,456 @@
+//! Optional on-disk cache of the raw JSON-RPC responses of the node, so that
+//! block ranges can be fetched or proven again without contacting it.
+//!
+//! Each successful response is stored in its own file, named after the hash of
+//! the method and the parameters of its request. In
+//! [`record`](ResponseCacheMode::Record) mode, the cached responses are served
+//! and the other requests are sent to the node, caching their responses. In
+//! [`replay`](ResponseCacheMode::Replay) mode, a request without a cached
+//! response fails instead, so that a recorded directory can be used as a
+//! fixture with no network.
+//!
+//! Responses which may change over time aren't cached, i.e. those to requests
+//! for the chain head or for a block tag such as `latest`, and `null` results.
+
+use std::future::Future;
+use std::path::PathBuf;
+use std::pin::Pin;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::task::{Context, Poll};
+
+use alloy::primitives::keccak256;
+use alloy::rpc::json_rpc::{
+    RequestPacket, Response, ResponsePacket, ResponsePayload, SerializedRequest,
+};
+use alloy::transports::{TransportError, TransportErrorKind};
+use clap::{Args, ValueEnum, ValueHint};
+use serde_json::value::RawValue;
+use tower::{Layer, Service};
+
+const HELP_HEADING: &str = "RPC cache options";
+
+/// Distinguishes the temporary files of concurrent writes of a response.
+static TEMP_FILE_COUNTER: AtomicU64 = AtomicU64::new(0);
+
+/// What the response cache is used for.
+#[derive(ValueEnum, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ResponseCacheMode {
+    /// Serve the cached responses, and cache the responses of the node to the
+    /// other requests.
+    #[default]
+    Record,
+    /// Only serve the cached responses, failing the other requests without
+    /// contacting the node.
+    Replay,
+}
+
+/// Response cache configuration.
+#[derive(Args, Clone, Debug, Default)]
+pub struct CliResponseCacheConfig {
+    /// If provided, cache the JSON-RPC responses of the node in this
+    /// directory.
+    #[arg(long, env = "ZERO_BIN_RPC_CACHE_DIR", value_hint = ValueHint::DirPath, help_heading = HELP_HEADING)]
+    pub rpc_cache_dir: Option<PathBuf>,
+    /// Whether to record new responses in the cache, or to only replay the
+    /// cached ones.
+    #[arg(long, env = "ZERO_BIN_RPC_CACHE_MODE", value_enum, default_value_t, help_heading = HELP_HEADING)]
+    pub rpc_cache_mode: ResponseCacheMode,
+}
+
+impl CliResponseCacheConfig {
+    /// Opens the response cache, if a cache directory is provided.
+    pub fn open(&self) -> anyhow::Result<Option<ResponseCache>> {
+        self.rpc_cache_dir
+            .as_ref()
+            .map(|dir| ResponseCache::open(dir.clone(), self.rpc_cache_mode))
+            .transpose()
+    }
+}
+
+/// An on-disk cache of JSON-RPC responses, keyed by the method and the
+/// parameters of their requests.
+#[derive(Clone, Debug)]
+pub struct ResponseCache {
+    dir: PathBuf,
+    mode: ResponseCacheMode,
+}
+
+impl ResponseCache {
+    /// Opens the cache in `dir`, which is created in
+    /// [`record`](ResponseCacheMode::Record) mode.
+    pub fn open(dir: PathBuf, mode: ResponseCacheMode) -> anyhow::Result<Self> {
+        match mode {
+            ResponseCacheMode::Record => std::fs::create_dir_all(&dir)?,
+            ResponseCacheMode::Replay => anyhow::ensure!(
+                dir.is_dir(),
+                "{} is not a response cache directory",
+                dir.display()
+            ),
+        }
+        Ok(Self { dir, mode })
+    }
+
+    fn path(&self, request: &SerializedRequest) -> PathBuf {
+        let params = request.params().map_or("null", RawValue::get);
+        let key = keccak256(format!("{}\n{params}", request.method()));
+        self.dir.join(format!("{key:x}.json"))
+    }
+
+    async fn load(
+        &self,
+        request: &SerializedRequest,
+    ) -> Result<Option<Box<RawValue>>, TransportError> {
+        match tokio::fs::read_to_string(self.path(request)).await {
+            Ok(result) => RawValue::from_string(result)
+                .map(Some)
+                .map_err(TransportErrorKind::custom),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(TransportErrorKind::custom(e)),
+        }
+    }
+
+    async fn store(
+        &self,
+        request: &SerializedRequest,
+        result: &RawValue,
+    ) -> Result<(), TransportError> {
+        // Write to a temporary file first, so that a response is never read
+        // while partially written.
+        let path = self.path(request);
+        let temp_path = path.with_extension(format!(
+            "{}.tmp",
+            TEMP_FILE_COUNTER.fetch_add(1, Ordering::Relaxed)
+        ));
+        tokio::fs::write(&temp_path, result.get())
+            .await
+            .map_err(TransportErrorKind::custom)?;
+        tokio::fs::rename(&temp_path, &path)
+            .await
+            .map_err(TransportErrorKind::custom)
+    }
+}
+
+/// Methods whose responses depend on the head of the chain.
+const UNCACHEABLE_METHODS: &[&str] = &["eth_blockNumber", "eth_gasPrice", "eth_syncing"];
+
+/// Block tags which refer to different blocks as the chain progresses.
+const MOVING_BLOCK_TAGS: &[&str] = &["latest", "pending", "safe", "finalized"];
+
+/// Whether the response to `request` can't change over time, and so can be
+/// cached.
+fn is_cacheable(request: &SerializedRequest) -> bool {
+    fn has_moving_block_tag(value: &serde_json::Value) -> bool {
+        match value {
+            serde_json::Value::String(s) => MOVING_BLOCK_TAGS.contains(&s.as_str()),
+            serde_json::Value::Array(values) => values.iter().any(has_moving_block_tag),
+            serde_json::Value::Object(map) => map.values().any(has_moving_block_tag),
+            _ => false,
+        }
+    }
+
+    if UNCACHEABLE_METHODS.contains(&request.method()) {
+        return false;
+    }
+    match request.params() {
+        Some(params) => serde_json::from_str::<serde_json::Value>(params.get())
+            .is_ok_and(|params| !has_moving_block_tag(&params)),
+        None => true,
+    }
+}
+
+/// ResponseCacheLayer
+pub struct ResponseCacheLayer {
+    cache: Option<Arc<ResponseCache>>,
+}
+
+impl ResponseCacheLayer {
+    /// A layer which doesn't cache anything for [`None`].
+    pub fn new(cache: Option<ResponseCache>) -> Self {
+        Self {
+            cache: cache.map(Arc::new),
+        }
+    }
+}
+
+impl<S> Layer<S> for ResponseCacheLayer {
+    type Service = ResponseCacheService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        ResponseCacheService {
+            inner,
+            cache: self.cache.clone(),
+        }
+    }
+}
+
+/// ResponseCacheService
+#[derive(Debug, Clone)]
+pub struct ResponseCacheService<S> {
+    inner: S,
+    cache: Option<Arc<ResponseCache>>,
+}
+
+impl<S> Service<RequestPacket> for ResponseCacheService<S>
+where
+    S: Service<RequestPacket, Response = ResponsePacket, Error = TransportError>
+        + Send
+        + 'static
+        + Clone,
+    S::Future: Send + 'static,
+{
+    type Response = ResponsePacket;
+    type Error = TransportError;
+    type Future = Pin<Box<dyn Future<Output = Result<Self::Response, Self::Error>> + Send>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, req: RequestPacket) -> Self::Future {
+        let inner = self.inner.clone();
+        let cache = self.cache.clone();
+
+        let mut inner = std::mem::replace(&mut self.inner, inner);
+        Box::pin(async move {
+            let Some(cache) = cache else {
+                return inner.call(req).await;
+            };
+
+            let (is_single, requests) = match req {
+                RequestPacket::Single(request) => (true, vec![request]),
+                RequestPacket::Batch(requests) => (false, requests),
+            };
+            let mut responses = Vec::with_capacity(requests.len());
+            let mut missed = vec![];
+            for request in requests {
+                let cached = match is_cacheable(&request) {
+                    true => cache.load(&request).await?,
+                    false => None,
+                };
+                match cached {
+                    Some(result) => responses.push(Response {
+                        id: request.id().clone(),
+                        payload: ResponsePayload::Success(result),
+                    }),
+                    None => missed.push(request),
+                }
+            }
+
+            if let (Some(request), ResponseCacheMode::Replay) = (missed.first(), cache.mode) {
+                return Err(TransportErrorKind::custom_str(&format!(
+                    "no cached response to {} with params {}",
+                    request.method(),
+                    request.params().map_or("null", RawValue::get)
+                )));
+            }
+
+            // Only the requests without a cached response are sent to the node.
+            if !missed.is_empty() {
+                let fetched = match is_single {
+                    true => inner.call(RequestPacket::Single(missed[0].clone())).await?,
+                    false => inner.call(RequestPacket::Batch(missed.clone())).await?,
+                };
+                let fetched = match fetched {
+                    ResponsePacket::Single(response) => vec![response],
+                    ResponsePacket::Batch(responses) => responses,
+                };
+                for response in fetched {
+                    if let (Some(request), ResponsePayload::Success(result)) = (
+                        missed.iter().find(|request| request.id() == &response.id),
+                        &response.payload,
+                    ) {
+                        // Errors and `null` results, e.g. for a block which
+                        // isn't known yet, aren't cached, so that they are
+                        // retried on the next run.
+                        if is_cacheable(request) && result.get() != "null" {
+                            cache.store(request, result).await?;
+                        }
+                    }
+                    responses.push(response);
+                }
+            }
+
+            Ok(match is_single {
+                true => ResponsePacket::Single(
+                    responses.pop().expect("a response for the single request"),
+                ),
+                false => ResponsePacket::Batch(responses),
+            })
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Mutex;
+
+    use alloy::rpc::json_rpc::{Id, Request};
+    use serde_json::json;
+    use tower::ServiceExt as _;
+
+    use super::*;
+
+    /// Opens a cache in a new temporary directory.
+    fn open_cache(mode: ResponseCacheMode) -> ResponseCache {
+        static COUNTER: AtomicU64 = AtomicU64::new(0);
+        let dir = std::env::temp_dir().join(format!(
+            "rpc_cache_{}_{}",
+            std::process::id(),
+            COUNTER.fetch_add(1, Ordering::Relaxed)
+        ));
+        std::fs::create_dir_all(&dir).unwrap();
+        ResponseCache::open(dir, mode).unwrap()
+    }
+
+    fn request(id: u64, method: &'static str, params: serde_json::Value) -> SerializedRequest {
+        Request::new(method, Id::Number(id), params)
+            .serialize()
+            .unwrap()
+    }
+
+    /// Calls a cache service in front of a mock node, which answers
+    /// `eth_getTransactionReceipt` with `null` and the other requests with
+    /// their method, and returns the responses along with the requests
+    /// received by the node.
+    async fn call(
+        cache: &ResponseCache,
+        req: RequestPacket,
+    ) -> Result<(Vec<(Id, String)>, Vec<String>), TransportError> {
+        let received = Arc::new(Mutex::new(vec![]));
+        let node = tower::service_fn({
+            let received = received.clone();
+            move |req: RequestPacket| {
+                let respond = |request: &SerializedRequest| {
+                    received.lock().unwrap().push(request.method().to_string());
+                    let result = match request.method() {
+                        "eth_getTransactionReceipt" => json!(null),
+                        method => json!(method),
+                    };
+                    Response {
+                        id: request.id().clone(),
+                        payload: ResponsePayload::Success(
+                            serde_json::value::to_raw_value(&result).unwrap(),
+                        ),
+                    }
+                };
+                let res = match &req {
+                    RequestPacket::Single(request) => ResponsePacket::Single(respond(request)),
+                    RequestPacket::Batch(requests) => {
+                        ResponsePacket::Batch(requests.iter().map(respond).collect())
+                    }
+                };
+                async move { Ok::<_, TransportError>(res) }
+            }
+        });
+
+        let res = ResponseCacheLayer::new(Some(cache.clone()))
+            .layer(node)
+            .oneshot(req)
+            .await?;
+        let responses = match res {
+            ResponsePacket::Single(response) => vec![response],
+            ResponsePacket::Batch(responses) => responses,
+        }
+        .into_iter()
+        .map(|response| match response.payload {
+            ResponsePayload::Success(result) => (response.id, result.get().to_string()),
+            ResponsePayload::Failure(e) => panic!("unexpected error {e}"),
+        })
+        .collect();
+        let received = received.lock().unwrap().clone();
+        Ok((responses, received))
+    }
+
+    #[tokio::test]
+    async fn test_miss_then_hit() {
+        let cache = open_cache(ResponseCacheMode::Record);
+        let req = RequestPacket::Single(request(1, "eth_getBlockByNumber", json!(["0x1", false])));
+
+        // The response is fetched from the node and stored...
+        let (responses, received) = call(&cache, req.clone()).await.unwrap();
+        assert_eq!(
+            responses,
+            vec![(Id::Number(1), String::from("\"eth_getBlockByNumber\""))]
+        );
+        assert_eq!(received, vec!["eth_getBlockByNumber"]);
+
+        // ...then served from the cache, also in replay mode.
+        for mode in [ResponseCacheMode::Record, ResponseCacheMode::Replay] {
+            let cache = ResponseCache::open(cache.dir.clone(), mode).unwrap();
+            let (responses, received) = call(&cache, req.clone()).await.unwrap();
+            assert_eq!(
+                responses,
+                vec![(Id::Number(1), String::from("\"eth_getBlockByNumber\""))]
+            );
+            assert!(received.is_empty());
+        }
+
+        std::fs::remove_dir_all(&cache.dir).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_replay_miss() {
+        let cache = open_cache(ResponseCacheMode::Replay);
+        let req = RequestPacket::Single(request(1, "eth_getBlockByNumber", json!(["0x1", false])));
+
+        assert!(call(&cache, req).await.is_err());
+
+        std::fs::remove_dir_all(&cache.dir).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_uncacheable() {
+        let cache = open_cache(ResponseCacheMode::Record);
+        let requests = [
+            request(1, "eth_blockNumber", json!([])),
+            request(2, "eth_getBlockByNumber", json!(["latest", false])),
+            request(3, "eth_call", json!([{"data": "0x00"}, "finalized"])),
+            request(4, "eth_getTransactionReceipt", json!(["0x00"])),
+        ];
+
+        for _ in 0..2 {
+            let (_, received) = call(&cache, RequestPacket::Batch(requests.to_vec()))
+                .await
+                .unwrap();
+            assert_eq!(received.len(), requests.len());
+        }
+        assert_eq!(std::fs::read_dir(&cache.dir).unwrap().count(), 0);
+
+        std::fs::remove_dir_all(&cache.dir).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_partial_batch_hit() {
+        let cache = open_cache(ResponseCacheMode::Record);
+        let cached = request(1, "eth_getBlockByNumber", json!(["0x1", false]));
+        let missed = request(2, "eth_getBlockByNumber", json!(["0x2", false]));
+        call(&cache, RequestPacket::Single(cached.clone()))
+            .await
+            .unwrap();
+
+        // Only the missed request is sent to the node.
+        let (mut responses, received) =
+            call(&cache, RequestPacket::Batch(vec![cached, missed.clone()]))
+                .await
+                .unwrap();
+        responses.sort_by_key(|(id, _)| id.as_number());
+        assert_eq!(
+            responses,
+            vec![
+                (Id::Number(1), String::from("\"eth_getBlockByNumber\"")),
+                (Id::Number(2), String::from("\"eth_getBlockByNumber\"")),
+            ]
+        );
+        assert_eq!(received, vec!["eth_getBlockByNumber"]);
+
+        // A partially cached batch fails in replay mode.
+        let cache = ResponseCache::open(cache.dir.clone(), ResponseCacheMode::Replay).unwrap();
+        let other = request(3, "eth_getBlockByNumber", json!(["0x3", false]));
+        assert!(call(&cache, RequestPacket::Batch(vec![missed, other]))
+            .await
+            .is_err());
+
+        std::fs::remove_dir_all(&cache.dir).unwrap();
+    }
+}
@@ -3,7 +3,7 @@ zk_evm_common::check_chain_features!();
 use std::sync::Arc;
 
 use alloy::{
-    primitives::{Address, Bloom, Bytes, FixedBytes, B256, U256},
+    primitives::{address, Address, Bloom, Bytes, FixedBytes, B256, U256},
     providers::Provider,
     rpc::types::eth::{BlockId, BlockTransactionsKind, Withdrawal},
     transports::Transport,
@@ -20,8 +20,10 @@ use serde_json::json;
 use trace_decoder::{BlockLevelData, OtherBlockData};
 use tracing::warn;
 
+use crate::pre_checks::Checkpoint;
 use crate::prover::BlockProverInput;
 
+pub mod cache;
 pub mod jerigon;
 pub mod native;
 pub mod retry;
@@ -198,24 +200,62 @@ where
     fetch_previous_block_hashes_from_block(cached_provider, target_block_number).await
 }
 
-/// Fetches other block data
-async fn fetch_other_block_data<ProviderT, TransportT>(
+/// The header fields which aren't deserialized by the `alloy` version in use.
+#[derive(Debug, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct PragueHeaderFields {
+    /// Only present from Prague onwards, see <https://eips.ethereum.org/EIPS/eip-7685>.
+    requests_hash: Option<B256>,
+}
+
+/// Fetches the `requestsHash` of the header of the target block, which is zero
+/// before Prague.
+async fn fetch_requests_hash<ProviderT, TransportT>(
     cached_provider: Arc<CachedProvider<ProviderT, TransportT>>,
-    target_block_id: BlockId,
-    checkpoint_block_number: u64,
-) -> anyhow::Result<OtherBlockData>
+    target_block_number: u64,
+) -> anyhow::Result<B256>
 where
     ProviderT: Provider<TransportT>,
     TransportT: Transport + Clone,
 {
-    let target_block = cached_provider
-        .get_block(target_block_id, BlockTransactionsKind::Hashes)
+    let header = cached_provider
+        .get_provider()
         .await?
-        .ok_or(anyhow!("target block not found {}", target_block_id))?;
-    let chain_id = cached_provider.get_provider().await?.get_chain_id().await?;
+        .raw_request::<_, Option<PragueHeaderFields>>(
+            "eth_getBlockByNumber".into(),
+            (format!("{:#x}", target_block_number), false),
+        )
+        .await?
+        .ok_or(anyhow!("target block not found {}", target_block_number))?;
+    Ok(header.requests_hash.unwrap_or_default())
+}
+
+/// Returns the address of the deposit contract of the given chain, whose
+/// `DepositEvent` logs are parsed into deposit requests from Prague onwards,
+/// see <https://eips.ethereum.org/EIPS/eip-6110>.
+fn deposit_contract_address(chain_id: u64) -> anyhow::Result<Address> {
+    match chain_id {
+        // Mainnet and Hoodi
+        1 | 560048 => Ok(address!("00000000219ab540356cBB839Cbe05303d7705Fa")),
+        // Sepolia
+        11155111 => Ok(address!("7f02C3E3c98b133055B8B348B2Ac625669Ed295D")),
+        // Holesky
+        17000 => Ok(address!("4242424242424242424242424242424242424242")),
+        _ => Err(anyhow!("unknown deposit contract for chain {chain_id}")),
+    }
+}
 
-    // Grab interval checkpoint block state trie
-    let checkpoint_state_trie_root = cached_provider
+/// Fetches the state trie root and the consolidated previous block hashes of
+/// the checkpoint block.
+pub async fn fetch_checkpoint<ProviderT, TransportT>(
+    cached_provider: Arc<CachedProvider<ProviderT, TransportT>>,
+    checkpoint_block_number: u64,
+) -> anyhow::Result<Checkpoint>
+where
+    ProviderT: Provider<TransportT>,
+    TransportT: Transport + Clone,
+{
+    let state_trie_root = cached_provider
         .get_block(
             checkpoint_block_number.into(),
             BlockTransactionsKind::Hashes,
@@ -227,14 +267,50 @@ where
         ))?
         .header
         .state_root;
-
     let prev_hashes =
-        fetch_previous_block_hashes(cached_provider.clone(), target_block.header.number).await?;
-    let checkpoint_prev_hashes =
         fetch_previous_block_hashes(cached_provider, checkpoint_block_number + 1) // include the checkpoint block
             .await?
             .map(|it| it.compat());
 
+    Ok(Checkpoint {
+        block_number: checkpoint_block_number,
+        state_trie_root: state_trie_root.compat(),
+        consolidated_hash: consolidate_hashes::<Hasher, Field>(&prev_hashes),
+    })
+}
+
+/// Fetches other block data
+async fn fetch_other_block_data<ProviderT, TransportT>(
+    cached_provider: Arc<CachedProvider<ProviderT, TransportT>>,
+    target_block_id: BlockId,
+    checkpoint_block_number: u64,
+) -> anyhow::Result<OtherBlockData>
+where
+    ProviderT: Provider<TransportT>,
+    TransportT: Transport + Clone,
+{
+    let target_block = cached_provider
+        .get_block(target_block_id, BlockTransactionsKind::Hashes)
+        .await?
+        .ok_or(anyhow!("target block not found {}", target_block_id))?;
+    let chain_id = cached_provider.get_provider().await?.get_chain_id().await?;
+
+    let checkpoint = fetch_checkpoint(cached_provider.clone(), checkpoint_block_number).await?;
+
+    let requests_hash = if cfg!(feature = "eth_mainnet") {
+        fetch_requests_hash(cached_provider.clone(), target_block.header.number).await?
+    } else {
+        Default::default()
+    };
+    // There is no deposit request before Prague.
+    let deposit_contract_address = match requests_hash.is_zero() {
+        true => Address::ZERO,
+        false => deposit_contract_address(chain_id)?,
+    };
+
+    let prev_hashes =
+        fetch_previous_block_hashes(cached_provider, target_block.header.number).await?;
+
     let other_data = OtherBlockData {
         b_data: BlockLevelData {
             b_meta: BlockMetadata {
@@ -283,6 +359,13 @@ where
                 } else {
                     Default::default()
                 },
+                // Only present from Prague onwards, see <https://eips.ethereum.org/EIPS/eip-7685>.
+                requests_hash: if cfg!(feature = "eth_mainnet") {
+                    requests_hash.compat()
+                } else {
+                    Default::default()
+                },
+                deposit_contract_address: deposit_contract_address.compat(),
                 block_blob_gas_used: if cfg!(feature = "eth_mainnet") {
                     target_block
                         .header
@@ -317,8 +400,8 @@ where
                 )
                 .collect(),
         },
-        checkpoint_state_trie_root: checkpoint_state_trie_root.compat(),
-        checkpoint_consolidated_hash: consolidate_hashes::<Hasher, Field>(&checkpoint_prev_hashes),
+        checkpoint_state_trie_root: checkpoint.state_trie_root,
+        checkpoint_consolidated_hash: checkpoint.consolidated_hash,
         burn_addr: if cfg!(feature = "cdk_erigon") {
             // TODO: https://github.com/0xPolygonZero/zk_evm/issues/565
             //       Retrieve the actual burn address from `cdk-erigon`.
@@ -336,3 +419,60 @@ where
     };
     Ok(other_data)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_prague_header_fields() {
+        // A block without transactions in the Prague format of
+        // `eth_getBlockByNumber`.
+        let header: PragueHeaderFields = serde_json::from_str(
+            r#"{
+                "baseFeePerGas": "0x7",
+                "blobGasUsed": "0x0",
+                "difficulty": "0x0",
+                "excessBlobGas": "0x0",
+                "extraData": "0x",
+                "gasLimit": "0x1c9c380",
+                "gasUsed": "0x0",
+                "hash": "0x2b7ef1b4d2a5a8e84cb3f0bc9c5dbdaef0e6a1a0d6a8bcc0b5e0e3d9fb4c6c1a",
+                "logsBloom": "0x00000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000",
+                "miner": "0x0000000000000000000000000000000000000000",
+                "mixHash": "0x0000000000000000000000000000000000000000000000000000000000000000",
+                "nonce": "0x0000000000000000",
+                "number": "0x1",
+                "parentBeaconBlockRoot": "0x0000000000000000000000000000000000000000000000000000000000000000",
+                "parentHash": "0x6f2a4cdc30a2d52e5bd3dfd3e4e1e1a0fd2ab4c4ad6b3a1b4f2d1d1b8c0e0b1e",
+                "receiptsRoot": "0x56e81f171bcc55a6ff8345e692c0f86e5b48e01b996cadc001622fb5e363b421",
+                "requestsHash": "0xe3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855",
+                "sha3Uncles": "0x1dcc4de8dec75d7aab85b567b6ccd41ad312451b948a7413f0a142fd40d49347",
+                "size": "0x2a3",
+                "stateRoot": "0x2c5a5bba0e6d0a2a1aab07e7e5e1e8a7a0c6bd6b7ddad1d7c2fd8fd9b1b1f5c7",
+                "timestamp": "0x66f1a0c4",
+                "totalDifficulty": "0x0",
+                "transactions": [],
+                "transactionsRoot": "0x56e81f171bcc55a6ff8345e692c0f86e5b48e01b996cadc001622fb5e363b421",
+                "uncles": [],
+                "withdrawals": [],
+                "withdrawalsRoot": "0x56e81f171bcc55a6ff8345e692c0f86e5b48e01b996cadc001622fb5e363b421"
+            }"#,
+        )
+        .unwrap();
+        // The requests hash of a block without any request, i.e. `sha256("")`.
+        assert_eq!(
+            header.requests_hash,
+            Some(
+                "0xe3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855"
+                    .parse()
+                    .unwrap()
+            )
+        );
+
+        // Pre-Prague headers have no `requestsHash`.
+        let header: PragueHeaderFields =
+            serde_json::from_str(r#"{"number": "0x1", "withdrawals": []}"#).unwrap();
+        assert_eq!(header.requests_hash, None);
+    }
+}
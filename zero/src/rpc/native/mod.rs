@@ -53,8 +53,13 @@ where
         .await?
         .ok_or(anyhow::anyhow!("block not found {}", block_number))?;
 
-    let (code_db, txn_info) =
+    let (mut code_db, txn_info) =
         txn::process_transactions(&block, cached_provider.get_provider().await?.deref()).await?;
+    if cfg!(feature = "eth_mainnet") && block.header.requests_root.is_some() {
+        code_db.extend(
+            state::fetch_requests_predeploys_code(&cached_provider, block.header.number).await?,
+        );
+    }
     let trie_pre_images = state::process_state_witness(cached_provider, block, &txn_info).await?;
 
     Ok(BlockTrace {
@@ -5,7 +5,7 @@ use alloy::primitives::Bytes;
 use alloy::{
     primitives::{keccak256, Address, StorageKey, B256},
     providers::Provider,
-    rpc::types::eth::{Block, BlockTransactionsKind, EIP1186AccountProofResponse},
+    rpc::types::eth::{Block, BlockId, BlockTransactionsKind, EIP1186AccountProofResponse},
     transports::Transport,
 };
 use alloy_compat::Compat;
@@ -16,9 +16,14 @@ use trace_decoder::{
     BlockTraceTriePreImages, SeparateStorageTriesPreImage, SeparateTriePreImage,
     SeparateTriePreImages, TxnInfo,
 };
+use tracing::warn;
 
 use crate::provider::CachedProvider;
 
+/// The maximum number of requests in a JSON-RPC batch, which many providers
+/// limit to 100.
+const MAX_PROOF_BATCH_LEN: usize = 100;
+
 /// Processes the state witness for the given block.
 pub async fn process_state_witness<ProviderT, TransportT>(
     cached_provider: Arc<CachedProvider<ProviderT, TransportT>>,
@@ -29,9 +34,12 @@ where
     ProviderT: Provider<TransportT>,
     TransportT: Transport + Clone,
 {
-    let state_access = process_states_access(txn_infos, &block)?;
+    let mut state_access = process_states_access(txn_infos, &block)?;
 
     let block_number = block.header.number;
+    if cfg!(feature = "eth_mainnet") && block.header.requests_root.is_some() {
+        insert_requests_queues_access(&mut state_access, &cached_provider, block_number).await?;
+    }
     let prev_state_root = cached_provider
         .get_block((block_number - 1).into(), BlockTransactionsKind::Hashes)
         .await?
@@ -55,7 +63,8 @@ where
 }
 
 /// Iterate over the tx_infos and process the state access for each address.
-/// Also includes the state access for the beacon roots contract, withdrawals
+/// Also includes the state access for the beacon roots and history storage
+/// contracts, withdrawals
 /// and the block author.
 ///
 /// Returns a map from address to the set of storage keys accessed by that
@@ -68,6 +77,7 @@ pub fn process_states_access(
 
     if cfg!(feature = "eth_mainnet") {
         insert_beacon_roots_update(&mut state_access, block)?;
+        insert_history_storage_update(&mut state_access, block)?;
     }
 
     if let Some(w) = block.withdrawals.as_ref() {
@@ -113,6 +123,121 @@ fn insert_beacon_roots_update(
     Ok(())
 }
 
+/// Prague HF specific, see <https://eips.ethereum.org/EIPS/eip-2935>.
+fn insert_history_storage_update(
+    state_access: &mut HashMap<Address, HashSet<StorageKey>>,
+    block: &Block,
+) -> anyhow::Result<()> {
+    use alloy::primitives::U256;
+    use evm_arithmetization::testing_utils::{HISTORY_SERVE_WINDOW, HISTORY_STORAGE_ADDRESS};
+
+    let mut keys = HashSet::new();
+    if let Some(parent_number) = block.header.number.checked_sub(1) {
+        let window = HISTORY_SERVE_WINDOW.value.compat();
+        keys.insert((U256::from(parent_number) % window).into());
+    }
+    state_access.insert(HISTORY_STORAGE_ADDRESS.as_fixed_bytes().into(), keys);
+
+    Ok(())
+}
+
+/// Prague HF specific, see <https://eips.ethereum.org/EIPS/eip-7002> and
+/// <https://eips.ethereum.org/EIPS/eip-7251>.
+///
+/// The system calls at the end of the block dequeue the pending requests,
+/// whose location depends on the queue head and tail before the block.
+async fn insert_requests_queues_access<ProviderT, TransportT>(
+    state_access: &mut HashMap<Address, HashSet<StorageKey>>,
+    cached_provider: &CachedProvider<ProviderT, TransportT>,
+    block_number: u64,
+) -> anyhow::Result<()>
+where
+    ProviderT: Provider<TransportT>,
+    TransportT: Transport + Clone,
+{
+    use alloy::primitives::U256;
+    use evm_arithmetization::testing_utils::{
+        CONSOLIDATION_REQUEST_PREDEPLOY_ADDRESS, CONSOLIDATION_REQUEST_SLOTS, EXCESS_REQUESTS_SLOT,
+        MAX_CONSOLIDATION_REQUESTS_PER_BLOCK, MAX_WITHDRAWAL_REQUESTS_PER_BLOCK,
+        REQUESTS_COUNT_SLOT, REQUESTS_QUEUE_HEAD_SLOT, REQUESTS_QUEUE_OFFSET,
+        REQUESTS_QUEUE_TAIL_SLOT, WITHDRAWAL_REQUEST_PREDEPLOY_ADDRESS, WITHDRAWAL_REQUEST_SLOTS,
+    };
+
+    for (address, slots_per_request, max_per_block) in [
+        (
+            WITHDRAWAL_REQUEST_PREDEPLOY_ADDRESS,
+            WITHDRAWAL_REQUEST_SLOTS,
+            MAX_WITHDRAWAL_REQUESTS_PER_BLOCK,
+        ),
+        (
+            CONSOLIDATION_REQUEST_PREDEPLOY_ADDRESS,
+            CONSOLIDATION_REQUEST_SLOTS,
+            MAX_CONSOLIDATION_REQUESTS_PER_BLOCK,
+        ),
+    ] {
+        let address = Address::from(address.to_fixed_bytes());
+        let provider = cached_provider.get_provider().await?;
+        let load = |slot: u64| {
+            provider
+                .get_storage_at(address, U256::from(slot))
+                .block_id((block_number - 1).into())
+        };
+        let head = load(REQUESTS_QUEUE_HEAD_SLOT)
+            .await
+            .context("Failed to get requests queue head")?;
+        let tail = load(REQUESTS_QUEUE_TAIL_SLOT)
+            .await
+            .context("Failed to get requests queue tail")?;
+        let num_dequeued = tail.saturating_sub(head).min(U256::from(max_per_block));
+
+        let keys = state_access.entry(address).or_default();
+        keys.extend(
+            [
+                EXCESS_REQUESTS_SLOT,
+                REQUESTS_COUNT_SLOT,
+                REQUESTS_QUEUE_HEAD_SLOT,
+                REQUESTS_QUEUE_TAIL_SLOT,
+            ]
+            .map(|slot| StorageKey::from(U256::from(slot))),
+        );
+        let first_slot = U256::from(REQUESTS_QUEUE_OFFSET) + head * U256::from(slots_per_request);
+        let num_slots = num_dequeued.to::<u64>() * slots_per_request;
+        keys.extend((0..num_slots).map(|offset| StorageKey::from(first_slot + U256::from(offset))));
+    }
+
+    Ok(())
+}
+
+/// Fetches the code of the request queue predeploys, which the kernel runs at
+/// the end of Prague blocks.
+pub(super) async fn fetch_requests_predeploys_code<ProviderT, TransportT>(
+    cached_provider: &CachedProvider<ProviderT, TransportT>,
+    block_number: u64,
+) -> anyhow::Result<Vec<Vec<u8>>>
+where
+    ProviderT: Provider<TransportT>,
+    TransportT: Transport + Clone,
+{
+    use evm_arithmetization::testing_utils::{
+        CONSOLIDATION_REQUEST_PREDEPLOY_ADDRESS, WITHDRAWAL_REQUEST_PREDEPLOY_ADDRESS,
+    };
+
+    let provider = cached_provider.get_provider().await?;
+    let mut out = vec![];
+    for address in [
+        WITHDRAWAL_REQUEST_PREDEPLOY_ADDRESS,
+        CONSOLIDATION_REQUEST_PREDEPLOY_ADDRESS,
+    ] {
+        let code = provider
+            .get_code_at(address.to_fixed_bytes().into())
+            .block_id((block_number - 1).into())
+            .await
+            .context("Failed to get request predeploy code")?;
+        out.push(code.to_vec());
+    }
+    Ok(out)
+}
+
 /// Generates the state witness for the given block.
 async fn generate_state_witness<ProviderT, TransportT>(
     prev_state_root: B256,
@@ -167,7 +292,11 @@ fn conv_vec_bytes(bytes: Vec<Bytes>) -> Vec<Vec<u8>> {
     bytes.into_iter().map(|bytes| bytes.to_vec()).collect()
 }
 
-/// Fetches the proof data for the given accounts and associated storage keys.
+/// Fetches the proof data for the given accounts and associated storage keys,
+/// before and after the block.
+///
+/// The `eth_getProof` requests are sent in JSON-RPC batches, falling back to
+/// one request per proof if the provider rejects batches.
 async fn fetch_proof_data<ProviderT, TransportT>(
     accounts_state: HashMap<Address, HashSet<StorageKey>>,
     provider: Arc<CachedProvider<ProviderT, TransportT>>,
@@ -180,37 +309,72 @@ where
     ProviderT: Provider<TransportT>,
     TransportT: Transport + Clone,
 {
-    let account_proofs_fut = accounts_state
-        .clone()
+    let accounts_state = accounts_state
         .into_iter()
-        .map(|(address, keys)| {
-            let provider = provider.clone();
-            async move {
-                let proof = provider
-                    .get_provider()
-                    .await?
-                    .get_proof(address, keys.into_iter().collect())
-                    .block_id((block_number - 1).into())
-                    .await
-                    .context("Failed to get proof for account")?;
-                anyhow::Result::Ok((address, proof))
-            }
-        })
+        .map(|(address, keys)| (address, keys.into_iter().collect::<Vec<_>>()))
         .collect::<Vec<_>>();
 
-    let next_account_proofs_fut = accounts_state.into_iter().map(|(address, keys)| {
-        let provider = provider.clone();
-        async move {
-            let proof = provider
-                .get_provider()
-                .await?
-                .get_proof(address, keys.into_iter().collect())
-                .block_id(block_number.into())
-                .await
-                .context("Failed to get proof for account")?;
-            anyhow::Result::Ok((address, proof))
+    let batches_fut = accounts_state
+        .chunks(MAX_PROOF_BATCH_LEN / 2)
+        .map(|accounts| fetch_proof_batch(accounts, &provider, block_number));
+    match try_join_all(batches_fut).await {
+        Ok(batches) => {
+            let (account_proofs, next_account_proofs): (Vec<_>, Vec<_>) =
+                batches.into_iter().unzip();
+            Ok((
+                account_proofs.into_iter().flatten().collect(),
+                next_account_proofs.into_iter().flatten().collect(),
+            ))
         }
-    });
+        Err(e) => {
+            warn!("batched eth_getProof failed, falling back to single requests: {e:#}");
+            try_join(
+                try_join_all(accounts_state.iter().map(|(address, keys)| {
+                    fetch_proof(*address, keys.clone(), &provider, block_number - 1)
+                })),
+                try_join_all(accounts_state.iter().map(|(address, keys)| {
+                    fetch_proof(*address, keys.clone(), &provider, block_number)
+                })),
+            )
+            .await
+        }
+    }
+}
+
+/// Fetches the proofs of the given accounts before and after the block, in a
+/// single JSON-RPC batch.
+async fn fetch_proof_batch<ProviderT, TransportT>(
+    accounts: &[(Address, Vec<StorageKey>)],
+    provider: &CachedProvider<ProviderT, TransportT>,
+    block_number: u64,
+) -> anyhow::Result<(
+    Vec<(Address, EIP1186AccountProofResponse)>,
+    Vec<(Address, EIP1186AccountProofResponse)>,
+)>
+where
+    ProviderT: Provider<TransportT>,
+    TransportT: Transport + Clone,
+{
+    let provider = provider.get_provider().await?;
+    let mut batch = provider.client().new_batch();
+    let mut add_calls = |block_id: BlockId| {
+        accounts
+            .iter()
+            .map(|(address, keys)| {
+                let waiter = batch.add_call::<_, EIP1186AccountProofResponse>(
+                    "eth_getProof",
+                    &(address, keys, block_id),
+                )?;
+                anyhow::Ok(async move {
+                    let proof = waiter.await.context("Failed to get proof for account")?;
+                    anyhow::Ok((*address, proof))
+                })
+            })
+            .collect::<anyhow::Result<Vec<_>>>()
+    };
+    let account_proofs_fut = add_calls((block_number - 1).into())?;
+    let next_account_proofs_fut = add_calls(block_number.into())?;
+    batch.send().await.context("Failed to send proof batch")?;
 
     try_join(
         try_join_all(account_proofs_fut),
@@ -218,3 +382,24 @@ where
     )
     .await
 }
+
+/// Fetches the proof of the given account at the given block.
+async fn fetch_proof<ProviderT, TransportT>(
+    address: Address,
+    keys: Vec<StorageKey>,
+    provider: &CachedProvider<ProviderT, TransportT>,
+    block_number: u64,
+) -> anyhow::Result<(Address, EIP1186AccountProofResponse)>
+where
+    ProviderT: Provider<TransportT>,
+    TransportT: Transport + Clone,
+{
+    let proof = provider
+        .get_provider()
+        .await?
+        .get_proof(address, keys)
+        .block_id(block_number.into())
+        .await
+        .context("Failed to get proof for account")?;
+    Ok((address, proof))
+}
@@ -8,7 +8,7 @@ use alloy::{
         Provider,
     },
     rpc::types::{
-        eth::{AccessList, Block, Transaction},
+        eth::{AccessList, Block, BlockNumberOrTag, Transaction},
         trace::geth::{
             AccountState, DiffMode, GethDebugBuiltInTracerType, GethDebugTracerType,
             GethDebugTracingOptions, GethTrace, PreStateConfig, PreStateFrame, PreStateMode,
@@ -17,13 +17,35 @@ use alloy::{
     transports::Transport,
 };
 use alloy_compat::Compat;
-use anyhow::Context as _;
+use anyhow::{bail, ensure, Context as _};
 use futures::stream::{FuturesOrdered, TryStreamExt};
+use serde::Deserialize;
 use trace_decoder::{ContractCodeUsage, TxnInfo, TxnMeta, TxnTrace};
+use tracing::warn;
 
 use super::CodeDb;
 
+/// The receipt of a transaction, with its prestate traces in default and diff
+/// mode.
+type TxData = (
+    <Ethereum as Network>::ReceiptResponse,
+    PreStateMode,
+    DiffMode,
+);
+
+/// The result of tracing one transaction of a block with
+/// `debug_traceBlockByNumber`.
+#[derive(Debug, Deserialize)]
+struct TxTraceResult<T> {
+    #[serde(rename = "txHash")]
+    tx_hash: Option<B256>,
+    result: T,
+}
+
 /// Processes the transactions in the given block and updates the code db.
+///
+/// The whole block is traced at once if the node supports it, otherwise each
+/// transaction is traced on its own.
 pub(super) async fn process_transactions<ProviderT, TransportT>(
     block: &Block,
     provider: &ProviderT,
@@ -32,12 +54,32 @@ where
     ProviderT: Provider<TransportT>,
     TransportT: Transport + Clone,
 {
-    block
+    let txs = block
         .transactions
         .as_transactions()
-        .context("No transactions in block")?
-        .iter()
-        .map(|tx| process_transaction(provider, tx))
+        .context("No transactions in block")?;
+    if txs.is_empty() {
+        return Ok((BTreeSet::new(), Vec::new()));
+    }
+
+    let tx_data = match fetch_block_tx_data(provider, block).await {
+        Ok(tx_data) => tx_data,
+        Err(e) => {
+            warn!(
+                "failed to trace block {} at once, tracing each transaction: {e:#}",
+                block.header.number
+            );
+            txs.iter()
+                .map(|tx| fetch_tx_data(provider, &tx.hash))
+                .collect::<FuturesOrdered<_>>()
+                .try_collect::<Vec<_>>()
+                .await?
+        }
+    };
+
+    txs.iter()
+        .zip(tx_data)
+        .map(|(tx, tx_data)| process_transaction(tx, tx_data))
         .collect::<FuturesOrdered<_>>()
         .try_fold(
             (BTreeSet::new(), Vec::new()),
@@ -50,17 +92,12 @@ where
         .await
 }
 
-/// Processes the transaction with the given transaction hash and updates the
-/// accounts state.
-async fn process_transaction<ProviderT, TransportT>(
-    provider: &ProviderT,
+/// Processes the given transaction, with its receipt and traces, and updates
+/// the accounts state.
+async fn process_transaction(
     tx: &Transaction,
-) -> anyhow::Result<(CodeDb, TxnInfo)>
-where
-    ProviderT: Provider<TransportT>,
-    TransportT: Transport + Clone,
-{
-    let (tx_receipt, pre_trace, diff_trace) = fetch_tx_data(provider, &tx.hash).await?;
+    (tx_receipt, pre_trace, diff_trace): TxData,
+) -> anyhow::Result<(CodeDb, TxnInfo)> {
     let tx_status = tx_receipt.status();
     let tx_receipt = tx_receipt.map_inner(rlp::map_receipt_envelope);
     let access_list = parse_access_list(tx.access_list.as_ref());
@@ -71,13 +108,7 @@ where
         gas_used: tx_receipt.gas_used as u64,
     };
 
-    let (code_db, mut tx_traces) = match (pre_trace, diff_trace) {
-        (
-            GethTrace::PreStateTracer(PreStateFrame::Default(read)),
-            GethTrace::PreStateTracer(PreStateFrame::Diff(diff)),
-        ) => process_tx_traces(access_list, read, diff).await?,
-        _ => unreachable!(),
-    };
+    let (code_db, mut tx_traces) = process_tx_traces(access_list, pre_trace, diff_trace).await?;
 
     // Handle case when transaction failed and a contract creation was reverted
     if !tx_status && tx_receipt.contract_address.is_some() {
@@ -96,11 +127,70 @@ where
     ))
 }
 
+/// Fetches the receipts and traces of all the transactions of the given block,
+/// with one request each.
+async fn fetch_block_tx_data<ProviderT, TransportT>(
+    provider: &ProviderT,
+    block: &Block,
+) -> anyhow::Result<Vec<TxData>>
+where
+    ProviderT: Provider<TransportT>,
+    TransportT: Transport + Clone,
+{
+    let block_number = BlockNumberOrTag::Number(block.header.number);
+    let receipts_fut = provider
+        .raw_request::<_, Option<Vec<<Ethereum as Network>::ReceiptResponse>>>(
+            "eth_getBlockReceipts".into(),
+            (block_number,),
+        );
+    let pre_traces_fut = provider.raw_request::<_, Vec<TxTraceResult<PreStateMode>>>(
+        "debug_traceBlockByNumber".into(),
+        (block_number, prestate_tracing_options(false)),
+    );
+    let diff_traces_fut = provider.raw_request::<_, Vec<TxTraceResult<DiffMode>>>(
+        "debug_traceBlockByNumber".into(),
+        (block_number, prestate_tracing_options(true)),
+    );
+
+    let (receipts, pre_traces, diff_traces) =
+        futures::try_join!(receipts_fut, pre_traces_fut, diff_traces_fut)?;
+    let receipts = receipts.context("Block receipts not found.")?;
+
+    let txs = block
+        .transactions
+        .as_transactions()
+        .context("No transactions in block")?;
+    ensure!(
+        receipts.len() == txs.len()
+            && pre_traces.len() == txs.len()
+            && diff_traces.len() == txs.len(),
+        "expected {} receipts and traces, got {} receipts, {} prestate traces and {} diff traces",
+        txs.len(),
+        receipts.len(),
+        pre_traces.len(),
+        diff_traces.len()
+    );
+
+    itertools::izip!(txs, receipts, pre_traces, diff_traces)
+        .map(|(tx, receipt, pre_trace, diff_trace)| {
+            ensure!(
+                receipt.transaction_hash == tx.hash
+                    && [pre_trace.tx_hash, diff_trace.tx_hash]
+                        .into_iter()
+                        .flatten()
+                        .all(|hash| hash == tx.hash),
+                "receipts and traces don't match the transactions of the block"
+            );
+            Ok((receipt, pre_trace.result, diff_trace.result))
+        })
+        .collect()
+}
+
 /// Fetches the transaction data for the given transaction hash.
 async fn fetch_tx_data<ProviderT, TransportT>(
     provider: &ProviderT,
     tx_hash: &B256,
-) -> anyhow::Result<(<Ethereum as Network>::ReceiptResponse, GethTrace, GethTrace), anyhow::Error>
+) -> anyhow::Result<TxData>
 where
     ProviderT: Provider<TransportT>,
     TransportT: Transport + Clone,
@@ -112,11 +202,17 @@ where
     let (tx_receipt, pre_trace, diff_trace) =
         futures::try_join!(tx_receipt_fut, pre_trace_fut, diff_trace_fut,)?;
 
-    Ok((
-        tx_receipt.context("Transaction receipt not found.")?,
-        pre_trace,
-        diff_trace,
-    ))
+    match (pre_trace, diff_trace) {
+        (
+            GethTrace::PreStateTracer(PreStateFrame::Default(read)),
+            GethTrace::PreStateTracer(PreStateFrame::Diff(diff)),
+        ) => Ok((
+            tx_receipt.context("Transaction receipt not found.")?,
+            read,
+            diff,
+        )),
+        _ => bail!("unexpected trace type for transaction {tx_hash}"),
+    }
 }
 
 /// Parse the access list data into a hashmap.
@@ -331,7 +427,8 @@ mod rlp {
     }
 }
 
-/// Tracing options for the debug_traceTransaction call.
+/// Tracing options for the debug_traceTransaction and debug_traceBlockByNumber
+/// calls.
 fn prestate_tracing_options(diff_mode: bool) -> GethDebugTracingOptions {
     GethDebugTracingOptions {
         tracer_config: PreStateConfig {
@@ -16,6 +16,9 @@ use alloy::{
 };
 use tower::{retry::Policy, Layer, Service};
 
+use super::cache::{ResponseCache, ResponseCacheLayer, ResponseCacheService};
+use crate::metrics;
+
 const HTTP_CLIENT_CONNECTION_POOL_IDLE_TIMEOUT: u64 = 90;
 const HTTP_CLIENT_MAX_IDLE_CONNECTIONS_PER_HOST: usize = 64;
 
@@ -127,23 +130,34 @@ where
 
         let mut inner = std::mem::replace(&mut self.inner, inner);
         Box::pin(async move {
+            let _timer = metrics::RPC_REQUEST_SECONDS.start_timer();
             let mut res = inner.call(req.clone()).await;
 
             while let Some(new_policy) = policy.retry(&req, res.as_ref()) {
+                metrics::RPC_RETRIES_TOTAL.inc();
                 policy = new_policy.await;
                 res = inner.call(req.clone()).await;
             }
 
+            if res.is_err() {
+                metrics::RPC_FAILURES_TOTAL.inc();
+            }
             res
         })
     }
 }
 
+/// Builds a provider retrying the failed requests, and caching the responses
+/// in `cache` if provided.
 pub fn build_http_retry_provider(
     rpc_url: url::Url,
     backoff: u64,
     max_retries: u32,
-) -> Result<RootProvider<RetryService<alloy::transports::http::ReqwestTransport>>, anyhow::Error> {
+    cache: Option<ResponseCache>,
+) -> Result<
+    RootProvider<ResponseCacheService<RetryService<alloy::transports::http::ReqwestTransport>>>,
+    anyhow::Error,
+> {
     let retry_policy = RetryLayer::new(RetryPolicy::new(
         Duration::from_millis(backoff),
         max_retries,
@@ -158,6 +172,7 @@ pub fn build_http_retry_provider(
     let http = alloy::transports::http::Http::with_client(reqwest_client, rpc_url);
     let is_local = http.guess_local();
     let client = ClientBuilder::default()
+        .layer(ResponseCacheLayer::new(cache))
         .layer(retry_policy)
         .transport(http, is_local);
     Ok(ProviderBuilder::new().on_client(client))